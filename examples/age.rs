@@ -0,0 +1,70 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// Calculates the age in years, months and days and the days until the next birthday.
+// Usage: cargo run --example age [DD.MM.YYYY]
+//
+use date_and_time::date::*;
+
+fn main() {
+    let birthday = match std::env::args().nth(1) {
+        Some(text) => parse_date(&text),
+        None => Date::from(9, 5, 1973),
+    };
+    if birthday.m == 0 {
+        eprintln!("invalid birthday, use the format DD.MM.YYYY");
+        return;
+    }
+    let today = Date::from_system_date();
+    if birthday > today {
+        eprintln!("the birthday is in the future");
+        return;
+    }
+
+    let (years, months, days) = birthday.diff_ymd(&today);
+    println!("Born on a {}", birthday.as_formated_string("%A, %d. %B %Y"));
+    println!("Age: {} years, {} months and {} days", years, months, days);
+    println!("That are {} days", birthday.diff_in_days(&today));
+
+    // a birthday on the 29th February is celebrated on the 28th February in common years
+    let mut next = birthday_in_year(&birthday, today.y);
+    if next < today {
+        next = birthday_in_year(&birthday, today.y + 1);
+    }
+    match today.diff_in_days(&next) {
+        0 => println!("Happy birthday!"),
+        n => println!(
+            "The next birthday is in {} days on a {}",
+            n,
+            next.as_formated_string("%A")
+        ),
+    }
+}
+
+// Returns the date of the text in the format DD.MM.YYYY, invalid dates have the month 0
+fn parse_date(text: &str) -> Date {
+    let parts: Vec<&str> = text.split('.').collect();
+    if parts.len() != 3 {
+        return Date { d: 0, m: 0, y: 0 };
+    }
+    match (parts[0].parse(), parts[1].parse(), parts[2].parse()) {
+        (Ok(d), Ok(m), Ok(y)) => Date::from(d, m, y),
+        _ => Date { d: 0, m: 0, y: 0 },
+    }
+}
+
+// Returns the birthday in the year, the 29th February becomes the 28th in common years
+fn birthday_in_year(birthday: &Date, year: i32) -> Date {
+    let day = birthday.d.min(days_in_month(birthday.m, year));
+    Date::from(day, birthday.m, year)
+}
+
+// Returns the number of days of the month in the year
+fn days_in_month(month: u8, year: i32) -> u8 {
+    (28..=31)
+        .rev()
+        .find(|d| Date::from(*d, month, year).m != 0)
+        .unwrap_or(0)
+}
@@ -0,0 +1,48 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// Prints the calendar of a month like the unix command cal, the weeks start with Monday.
+// Usage: cargo run --example calendar [month year]
+//
+use date_and_time::date::*;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let today = Date::from_system_date();
+    let (month, year) = if args.len() == 3 {
+        (
+            args[1].parse().unwrap_or(today.m),
+            args[2].parse().unwrap_or(today.y),
+        )
+    } else {
+        (today.m, today.y)
+    };
+    let first = Date::from(1, month, year);
+    if first.m == 0 {
+        eprintln!("invalid month: {} {}", month, year);
+        return;
+    }
+
+    let title = first.as_formated_string("%B %Y");
+    println!("{:^21}", title);
+    println!(" Mo Tu We Th Fr Sa Su");
+
+    // the weekday of the 1st as a column from 0 = Monday to 6 = Sunday
+    let offset = (first.get_weekday() as usize + 6) % 7;
+    let mut line = "   ".repeat(offset);
+    let mut day = first;
+    while day.m == month {
+        let mark = if day == today { '*' } else { ' ' };
+        line.push_str(&format!("{}{:2}", mark, day.d));
+        if day.get_weekday() == 0 {
+            println!("{}", line);
+            line.clear();
+        }
+        day = day.add_days(1);
+    }
+    if !line.is_empty() {
+        println!("{}", line);
+    }
+}
@@ -0,0 +1,59 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// Prints the current time in some cities around the world. The timezones are read from
+// the zoneinfo files of the system, or from the embedded database with the feature tzdb.
+// Usage: cargo run --example world_clock [--features tzdb]
+//
+use date_and_time::datetime::*;
+use date_and_time::offset::*;
+use date_and_time::timezone::*;
+
+const CITIES: [(&str, &str); 8] = [
+    ("Los Angeles", "America/Los_Angeles"),
+    ("New York", "America/New_York"),
+    ("London", "Europe/London"),
+    ("Berlin", "Europe/Berlin"),
+    ("Kolkata", "Asia/Kolkata"),
+    ("Tokyo", "Asia/Tokyo"),
+    ("Sydney", "Australia/Sydney"),
+    ("Auckland", "Pacific/Auckland"),
+];
+
+fn main() {
+    let utc = DateTime::from_system_clock();
+    let secs = utc.as_timestamp();
+    println!(
+        "{:<12} {} {}  UTC",
+        "UTC",
+        utc.date.as_formated_string("%a %d. %b"),
+        utc.time.as_string()
+    );
+
+    for (city, name) in CITIES {
+        let zone = match TimeZone::by_name(name) {
+            Ok(zone) => zone,
+            Err(e) => {
+                println!("{:<12} unknown ({})", city, e);
+                continue;
+            }
+        };
+        let local = zone.utc_to_local(&utc);
+        let offset = UtcOffset::from_seconds(zone.offset_at_timestamp(secs));
+        println!(
+            "{:<12} {} {}  {} (UTC{}){}",
+            city,
+            local.date.as_formated_string("%a %d. %b"),
+            local.time.as_string(),
+            zone.abbreviation_at_timestamp(secs),
+            offset.as_string(),
+            if zone.is_dst_at_timestamp(secs) {
+                ", summer time"
+            } else {
+                ""
+            }
+        );
+    }
+}
@@ -35,10 +35,104 @@
 //! date (```use crate::date_and_time::date::*;```) or time (```use crate::date_and_time::time::*;```)
 //! calculations.
 //!
+//! ## Examples
+//!
+//! The programs in the ```examples``` directory are part of this documentation and are
+//! tested like all other examples with ```cargo test```.
+//!
+//! A calendar printer (```cargo run --example calendar 12 2024```):
+//!
+//! ```
+#![doc = include_str!("../examples/calendar.rs")]
+//! ```
+//!
+//! An age calculator (```cargo run --example age 09.05.1973```):
+//!
+//! ```
+#![doc = include_str!("../examples/age.rs")]
+//! ```
+//!
+//! A world clock (```cargo run --example world_clock```):
+//!
+//! ```
+#![doc = include_str!("../examples/world_clock.rs")]
+//! ```
+//!
+
+// Emits a tracing event with the feature debug-trace, without the feature the arguments are
+// not evaluated
+#[cfg(feature = "debug-trace")]
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        tracing::trace!($($arg)*)
+    };
+}
+#[cfg(not(feature = "debug-trace"))]
+macro_rules! trace_event {
+    ($($arg:tt)*) => {};
+}
 
+// Enters a tracing span until the end of the block with the feature debug-trace
+#[cfg(feature = "debug-trace")]
+macro_rules! enter_trace_span {
+    ($($arg:tt)*) => {
+        let _span = tracing::trace_span!($($arg)*).entered();
+    };
+}
+#[cfg(not(feature = "debug-trace"))]
+macro_rules! enter_trace_span {
+    ($($arg:tt)*) => {};
+}
+
+pub mod abbreviation;
+pub mod builder;
+#[cfg(feature = "chrono")]
+pub mod chrono_interop;
+pub mod clock;
+pub mod components;
+pub mod countdown;
+pub mod cron;
 pub mod date;
+pub mod datetime;
+pub mod drift;
+pub mod duration;
+pub mod error;
+pub mod event;
+pub mod formatted;
+pub mod histogram;
+pub mod holiday;
+pub mod ical;
+pub mod interval;
+pub mod invalid;
+pub mod iso8601;
+pub mod julian;
+#[cfg(feature = "leap-seconds")]
+pub mod leap_seconds;
 pub mod local;
+pub mod locale;
+pub mod offset;
+pub mod posix_tz;
+pub mod quarter;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod range;
+pub mod recurrence;
+pub mod schedule;
+pub mod summary;
 pub mod time;
+#[cfg(feature = "time")]
+pub mod time_interop;
+pub mod timecode;
+pub mod timeline;
+pub mod timezone;
+#[cfg(feature = "tzdb")]
+pub mod tzdb;
+pub mod tzif;
+
+// The examples of the README are tested as doctests, too
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+pub struct ReadmeDoctests;
 
 // TEST area
 #[cfg(test)]
@@ -66,6 +160,133 @@ mod tests {
         assert_eq!(t1.add_time(&t2), Time::from(18, 30, 0));
     }
 
+    #[test]
+    fn test_time_12h_clock() {
+        for h in 0..24 {
+            let time = Time::from(h, 7, 9);
+            let (hour, meridiem) = time.to_12h();
+            assert!((1..=12).contains(&hour));
+            assert_eq!(Time::from_12h(hour, 7, 9, meridiem), Ok(time));
+            assert_eq!(
+                time.as_formated_string("%I %p"),
+                format!(
+                    "{:02} {}",
+                    hour,
+                    if meridiem == Meridiem::AM {
+                        "a.m."
+                    } else {
+                        "p.m."
+                    }
+                )
+            );
+        }
+        assert_eq!(Time::from(36, 0, 0).to_12h(), (12, Meridiem::PM));
+        assert!(Time::from_12h(0, 0, 0, Meridiem::AM).is_err());
+        assert!(Time::from_12h(11, 60, 0, Meridiem::AM).is_err());
+        assert!(Time::from_12h(11, 0, -1, Meridiem::PM).is_err());
+        assert!(Time::from_12h(12, -5, 0, Meridiem::AM).is_err());
+        assert!(Time::from_12h(12, 0, -5, Meridiem::AM).is_err());
+    }
+
+    #[test]
+    fn test_time_milliseconds() {
+        for ms in [
+            0,
+            999,
+            1_000,
+            1_500,
+            59_999,
+            86_399_999,
+            86_400_000,
+            360_000_000_000,
+            -1_500,
+        ] {
+            // the fractions of a second are kept in the round trip
+            let duration = Time::from_milliseconds(ms);
+            assert_eq!(duration.as_milliseconds(), ms);
+            assert_eq!(duration.as_time(), Time::from_seconds(ms / 1_000));
+        }
+        // more hours than fit in the seconds of an u32
+        let long = Time::from(2_000_000, 0, 0);
+        assert_eq!(long.as_milliseconds(), 7_200_000_000_000);
+        assert_eq!(
+            Time::from_milliseconds(long.as_milliseconds()).as_time(),
+            long
+        );
+        assert_eq!(
+            Time::from_milliseconds(-3_600_500).as_milliseconds(),
+            -3_600_500
+        );
+    }
+
+    #[test]
+    fn test_time_normalize() {
+        for secs in (-200_000..200_000).step_by(997) {
+            let (days, clock) = Time::from_seconds(secs).normalize();
+            assert!(clock.h < 24 && clock.m >= 0 && clock.s >= 0);
+            assert_eq!(days * 86_400 + clock.as_seconds(), secs);
+        }
+        let (days, clock) = Time::from(48, 0, 0).normalize();
+        assert_eq!((days, clock.as_string()), (2, String::from("00:00:00")));
+        let date = Date::from(30, 12, 2024);
+        let counter = Time::from(75, 30, 0);
+        let (days, clock) = counter.normalize();
+        assert_eq!(
+            DateTime::from(date.add_days(days as u64), clock),
+            DateTime::from(date, counter)
+        );
+    }
+
+    #[test]
+    fn test_negative_time() {
+        let samples = [
+            (-5_400, Time { h: -1, m: 30, s: 0 }, "-01:30:00"),
+            (-1_800, Time { h: 0, m: -30, s: 0 }, "-00:30:00"),
+            (-61, Time { h: 0, m: -1, s: 1 }, "-00:01:01"),
+            (-5, Time { h: 0, m: 0, s: -5 }, "-00:00:05"),
+            (-90_061, Time { h: -25, m: 1, s: 1 }, "-25:01:01"),
+        ];
+        for (secs, time, text) in samples {
+            assert_eq!(Time::from_seconds(secs), time);
+            assert_eq!(time.as_seconds(), secs);
+            assert_eq!(time.as_string(), text);
+            assert_eq!(time.as_formated_string("%T"), text);
+            assert!(time.is_negative() && time.as_option().is_some());
+            assert_eq!(time.abs().as_seconds(), -secs);
+        }
+        let time = Time::from(0, 30, 0).sub_time(&Time::from(2, 0, 0));
+        assert_eq!(time.as_string(), "-01:30:00");
+        assert_eq!(time.add_hours(2).as_string(), "00:30:00");
+        assert_eq!(time.diff_in_seconds(&Time::new()), 5_400);
+        // the fields of a negative time have one sign
+        assert_eq!(Time::from(1, -30, 0).as_option(), None);
+        assert_eq!(Time::from(0, -1, -1).as_option(), None);
+        assert!(!Time::new().is_negative());
+    }
+
+    #[test]
+    fn test_time_wrapping() {
+        let night = Time::from(22, 0, 0);
+        assert_eq!(night.add_hours_wrapping(8).as_string(), "06:00:00");
+        assert_eq!(night.add_hours_wrapping(24 * 365 + 2), Time::from(0, 0, 0));
+        assert_eq!(night.add_hours_wrapping(i64::MIN), Time::from(14, 0, 0));
+        assert_eq!(
+            night.add_minutes_wrapping(-1_440 * 3 - 30),
+            Time::from(21, 30, 0)
+        );
+        assert_eq!(night.add_seconds_wrapping(i64::MAX), Time::from(13, 30, 7));
+        let shift = Time::from(9, 30, 0);
+        assert_eq!(night.add_time_wrapping(&shift), Time::from(7, 30, 0));
+        assert_eq!(shift.sub_time_wrapping(&night), Time::from(11, 30, 0));
+        // a counting time is reduced to the clock, too
+        let counter = Time::from(49, 0, 0);
+        assert_eq!(counter.add_minutes_wrapping(0), Time::from(1, 0, 0));
+        assert_eq!(
+            Time::from(0, -30, 0).add_seconds_wrapping(0),
+            Time::from(23, 30, 0)
+        );
+    }
+
     use crate::date_and_time::date::*;
     #[test]
     fn test_date_set_and_from() {
@@ -75,9 +296,3144 @@ mod tests {
         assert_eq!(d1, d2);
     }
 
+    const LEAP_DAYS: [Date; 3] = [
+        Date::from_ymd(2000, 2, 29),
+        Date::from_ymd(1900, 2, 29),
+        Date::from(29, 2, 2024),
+    ];
+    const NOON: Time = Time::from_seconds(43_200);
+    #[test]
+    fn test_const_constructors() {
+        assert_eq!(LEAP_DAYS[0].as_string(), "2000-02-29");
+        assert_eq!(LEAP_DAYS[1], Date { d: 0, m: 0, y: 0 });
+        assert_eq!(LEAP_DAYS[2], Date::from_ymd(2024, 2, 29));
+        assert_eq!(NOON, Time::from(12, 0, 0));
+        const DAYS: Option<i64> = Date::from_ymd(2024, 6, 22).to_epoch_days();
+        const BACK: Date = Date::from_epoch_days(19_896);
+        assert_eq!(DAYS, Some(19_896));
+        assert_eq!(BACK, Date::from(22, 6, 2024));
+        const LEAP_YEARS: [bool; 3] = [is_leap_year(2000), is_leap_year(2100), is_leap_year(2024)];
+        assert_eq!(LEAP_YEARS, [true, false, true]);
+    }
+
+    #[test]
+    fn test_checked_years() {
+        let leap_day = Date::from(29, 2, 2024);
+        assert_eq!(leap_day.add_years(1), Date { d: 0, m: 0, y: 0 });
+        assert_eq!(leap_day.sub_years(4), Date::from(29, 2, 2020));
+        match leap_day.checked_add_years(1) {
+            Err(DateTimeError::OutOfRange(reason)) => assert!(reason.contains("2025-02-29")),
+            other => panic!("expected an out of range error, got {:?}", other),
+        }
+        assert!(Date::from(1, 1, 2024).checked_add_years(u32::MAX).is_err());
+        assert!(Date::from(1, 1, i32::MIN + 1).checked_sub_years(2).is_err());
+        assert!(Date::from(0, 0, 0).checked_add_years(1).is_err());
+    }
+
+    #[test]
+    fn test_week_bounds() {
+        let weekdays = [
+            Weekday::Sunday,
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+        ];
+        // the new year 2025 was a Wednesday, the weeks are over the end of the year
+        let new_year = Date::from(1, 1, 2025);
+        for (n, week_start) in weekdays.iter().enumerate() {
+            let start = new_year.start_of_week(*week_start);
+            let end = new_year.end_of_week(*week_start);
+            assert_eq!(start.get_weekday(), n as u8);
+            assert_eq!(start.diff_in_days(&end), 6);
+            assert!(start <= new_year && new_year <= end);
+        }
+        assert_eq!(
+            new_year.start_of_week(Weekday::Thursday),
+            Date::from(26, 12, 2024)
+        );
+        assert_eq!(
+            new_year.end_of_week(Weekday::Wednesday),
+            Date::from(7, 1, 2025)
+        );
+        let invalid = Date {
+            d: 0,
+            m: 1,
+            y: 2025,
+        };
+        assert_eq!(
+            invalid.start_of_week(Weekday::Monday),
+            Date { d: 0, m: 0, y: 0 }
+        );
+        assert_eq!(
+            invalid.end_of_week(Weekday::Monday),
+            Date { d: 0, m: 0, y: 0 }
+        );
+    }
+
+    #[test]
+    fn test_invalid_as_option() {
+        assert_eq!(Date::from(29, 2, 2023).as_option(), None);
+        assert_eq!(Date { d: 0, m: 0, y: 0 }.as_option(), None);
+        let leap_day = Date::from(29, 2, 2024);
+        assert_eq!(leap_day.as_option().map(|date| date.d), Some(29));
+        assert_eq!(Time::from(23, 59, 60).as_option(), None);
+        assert_eq!(Time::from(0, 0, 0).as_option(), Some(Time::new()));
+        // the hours are not checked, a Time can count more than one day
+        assert!(Time::from(36, 0, 0).as_option().is_some());
+        // the text of set_invalid_display() is global, so it is only tested in its doctest
+        let days: Vec<u8> = (28..=31)
+            .filter_map(|day| Date::from(day, 2, 2024).as_option())
+            .map(|date| date.d)
+            .collect();
+        assert_eq!(days, [28, 29]);
+    }
+
+    #[test]
+    fn test_weekend() {
+        // the week from Monday 1st July 2024
+        let week: Vec<Date> = (1..=7).map(|day| Date::from(day, 7, 2024)).collect();
+        let weekends: Vec<bool> = week.iter().map(|date| date.is_weekend()).collect();
+        assert_eq!(weekends, [false, false, false, false, false, true, true]);
+        assert!(week
+            .iter()
+            .all(|date| date.is_weekday() != date.is_weekend()));
+        let friday_saturday = [Weekday::Friday, Weekday::Saturday];
+        let weekends: Vec<bool> = week
+            .iter()
+            .map(|date| date.is_weekend_with(&friday_saturday))
+            .collect();
+        assert_eq!(weekends, [false, false, false, false, true, true, false]);
+        assert!(!week[0].is_weekend_with(&[]));
+        let invalid = Date { d: 0, m: 0, y: 0 };
+        assert!(!invalid.is_weekend() && !invalid.is_weekday());
+    }
+
+    #[test]
+    fn test_weekday_occurrences() {
+        // from the Friday 27th December 2024 over the end of the year
+        let friday = Date::from(27, 12, 2024);
+        let next: Vec<String> = [Weekday::Thursday, Weekday::Friday, Weekday::Saturday]
+            .iter()
+            .map(|&weekday| friday.next_occurrence(weekday, false).as_string())
+            .collect();
+        assert_eq!(next, ["2025-01-02", "2025-01-03", "2024-12-28"]);
+        let new_year = Date::from(1, 1, 2025);
+        assert_eq!(new_year.previous_occurrence(Weekday::Friday, true), friday);
+        assert_eq!(
+            new_year.previous_occurrence(Weekday::Wednesday, true),
+            new_year
+        );
+        assert_eq!(
+            new_year.previous_occurrence(Weekday::Wednesday, false),
+            Date::from(25, 12, 2024)
+        );
+        for inclusive in [false, true] {
+            let date = friday.next_occurrence(Weekday::Sunday, inclusive);
+            assert_eq!(date.previous_occurrence(Weekday::Friday, inclusive), friday);
+        }
+        let invalid = Date {
+            d: 32,
+            m: 12,
+            y: 2024,
+        };
+        assert_eq!(
+            invalid.next_occurrence(Weekday::Monday, true),
+            Date { d: 0, m: 0, y: 0 }
+        );
+    }
+
+    #[test]
+    fn test_nth_weekday_of_month() {
+        // February 2024 starts on a Thursday and has five Thursdays
+        let thursdays: Vec<Option<Date>> = (0..=6)
+            .map(|n| nth_weekday_of_month(2024, 2, n, Weekday::Thursday))
+            .collect();
+        assert_eq!(thursdays[0], None);
+        assert_eq!(thursdays[1], Some(Date::from(1, 2, 2024)));
+        assert_eq!(thursdays[5], Some(Date::from(29, 2, 2024)));
+        assert_eq!(thursdays[6], None);
+        assert_eq!(nth_weekday_of_month(2023, 2, 5, Weekday::Thursday), None);
+        let first_wednesday = nth_weekday_of_month(2024, 2, 1, Weekday::Wednesday);
+        assert_eq!(first_wednesday, Some(Date::from(7, 2, 2024)));
+        assert_eq!(
+            last_weekday_of_month(2024, 2, Weekday::Thursday),
+            thursdays[5]
+        );
+        let last_friday = last_weekday_of_month(2024, 2, Weekday::Friday);
+        assert_eq!(last_friday, Some(Date::from(23, 2, 2024)));
+        assert_eq!(last_weekday_of_month(2024, 13, Weekday::Friday), None);
+        // the DST in the EU starts on the last Sunday of March
+        for year in 2020..2030 {
+            let last = last_weekday_of_month(year, 3, Weekday::Sunday).unwrap();
+            assert!(last.d > 24 && last.get_weekday() == 0);
+            let fifth = nth_weekday_of_month(year, 3, 5, Weekday::Sunday);
+            assert_eq!(fifth.unwrap_or(last), last);
+        }
+    }
+
+    #[test]
+    fn test_month_bounds() {
+        let last: Vec<String> = (1..=12)
+            .map(|month| Date::from(1, month, 2100).last_day_of_month().as_string())
+            .collect();
+        assert_eq!(last[1], "2100-02-28");
+        assert_eq!(last[11], "2100-12-31");
+        assert!(last
+            .iter()
+            .zip(1..=12)
+            .all(|(date, month)| { date.ends_with(&format!("{}", days_in_month(2100, month))) }));
+        let leap_day = Date::from(29, 2, 2000);
+        assert_eq!(leap_day.last_day_of_month(), leap_day);
+        assert_eq!(leap_day.first_day_of_month(), Date::from(1, 2, 2000));
+        let invalid = Date {
+            d: 30,
+            m: 2,
+            y: 2000,
+        };
+        assert_eq!(invalid.first_day_of_month(), Date { d: 0, m: 0, y: 0 });
+        assert_eq!(invalid.last_day_of_month(), Date { d: 0, m: 0, y: 0 });
+    }
+
+    #[test]
+    fn test_quarters() {
+        let bounds: Vec<(u8, String, String)> = (1..=12)
+            .map(|month| {
+                let date = Date::from(15, month, 2024);
+                (
+                    date.quarter(),
+                    date.first_day_of_quarter().as_string(),
+                    date.last_day_of_quarter().as_string(),
+                )
+            })
+            .step_by(3)
+            .collect();
+        let expected = [
+            (1, "2024-01-01", "2024-03-31"),
+            (2, "2024-04-01", "2024-06-30"),
+            (3, "2024-07-01", "2024-09-30"),
+            (4, "2024-10-01", "2024-12-31"),
+        ];
+        for (bound, (quarter, first, last)) in bounds.iter().zip(expected) {
+            assert_eq!(*bound, (quarter, String::from(first), String::from(last)));
+        }
+        let date = Date::from(31, 12, 2024);
+        assert_eq!(date.first_day_of_quarter().get_day_of_quarter(), 1);
+        assert_eq!(date.get_day_of_quarter(), 92);
+        let invalid = Date {
+            d: 31,
+            m: 4,
+            y: 2024,
+        };
+        assert_eq!(invalid.quarter(), 0);
+        assert_eq!(invalid.get_day_of_quarter(), 0);
+        assert_eq!(invalid.get_week_of_month(), 0);
+        assert_eq!(invalid.get_days_remaining_in_year(), 0);
+        assert_eq!(invalid.as_formated_string("%Q %K %J"), "0 0 0");
+        let sentinel = Date { d: 0, m: 0, y: 0 };
+        assert_eq!(sentinel.as_formated_string("%Q %K %J"), "0 0 0");
+        assert_eq!(invalid.last_day_of_quarter(), Date { d: 0, m: 0, y: 0 });
+    }
+
+    #[test]
+    fn test_leap_years() {
+        let leap: Vec<i32> = (1896..=1912).filter(|&y| is_leap_year(y)).collect();
+        assert_eq!(leap, [1896, 1904, 1908, 1912]);
+        for year in [-400, -4, 0, 400, 1600, 2000, 2400] {
+            assert!(is_leap_year(year), "{}", year);
+        }
+        for year in [-100, -1, 1, 100, 1700, 1800, 2100] {
+            assert!(!is_leap_year(year), "{}", year);
+        }
+        assert!(Date::from(31, 12, 2000).is_in_leap_year());
+        assert!(!Date::from(31, 12, 2100).is_in_leap_year());
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        let lengths: Vec<u8> = (1..=12).map(|month| days_in_month(2023, month)).collect();
+        assert_eq!(lengths, [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(-4, 2), 29);
+        assert_eq!(days_in_month(2024, 0), 0);
+        assert_eq!(Date::from(31, 12, 2024).days_in_month(), 31);
+        assert_eq!(
+            Date {
+                d: 31,
+                m: 2,
+                y: 2024
+            }
+            .days_in_month(),
+            0
+        );
+        const FEBRUARY: u8 = days_in_month(2024, 2);
+        assert_eq!(FEBRUARY, 29);
+    }
+
+    #[test]
+    fn test_leap_day_policy() {
+        let leap_day = Date::from(29, 2, 2024);
+        let birthdays: Vec<Option<Date>> = (1..=4)
+            .map(|n| leap_day.add_years_with(n, LeapDayPolicy::Skip))
+            .collect();
+        assert_eq!(birthdays, [None, None, None, Some(Date::from(29, 2, 2028))]);
+        assert_eq!(
+            leap_day.sub_years_with(100, LeapDayPolicy::Feb28),
+            Some(Date::from(29, 2, 1924))
+        );
+        assert_eq!(
+            leap_day.add_years_with(76, LeapDayPolicy::Feb28),
+            Some(Date::from(28, 2, 2100))
+        );
+        let date = Date::from(28, 2, 2023);
+        assert_eq!(
+            date.add_years_with(1, LeapDayPolicy::Mar1),
+            Some(Date::from(28, 2, 2024))
+        );
+    }
+
+    #[test]
+    fn test_report_placeholders() {
+        let date = Date::from(29, 2, 2024);
+        assert_eq!(date.as_formated_string("%Q/%K/%J"), "60/5/306");
+        let date = Date::from(31, 12, 2023);
+        assert_eq!(date.as_formated_string("%Q/%K/%J"), "92/5/0");
+        assert_eq!(Date::from(1, 7, 2024).as_formated_string("%Q %K"), "1 1");
+        // the 1st September 2024 was a Sunday, the 30th is in the 6th week
+        assert_eq!(Date::from(30, 9, 2024).as_formated_string("%Q %K"), "92 6");
+    }
+
     #[test]
     fn test_invalid_date() {
         let d1 = Date::from(29, 2, 1985);
         assert_eq!(d1, Date { d: 0, m: 0, y: 0 });
     }
+
+    #[test]
+    fn test_week_parity() {
+        // a Wednesday as anchor, its week starts on Monday the 4th March 2024
+        let anchor = Date::from(6, 3, 2024);
+        assert_eq!(Date::from(4, 3, 2024).week_parity(anchor), WeekParity::Even);
+        assert_eq!(
+            Date::from(10, 3, 2024).week_parity(anchor),
+            WeekParity::Even
+        );
+        assert_eq!(Date::from(11, 3, 2024).week_parity(anchor), WeekParity::Odd);
+        assert_eq!(Date::from(3, 3, 2024).week_parity(anchor), WeekParity::Odd);
+        assert_eq!(Date::from(26, 2, 2024).week_parity(anchor), WeekParity::Odd);
+        assert_eq!(
+            Date::from(19, 2, 2024).week_parity(anchor),
+            WeekParity::Even
+        );
+        assert_eq!(Date::from(17, 3, 2024).biweek_index(anchor), 0);
+        assert_eq!(Date::from(18, 3, 2024).biweek_index(anchor), 1);
+        assert_eq!(Date::from(3, 3, 2024).biweek_index(anchor), -1);
+        assert_eq!(Date::from(19, 2, 2024).biweek_index(anchor), -1);
+        assert_eq!(Date::from(18, 2, 2024).biweek_index(anchor), -2);
+        // across the new year
+        assert_eq!(Date::from(6, 3, 2025).biweek_index(anchor), 26);
+    }
+
+    use crate::date_and_time::duration::*;
+    #[test]
+    fn test_duration_parse_clock() {
+        let d1 = Duration::parse_clock("1:23:45.67").unwrap();
+        assert_eq!(d1.as_milliseconds(), 5_025_670);
+        let d2 = Duration::parse_clock("23:45").unwrap();
+        assert_eq!(d2.as_seconds(), 1_425);
+        let d3 = Duration::parse_clock("-9.5").unwrap();
+        assert_eq!(d3.as_milliseconds(), -9_500);
+    }
+
+    #[test]
+    fn test_duration_parse_clock_invalid() {
+        assert!(Duration::parse_clock("1:60:00").is_err());
+        assert!(Duration::parse_clock("1:2:03").is_err());
+        assert!(Duration::parse_clock("12:34.").is_err());
+        assert!(Duration::parse_clock("1:2:3:4").is_err());
+        assert!(Duration::parse_clock("1000000000000000000000000000000").is_err());
+        assert!(Duration::parse_clock("10000000000000000000000000000:00:00").is_err());
+    }
+
+    use crate::date_and_time::tzif::*;
+    // A minimal version 2 TZif file with one transition from CET to CEST
+    fn build_tzif_v2() -> Vec<u8> {
+        let mut block = Vec::new();
+        let counts: [u32; 6] = [0, 0, 0, 1, 2, 10];
+        let mut header = Vec::new();
+        header.extend_from_slice(b"TZif2");
+        header.extend_from_slice(&[0; 15]);
+        for c in counts {
+            header.extend_from_slice(&c.to_be_bytes());
+        }
+        // 32 bit block
+        block.extend_from_slice(&header);
+        block.extend_from_slice(&1_711_846_800_i32.to_be_bytes());
+        block.push(1);
+        block.extend_from_slice(&3_600_i32.to_be_bytes());
+        block.extend_from_slice(&[0, 0]);
+        block.extend_from_slice(&7_200_i32.to_be_bytes());
+        block.extend_from_slice(&[1, 4]);
+        block.extend_from_slice(b"CET\0CEST\0\0");
+        // 64 bit block
+        block.extend_from_slice(&header);
+        block.extend_from_slice(&1_711_846_800_i64.to_be_bytes());
+        block.push(1);
+        block.extend_from_slice(&3_600_i32.to_be_bytes());
+        block.extend_from_slice(&[0, 0]);
+        block.extend_from_slice(&7_200_i32.to_be_bytes());
+        block.extend_from_slice(&[1, 4]);
+        block.extend_from_slice(b"CET\0CEST\0\0");
+        block.extend_from_slice(b"\nCET-1CEST,M3.5.0,M10.5.0/3\n");
+        block
+    }
+
+    #[test]
+    fn test_tzif_parse() {
+        let info = ZoneInfo::parse(&build_tzif_v2()).unwrap();
+        assert_eq!(info.version, 2);
+        assert_eq!(info.transitions.len(), 1);
+        assert_eq!(info.local_time_types[1].abbreviation, "CEST");
+        assert_eq!(info.offset_at(1_711_846_799), Some(3_600));
+        assert_eq!(info.offset_at(1_711_846_800), Some(7_200));
+        assert_eq!(info.footer.as_deref(), Some("CET-1CEST,M3.5.0,M10.5.0/3"));
+    }
+
+    #[test]
+    fn test_tzif_parse_invalid() {
+        let data = build_tzif_v2();
+        assert!(ZoneInfo::parse(&data[..50]).is_err());
+        assert!(ZoneInfo::parse(b"TZxx").is_err());
+        // counts which exceed the data in the first and in the second header
+        let mut huge = data.clone();
+        huge[32..36].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(ZoneInfo::parse(&huge).is_err());
+        let mut huge = data.clone();
+        huge[99..103].copy_from_slice(&0x1000_0000_u32.to_be_bytes());
+        assert!(ZoneInfo::parse(&huge).is_err());
+    }
+
+    use crate::date_and_time::timecode::*;
+    #[test]
+    fn test_timecode_drop_frame() {
+        let rate = FrameRate::FPS_29_97_DF;
+        let tc = Timecode::from_frames(1_800, rate).unwrap();
+        assert_eq!(tc.as_string(), "00:01:00;02");
+        let tc = Timecode::from_frames(17_982, rate).unwrap();
+        assert_eq!(tc.as_string(), "00:10:00;00");
+        let tc = Timecode::parse("01:23:45;12", rate).unwrap();
+        assert_eq!(Timecode::from_frames(tc.to_frames(), rate).unwrap(), tc);
+        assert!(Timecode::parse("00:01:00;01", rate).is_err());
+    }
+
+    #[test]
+    fn test_timecode_duration() {
+        let tc = Timecode::parse("00:00:01:12", FrameRate::FPS_24).unwrap();
+        assert_eq!(tc.to_duration().as_milliseconds(), 1_500);
+        let d = Duration::from_seconds(3_600);
+        let tc = Timecode::from_duration(&d, FrameRate::FPS_29_97_DF).unwrap();
+        assert_eq!(tc.as_string(), "01:00:00;00");
+        assert_eq!(tc.add_frames(-1).as_string(), "00:59:59;29");
+        let tc = Timecode::from_duration(&d, FrameRate::FPS_29_97).unwrap();
+        assert_eq!(tc.as_string(), "00:59:56:12");
+        // the large values wrap around at 24 hours without an overflow
+        let midnight = Timecode::new(0, 0, 0, 0, FrameRate::FPS_25).unwrap();
+        let day = 25 * 86_400;
+        assert_eq!(
+            midnight.add_frames(i64::MAX),
+            midnight.add_frames(i64::MAX % day)
+        );
+        assert_eq!(
+            midnight.add_frames(i64::MIN),
+            midnight.add_frames(i64::MIN % day)
+        );
+        let d = Duration::from_nanoseconds(i128::MAX / 2);
+        let rest = Duration::from_nanoseconds(i128::MAX / 2 % (day as i128 * 1_000_000_000));
+        assert_eq!(
+            Timecode::from_duration(&d, FrameRate::FPS_25),
+            Timecode::from_duration(&rest, FrameRate::FPS_25)
+        );
+        assert!(Timecode::from_duration(&d, FrameRate::FPS_29_97_DF).is_ok());
+        // the frames are a u8, the higher frame rates are rejected instead of wrapping
+        let rate = FrameRate::new(255, 1, false);
+        assert_eq!(
+            Timecode::from_frames(254, rate).unwrap().as_string(),
+            "00:00:00:254"
+        );
+        assert!(Timecode::from_frames(999, FrameRate::new(1_000, 1, false)).is_err());
+        assert_eq!(FrameRate::new(u32::MAX, u32::MAX, false).nominal_fps(), 1);
+        assert_eq!(
+            FrameRate::new(u32::MAX, 2, false).nominal_fps(),
+            2_147_483_648
+        );
+    }
+
+    #[test]
+    fn test_date_from_days_february() {
+        let d1 = Date::from(31, 1, 2024);
+        assert_eq!(d1.add_days(1), Date::from(1, 2, 2024));
+        assert_eq!(d1.add_days(29), Date::from(29, 2, 2024));
+    }
+
+    use crate::date_and_time::posix_tz::*;
+    #[test]
+    fn test_posix_tz_central_europe() {
+        let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        assert_eq!(rule.std_offset, 3_600);
+        // DST starts 2024-03-31 at 01:00 UTC and ends 2024-10-27 at 01:00 UTC
+        assert_eq!(
+            rule.offset_at(&Date::from(31, 3, 2024), &Time::from(0, 59, 59)),
+            3_600
+        );
+        assert_eq!(
+            rule.offset_at(&Date::from(31, 3, 2024), &Time::from(1, 0, 0)),
+            7_200
+        );
+        assert_eq!(
+            rule.offset_at(&Date::from(27, 10, 2024), &Time::from(0, 59, 59)),
+            7_200
+        );
+        assert_eq!(
+            rule.offset_at(&Date::from(27, 10, 2024), &Time::from(1, 0, 0)),
+            3_600
+        );
+        let summer = (Date::from(1, 7, 2024), Time::from(12, 0, 0));
+        assert_eq!(rule.abbreviation_at(&summer.0, &summer.1), "CEST");
+    }
+
+    #[test]
+    fn test_posix_tz_southern_and_quoted() {
+        let rule = TzRule::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        assert!(rule.is_dst_at(&Date::from(1, 1, 2024), &Time::from(0, 0, 0)));
+        assert!(!rule.is_dst_at(&Date::from(1, 7, 2024), &Time::from(0, 0, 0)));
+        let rule = TzRule::parse("<+0530>-5:30").unwrap();
+        assert_eq!(rule.std_name, "+0530");
+        assert_eq!(rule.std_offset, 19_800);
+        assert!(TzRule::parse("CET-1CEST,M13.5.0,M10.5.0").is_err());
+        assert!(TzRule::parse("X-1").is_err());
+    }
+
+    use crate::date_and_time::timezone::*;
+    #[test]
+    fn test_timezone_fixed_and_rule() {
+        let tz = TimeZone::fixed("IST", 19_800);
+        assert_eq!(
+            tz.offset_at(&Date::from(1, 1, 2024), &Time::from(0, 0, 0)),
+            19_800
+        );
+        let rule = TzRule::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        let tz = TimeZone::from_rule("America/New_York", rule);
+        assert_eq!(tz.offset_at_timestamp(1_720_000_000), -14_400);
+        assert_eq!(tz.abbreviation_at_timestamp(1_704_067_200), "EST");
+    }
+
+    #[test]
+    fn test_local_day_bounds() {
+        // the transitions are at the midnight, in the spring the midnight is skipped and
+        // in the fall it occurs twice
+        let rule = TzRule::parse("XST3XDT,M3.2.0/0,M11.1.0/1").unwrap();
+        let tz = TimeZone::from_rule("Test/Midnight", rule);
+        let (start, end) = local_day_bounds(&Date::from(10, 3, 2024), &tz);
+        assert_eq!(start.as_string(), "2024-03-10T03:00:00");
+        assert_eq!(end.as_string(), "2024-03-11T02:00:00");
+        assert_eq!(tz.utc_to_local(&start).as_string(), "2024-03-10T01:00:00");
+        let (start, end) = local_day_bounds(&Date::from(3, 11, 2024), &tz);
+        assert_eq!(start.as_string(), "2024-11-03T02:00:00");
+        assert_eq!(start.diff(&end).as_seconds(), 25 * 3_600);
+        let (start, end) = local_day_bounds(&Date::from(22, 6, 2024), &TimeZone::utc());
+        assert_eq!(start, DateTime::from(Date::from(22, 6, 2024), Time::new()));
+        assert_eq!(start.diff(&end).as_seconds(), 86_400);
+    }
+
+    #[test]
+    fn test_week_bounds_local() {
+        // New York, the daylight saving time starts on Sunday the 10th March 2024
+        let rule = TzRule::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        let tz = TimeZone::from_rule("America/New_York", rule);
+        let date = Date::from(13, 3, 2024);
+        let (start, end) = week_bounds_local(&date, &tz, WeekConfig::us());
+        assert_eq!(start.as_string(), "2024-03-10T05:00:00");
+        assert_eq!(end.as_string(), "2024-03-17T04:00:00");
+        assert_eq!(start.diff(&end).as_seconds(), 167 * 3_600);
+        // the ISO week before contains the transition at its end
+        let (start, end) = week_bounds_local(&date.sub_days(7), &tz, WeekConfig::iso());
+        assert_eq!(start.as_string(), "2024-03-04T05:00:00");
+        assert_eq!(start.diff(&end).as_seconds(), 167 * 3_600);
+        // the weeks are consecutive, the end is the start of the next week
+        let next = week_bounds_local(&end.date, &tz, WeekConfig::iso());
+        assert_eq!(next.0, end);
+        assert_eq!(next.0.diff(&next.1).as_seconds(), 168 * 3_600);
+        let (start, end) = week_bounds_local(&Date::from(3, 11, 2024), &tz, WeekConfig::us());
+        assert_eq!(tz.utc_to_local(&start).as_string(), "2024-11-03T00:00:00");
+        assert_eq!(start.diff(&end).as_seconds(), 169 * 3_600);
+    }
+
+    #[cfg(feature = "tzdb")]
+    #[test]
+    fn test_timezone_embedded_database() {
+        use crate::date_and_time::tzdb;
+        assert_eq!(
+            tzdb::lookup("Europe/Berlin"),
+            Some("CET-1CEST,M3.5.0,M10.5.0/3")
+        );
+        assert_eq!(tzdb::lookup("Mars/Olympus_Mons"), None);
+        let tz = TimeZone::by_name("Asia/Kolkata").unwrap();
+        assert_eq!(tz.offset_at_timestamp(1_720_000_000), 19_800);
+    }
+
+    #[test]
+    fn test_duration_round_to_ticks() {
+        let d = Duration::from_nanoseconds(1_234_567_891);
+        assert_eq!(d.round_to_ticks(1_000).as_milliseconds(), 1_235);
+        assert_eq!(d.round_to_ticks(4).as_milliseconds(), 1_250);
+        let d = Duration::from_milliseconds(-1_125);
+        assert_eq!(d.round_to_ticks(4).as_milliseconds(), -1_250);
+        let d = Duration::from_milliseconds(1_020);
+        assert_eq!(
+            d.round_to_frame(&FrameRate::FPS_25).as_milliseconds(),
+            1_040
+        );
+        let d = Duration::from_milliseconds(-1_020);
+        assert_eq!(
+            d.round_to_frame(&FrameRate::FPS_25).as_milliseconds(),
+            -1_040
+        );
+        // the large durations are rounded without an overflow
+        let d = Duration::from_nanoseconds(i128::MAX / 2);
+        let rounded = d.round_to_frame(&FrameRate::FPS_29_97);
+        assert!((rounded.as_nanoseconds() - d.as_nanoseconds()).abs() <= 16_683_350);
+        // at the extremes the ticks are rounded without an overflow, beyond them it saturates
+        let max = Duration::from_nanoseconds(i128::MAX);
+        assert_eq!(max.round_to_ticks(2), max);
+        assert_eq!(max.round_to_ticks(1_000_000_000), max);
+        let min = Duration::from_nanoseconds(i128::MIN);
+        assert_eq!(min.round_to_ticks(4), min);
+        let d = Duration::from_nanoseconds(i128::MAX / 2);
+        assert!((d.round_to_ticks(44_100).as_nanoseconds() - d.as_nanoseconds()).abs() <= 11_338);
+    }
+
+    use crate::date_and_time::datetime::*;
+    #[test]
+    fn test_datetime_timestamp_and_round() {
+        let dt = DateTime::from(Date::from(22, 6, 2024), Time::from(18, 30, 0));
+        assert_eq!(dt.as_timestamp(), 1_719_081_000);
+        assert_eq!(
+            DateTime::from_timestamp(-1).as_string(),
+            "1969-12-31T23:59:59"
+        );
+        let dt =
+            DateTime::from_timestamp(1_719_081_059).add_duration(&Duration::from_milliseconds(999));
+        let rounded = dt.round_to_tick(10);
+        assert_eq!(rounded.as_string(), "2024-06-22T18:31:00");
+    }
+
+    #[test]
+    fn test_date_add_duration_and_split() {
+        let date = Date::from(28, 2, 2024);
+        let cases = [
+            (0, "2024-02-28T00:00:00"),
+            (86_399, "2024-02-28T23:59:59"),
+            (86_400, "2024-02-29T00:00:00"),
+            (2 * 86_400 + 3_661, "2024-03-01T01:01:01"),
+            (-1, "2024-02-27T23:59:59"),
+            (-86_400, "2024-02-27T00:00:00"),
+        ];
+        for (seconds, expected) in cases {
+            let dt = date.add_duration(&Duration::from_seconds(seconds));
+            assert_eq!(dt.as_string(), expected);
+        }
+        let dt = date.add_duration(&Duration::from_milliseconds(90_061_500));
+        assert_eq!(dt.as_string(), "2024-02-29T01:01:01.500000000");
+        let (split_date, split_time, offset) = dt.split();
+        assert_eq!(split_date, Date::from(29, 2, 2024));
+        assert_eq!(split_time.as_string(), "01:01:01");
+        assert!(offset.is_none());
+        assert_eq!(DateTime::from(split_date, split_time).nanos, 0);
+    }
+
+    #[test]
+    fn test_datetime_epoch_units() {
+        for nanos in [0, -1, 1, 1_719_081_000_123_456_789, i64::MIN, i64::MAX] {
+            let dt = DateTime::from_epoch_nanos(nanos);
+            assert_eq!(dt.to_epoch_nanos().unwrap(), nanos);
+            assert_eq!(dt.to_epoch_micros().unwrap(), nanos.div_euclid(1_000));
+            assert_eq!(dt.to_epoch_millis().unwrap(), nanos.div_euclid(1_000_000));
+        }
+        assert_eq!(
+            DateTime::from_epoch_nanos(i64::MIN).as_string(),
+            "1677-09-21T00:12:43.145224192"
+        );
+        for millis in [i64::MIN, -1, 0, i64::MAX] {
+            let dt = DateTime::from_epoch_millis(millis);
+            assert_eq!(dt.to_epoch_millis().unwrap(), millis);
+            assert_eq!(
+                DateTime::from_epoch_micros(millis)
+                    .to_epoch_micros()
+                    .unwrap(),
+                millis
+            );
+        }
+        let dt = DateTime::from_epoch_millis(i64::MAX);
+        assert!(dt.to_epoch_micros().is_err());
+        assert!(dt.to_epoch_nanos().is_err());
+        let next =
+            DateTime::from_epoch_nanos(i64::MAX).add_duration(&Duration::from_nanoseconds(1));
+        assert!(next.to_epoch_nanos().is_err());
+        assert!(next.to_epoch_micros().is_ok());
+        let invalid = DateTime {
+            date: Date::from(30, 2, 2024),
+            ..DateTime::new()
+        };
+        assert!(invalid.to_epoch_millis().is_err());
+    }
+
+    #[test]
+    fn test_datetime_from_epoch_f64() {
+        let nanos = |secs, rounding| DateTime::from_epoch_f64(secs, rounding).unwrap().nanos;
+        assert_eq!(nanos(1.25e-9, RoundingMode::Floor), 1);
+        assert_eq!(nanos(1.25e-9, RoundingMode::Ceil), 2);
+        assert_eq!(nanos(1.25e-9, RoundingMode::Nearest), 1);
+        assert_eq!(nanos(1.75e-9, RoundingMode::Nearest), 2);
+        assert_eq!(nanos(1.75e-9, RoundingMode::TowardZero), 1);
+        // the negative seconds are the second before the epoch plus the nanoseconds
+        assert_eq!(nanos(-1.25e-9, RoundingMode::Floor), 999_999_998);
+        assert_eq!(nanos(-1.25e-9, RoundingMode::Ceil), 999_999_999);
+        assert_eq!(nanos(-1.25e-9, RoundingMode::TowardZero), 999_999_999);
+        assert_eq!(nanos(0.1, RoundingMode::Floor), 100_000_000);
+        let dt = DateTime::from_epoch_f64(-0.9999999999, RoundingMode::Floor).unwrap();
+        assert_eq!(dt.as_string(), "1969-12-31T23:59:59");
+        let dt = DateTime::from_epoch_f64(-1e-12, RoundingMode::Ceil).unwrap();
+        assert_eq!(dt.as_string(), "1970-01-01T00:00:00");
+        let dt = DateTime::from_epoch_f64(253_402_300_799.0, RoundingMode::Nearest).unwrap();
+        assert_eq!(dt.as_string(), "9999-12-31T23:59:59");
+        for secs in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e17, -1e17] {
+            assert!(
+                DateTime::from_epoch_f64(secs, RoundingMode::Floor).is_err(),
+                "{}",
+                secs
+            );
+        }
+    }
+
+    #[test]
+    fn test_approximate_equality() {
+        let dt = DateTime::from_timestamp(1_000);
+        let mut later = dt.add_duration(&Duration::from_milliseconds(500));
+        assert!(dt.approx_eq(&later, Duration::from_milliseconds(500)));
+        assert!(later.approx_eq(&dt, Duration::from_milliseconds(500)));
+        assert!(dt.approx_eq(&later, Duration::from_milliseconds(-500)));
+        later.nanos += 1;
+        assert!(!dt.approx_eq(&later, Duration::from_milliseconds(500)));
+        let t = Time::from(23, 59, 50);
+        assert!(t.within(&Time::from(24, 0, 0), 10));
+        assert!(!t.within(&Time::from(23, 59, 39), 10));
+    }
+
+    #[test]
+    fn test_timezone_local_to_utc() {
+        let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        let tz = TimeZone::from_rule("Europe/Berlin", rule);
+        let summer = DateTime::from(Date::from(22, 6, 2024), Time::from(18, 30, 0));
+        let utc = tz.local_to_utc(&summer).single().unwrap();
+        assert_eq!(utc.as_string(), "2024-06-22T16:30:00");
+        assert_eq!(tz.utc_to_local(&utc), summer);
+        // 02:30 doesn't exist on 2024-03-31, 02:30 exists twice on 2024-10-27
+        let gap = DateTime::from(Date::from(31, 3, 2024), Time::from(2, 30, 0));
+        assert_eq!(tz.local_to_utc(&gap), LocalResult::None);
+        let overlap = DateTime::from(Date::from(27, 10, 2024), Time::from(2, 30, 0));
+        let LocalResult::Ambiguous(a, b) = tz.local_to_utc(&overlap) else {
+            panic!("02:30 should be ambiguous");
+        };
+        assert_eq!(a.as_string(), "2024-10-27T00:30:00");
+        assert_eq!(b.as_string(), "2024-10-27T01:30:00");
+    }
+
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    #[test]
+    fn test_tm_interop() {
+        let dt = DateTime::from(Date::from(22, 6, 2024), Time::from(18, 30, 5));
+        let t = dt.to_tm();
+        assert_eq!((t.tm_year, t.tm_mon, t.tm_mday), (124, 5, 22));
+        assert_eq!((t.tm_wday, t.tm_yday), (6, 173));
+        assert_eq!(DateTime::from_tm(&t), dt);
+        let mut t = Time::from(23, 59, 59).to_tm();
+        t.tm_sec = 60;
+        assert_eq!(Time::from_tm(&t), Time::from(23, 59, 59));
+        // the invalid dates and the years before tm_year can hold are a zeroed struct tm
+        for date in [
+            Date { d: 0, m: 0, y: 0 },
+            Date::from(31, 4, 2024),
+            Date::MIN,
+        ] {
+            let t = date.to_tm();
+            assert_eq!((t.tm_year, t.tm_mon, t.tm_mday, t.tm_yday), (0, 0, 0, 0));
+        }
+    }
+
+    // Returns the lock of the local timezone, the tests which read the local timezone don't
+    // run while the differential test changes the TZ environment variable
+    fn lock_local_timezone() -> std::sync::MutexGuard<'static, ()> {
+        static LOCAL_TIMEZONE: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        LOCAL_TIMEZONE.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    #[test]
+    fn test_local_date() {
+        use crate::date_and_time::local::*;
+        let _timezone = lock_local_timezone();
+        let local = get_local_date();
+        let utc = Date::from_system_date();
+        assert!(local.diff_in_days(&utc).abs() <= 1);
+        let now = get_local_datetime();
+        let utc = DateTime::from_system_clock();
+        let offset = now.diff(&utc).as_seconds().abs();
+        assert!(offset <= 26 * 3_600);
+        assert!(!get_timezone_name().is_empty());
+        assert!(!get_timezone_abbreviation().is_empty());
+    }
+
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    #[test]
+    fn test_local_datetime_before_epoch() {
+        use crate::date_and_time::local::*;
+        let _timezone = lock_local_timezone();
+        // a system clock before 1970 is valid, the seconds are negative
+        let nanos = -1_500_000_000;
+        let local = get_local_datetime_at(nanos);
+        let utc = DateTime::from_epoch_nanos(nanos as i64);
+        assert_eq!(utc.as_string(), "1969-12-31T23:59:58.500000000");
+        assert_eq!(local.nanos, 500_000_000);
+        assert!(is_date_valid(&local.date));
+        assert!(local.diff(&utc).as_seconds().abs() <= 26 * 3_600);
+    }
+
+    #[cfg(all(feature = "local", target_os = "windows"))]
+    #[test]
+    fn test_systemtime_interop() {
+        use windows_sys::Win32::Foundation::SYSTEMTIME;
+        let dt = DateTime::from(Date::from(22, 6, 2024), Time::from(18, 30, 5));
+        let st = SYSTEMTIME::try_from(dt).unwrap();
+        assert_eq!(
+            (st.wYear, st.wMonth, st.wDay, st.wDayOfWeek),
+            (2024, 6, 22, 6)
+        );
+        let back: DateTime = st.into();
+        assert_eq!(back, dt);
+        // the years out of 1601 to 30827 and the invalid dates don't wrap around
+        for date in [
+            Date::from(31, 12, 1600),
+            Date::from(1, 1, 30_828),
+            Date::from(1, 1, -2024),
+            Date { d: 0, m: 0, y: 0 },
+        ] {
+            assert!(SYSTEMTIME::try_from(date).is_err(), "{:?}", date);
+        }
+        assert!(SYSTEMTIME::try_from(Date::from(1, 1, 1601)).is_ok());
+    }
+
+    #[test]
+    fn test_std_systemtime_conversion() {
+        use std::time::SystemTime;
+        for nanos in [
+            0,
+            1,
+            -1,
+            1_700_000_000_123_456_789,
+            -2_208_988_800_000_000_001,
+        ] {
+            let dt = DateTime::from_nanoseconds(nanos);
+            let st = SystemTime::try_from(dt).unwrap();
+            let back: DateTime = st.into();
+            assert_eq!(back, dt, "{}", nanos);
+        }
+        let epoch: DateTime = SystemTime::UNIX_EPOCH.into();
+        assert_eq!(epoch.as_string(), "1970-01-01T00:00:00");
+    }
+
+    use crate::date_and_time::offset::*;
+    #[test]
+    fn test_utc_offset() {
+        let india = UtcOffset::from_seconds(19_800);
+        assert_eq!((india.offset_hours(), india.offset_minutes()), (5, 30));
+        assert_eq!(india.as_string(), "+05:30");
+        let newfoundland = UtcOffset::from_hours_minutes(-3, -30);
+        assert_eq!(newfoundland.as_seconds(), -12_600);
+        assert_eq!(newfoundland.as_string(), "-03:30");
+        // the local mean time of Amsterdam until 1937
+        assert_eq!(UtcOffset::from_seconds(1_172).as_string(), "+00:19:32");
+        let _timezone = lock_local_timezone();
+        let local = crate::date_and_time::local::get_gmt_offset();
+        assert!(local.as_seconds().abs() <= 14 * 3_600);
+    }
+
+    use crate::date_and_time::clock::*;
+    #[test]
+    fn test_clock() {
+        let mut clock = FixedClock::new(DateTime::from_timestamp(1_704_067_199));
+        assert_eq!(Date::today_with(&clock), Date::from(31, 12, 2023));
+        assert_eq!(Time::now_with(&clock), Time::from(23, 59, 59));
+        clock.advance(&Duration::from_seconds(1));
+        let dyn_clock: &dyn Clock = &clock;
+        assert_eq!(
+            DateTime::now_with(dyn_clock).as_string(),
+            "2024-01-01T00:00:00"
+        );
+        clock.set(DateTime::from_timestamp(0));
+        assert_eq!(clock.now(), DateTime::from_timestamp(0));
+
+        let frozen = DateTime::from_timestamp(1_000_000_000);
+        freeze(frozen);
+        assert_eq!(SystemClock.now(), frozen);
+        // the freeze is only visible in this thread
+        let other = std::thread::spawn(|| SystemClock.now()).join().unwrap();
+        assert!(other > frozen);
+        reset();
+        travel(Duration::from_seconds(-3_600));
+        travel(Duration::from_seconds(-3_600));
+        let past = SystemClock.now();
+        reset();
+        let diff = past.diff(&DateTime::from_system_clock()).as_seconds();
+        assert!((7_200..7_260).contains(&diff));
+        assert!(SystemClock
+            .now()
+            .approx_eq(&DateTime::from_system_clock(), Duration::from_seconds(5)));
+    }
+
+    #[test]
+    fn test_compare_with_now() {
+        // a token which expires after one hour
+        let issued = DateTime::from_timestamp(1_719_792_000);
+        let expires = issued.add_duration(&Duration::from_seconds(3_600));
+        let mut clock = FixedClock::new(issued);
+        assert!(expires.is_after_now(&clock) && !expires.is_before_now(&clock));
+        assert_eq!(expires.elapsed_since(&clock).as_seconds(), -3_600);
+        clock.advance(&Duration::from_seconds(3_600));
+        assert!(!expires.is_after_now(&clock) && !expires.is_before_now(&clock));
+        assert_eq!(expires.elapsed_since(&clock), Duration::new());
+        clock.advance(&Duration::from_nanoseconds(1));
+        assert!(expires.is_before_now(&clock));
+        assert_eq!(
+            issued.elapsed_since(&clock).as_nanoseconds(),
+            3_600_000_000_001
+        );
+        // the frozen SystemClock works the same
+        freeze(issued);
+        assert!(expires.is_after_now(&SystemClock));
+        reset();
+        assert!(expires.is_before_now(&SystemClock));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_interop() {
+        use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+        let dates = [
+            Date::from(1, 1, 1970),
+            Date::from(29, 2, 2024),
+            Date::from(31, 12, -44),
+        ];
+        for date in dates {
+            let naive = NaiveDate::try_from(date).unwrap();
+            let back: Date = naive.into();
+            assert_eq!(back, date);
+        }
+        assert!(NaiveDate::try_from(Date::from(0, 0, 0)).is_err());
+        assert!(NaiveDate::try_from(Date::from(1, 1, 300_000)).is_err());
+        assert!(NaiveTime::try_from(Time::from(0, -1, -1)).is_err());
+        assert!(NaiveTime::try_from(Time::from(24, 0, 0)).is_err());
+        let mut dt = DateTime::from_timestamp(-1);
+        dt.nanos = 999_999_999;
+        let naive = NaiveDateTime::try_from(dt).unwrap();
+        let back: DateTime = naive.into();
+        assert_eq!(back, dt);
+        let leap = NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_time(NaiveTime::from_hms_nano_opt(23, 59, 59, 1_500_000_000).unwrap());
+        let leap: DateTime = leap.into();
+        assert_eq!(leap.as_string(), "2016-12-31T23:59:59.500000000");
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_interop() {
+        let date = Date::from(29, 2, 2024);
+        let back: Date = ::time::Date::try_from(date).unwrap().into();
+        assert_eq!(back, date);
+        assert!(::time::Date::try_from(Date::from(1, 1, 10_000)).is_err());
+        assert!(::time::Time::try_from(Time::from(0, -1, -1)).is_err());
+        let mut dt = DateTime::from_timestamp(-1);
+        dt.nanos = 999_999_999;
+        let odt = ::time::OffsetDateTime::try_from(dt).unwrap();
+        assert_eq!(odt.unix_timestamp(), -1);
+        let back: DateTime = odt.into();
+        assert_eq!(back, dt);
+        let tokyo = odt.to_offset(::time::UtcOffset::from_hms(9, 0, 0).unwrap());
+        let back: DateTime = tokyo.into();
+        assert_eq!(back, dt);
+        let offset = UtcOffset::from_seconds(-34_200);
+        let back: UtcOffset = ::time::UtcOffset::try_from(offset).unwrap().into();
+        assert_eq!(back, offset);
+        assert!(::time::UtcOffset::try_from(UtcOffset::from_hours_minutes(26, 0)).is_err());
+    }
+
+    use crate::date_and_time::quarter::*;
+    #[test]
+    fn test_year_quarter() {
+        let mut quarters = vec![YearQuarter::parse("2024-Q3").unwrap()];
+        for _ in 0..4 {
+            quarters.push(quarters.last().unwrap().succ().unwrap());
+        }
+        let names: Vec<String> = quarters.iter().map(|q| q.as_string()).collect();
+        assert_eq!(
+            names,
+            ["2024-Q3", "2024-Q4", "2025-Q1", "2025-Q2", "2025-Q3"]
+        );
+        assert!(quarters
+            .windows(2)
+            .all(|w| w[0] < w[1] && w[1].pred() == Some(w[0])));
+        let days: usize = quarters[1..].iter().map(|q| q.dates().count()).sum();
+        assert_eq!(days, 92 + 90 + 91 + 92);
+        for quarter in &quarters {
+            assert!(quarter.dates().all(|date| quarter.contains(&date)));
+            assert_eq!(
+                YearQuarter::from_date(&quarter.last_day()).unwrap(),
+                *quarter
+            );
+            assert!(!quarter.contains(&quarter.succ().unwrap().first_day()));
+        }
+        for text in ["2024-Q5", "2024-Q", "2024-Q12", "Q3-2024", "2024-3"] {
+            assert!(YearQuarter::parse(text).is_err(), "{}", text);
+        }
+        assert!(YearQuarter::from_date(&Date::from(30, 2, 2024)).is_err());
+        let q4 = YearQuarter::new(2024, 4).unwrap();
+        assert_eq!((q4.year(), q4.quarter()), (2024, 4));
+        let next = q4.succ().unwrap();
+        assert_eq!((next.year(), next.quarter()), (2025, 1));
+        // the quarters at the ends of the years of an i32 don't overflow
+        let last = YearQuarter::new(i32::MAX, 4).unwrap();
+        assert_eq!(last.succ(), None);
+        assert_eq!(last.pred(), YearQuarter::new(i32::MAX, 3).ok());
+        let first = YearQuarter::new(i32::MIN, 1).unwrap();
+        assert_eq!(first.pred(), None);
+        assert_eq!(first.succ(), YearQuarter::new(i32::MIN, 2).ok());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_year_quarter_deserialize() {
+        use serde::de::value::{Error, StrDeserializer};
+        use serde::de::{Deserialize, IntoDeserializer};
+        let text: StrDeserializer<Error> = "2024-Q3".into_deserializer();
+        assert_eq!(
+            YearQuarter::deserialize(text),
+            Ok(YearQuarter::new(2024, 3).unwrap())
+        );
+        let text: StrDeserializer<Error> = "2024-Q9".into_deserializer();
+        assert!(YearQuarter::deserialize(text).is_err());
+    }
+
+    use crate::date_and_time::summary::*;
+    #[test]
+    fn test_summarize_dates() {
+        assert!(summarize_dates(&[]).is_empty());
+        let date = |day, month| Date::from(day, month, 2024);
+        // weekends in June 2024 and a daily run over the end of the month
+        let mut dates: Vec<Date> = [1, 2, 8, 9, 15, 16, 29]
+            .iter()
+            .map(|&day| date(day, 6))
+            .collect();
+        dates.extend([
+            date(30, 6),
+            date(1, 7),
+            date(2, 7),
+            date(1, 7),
+            Date::from(31, 2, 2024),
+        ]);
+        let summary = summarize_dates(&dates);
+        assert_eq!(summary.len(), 2);
+        assert_eq!(
+            summary[0].as_string(),
+            "Sat–Sun ×3 weeks from 2024-06-01 to 2024-06-16"
+        );
+        assert_eq!(summary[0].count(), 6);
+        assert_eq!(
+            summary[1],
+            DateSpanSummary::Range {
+                start: date(29, 6),
+                end: date(2, 7)
+            }
+        );
+        // a working week which starts on Wednesday, every Thursday and a full week
+        let dates: Vec<Date> = [3, 4, 5, 8, 9, 10, 11, 12, 15, 16, 17, 18, 19, 22, 23]
+            .iter()
+            .map(|&day| date(day, 7))
+            .chain([1, 8, 15, 22].iter().map(|&day| date(day, 8)))
+            .collect();
+        let text = describe_dates(&dates);
+        assert_eq!(
+            text,
+            "2024-07-03 to 2024-07-05 (3 days), plus Mon–Fri ×2 weeks from 2024-07-08 to \
+             2024-07-19, plus 2024-07-22 to 2024-07-23 (2 days), plus Thu ×4 weeks from \
+             2024-08-01 to 2024-08-22"
+        );
+        let total: usize = summarize_dates(&dates).iter().map(|s| s.count()).sum();
+        assert_eq!(total, dates.len());
+    }
+
+    use crate::date_and_time::recurrence::*;
+    #[test]
+    fn test_recurrence_rfc5545_examples() {
+        let days = |rule: &Recurrence, n: usize| -> Vec<String> {
+            rule.occurrences()
+                .take(n)
+                .map(|date| date.as_formated_string("%Y%m%d"))
+                .collect()
+        };
+        let start = Date::from(2, 9, 1997);
+        // daily for 10 occurrences
+        let rule = Recurrence::parse(start, "FREQ=DAILY;COUNT=10").unwrap();
+        assert_eq!(rule.occurrences().last(), Some(Date::from(11, 9, 1997)));
+        // every other week on Monday, Wednesday and Friday until the 23rd December 1997 (the
+        // time of UNTIL is ignored, so the RFC example before the 24th at 09:00 is changed)
+        let text = "FREQ=WEEKLY;INTERVAL=2;UNTIL=19971223T000000Z;WKST=SU;BYDAY=MO,WE,FR";
+        let rule = Recurrence::parse(Date::from(1, 9, 1997), text).unwrap();
+        assert_eq!(rule.occurrences().count(), 25);
+        assert_eq!(
+            days(&rule, 4),
+            ["19970901", "19970903", "19970905", "19970915"]
+        );
+        assert_eq!(rule.occurrences().last(), Some(Date::from(22, 12, 1997)));
+        // every other week on Tuesday and Sunday, the weeks start on Monday or on Sunday
+        let text = "FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU";
+        let rule = Recurrence::parse(Date::from(5, 8, 1997), text).unwrap();
+        assert_eq!(
+            days(&rule, 4),
+            ["19970805", "19970810", "19970819", "19970824"]
+        );
+        let rule = rule.with_week_start(Weekday::Sunday);
+        assert_eq!(
+            days(&rule, 4),
+            ["19970805", "19970817", "19970819", "19970831"]
+        );
+        // monthly on the first Friday, on the third-to-last day and on Friday the 13th
+        let rule = Recurrence::parse(Date::from(5, 9, 1997), "FREQ=MONTHLY;COUNT=10;BYDAY=1FR");
+        let rule = rule.unwrap();
+        assert_eq!(days(&rule, 10)[4..6], ["19980102", "19980206"]);
+        let rule = Recurrence::parse(Date::from(28, 9, 1997), "FREQ=MONTHLY;BYMONTHDAY=-3");
+        assert_eq!(
+            days(&rule.unwrap(), 3),
+            ["19970928", "19971029", "19971128"]
+        );
+        let rule = Recurrence::parse(start, "FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13").unwrap();
+        assert_eq!(
+            days(&rule, 4),
+            ["19980213", "19980313", "19981113", "19990813"]
+        );
+        // yearly on the 20th Monday and on the last days of the months which are Saturdays
+        let rule = Recurrence::parse(start, "FREQ=YEARLY;BYDAY=20MO").unwrap();
+        assert_eq!(days(&rule, 3), ["19980518", "19990517", "20000515"]);
+        let rule = Recurrence::parse(start, "FREQ=YEARLY;BYMONTHDAY=-1;BYDAY=SA").unwrap();
+        assert_eq!(days(&rule, 2), ["19980131", "19980228"]);
+    }
+
+    #[test]
+    fn test_recurrence_rules() {
+        // the 31st of a month and the 29th February are skipped
+        let rule = Recurrence::new(Date::from(31, 1, 2024), Frequency::Monthly).with_count(3);
+        let dates: Vec<u8> = rule.occurrences().map(|date| date.m).collect();
+        assert_eq!(dates, [1, 3, 5]);
+        let leap_day = Recurrence::new(Date::from(29, 2, 2024), Frequency::Yearly);
+        assert_eq!(leap_day.occurrences().nth(1), Some(Date::from(29, 2, 2028)));
+        let leap_day = leap_day.with_leap_day_policy(LeapDayPolicy::Mar1);
+        assert_eq!(leap_day.occurrences().nth(1), Some(Date::from(1, 3, 2025)));
+        // a rule without any date ends, the 31st April doesn't exist
+        let never = Recurrence::new(Date::from(1, 4, 2024), Frequency::Monthly)
+            .with_interval(12)
+            .with_by_month_day(&[31]);
+        assert_eq!(never.occurrences().next(), None);
+        let invalid = Recurrence::new(Date::from(31, 4, 2024), Frequency::Daily);
+        assert_eq!(invalid.occurrences().next(), None);
+        // the text of the rule
+        let text = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,-1FR;COUNT=5;WKST=SU";
+        let error = Recurrence::parse(Date::from(1, 7, 2024), text).unwrap_err();
+        assert!(error.to_string().contains("-1FR"));
+        let text = "FREQ=MONTHLY;INTERVAL=2;BYDAY=MO,-1FR;COUNT=5;WKST=SU";
+        let rule = Recurrence::parse(Date::from(1, 7, 2024), text).unwrap();
+        assert_eq!(rule.as_string(), text);
+        assert_eq!(Recurrence::parse(rule.start, &rule.as_string()), Ok(rule));
+        let invalid_rules = [
+            "INTERVAL=2",
+            "FREQ=HOURLY",
+            "FREQ=DAILY;COUNT=2;UNTIL=20241231",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;BYMONTH=1",
+            "FREQ=WEEKLY;BYMONTHDAY=1",
+            "FREQ=MONTHLY;BYDAY=6MO",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=DAILY;UNTIL=20240230",
+        ];
+        for text in invalid_rules {
+            assert!(
+                Recurrence::parse(Date::from(1, 7, 2024), text).is_err(),
+                "{}",
+                text
+            );
+        }
+        // the builder ignores BYMONTHDAY in a weekly rule like parse() rejects it
+        let weekly = Recurrence::new(Date::from(1, 7, 2024), Frequency::Weekly);
+        let rule = weekly.clone().with_by_month_day(&[1, 15]);
+        assert_eq!(rule, weekly);
+        assert_eq!(Recurrence::parse(rule.start, &rule.as_string()), Ok(rule));
+        // the occurrences at a time of the day
+        let rule = Recurrence::parse(Date::from(1, 7, 2024), "FREQ=DAILY;UNTIL=20240702").unwrap();
+        let times: Vec<DateTime> = rule.occurrences_at(Time::from(8, 0, 0)).collect();
+        assert_eq!(times.len(), 2);
+        assert_eq!(times[1].as_string(), "2024-07-02T08:00:00");
+    }
+
+    use crate::date_and_time::formatted::*;
+    #[test]
+    fn test_formatted_round_trip() {
+        let date_formats = [
+            "%F",
+            "%Y%m%d",
+            "%d.%m.%Y",
+            "%A, %e. %B %Y",
+            "%a %b %d %Y",
+            "%Y-%j",
+            "%Y/%m/%d (%u, %w)",
+        ];
+        let mut days = -800_000;
+        while days < 800_000 {
+            let date = get_date_from_days(days);
+            for format in date_formats {
+                assert!(is_round_trip_date_format(format), "{}", format);
+                let text = date.as_formated_string(format);
+                assert_eq!(Date::parse_formatted(&text, format), Ok(date), "{}", text);
+            }
+            days += 997;
+        }
+        let time_formats = ["%T", "%H%M%S", "%r", "%I.%M.%S%p", "%Hh %Mm %Ss"];
+        for seconds in (0..86_400).step_by(37) {
+            let time = Time::from_seconds(seconds);
+            for format in time_formats {
+                assert!(is_round_trip_time_format(format), "{}", format);
+                let text = time.as_formated_string(format);
+                assert_eq!(Time::parse_formatted(&text, format), Ok(time), "{}", text);
+            }
+        }
+        // the formats which lose a part of the value
+        for format in ["%d.%m.%y", "%m/%d", "%D", "%Y-W%V-%u", "%b %Y", "%G-%j"] {
+            assert!(!is_round_trip_date_format(format), "{}", format);
+        }
+        for format in ["%H:%M", "%I:%M:%S", "%R", "%M:%S"] {
+            assert!(!is_round_trip_time_format(format), "{}", format);
+        }
+        assert!(Date::parse_formatted("2024-366", "%Y-%j").is_ok());
+        assert!(Date::parse_formatted("2023-366", "%Y-%j").is_err());
+        assert!(Date::parse_formatted("2024-02-30", "%F").is_err());
+        assert!(Date::parse_formatted("2024-07-01 ", "%F").is_err());
+        assert!(Time::parse_formatted("13:00:00 PM", "%r").is_err());
+    }
+
+    use crate::date_and_time::ical::*;
+    #[test]
+    fn test_ical_values() {
+        // an invitation in Berlin from 18:30 to 20:00 and an all-day event
+        let start = IcalValue::Zoned {
+            value: DateTime::from(Date::from(22, 6, 2024), Time::from(18, 30, 0)),
+            tzid: String::from("Europe/Berlin"),
+        };
+        let end = start.add_duration(&Duration::parse_ical("PT1H30M").unwrap());
+        assert_eq!(
+            start.dtstart(),
+            "DTSTART;TZID=Europe/Berlin:20240622T183000"
+        );
+        assert_eq!(end.dtend(), "DTEND;TZID=Europe/Berlin:20240622T200000");
+        let day = IcalValue::Date(Date::from(31, 12, 2024));
+        assert_eq!(
+            day.add_duration(&Duration::parse_ical("P1D").unwrap())
+                .dtend(),
+            "DTEND;VALUE=DATE:20250101"
+        );
+        for value in [
+            start,
+            end,
+            day,
+            IcalValue::parse("19970714T173000Z").unwrap(),
+        ] {
+            let line = value.dtstart();
+            assert_eq!(
+                IcalValue::parse_property(&line),
+                Ok((String::from("DTSTART"), value))
+            );
+        }
+        let invalid_lines = [
+            "DTSTART:20240230",
+            "DTSTART:20240622T1830",
+            "DTSTART;TZID=Europe/Berlin:20240622T183000Z",
+            "DTSTART;VALUE=DATE-TIME:20240622",
+            "DTSTART;VALUE=PERIOD:20240622",
+            "DTSTART;TZID:20240622T183000",
+            ":20240622",
+            "DTSTART",
+        ];
+        for line in invalid_lines {
+            assert!(IcalValue::parse_property(line).is_err(), "{}", line);
+        }
+        // a TZID with a semicolon and a colon is quoted
+        let quoted = IcalValue::Zoned {
+            value: DateTime::from(Date::from(22, 6, 2024), Time::from(18, 30, 0)),
+            tzid: String::from("Custom;Zone:1"),
+        };
+        assert_eq!(
+            quoted.dtstart(),
+            "DTSTART;TZID=\"Custom;Zone:1\":20240622T183000"
+        );
+        assert_eq!(
+            IcalValue::parse_property(&quoted.dtstart()),
+            Ok((String::from("DTSTART"), quoted))
+        );
+        // the durations of the RFC 5545 examples
+        assert_eq!(
+            Duration::parse_ical("P15DT5H0M20S").unwrap().as_seconds(),
+            1_314_020
+        );
+        assert_eq!(Duration::parse_ical("P7W").unwrap().as_seconds(), 4_233_600);
+        for seconds in [0, 1, 59, 3_600, 3_601, 86_400, 604_800, 694_861, -90] {
+            let duration = Duration::from_seconds(seconds);
+            assert_eq!(
+                Duration::parse_ical(&duration.as_ical_string()),
+                Ok(duration)
+            );
+        }
+        let too_large = ["P106751991167300DT16H", "PT9223372036854775808S"];
+        for text in ["P", "PT", "P1DT", "P1W2D", "PT1S2M", "P-1D", "1D", "PT1.5S"]
+            .into_iter()
+            .chain(too_large)
+        {
+            assert!(Duration::parse_ical(text).is_err(), "{}", text);
+        }
+    }
+
+    use crate::date_and_time::cron::*;
+    #[test]
+    fn test_cron_schedule() {
+        let at = |d: u8, m: u8, y: i32, h: i32, min: i8| {
+            DateTime::from(Date::from(d, m, y), Time::from(h, min, 0))
+        };
+        let next = |expression: &str, after: DateTime| {
+            CronSchedule::parse(expression)
+                .unwrap()
+                .next_after(&after)
+                .map(|dt| dt.as_string())
+        };
+        let start = at(31, 12, 2024, 23, 59);
+        assert_eq!(next("* * * * *", start).unwrap(), "2025-01-01T00:00:00");
+        assert_eq!(
+            next("@hourly", at(1, 7, 2024, 8, 0)).unwrap(),
+            "2024-07-01T09:00:00"
+        );
+        assert_eq!(next("@monthly", start).unwrap(), "2025-01-01T00:00:00");
+        assert_eq!(
+            next("5/20 * * * *", at(1, 7, 2024, 8, 45)).unwrap(),
+            "2024-07-01T09:05:00"
+        );
+        assert_eq!(
+            next("0 0 * * 7", at(1, 7, 2024, 8, 0)).unwrap(),
+            "2024-07-07T00:00:00"
+        );
+        assert_eq!(
+            next("0 0 31 * *", at(1, 4, 2024, 0, 0)).unwrap(),
+            "2024-05-31T00:00:00"
+        );
+        // the first seven days of the month or any Monday
+        let first_week = "0 9 1-7 * mon";
+        assert_eq!(
+            next(first_week, at(3, 7, 2024, 0, 0)).unwrap(),
+            "2024-07-03T09:00:00"
+        );
+        assert_eq!(
+            next(first_week, at(7, 7, 2024, 9, 0)).unwrap(),
+            "2024-07-08T09:00:00"
+        );
+        assert_eq!(
+            next(first_week, at(29, 7, 2024, 9, 0)).unwrap(),
+            "2024-08-01T09:00:00"
+        );
+        assert_eq!(next("0 9 31 4,6,9,11 *", start), None);
+        // the seconds are ignored, the next time is after the minute
+        let schedule = CronSchedule::parse("30 8 * * *").unwrap();
+        let after = DateTime::from(Date::from(1, 7, 2024), Time::from(8, 30, 59));
+        assert!(schedule.matches(&after));
+        assert_eq!(schedule.next_after(&after).unwrap(), at(2, 7, 2024, 8, 30));
+        let times: Vec<DateTime> = schedule.occurrences_after(&start).take(365).collect();
+        assert_eq!(times[364], at(31, 12, 2025, 8, 30));
+        assert!(times.windows(2).all(|w| w[0] < w[1]));
+        let invalid = [
+            "* * * *",
+            "* * * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "5-1 * * * *",
+            "*/0 * * * *",
+            "1,,2 * * * *",
+            "* * * foo *",
+            "@reboot",
+        ];
+        for expression in invalid {
+            assert!(CronSchedule::parse(expression).is_err(), "{}", expression);
+        }
+        // the invalid dates and the times which are not a time of the clock never match
+        let every = CronSchedule::parse("* * * * *").unwrap();
+        let date = Date::from(1, 7, 2024);
+        for time in [
+            Time::from(10, 70, 0),
+            Time { h: 10, m: 70, s: 0 },
+            Time::from(64, 0, 0),
+            Time::from(24, 0, 0),
+            Time::from(0, 0, -5),
+            Time::from(0, 0, 0).sub_hours(1),
+        ] {
+            let datetime = DateTime {
+                date,
+                time,
+                nanos: 0,
+            };
+            assert!(!every.matches(&datetime), "{:?}", time);
+        }
+        for date in [
+            Date { d: 0, m: 0, y: 0 },
+            Date {
+                d: 31,
+                m: 2,
+                y: 2024,
+            },
+        ] {
+            let datetime = DateTime {
+                date,
+                time: Time::new(),
+                nanos: 0,
+            };
+            assert!(!every.matches(&datetime), "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn test_date_range() {
+        let start = Date::from(28, 12, 2023);
+        let end = Date::from(3, 3, 2024);
+        let forward: Vec<Date> = start.iter_days_until(end).collect();
+        let mut backward: Vec<Date> = start.iter_days_until(end).rev().collect();
+        backward.reverse();
+        assert_eq!(forward.len(), 66);
+        assert_eq!(forward, backward);
+        assert!(forward.windows(2).all(|w| w[0].diff_in_days(&w[1]) == 1));
+        assert_eq!(start.iter_days_through(end).next_back(), Some(end));
+        assert_eq!(start.iter_days_through(end).len(), 67);
+        // both ends of the same range
+        let mut range = start.iter_days_through(Date::from(1, 1, 2024));
+        assert_eq!(range.next_back(), Some(Date::from(1, 1, 2024)));
+        assert_eq!(range.next(), Some(start));
+        assert_eq!(range.len(), 3);
+        assert_eq!(range.nth(1), Some(Date::from(30, 12, 2023)));
+        assert_eq!(range.next(), Some(Date::from(31, 12, 2023)));
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next_back(), None);
+        // the empty ranges
+        assert_eq!(end.iter_days_until(start).count(), 0);
+        assert_eq!(end.iter_days_until(end).count(), 0);
+        assert_eq!(Date::from(30, 2, 2024).iter_days_through(end).count(), 0);
+        assert_eq!(start.iter_days_through(Date::new()).count(), 0);
+    }
+
+    use crate::date_and_time::range::*;
+    #[test]
+    fn test_period_steps() {
+        // the month ends of a year don't drift to the 28th
+        let start = Date::from(31, 1, 2023);
+        let ends: Vec<Date> = start.iter_months_until(Date::from(1, 1, 2024)).collect();
+        assert_eq!(ends.len(), 12);
+        assert!(ends
+            .iter()
+            .all(|date| date.d == get_max_days_of_month(date.m, 2023)));
+        let weeks = Date::from(30, 12, 2024).iter_weeks_until(Date::from(31, 3, 2025));
+        assert_eq!(weeks.clone().count(), 13);
+        assert!(weeks
+            .map(|date| date.get_weekday())
+            .all(|weekday| weekday == 1));
+        let years: Vec<Date> = Date::from(29, 2, 2024)
+            .step_by_period(Date::from(1, 3, 2028), Period::Years(1))
+            .collect();
+        assert_eq!(years[1], Date::from(28, 2, 2025));
+        assert_eq!(years[4], Date::from(29, 2, 2028));
+        let days = Date::from(1, 7, 2024).step_by_period(Date::from(8, 7, 2024), Period::Days(0));
+        assert_eq!(days.count(), 7);
+        // the end is excluded, an invalid date gives no steps
+        let end = Date::from(1, 4, 2024);
+        assert_eq!(
+            Date::from(1, 1, 2024)
+                .step_by_period(end, Period::Quarters(1))
+                .count(),
+            1
+        );
+        assert_eq!(end.iter_months_until(end).count(), 0);
+        assert_eq!(end.iter_months_until(Date::from(31, 4, 2024)).count(), 0);
+    }
+    #[test]
+    fn test_month_grid() {
+        for year in [1900, 2000, 2023, 2024] {
+            for month in 1..=12 {
+                for week_start in [Weekday::Sunday, Weekday::Monday, Weekday::Saturday] {
+                    let date = Date::from(15, month, year);
+                    let grid = date.month_grid(week_start);
+                    assert!((4..=6).contains(&grid.len()));
+                    let days: Vec<Date> = grid.iter().flatten().flatten().copied().collect();
+                    let expected: Vec<Date> = (1..=get_max_days_of_month(month, year))
+                        .map(|day| Date::from(day, month, year))
+                        .collect();
+                    assert_eq!(days, expected);
+                    let padded = date.month_grid_padded(week_start);
+                    assert_eq!(padded[0][0].get_weekday(), week_start as u8);
+                    assert!(padded
+                        .iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .windows(2)
+                        .all(|w| w[0].diff_in_days(w[1]) == 1));
+                }
+            }
+        }
+        assert!(Date::from(30, 2, 2024)
+            .month_grid(Weekday::Monday)
+            .is_empty());
+    }
+    #[test]
+    fn test_iso_week() {
+        // the week 1 starts with the Monday on or before the 4th January
+        let week_1 = |year: i32| Date::from(4, 1, year).start_of_week(Weekday::Monday);
+        for year in 1981..2060 {
+            let (start, end) = (week_1(year), week_1(year + 1));
+            for date in start.iter_days_until(end) {
+                let week = start.diff_in_days(&date) / 7 + 1;
+                assert_eq!(
+                    date.get_iso_week(),
+                    (year, week as u8),
+                    "{}",
+                    date.as_string()
+                );
+            }
+        }
+        // the long years start or end with a Thursday
+        for year in 1981..2060 {
+            let weeks = Date::from(28, 12, year).get_iso_week_of_year();
+            let thursday = Date::from(1, 1, year).get_weekday() == 4
+                || Date::from(31, 12, year).get_weekday() == 4;
+            assert_eq!(weeks == 53, thursday, "{}", year);
+        }
+        let date = Date::from(1, 1, 2010);
+        assert_eq!(date.as_formated_string("%G-W%V-%u, %g"), "2009-W53-5, 09");
+        assert_eq!(
+            Date::from(4, 1, 2010).as_formated_string("%G-W%V"),
+            "2010-W01"
+        );
+        // the Thursday of the last week is after Date::MAX
+        assert_eq!(Date::MAX.get_iso_week(), (i32::MAX, 1));
+        assert_eq!(Date::from(29, 12, i32::MAX).get_iso_week(), (i32::MAX, 52));
+        assert_eq!(Date::MIN.get_iso_week(), (i32::MIN, 1));
+        assert_eq!(
+            Date::MAX.as_formated_string("%G-W%V"),
+            format!("{}-W01", i32::MAX)
+        );
+    }
+    #[test]
+    fn test_date_from_ordinal() {
+        for year in [1900, 2000, 2023, 2024, -4] {
+            for date in Date::from(1, 1, year).iter_days_through(Date::from(31, 12, year)) {
+                assert_eq!(Date::from_ordinal(year, date.get_day_of_year()), Ok(date));
+            }
+        }
+        assert!(Date::from_ordinal(1900, 366).is_err());
+        assert!(Date::from_ordinal(2000, 367).is_err());
+    }
+    #[test]
+    fn test_date_from_iso_week_date() {
+        let start = Date::from(1, 1, 1995);
+        for date in start.iter_days_until(Date::from(1, 1, 2035)) {
+            let (year, week) = date.get_iso_week();
+            let weekday = date.as_formated_string("%u").parse().unwrap();
+            assert_eq!(Date::from_iso_week_date(year, week, weekday), Ok(date));
+        }
+        assert!(Date::from_iso_week_date(2026, 53, 7).is_ok());
+        assert!(Date::from_iso_week_date(2025, 53, 1).is_err());
+        assert!(Date::from_iso_week_date(2025, 0, 1).is_err());
+        assert!(Date::from_iso_week_date(2025, 1, 8).is_err());
+        // the week 1 of the year i32::MIN starts before Date::MIN, this is an error and not
+        // the invalid date
+        assert!(Date::from_iso_week_date(i32::MIN, 1, 1).is_err());
+        assert_eq!(Date::from_iso_week_date(i32::MIN, 1, 2), Ok(Date::MIN));
+        assert_eq!(
+            Date::from_iso_week_date(i32::MAX, 52, 7),
+            Ok(Date::from(29, 12, i32::MAX))
+        );
+    }
+    #[test]
+    fn test_rata_die() {
+        // the day numbers of "Calendrical Calculations"
+        let samples = [
+            (-586, 7, 24, -214_193),
+            (1096, 5, 24, 400_085),
+            (1945, 11, 12, 710_347),
+        ];
+        for (y, m, d, rata_die) in samples {
+            let date = Date::from(d, m, y);
+            assert_eq!(date.to_rata_die(), Some(rata_die), "{}", date.as_string());
+            assert_eq!(Date::from_rata_die(rata_die), date);
+        }
+        for rata_die in (-800_000..1_500_000).step_by(331) {
+            assert_eq!(Date::from_rata_die(rata_die).to_rata_die(), Some(rata_die));
+        }
+        assert_eq!(Date::from(31, 12, 0).to_rata_die(), Some(0));
+        assert_eq!(Date::from(31, 2, 2024).to_rata_die(), None);
+    }
+    #[test]
+    fn test_add_months_with() {
+        let day = Date::from(31, 10, 2023);
+        let clamped: Vec<String> = (0..6)
+            .map(|n| day.add_months_with(n, MonthEndPolicy::Clamp).unwrap())
+            .map(|date| date.as_string())
+            .collect();
+        assert_eq!(
+            clamped,
+            [
+                "2023-10-31",
+                "2023-11-30",
+                "2023-12-31",
+                "2024-01-31",
+                "2024-02-29",
+                "2024-03-31"
+            ]
+        );
+        let overflow = Date::from(30, 1, 2023).add_months_with(1, MonthEndPolicy::Overflow);
+        assert_eq!(overflow, Ok(Date::from(2, 3, 2023)));
+        let overflow = Date::from(31, 12, 2024).sub_months_with(10, MonthEndPolicy::Overflow);
+        assert_eq!(overflow, Ok(Date::from(2, 3, 2024)));
+        let back = Date::from(31, 3, 2024).sub_months_with(13, MonthEndPolicy::Clamp);
+        assert_eq!(back, Ok(Date::from(28, 2, 2023)));
+        assert!(day.sub_months_with(1, MonthEndPolicy::Error).is_err());
+        assert!(Date::from(31, 2, 2024)
+            .add_months_with(1, MonthEndPolicy::Clamp)
+            .is_err());
+        assert!(Date::from(1, 12, i32::MAX)
+            .add_months_with(1, MonthEndPolicy::Clamp)
+            .is_err());
+    }
+    #[test]
+    fn test_add_months() {
+        for y in [-1, 0, 1999, 2000, 2023, 2024] {
+            for m in 1..=12u8 {
+                for d in [1, 15, 28, 29, 30, 31] {
+                    let date = Date::from(d, m, y);
+                    if !is_date_valid(&date) {
+                        continue;
+                    }
+                    for months in -30..=30 {
+                        let month = y * 12 + m as i32 - 1 + months;
+                        let (new_y, new_m) = (month.div_euclid(12), month.rem_euclid(12) as u8 + 1);
+                        let result = date.add_months(months);
+                        assert_eq!(result, date.sub_months(-months));
+                        if d <= get_max_days_of_month(new_m, new_y) {
+                            assert_eq!(
+                                result,
+                                Date::from(d, new_m, new_y),
+                                "{:?} {}",
+                                date,
+                                months
+                            );
+                            assert_eq!(result.sub_months(months), date);
+                        } else {
+                            assert_eq!(result, Date { d: 0, m: 0, y: 0 });
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            Date::from(1, 12, 2024).add_months(12),
+            Date::from(1, 12, 2025)
+        );
+        assert_eq!(
+            Date::from(1, 1, 2024).sub_months(1),
+            Date::from(1, 12, 2023)
+        );
+        assert_eq!(
+            Date::from(1, 1, 2024).sub_months(12),
+            Date::from(1, 1, 2023)
+        );
+        assert_eq!(Date::from(1, 6, 2024).add_months(i32::MIN).y, -178_954_947);
+        assert_eq!(Date::from(1, 6, i32::MAX).add_months(7).m, 0);
+    }
+    #[test]
+    fn test_date_min_max() {
+        assert!(Date::MIN < Date::MAX);
+        assert_eq!(Date::from(1, 1, i32::MIN), Date::MIN);
+        assert_eq!(Date::from_ymd(i32::MAX, 12, 31), Date::MAX);
+        assert_eq!(Date::MIN.add_days(1), Date::from(2, 1, i32::MIN));
+        assert_eq!(Date::MAX.sub_days(1), Date::from(30, 12, i32::MAX));
+        assert_eq!(Date::MIN.sub_days(1), Date { d: 0, m: 0, y: 0 });
+        assert_eq!(Date::MAX.add_days(1), Date { d: 0, m: 0, y: 0 });
+        assert_eq!(Date::MIN.sub_days(u64::MAX), Date { d: 0, m: 0, y: 0 });
+        assert_eq!(Date::MAX.add_months(1), Date { d: 0, m: 0, y: 0 });
+        assert_eq!(Date::MIN.sub_years(1), Date { d: 0, m: 0, y: 0 });
+        assert_eq!(Date::MAX.add_years_with(1, LeapDayPolicy::Feb28), None);
+        let max = Date::MAX.to_rata_die().unwrap();
+        assert_eq!(Date::from_rata_die(max), Date::MAX);
+        assert_eq!(Date::from_rata_die(max + 1).m, 0);
+        assert_eq!(Date::from_rata_die(i64::MIN).m, 0);
+        let last = DateTime::from(Date::MAX, Time::from(23, 59, 59));
+        assert_eq!(last.add_duration(&Duration::from_seconds(1)).date.m, 0);
+        assert_eq!(DateTime::from_nanoseconds(i128::MAX).date.m, 0);
+    }
+    #[test]
+    fn test_date_with_fields() {
+        let date = Date::from(31, 1, 2024);
+        assert_eq!(date.with_day(1), Ok(Date::from(1, 1, 2024)));
+        assert_eq!(date.with_month(3), Ok(Date::from(31, 3, 2024)));
+        assert_eq!(date.with_year(-44), Ok(Date::from(31, 1, -44)));
+        assert!(date.with_day(0).is_err());
+        assert!(date.with_day(32).is_err());
+        assert!(date.with_month(0).is_err());
+        assert!(date.with_month(2).is_err());
+        // the date is kept and a chain can change the order of the fields
+        assert_eq!(date, Date::from(31, 1, 2024));
+        let chained = date.with_day(29).and_then(|d| d.with_month(2));
+        assert_eq!(chained, Ok(Date::from(29, 2, 2024)));
+        assert!(chained.and_then(|d| d.with_year(2023)).is_err());
+        assert!(Date::from(30, 2, 2024).with_year(2025).is_err());
+    }
+
+    use crate::date_and_time::builder::*;
+    #[test]
+    fn test_builders() {
+        let config: [Option<u8>; 2] = [Some(6), None];
+        let date = DateBuilder::new()
+            .year(2024)
+            .month_opt(config[0])
+            .day_opt(config[1])
+            .build();
+        assert_eq!(date, Ok(Date::from(1, 6, 2024)));
+        // None keeps the field which was set before
+        let date = DateBuilder::new().day(9).day_opt(None).build();
+        assert_eq!(date, Ok(Date::from(9, 1, 0)));
+        let date = DateBuilder::new()
+            .defaults(Date::from(31, 1, 2024))
+            .month(4)
+            .build();
+        assert!(date.is_err());
+        assert!(DateBuilder::new().month(13).build().is_err());
+        let now = DateTime::from(Date::from(22, 6, 2024), Time::from(18, 30, 15));
+        let dt = DateTimeBuilder::new()
+            .defaults(now)
+            .day(23)
+            .hour_opt(Some(7))
+            .nanos(5)
+            .build()
+            .unwrap();
+        assert_eq!(dt.as_string(), "2024-06-23T07:30:15.000000005");
+        let dt = DateTimeBuilder::new().year(2024).month(2).day(29).build();
+        assert_eq!(dt.unwrap().as_string(), "2024-02-29T00:00:00");
+        assert!(DateTimeBuilder::new().minute(60).build().is_err());
+        assert!(DateTimeBuilder::new().second(60).build().is_err());
+        assert!(DateTimeBuilder::new().day(0).build().is_err());
+    }
+
+    #[test]
+    fn test_diff_ymd() {
+        let start = Date::from(31, 1, 2023);
+        for offset in 0..800 {
+            let end = start.add_days(offset * 3);
+            let (years, months, days) = start.diff_ymd(&end);
+            assert!((0..12).contains(&months) && (0..31).contains(&days));
+            let anchor = start
+                .add_months_with(years as i32 * 12 + months, MonthEndPolicy::Clamp)
+                .unwrap();
+            assert_eq!(anchor.add_days(days as u64), end, "{}", end.as_string());
+            assert_eq!(end.diff_ymd(&start), (-years, -months, -days));
+        }
+        let leap_day = Date::from(29, 2, 2024);
+        assert_eq!(leap_day.diff_ymd(&Date::from(28, 2, 2025)), (1, 0, 0));
+        assert_eq!(leap_day.diff_ymd(&Date::from(1, 3, 2025)), (1, 0, 1));
+        assert_eq!(leap_day.diff_ymd(&Date::from(31, 2, 2025)), (0, 0, 0));
+        // the years between the first and the last date don't fit into an i32
+        let years = u32::MAX as i64;
+        assert_eq!(Date::MIN.diff_ymd(&Date::MAX), (years, 11, 30));
+        assert_eq!(Date::MAX.diff_ymd(&Date::MIN), (-years, -11, -30));
+        assert_eq!(
+            Date::MIN.diff_ymd(&Date::from(1, 1, i32::MAX)),
+            (years, 0, 0)
+        );
+        let start = DateTime::from(Date::from(31, 12, 2023), Time::from(23, 59, 59));
+        let mut end = DateTime::from(Date::from(1, 1, 2024), Time::from(0, 0, 0));
+        end.nanos = 5;
+        let diff = start.diff_breakdown(&end);
+        assert_eq!(
+            diff,
+            DateTimeDiff {
+                seconds: 1,
+                nanos: 5,
+                ..DateTimeDiff::default()
+            }
+        );
+        assert_eq!(end.diff_breakdown(&start).nanos, -5);
+        let later = DateTime::from(Date::from(31, 1, 2025), Time::from(23, 59, 59));
+        let diff = start.diff_breakdown(&later);
+        assert_eq!(
+            (diff.years, diff.months, diff.days, diff.hours),
+            (1, 1, 0, 0)
+        );
+    }
+
+    use crate::date_and_time::julian::*;
+    #[test]
+    fn test_julian_calendar() {
+        // the difference grows by one day in every century year without a Gregorian leap day
+        let samples = [
+            (200, 3, 1, 0),
+            (1582, 10, 15, 10),
+            (1700, 3, 12, 11),
+            (2100, 3, 15, 14),
+        ];
+        for (y, m, d, difference) in samples {
+            let gregorian = Date::from(d, m, y);
+            let julian = gregorian.to_julian_calendar();
+            let days = get_days_from_date(&Date::from(julian.d, julian.m, julian.y));
+            assert_eq!(
+                days - get_days_from_date(&gregorian),
+                -difference,
+                "{}",
+                gregorian.as_string()
+            );
+        }
+        // every day converts back, the Julian leap days too
+        let mut last = Date::from(1, 1, -500).to_julian_calendar();
+        for gregorian in Date::from(2, 1, -500).iter_days_until(Date::from(1, 1, 2500)) {
+            let julian = gregorian.to_julian_calendar();
+            assert!(
+                JulianDate::new(julian.y, julian.m, julian.d).is_ok(),
+                "{}",
+                julian.as_string()
+            );
+            assert!(julian > last);
+            assert_eq!(julian.to_gregorian(), gregorian);
+            last = julian;
+        }
+        assert_eq!(
+            Date::from_julian_calendar(1900, 2, 29),
+            Ok(Date::from(13, 3, 1900))
+        );
+        assert!(Date::from_julian_calendar(2023, 2, 29).is_err());
+        assert_eq!(Date::from(29, 2, 2023).to_julian_calendar().m, 0);
+    }
+    #[test]
+    fn test_filetime() {
+        // the leap day 2024 at 12:34:56.789 UTC
+        let dt = DateTime::from_filetime(133_536_836_967_890_000);
+        assert_eq!(dt.as_string(), "2024-02-29T12:34:56.789000000");
+        assert_eq!(dt.to_filetime(), Ok(133_536_836_967_890_000));
+        for filetime in [
+            0,
+            1,
+            99,
+            116_444_736_000_000_000,
+            250_000_000_000_000_000,
+            u64::MAX,
+        ] {
+            assert_eq!(
+                DateTime::from_filetime(filetime).to_filetime(),
+                Ok(filetime)
+            );
+        }
+        let mut before = DateTime::from_filetime(0);
+        before.nanos = 0;
+        assert!(before
+            .sub_duration(&Duration::from_nanoseconds(100))
+            .to_filetime()
+            .is_err());
+        let invalid = DateTime::from(Date::from(31, 4, 2024), Time::new());
+        assert!(invalid.to_filetime().is_err());
+    }
+
+    #[cfg(feature = "leap-seconds")]
+    #[test]
+    fn test_leap_seconds() {
+        use crate::date_and_time::leap_seconds::*;
+        let table = LeapSecondTable::builtin();
+        // every UTC second around the leap second of 2016 and back
+        let start = DateTime::from(Date::from(31, 12, 2016), Time::from(23, 59, 58));
+        let tai: Vec<String> = (0..3)
+            .map(|s| table.utc_to_tai(&DateTime::from_timestamp(start.as_timestamp() + s)))
+            .map(|dt| dt.as_string())
+            .collect();
+        assert_eq!(
+            tai,
+            [
+                "2017-01-01T00:00:34",
+                "2017-01-01T00:00:35",
+                "2017-01-01T00:00:37"
+            ]
+        );
+        let utc: Vec<String> = (33..39)
+            .map(|s| DateTime::from(Date::from(1, 1, 2017), Time::from(0, 0, s)))
+            .map(|dt| table.tai_to_utc(&dt).as_string())
+            .collect();
+        assert_eq!(
+            utc,
+            [
+                "2016-12-31T23:59:57",
+                "2016-12-31T23:59:58",
+                "2016-12-31T23:59:59",
+                "2016-12-31T23:59:59",
+                "2017-01-01T00:00:00",
+                "2017-01-01T00:00:01"
+            ]
+        );
+        // the GPS time of a GNSS log is 18 seconds ahead since 2017
+        let mut gps = DateTime::from(Date::from(5, 3, 2023), Time::from(8, 15, 18));
+        gps.nanos = 500_000_000;
+        assert_eq!(
+            table.gps_to_utc(&gps).as_string(),
+            "2023-03-05T08:15:00.500000000"
+        );
+        assert_eq!(table.utc_to_gps(&table.gps_to_utc(&gps)), gps);
+        let before = DateTime::from(Date::from(1, 1, 1960), Time::new());
+        assert_eq!(table.tai_minus_utc(&before), 10);
+        let list = "# comment\n#@ 3960057600\n2272060800 10\n\n2287785600 11 # 1 Jul 1972\n";
+        let parsed = LeapSecondTable::parse_leap_seconds_list(list).unwrap();
+        let july = DateTime::from(Date::from(1, 7, 1972), Time::new());
+        assert_eq!(parsed.tai_minus_utc(&july), 11);
+        assert_eq!(parsed.leap_seconds_between(&before, &july), 1);
+        assert!(LeapSecondTable::parse_leap_seconds_list("2272060800 ten").is_err());
+        assert!(LeapSecondTable::parse_leap_seconds_list("2272060800 10 5").is_err());
+        assert!(LeapSecondTable::parse_leap_seconds_list("-9223372036854775808 10").is_err());
+        assert!(LeapSecondTable::parse_leap_seconds_list("#@ -9223372036854775808").is_err());
+        // the elapsed seconds over the leap seconds of 1998 and 2005
+        let start = DateTime::from(Date::from(31, 12, 1998), Time::from(23, 59, 59));
+        let mut end = DateTime::from(Date::from(1, 1, 2006), Time::new());
+        end.nanos = 500_000_000;
+        let seconds = table.diff_in_seconds(&start, &end);
+        assert_eq!(seconds.posix, start.diff_in_seconds(&end));
+        assert_eq!(seconds.real, seconds.posix + 2);
+        let back = table.diff_in_seconds(&end, &start);
+        assert_eq!((back.posix, back.real), (-seconds.posix, -seconds.real));
+        assert_eq!(start.diff_in_real_seconds(&start).real, 0);
+    }
+
+    use crate::date_and_time::schedule::*;
+    #[test]
+    fn test_compare_schedules() {
+        let window = |from: i64, to: i64| {
+            TimeInterval::new(
+                Time::from_seconds(from * 3_600),
+                Time::from_seconds(to * 3_600),
+            )
+            .unwrap()
+        };
+        let mut old = AvailabilityRule::new();
+        old.add(1, window(9, 12))
+            .add(1, window(13, 18))
+            .add(6, window(10, 14));
+        assert!(compare_schedules(&old, &old.clone()).is_empty());
+        // the lunch break is removed, Saturday is closed and Sunday is open
+        let mut new = AvailabilityRule::new();
+        new.add(1, window(9, 18)).add(0, window(12, 24));
+        let changes: Vec<(u8, ChangeKind, String)> = compare_schedules(&old, &new)
+            .iter()
+            .map(|c| (c.weekday, c.kind, c.interval.as_string()))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                (0, ChangeKind::Added, String::from("12:00:00-24:00:00")),
+                (1, ChangeKind::Added, String::from("12:00:00-13:00:00")),
+                (6, ChangeKind::Removed, String::from("10:00:00-14:00:00")),
+            ]
+        );
+        let inner = compare_schedules(&new, &old);
+        assert_eq!(inner.len(), 3);
+        assert!(inner
+            .iter()
+            .all(|c| c.kind != ChangeKind::Added || c.weekday == 6));
+        // the negative and the invalid fields of a time are rejected
+        let one = Time::from(1, 0, 0);
+        assert!(TimeInterval::new(Time::from(0, 0, -5), one).is_err());
+        assert!(TimeInterval::new(Time::from(0, -1, 0), one).is_err());
+        assert!(TimeInterval::new(Time::from(0, 0, 75), one).is_err());
+        assert!(TimeInterval::new(Time::from(0, 0, 0), Time::from(0, 90, 0)).is_err());
+    }
+
+    use crate::date_and_time::histogram::*;
+    #[test]
+    fn test_render_histogram() {
+        use std::collections::BTreeMap;
+        let mut counts = BTreeMap::new();
+        assert_eq!(render_histogram(&counts, 10), "");
+        counts.insert(Date::from(31, 12, 2024), 0);
+        assert_eq!(render_histogram(&counts, 10), " \n2024-12-31");
+        // a year in 12 columns, every column has about 30 days
+        for day in 0..366 {
+            counts.insert(Date::from(1, 1, 2024).add_days(day), 1);
+        }
+        let chart = render_histogram_with(&counts, 12, "%b");
+        assert_eq!(chart, "@@@@@@@@@@@@\nJan      Dec");
+        counts.insert(Date::from(15, 6, 2024), 31);
+        let chart = render_histogram(&counts, 12);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines, ["+++++@++++++", "2024-01-01", "2024-12-31"]);
+        assert_eq!(render_histogram(&counts, 0), "");
+        // the invalid dates are skipped instead of panicking
+        let mut counts = BTreeMap::new();
+        counts.insert(
+            Date {
+                d: 31,
+                m: 2,
+                y: 2024,
+            },
+            5,
+        );
+        counts.insert(Date::from(1, 3, 2024), 1);
+        assert_eq!(render_histogram(&counts, 10), "@\n2024-03-01");
+        counts.insert(
+            Date {
+                d: 99,
+                m: 13,
+                y: 2024,
+            },
+            5,
+        );
+        assert_eq!(render_histogram(&counts, 10), "@\n2024-03-01");
+        counts.remove(&Date::from(1, 3, 2024));
+        assert_eq!(render_histogram(&counts, 10), "");
+    }
+
+    #[test]
+    fn test_weekday_hour_buckets() {
+        let buckets: Vec<u16> = [(0, 0), (0, 14), (0, 15), (23, 59), (24, 15)]
+            .iter()
+            .map(|&(h, m)| Time::from(h, m, 30).bucket(15))
+            .collect();
+        assert_eq!(buckets, [0, 0, 1, 95, 1]);
+        assert_eq!(Time::from(23, 59, 59).bucket(0), 1_439);
+        assert_eq!(Time::from(12, 0, 0).bucket(u16::MAX), 0);
+        // every hour of the week from Sunday 30th June 2024, and the Monday noon again
+        let start = DateTime::from(Date::from(30, 6, 2024), Time::new());
+        let week = (0..7 * 24).map(|h| start.add_duration(&Duration::from_seconds(h * 3_600)));
+        let noon = DateTime::from(Date::from(1, 7, 2024), Time::from(12, 59, 59));
+        let counts = weekday_hour_counts(week.chain([noon]));
+        assert!(counts[1][12] == 2 && counts[1][13] == 1 && counts[0][0] == 1);
+        assert_eq!(counts.iter().flatten().sum::<u32>(), 7 * 24 + 1);
+    }
+
+    use crate::date_and_time::interval::*;
+    #[test]
+    fn test_interval_progress() {
+        let rental = DateInterval::new(Date::from(30, 6, 2024), Date::from(1, 7, 2024)).unwrap();
+        let at =
+            |day, month, hour| DateTime::from(Date::from(day, month, 2024), Time::from(hour, 0, 0));
+        assert_eq!(rental.progress_at(&at(29, 6, 23)), 0.0);
+        assert_eq!(rental.progress_at(&at(30, 6, 12)), 0.25);
+        assert_eq!(rental.progress_at(&at(1, 7, 12)), 0.75);
+        assert_eq!(rental.progress_at(&at(2, 7, 0)), 1.0);
+        assert_eq!(rental.progress_at(&at(3, 7, 0)), 1.0);
+        assert_eq!(rental.remaining(&at(29, 6, 0)).as_seconds(), 3 * 86_400);
+        assert_eq!(rental.remaining(&at(1, 7, 23)).as_seconds(), 3_600);
+        assert_eq!(rental.remaining(&at(2, 7, 1)), Duration::ZERO);
+    }
+
+    use crate::date_and_time::timeline::*;
+    #[test]
+    fn test_timeline() {
+        let interval = |d1, m1, d2, m2| {
+            DateInterval::new(Date::from(d1, m1, 2024), Date::from(d2, m2, 2024)).unwrap()
+        };
+        let mut timeline = Timeline::new();
+        timeline
+            .add("before", interval(1, 5, 31, 5))
+            .add("first week", interval(1, 6, 7, 6))
+            .add("across", interval(20, 6, 10, 7))
+            .add("after", interval(1, 8, 2, 8));
+        let june = interval(1, 6, 30, 6);
+        let rows = timeline.rows(&june);
+        let spans: Vec<(f64, f64)> = rows.iter().map(|r| (r.start, r.length)).collect();
+        assert_eq!(
+            spans,
+            [
+                (0.0, 0.0),
+                (0.0, 7.0 / 30.0),
+                (19.0 / 30.0, 11.0 / 30.0),
+                (1.0, 0.0)
+            ]
+        );
+        let columns: Vec<(usize, usize)> = rows.iter().map(|r| r.columns(60)).collect();
+        assert_eq!(columns, [(0, 0), (0, 14), (38, 22), (60, 0)]);
+        assert_eq!(rows[2].interval, interval(20, 6, 10, 7));
+        assert_eq!(timeline.window(), Some(interval(1, 5, 2, 8)));
+        assert_eq!(timeline.fit()[3].start + timeline.fit()[3].length, 1.0);
+        assert!(Timeline::new().fit().is_empty());
+        assert!(DateInterval::new(Date::from(0, 0, 0), Date::from(1, 1, 2024)).is_err());
+        let feb_31 = Date {
+            d: 31,
+            m: 2,
+            y: 2024,
+        };
+        assert!(DateInterval::new(feb_31, Date::from(1, 3, 2024)).is_err());
+        assert!(DateInterval::new(Date::from(1, 1, 2024), feb_31).is_err());
+    }
+
+    use crate::date_and_time::components::*;
+    // Returns the components of value: &(impl HasDate + HasTime) as a compact string
+    fn compact(value: &(impl HasDate + HasTime)) -> String {
+        format!(
+            "{:04}{:02}{:02}{:02}{:02}{:02}.{}",
+            value.year(),
+            value.month(),
+            value.day(),
+            value.hour(),
+            value.minute(),
+            value.second(),
+            value.nanosecond()
+        )
+    }
+    #[test]
+    fn test_components() {
+        let mut dt = DateTime::from(Date::from(22, 6, 2024), Time::from(13, 45, 30));
+        dt.nanos = 5;
+        assert_eq!(compact(&dt), "20240622134530.5");
+        let parsed = IsoParser::new(IsoProfile::Rfc3339)
+            .parse("2024-06-22T13:45:30.000000005+02:00")
+            .unwrap();
+        assert_eq!(compact(&parsed), "20240622134530.5");
+        assert_eq!(parsed.as_date(), Date::from(22, 6, 2024));
+        assert_eq!(HasDate::year(&Date::from(1, 1, 1999)), 1999);
+        assert_eq!(HasTime::nanosecond(&Time::from(1, 2, 3)), 0);
+        #[cfg(feature = "time")]
+        {
+            let odt = ::time::OffsetDateTime::try_from(dt).unwrap();
+            let tokyo = odt.to_offset(::time::UtcOffset::from_hms(9, 0, 0).unwrap());
+            assert_eq!(compact(&tokyo), "20240622224530.5");
+        }
+    }
+
+    use crate::date_and_time::iso8601::*;
+    #[test]
+    fn test_iso8601_expanded_years() {
+        let basic = IsoParser::new(IsoProfile::Full);
+        let expanded = basic.with_expanded_years(5);
+        for text in [
+            "+02024-06-22",
+            "-00001-12-31",
+            "+00000-01-01",
+            "-12345-W01-1",
+            "+99996366",
+        ] {
+            assert!(basic.parse_date(text).is_err(), "{}", text);
+            assert!(expanded.parse_date(text).is_ok(), "{}", text);
+        }
+        for text in [
+            "02024-06-22",
+            "+2024-06-22",
+            "+002024-06-22",
+            "-00001-02-29",
+        ] {
+            assert!(expanded.parse_date(text).is_err(), "{}", text);
+        }
+        // the year 0 and the year -4 are leap years
+        assert_eq!(
+            expanded.parse_date("-00004-060").unwrap(),
+            Date::from(29, 2, -4)
+        );
+        let date = expanded.parse_date("+00000-02-29").unwrap();
+        assert_eq!(expanded.format_date(&date).unwrap(), "+00000-02-29");
+        assert_eq!(basic.format_date(&date).unwrap(), "0000-02-29");
+        assert!(basic.format_date(&Date::from(1, 1, 10_000)).is_err());
+        assert!(expanded.format_date(&Date::from(1, 1, 100_000)).is_err());
+        for text in [
+            "-00001-12-31T23:59:59Z",
+            "+12345-06-22T13:45:30.250000000+05:30",
+            "+00001-01-01T00:00:00-01:00",
+        ] {
+            let parsed = expanded.parse(text).unwrap();
+            assert_eq!(expanded.format(&parsed).unwrap(), text);
+        }
+        let before_epoch = expanded.parse("-00001-12-31T23:00:00-01:00").unwrap();
+        assert_eq!(before_epoch.as_utc().date, Date::from(1, 1, 0));
+    }
+    #[test]
+    fn test_iso8601_profiles() {
+        let strict = IsoParser::new(IsoProfile::Rfc3339);
+        let extended = IsoParser::new(IsoProfile::Extended);
+        let full = IsoParser::new(IsoProfile::Full);
+        let parsed = strict.parse("2024-06-22t13:45:30.25-05:30").unwrap();
+        assert_eq!(
+            parsed.date_time.as_string(),
+            "2024-06-22T13:45:30.250000000"
+        );
+        assert_eq!(parsed.offset.unwrap().as_seconds(), -19_800);
+        assert_eq!(parsed.as_utc().as_string(), "2024-06-22T19:15:30.250000000");
+        for text in [
+            "2024-06-22T13:45:30",
+            "2024-06-22T13:45Z",
+            "2024-06-22T13:45:30+02",
+        ] {
+            assert!(strict.parse(text).is_err(), "{}", text);
+            assert!(extended.parse(text).is_ok(), "{}", text);
+        }
+        for text in [
+            "2024-174T12:00:00Z",
+            "20240622T134530+0200",
+            "2024W256T12:00Z",
+        ] {
+            assert!(extended.parse(text).is_err(), "{}", text);
+            assert!(full.parse(text).is_ok(), "{}", text);
+        }
+        for text in ["2024-éabc", "2024-W€1", "2024é"] {
+            assert!(full.parse_date(text).is_err(), "{}", text);
+        }
+        assert_eq!(
+            full.parse("20240622T134530+0200")
+                .unwrap()
+                .as_utc()
+                .as_string(),
+            "2024-06-22T11:45:30"
+        );
+        assert_eq!(
+            full.parse_date("2024-366").unwrap().as_string(),
+            "2024-12-31"
+        );
+        assert!(full.parse_date("2023-366").is_err());
+        assert_eq!(
+            full.parse_date("2026-W53-1").unwrap().as_string(),
+            "2026-12-28"
+        );
+        assert!(full.parse_date("2025-W53-1").is_err());
+        assert_eq!(
+            full.parse_date("2025W011").unwrap().as_string(),
+            "2024-12-30"
+        );
+        assert!(strict.parse("2024-06-22T24:00:00Z").is_err());
+        assert!(strict.parse("2024-06-22T12:00:00,5Z").is_err());
+        assert!(extended.parse("2024-06-22T12:00:00,5Z").is_ok());
+        assert!(strict.parse("2024-06-22T12:00:00.Z").is_err());
+        assert!(strict.parse("2024-06-22T12:00:00+24:00").is_err());
+        assert!(strict.parse("2024-06-22").is_err());
+    }
+
+    use crate::date_and_time::abbreviation::*;
+    use crate::date_and_time::error::*;
+    #[test]
+    fn test_abbreviation_table() {
+        assert_eq!(candidates("CST").len(), 3);
+        assert_eq!(candidates(" est ")[0].offset.as_seconds(), -18_000);
+        let mut table = AbbreviationTable::new();
+        match table.resolve("IST") {
+            Err(DateTimeError::Ambiguous(reason)) => {
+                assert!(reason.contains("Asia/Kolkata (+05:30)"));
+                assert!(reason.contains("Europe/Dublin (+01:00)"));
+            }
+            other => panic!("expected an ambiguity error, got {:?}", other),
+        }
+        table.prefer("IST", "Europe/Dublin").unwrap();
+        table.prefer("ist", "asia/kolkata").unwrap();
+        assert_eq!(table.resolve("IST").unwrap().zone, "Asia/Kolkata");
+        assert!(table.resolve("AMT").is_err());
+        assert!(table.prefer("UTC", "Europe/London").is_err());
+        assert_eq!(table.resolve("utc").unwrap().offset, UtcOffset::default());
+        let tz = table.resolve("IST").unwrap().as_time_zone();
+        assert_eq!(tz.abbreviation_at_timestamp(0), "IST");
+        assert_eq!(tz.offset_at_timestamp(0), 19_800);
+        // the abbreviation of a parsed text
+        let parse = |text: &str, table: &AbbreviationTable| {
+            DateTime::parse_formatted_with_zone(text, "%d.%m.%Y %T %Z", table)
+        };
+        let (local, zone) = parse("22.06.2024 18:30:00 IST", &table).unwrap();
+        assert_eq!(
+            local,
+            DateTime::from(Date::from(22, 6, 2024), Time::from(18, 30, 0))
+        );
+        assert_eq!(zone.zone, "Asia/Kolkata");
+        let table = AbbreviationTable::new();
+        assert!(matches!(
+            parse("22.06.2024 18:30:00 IST", &table),
+            Err(DateTimeError::Ambiguous(_))
+        ));
+        assert_eq!(
+            parse("22.06.2024 18:30:00 cest", &table)
+                .unwrap()
+                .1
+                .offset
+                .as_seconds(),
+            7_200
+        );
+        assert!(parse("22.06.2024 18:30:00 XYZ", &table).is_err());
+        assert!(parse("22.06.2024 18:30:00 +02", &table).is_err());
+        assert!(parse("30.02.2024 18:30:00 CET", &table).is_err());
+    }
+
+    use crate::date_and_time::countdown::*;
+    #[test]
+    fn test_countdown() {
+        let mut clock = FixedClock::new(DateTime::from_timestamp(1_000));
+        let countdown = Countdown::from_duration_with(&Duration::from_seconds(90_061), &clock);
+        assert_eq!(countdown.target, DateTime::from_timestamp(91_061));
+        assert_eq!(countdown.as_string_with(&clock), "1d 01:01:01");
+        clock.advance(&Duration::from_milliseconds(3_601_500));
+        assert_eq!(
+            countdown.remaining_with(&clock).as_milliseconds(),
+            86_459_500
+        );
+        assert_eq!(countdown.as_string_with(&clock), "1d 00:01:00");
+        clock.advance(&Duration::from_seconds(86_459));
+        assert_eq!(countdown.as_string_with(&clock), "00:00:01");
+        assert!(!countdown.is_expired_with(&clock));
+        clock.advance(&Duration::from_seconds(10));
+        assert!(countdown.is_expired_with(&clock));
+        assert_eq!(countdown.remaining_with(&clock), Duration::ZERO);
+        assert_eq!(countdown.as_string_with(&clock), "00:00:00");
+    }
+
+    use crate::date_and_time::drift::*;
+    #[test]
+    fn test_estimate_drift() {
+        assert!(estimate_drift(&[]).is_err());
+        // the local clock is 1.5 seconds slow and loses 20 microseconds per second
+        let samples: Vec<(DateTime, DateTime)> = [0, 600, 1_200, 3_600]
+            .iter()
+            .map(|secs| {
+                let reference = DateTime::from_timestamp(1_000_000 + secs);
+                let offset = Duration::from_nanoseconds((-1_500_000_000 - secs * 20_000) as i128);
+                (reference.add_duration(&offset), reference)
+            })
+            .collect();
+        let report = estimate_drift(&samples).unwrap();
+        assert_eq!(report.samples, 4);
+        assert_eq!(report.at, DateTime::from_timestamp(1_003_600));
+        assert!((report.rate_ppm() + 20.0).abs() < 1e-6);
+        assert_eq!(report.offset.as_milliseconds(), -1_572);
+        let tomorrow = DateTime::from_timestamp(1_003_600 + 86_400);
+        assert_eq!(report.offset_at(&tomorrow).as_milliseconds(), -3_300);
+        let single = estimate_drift(&samples[..1]).unwrap();
+        assert_eq!(
+            (single.rate, single.offset.as_milliseconds()),
+            (0.0, -1_500)
+        );
+    }
+
+    use crate::date_and_time::locale::*;
+    #[test]
+    fn test_format_range() {
+        let label = |a: Date, b: Date| format_range(a, b, &English);
+        let d = Date::from;
+        assert_eq!(label(d(22, 6, 2024), d(22, 6, 2024)), "22 June 2024");
+        assert_eq!(label(d(25, 6, 2024), d(22, 6, 2024)), "22–25 June 2024");
+        assert_eq!(
+            label(d(22, 6, 2024), d(3, 7, 2024)),
+            "22 June – 3 July 2024"
+        );
+        assert_eq!(
+            label(d(28, 12, 2024), d(3, 1, 2025)),
+            "28 Dec 2024 – 3 Jan 2025"
+        );
+        assert_eq!(label(d(1, 6, 2024), d(30, 6, 2024)), "June 2024");
+        assert_eq!(label(d(1, 6, 2024), d(31, 7, 2024)), "June–July 2024");
+        assert_eq!(label(d(1, 2, 2024), d(29, 2, 2024)), "February 2024");
+        assert_eq!(label(d(1, 1, 2024), d(31, 12, 2024)), "2024");
+        assert_eq!(label(d(1, 1, 2024), d(31, 12, 2025)), "2024–2025");
+        assert_eq!(label(d(0, 0, 0), d(1, 1, 2024)), "0000-00-00 – 2024-01-01");
+    }
+
+    #[test]
+    fn test_formated_string_localized() {
+        struct Upper;
+        impl Locale for Upper {
+            fn month_name(&self, month: u8) -> &str {
+                ["JAN", "FEB", "MAR", "APR", "MAY", "JUN"][month as usize % 6]
+            }
+            fn month_abbreviation(&self, _: u8) -> &str {
+                "M"
+            }
+            fn weekday_name(&self, _: u8) -> &str {
+                "DAY"
+            }
+            fn meridiem(&self, meridiem: Meridiem) -> &str {
+                match meridiem {
+                    Meridiem::AM => "vorm.",
+                    Meridiem::PM => "nachm.",
+                }
+            }
+            fn meridiem_short(&self, meridiem: Meridiem) -> &str {
+                self.meridiem(meridiem)
+            }
+        }
+        let date = Date::from(24, 12, 2024);
+        let format = "%A %a, %e %B %b %Y";
+        assert_eq!(
+            date.as_formated_string_localized(format, &English),
+            date.as_formated_string(format)
+        );
+        assert_eq!(
+            date.as_formated_string_localized(format, &Upper),
+            "DAY Tue, 24 JAN M 2024"
+        );
+        assert_eq!(
+            date.as_formated_string_localized(Upper.date_format(), &Upper),
+            "12/24/2024"
+        );
+        let time = Time::from(0, 30, 0);
+        assert_eq!(
+            time.as_formated_string_localized("%I:%M %p", &English),
+            "12:30 a.m."
+        );
+        assert_eq!(
+            time.as_formated_string_localized("%I:%M %p", &Upper),
+            "12:30 vorm."
+        );
+        let time = Time::from(12, 0, 0);
+        assert_eq!(time.as_formated_string_localized("%p", &Upper), "nachm.");
+        assert_eq!(
+            time.as_formated_string_localized("%r", &Upper),
+            "12:00:00 nachm."
+        );
+        assert_eq!(time.as_formated_string("%r"), "12:00:00 PM");
+        assert_eq!(
+            time.as_formated_string_localized("%r", &English),
+            "12:00:00 PM"
+        );
+        assert_eq!(
+            time.as_formated_string_localized(English.time_format(), &English),
+            "12:00:00 p.m."
+        );
+    }
+
+    #[test]
+    fn test_ordinal_day() {
+        let ordinal = |day: u8| Date::from(day, 1, 2025).as_formated_string("%o");
+        let days: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 30, 31]
+            .into_iter()
+            .map(ordinal)
+            .collect();
+        assert_eq!(
+            days,
+            [
+                "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "23rd", "30th",
+                "31st"
+            ]
+        );
+        let date = Date::from(22, 6, 2024);
+        assert_eq!(
+            date.as_formated_string("Saturday, the %o of %B"),
+            "Saturday, the 22nd of June"
+        );
+        struct Numbers;
+        impl Locale for Numbers {
+            fn month_name(&self, _: u8) -> &str {
+                ""
+            }
+            fn month_abbreviation(&self, _: u8) -> &str {
+                ""
+            }
+            fn ordinal_suffix(&self, _: u8) -> &str {
+                "."
+            }
+        }
+        assert_eq!(
+            date.as_formated_string_localized("%o%m.", &Numbers),
+            "22.06."
+        );
+        #[cfg(feature = "locales")]
+        {
+            assert_eq!(
+                date.as_formated_string_localized("%o %B", &German),
+                "22. Juni"
+            );
+            let first = Date::from(1, 5, 2024);
+            assert_eq!(
+                first.as_formated_string_localized("%o %B", &French),
+                "1er mai"
+            );
+            assert_eq!(
+                date.as_formated_string_localized("%o %B", &French),
+                "22 juin"
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_padding_modifiers() {
+        let date = Date::from(5, 3, 987);
+        assert_eq!(date.as_formated_string("%d %-d %_d %0d"), "05 5  5 05");
+        assert_eq!(date.as_formated_string("%e %-e %_e %0e"), " 5 5  5 05");
+        assert_eq!(
+            date.as_formated_string("%-m/%_m/%Y/%-Y/%_Y"),
+            "3/ 3/0987/987/ 987"
+        );
+        assert_eq!(date.as_formated_string("%-j %_j"), "64  64");
+        // the names, the texts and the numbers without padding aren't changed
+        assert_eq!(
+            date.as_formated_string("%-B %_a %0F %-K %0Q"),
+            "March Mon 0987-03-05 2 64"
+        );
+        assert_eq!(
+            Date::from(1, 1, -5).as_formated_string("%Y %-Y %_Y %0Y"),
+            "-005 -5   -5 -005"
+        );
+        let time = Time::from(7, 0, 9);
+        assert_eq!(time.as_formated_string("%-H:%M:%-S"), "7:00:9");
+        assert_eq!(
+            time.as_formated_string("%_H|%-M|%0I|%-I%p"),
+            " 7|0|07|7a.m."
+        );
+        assert_eq!(time.as_formated_string("%-T %-%"), "07:00:09 %");
+        assert_eq!(Time::from(0, -5, 0).as_formated_string("%-H:%-M"), "-0:5");
+        // a modifier at the end of the format is ignored
+        assert_eq!(time.as_formated_string("%H%-"), "07");
+    }
+
+    #[test]
+    fn test_format_year_and_week_placeholders() {
+        // the expected values are the results of the libc strftime()
+        let expected = [
+            (Date::from(1, 1, 2024), "24 00 01 01/01/24"),
+            (Date::from(7, 1, 2024), "24 01 01 01/07/24"),
+            (Date::from(1, 1, 2023), "23 01 00 01/01/23"),
+            (Date::from(3, 1, 1995), "95 01 01 01/03/95"),
+            (Date::from(2, 1, 2005), "05 01 00 01/02/05"),
+            (Date::from(31, 12, 2000), "00 53 52 12/31/00"),
+        ];
+        for (date, text) in expected {
+            assert_eq!(date.as_formated_string("%y %U %W %D"), text);
+        }
+    }
+
+    #[test]
+    fn test_datetime_formated_string() {
+        let dt = DateTime::from_timestamp(1_735_689_599);
+        assert_eq!(
+            dt.as_formated_string("%Y-%m-%d %H:%M:%S"),
+            "2024-12-31 23:59:59"
+        );
+        assert_eq!(
+            dt.as_formated_string("%F %T"),
+            dt.as_string().replace('T', " ")
+        );
+        assert_eq!(
+            dt.as_formated_string("%a %e %b %Y, %-I:%M %p (day %j)"),
+            "Tue 31 Dec 2024, 11:59 p.m. (day 366)"
+        );
+        // the date and the time parts are the same as of Date and Time
+        let format = "%A %B %d %D %G %V %u|%H %I %M %S %p %r %R %T|%%%n%t%q";
+        let (date_part, time_part) = format.split_once('|').unwrap();
+        assert_eq!(
+            dt.as_formated_string(format),
+            format!(
+                "{}|{}|%\n\tq",
+                dt.date.as_formated_string(date_part),
+                dt.time
+                    .as_formated_string(time_part.split('|').next().unwrap())
+            )
+        );
+        // the time placeholders of a Date and the date placeholders of a Time are written
+        // as their character
+        assert_eq!(dt.date.as_formated_string("%Y %H"), "2024 H");
+        assert_eq!(dt.time.as_formated_string("%Y %H"), "Y 23");
+    }
+
+    #[test]
+    fn test_format_epoch_seconds() {
+        for timestamp in [0, 1, 86_399, 1_719_081_045, -1, -86_401, -62_135_596_800] {
+            let mut dt = DateTime::from_timestamp(timestamp);
+            dt.nanos = 999_999_999;
+            assert_eq!(dt.as_formated_string("%s"), timestamp.to_string());
+            let midnight = timestamp.div_euclid(86_400) * 86_400;
+            assert_eq!(dt.date.as_formated_string("%s"), midnight.to_string());
+        }
+        let dt = DateTime::from_timestamp(1_719_081_045);
+        assert_eq!(
+            dt.as_formated_string("%F %T %s"),
+            "2024-06-22 18:30:45 1719081045"
+        );
+        // a Time has no epoch
+        assert_eq!(dt.time.as_formated_string("%s"), "s");
+    }
+
+    #[test]
+    fn test_format_offset_and_zone() {
+        use crate::date_and_time::local::*;
+        let dt = DateTime::from_timestamp(1_719_081_045);
+        let offsets = [
+            (UtcOffset::from_hours_minutes(2, 0), "+0200 +02:00 +02:00"),
+            (UtcOffset::from_hours_minutes(5, 45), "+0545 +05:45 +05:45"),
+            (UtcOffset::from_seconds(-12_600), "-0330 -03:30 -03:30"),
+            (UtcOffset::from_seconds(0), "+0000 +00:00 UTC"),
+        ];
+        for (offset, text) in offsets {
+            assert_eq!(dt.as_formated_string_with_offset("%z %:z %Z", offset), text);
+        }
+        let offset = UtcOffset::from_seconds(-18_000);
+        assert_eq!(
+            dt.as_formated_string_with_offset("%FT%T%:z", offset),
+            "2024-06-22T18:30:45-05:00"
+        );
+        // without an offset the local timezone is used
+        let _timezone = lock_local_timezone();
+        let local = get_gmt_offset();
+        assert_eq!(
+            dt.as_formated_string("%z"),
+            dt.as_formated_string_with_offset("%z", local)
+        );
+        assert_eq!(
+            dt.time.as_formated_string("%Z"),
+            get_timezone_abbreviation()
+        );
+        assert_eq!(dt.as_formated_string("%:x %:"), ":x :");
+    }
+
+    #[test]
+    fn test_format_composite_placeholders() {
+        let dt = DateTime::from_timestamp(1_719_081_045);
+        assert_eq!(dt.as_formated_string("%c"), "06/22/2024 06:30:45 p.m.");
+        assert_eq!(
+            dt.as_formated_string("[%x] [%X]"),
+            "[06/22/2024] [06:30:45 p.m.]"
+        );
+        assert_eq!(dt.date.as_formated_string("%x %X %c"), "06/22/2024 X c");
+        assert_eq!(dt.time.as_formated_string("%x %X %c"), "x 06:30:45 p.m. c");
+        struct Iso;
+        impl Locale for Iso {
+            fn month_name(&self, _: u8) -> &str {
+                ""
+            }
+            fn month_abbreviation(&self, _: u8) -> &str {
+                ""
+            }
+            fn date_format(&self) -> &str {
+                "%F (%x)"
+            }
+            fn time_format(&self) -> &str {
+                "%T"
+            }
+            fn datetime_format(&self) -> &str {
+                "%xT%X"
+            }
+        }
+        // a format which contains itself is expanded only twice
+        assert_eq!(
+            dt.as_formated_string_localized("%c", &Iso),
+            "2024-06-22 (x)T18:30:45"
+        );
+        #[cfg(feature = "locales")]
+        assert_eq!(
+            dt.as_formated_string_localized("%c", &German),
+            "22.06.2024 18:30:45"
+        );
+    }
+
+    #[test]
+    fn test_format_spec() {
+        let formats = [
+            "%Y-%m-%d %H:%M:%S",
+            "%A, %o %B %Y at %-I:%M %p",
+            "%c|%x|%X|%s|%z|%:z|%:x|%%|%n%t|%q|%-d|%_H|%0e|%",
+            "",
+            "no placeholders",
+        ];
+        let dt = DateTime::from_timestamp(1_719_081_045);
+        for format in formats {
+            let spec = FormatSpec::parse(format);
+            assert_eq!(spec.format_datetime(&dt), dt.as_formated_string(format));
+            assert_eq!(spec.format(&dt.date), dt.date.as_formated_string(format));
+            assert_eq!(
+                spec.format_time(&dt.time),
+                dt.time.as_formated_string(format)
+            );
+            // the spec is used again
+            let next = dt.add_duration(&Duration::from_seconds(86_399));
+            assert_eq!(spec.format_datetime(&next), next.as_formated_string(format));
+        }
+        assert_eq!(FormatSpec::parse("%F"), FormatSpec::parse("%F"));
+        assert_ne!(FormatSpec::parse("%F"), FormatSpec::parse("%T"));
+    }
+
+    #[test]
+    fn test_format_into() {
+        // a writer on the stack which fails if it is full
+        struct Buffer {
+            bytes: [u8; 32],
+            len: usize,
+        }
+        impl std::fmt::Write for Buffer {
+            fn write_str(&mut self, s: &str) -> std::fmt::Result {
+                let end = self.len + s.len();
+                if end > self.bytes.len() {
+                    return Err(std::fmt::Error);
+                }
+                self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+                self.len = end;
+                Ok(())
+            }
+        }
+        let dt = DateTime::from_timestamp(1_719_081_045);
+        let mut buffer = Buffer {
+            bytes: [0; 32],
+            len: 0,
+        };
+        dt.format_into(&mut buffer, "%A, %o %B %Y").unwrap();
+        assert_eq!(&buffer.bytes[..buffer.len], b"Saturday, 22nd June 2024");
+        assert!(dt.format_into(&mut buffer, "%F").is_err());
+        // a padded text which isn't a number is written unchanged, also if it is longer than
+        // the buffer of the numbers
+        assert_eq!(
+            dt.as_formated_string("%-c|%_B"),
+            dt.as_formated_string("%c|%B")
+        );
+        struct Long;
+        impl Locale for Long {
+            fn month_name(&self, _: u8) -> &str {
+                "The month with a very long name which doesn't fit into the 64 bytes of the buffer"
+            }
+            fn month_abbreviation(&self, _: u8) -> &str {
+                ""
+            }
+        }
+        assert_eq!(
+            dt.as_formated_string_localized("%0B", &Long),
+            Long.month_name(6)
+        );
+        let formats = ["%c %s %-d %_m %0e %%%:x", "%F %T %j", "%x %X %-I %p"];
+        for format in formats {
+            let mut text = String::new();
+            dt.format_into(&mut text, format).unwrap();
+            assert_eq!(text, dt.as_formated_string(format));
+            text.clear();
+            dt.date.format_into(&mut text, format).unwrap();
+            assert_eq!(text, dt.date.as_formated_string(format));
+            text.clear();
+            dt.time.format_into(&mut text, format).unwrap();
+            assert_eq!(text, dt.time.as_formated_string(format));
+        }
+    }
+
+    #[test]
+    fn test_try_as_formated_string() {
+        let dt = DateTime::from_timestamp(1_719_081_045);
+        let format = "%c|%x|%X|%s|%z|%:z|%Z|%%|%n%t|%-d|%_H|%0e|%A %o %B";
+        assert_eq!(
+            dt.try_as_formated_string(format),
+            Ok(dt.as_formated_string(format))
+        );
+        let position = |result: Result<String, DateTimeError>| match result {
+            Err(DateTimeError::Parse(reason)) => reason.rsplit(' ').next().unwrap().to_string(),
+            _ => String::from("ok"),
+        };
+        assert_eq!(position(dt.try_as_formated_string("%Y-%m-%d %h")), "9");
+        assert_eq!(position(dt.try_as_formated_string("ä%Y %:x")), "4");
+        assert_eq!(position(dt.try_as_formated_string("%F %-")), "3");
+        assert_eq!(position(dt.try_as_formated_string("100%")), "3");
+        assert_eq!(position(dt.date.try_as_formated_string("%F %T")), "3");
+        assert_eq!(position(dt.date.try_as_formated_string("%x %c")), "3");
+        assert_eq!(position(dt.time.try_as_formated_string("%X %x")), "3");
+        assert_eq!(position(dt.time.try_as_formated_string("%T %s")), "3");
+        assert_eq!(position(dt.date.try_as_formated_string("%F %s %-j")), "ok");
+        assert_eq!(
+            dt.try_as_formated_string("%q"),
+            Err(DateTimeError::Parse(String::from(
+                "the format '%q' has the unknown placeholder %q at position 0"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_system_locale() {
+        let locale = SystemLocale::from_system();
+        for n in 1..=12 {
+            assert!(!locale.month_name(n).is_empty());
+            assert!(!locale.month_abbreviation(n).is_empty());
+        }
+        for n in 0..7 {
+            assert!(!locale.weekday_name(n).is_empty());
+            assert!(!locale.weekday_abbreviation(n).is_empty());
+        }
+        assert_eq!(locale.month_name(0), "");
+        assert!(!locale.date_format().is_empty());
+        let date = Date::from(22, 6, 2024);
+        assert!(date
+            .as_formated_string_localized(locale.date_format(), &locale)
+            .contains("22"));
+        assert_eq!(get_strftime_format("dd.MM.yyyy"), "%d.%m.%Y");
+        assert_eq!(get_strftime_format("M/d/yy"), "%m/%d/%y");
+        assert_eq!(get_strftime_format("dddd, d. MMMM yyyy"), "%A, %d. %B %Y");
+        assert_eq!(get_strftime_format("h:mm:ss tt"), "%I:%M:%S %p");
+        assert_eq!(get_strftime_format("HH'h'mm"), "%Hh%M");
+        assert_eq!(get_strftime_format("'100%' d''MMM"), "100%% %d'%b");
+    }
+
+    #[cfg(feature = "locales")]
+    #[test]
+    fn test_bundled_locales() {
+        let date = Date::from(22, 6, 2024);
+        let format = "%A %a, %e %B %b";
+        let texts = [
+            date.as_formated_string_localized(format, &German),
+            date.as_formated_string_localized(format, &French),
+            date.as_formated_string_localized(format, &Spanish),
+            date.as_formated_string_localized(format, &Italian),
+            date.as_formated_string_localized(format, &Dutch),
+        ];
+        assert_eq!(
+            texts,
+            [
+                "Samstag Sa, 22 Juni Jun",
+                "samedi sam., 22 juin juin",
+                "sábado sáb, 22 junio jun",
+                "sabato sab, 22 giugno giu",
+                "zaterdag za, 22 juni jun",
+            ]
+        );
+        let locales: [&dyn Locale; 5] = [&German, &French, &Spanish, &Italian, &Dutch];
+        for locale in locales {
+            for n in 1..=12 {
+                assert!(!locale.month_name(n).is_empty());
+                assert!(!locale.month_abbreviation(n).is_empty());
+            }
+            for n in 0..7 {
+                assert!(!locale.weekday_name(n).is_empty());
+                assert!(!locale.weekday_abbreviation(n).is_empty());
+            }
+            assert_eq!(locale.month_name(13), "");
+            assert_eq!(locale.weekday_name(7), "");
+        }
+        assert_eq!(
+            date.as_formated_string_localized(German.date_format(), &German),
+            "22.06.2024"
+        );
+        assert_eq!(
+            date.as_formated_string_localized(Dutch.date_format(), &Dutch),
+            "22-06-2024"
+        );
+        let time = Time::from(15, 4, 5);
+        assert_eq!(
+            time.as_formated_string_localized(French.time_format(), &French),
+            "15:04:05"
+        );
+        assert_eq!(
+            time.as_formated_string_localized("%I:%M %p", &Spanish),
+            "03:04 p. m."
+        );
+    }
+
+    use crate::date_and_time::event::*;
+    #[test]
+    fn test_event_stamp_order() {
+        let wall = DateTime::from_timestamp(1_700_000_000);
+        let first = EventStamp::from_wall(wall);
+        // the wall clock stepped back by one hour
+        let second = EventStamp::from_wall(wall.sub_duration(&Duration::from_seconds(3_600)));
+        let third = EventStamp::now();
+        assert!(first < second && second < third);
+        assert!(second.sequence() > first.sequence());
+        assert_eq!(first, first);
+        assert_ne!(first, second);
+        assert!(second.elapsed_since(&first) >= Duration::new());
+        assert!(first.elapsed_since(&third) <= Duration::new());
+        let mut events = vec![third, first, second];
+        events.sort();
+        assert_eq!(events, vec![first, second, third]);
+    }
+
+    use crate::date_and_time::holiday::*;
+    #[test]
+    fn test_holiday_business_days() {
+        let holidays = vec![Date::from(1, 1, 2024), Date::from(1, 5, 2024)];
+        assert!(!holidays.is_business_day(&Date::from(1, 1, 2024)));
+        assert!(holidays.is_business_day(&Date::from(2, 1, 2024)));
+        // Saturday and Sunday
+        assert!(!holidays.is_business_day(&Date::from(6, 1, 2024)));
+        assert!(!holidays.is_business_day(&Date::from(7, 1, 2024)));
+    }
+
+    #[test]
+    fn test_holiday_rule_calendar() {
+        // the Easter Sundays from the tables of the Gregorian calendar
+        let easter = [
+            (1818, 22, 3),
+            (1943, 25, 4),
+            (2024, 31, 3),
+            (2025, 20, 4),
+            (2038, 25, 4),
+        ];
+        for (year, day, month) in easter {
+            assert_eq!(easter_sunday(year), Date::from(day, month, year));
+        }
+        let mut calendar = RuleCalendar::new();
+        calendar
+            .add("Leap Day", HolidayRule::Fixed { month: 2, day: 29 })
+            .add("Good Friday", HolidayRule::Easter(-2))
+            .add(
+                "Fifth Friday",
+                HolidayRule::NthWeekday {
+                    month: 3,
+                    n: 5,
+                    weekday: Weekday::Friday,
+                },
+            )
+            .add(
+                "Last Friday",
+                HolidayRule::LastWeekday {
+                    month: 3,
+                    weekday: Weekday::Friday,
+                },
+            );
+        // in 2024 the Good Friday is the fifth and the last Friday of March
+        assert_eq!(
+            calendar.holiday_name(&Date::from(29, 3, 2024)),
+            Some("Good Friday")
+        );
+        assert_eq!(
+            calendar.holidays_in_year(2024),
+            [Date::from(29, 2, 2024), Date::from(29, 3, 2024)]
+        );
+        // in 2023 there is no leap day and no fifth Friday in March
+        assert_eq!(
+            calendar.holidays_in_year(2023),
+            [Date::from(31, 3, 2023), Date::from(7, 4, 2023)]
+        );
+        assert!(!calendar.is_business_day(&Date::from(7, 4, 2023)));
+        assert!(calendar.is_business_day(&Date::from(6, 4, 2023)));
+        // the default holidays_in_year() asks is_holiday() for every day
+        struct Mondays;
+        impl HolidayCalendar for Mondays {
+            fn is_holiday(&self, date: &Date) -> bool {
+                date.get_weekday() == 1
+            }
+        }
+        assert_eq!(Mondays.holidays_in_year(2024).len(), 53);
+    }
+
+    #[test]
+    fn test_orthodox_easter() {
+        let orthodox = [
+            (2000, 30, 4),
+            (2021, 2, 5),
+            (2023, 16, 4),
+            (2024, 5, 5),
+            (2100, 2, 5),
+        ];
+        for (year, day, month) in orthodox {
+            assert_eq!(orthodox_easter_sunday(year), Date::from(day, month, year));
+        }
+        // the Orthodox Easter is never before the Western one and always a Sunday
+        for year in 1900..2100 {
+            let (western, eastern) = (easter_sunday(year), orthodox_easter_sunday(year));
+            assert!(western <= eastern && eastern.get_weekday() == 0);
+            assert!(western.diff_in_days(&eastern) % 7 == 0);
+        }
+        let mut calendar = RuleCalendar::new();
+        calendar.add("Orthodox Good Friday", HolidayRule::OrthodoxEaster(-2));
+        assert_eq!(calendar.holidays_in_year(2024), [Date::from(3, 5, 2024)]);
+    }
+
+    #[cfg(feature = "holiday-calendars")]
+    #[test]
+    fn test_holiday_example_calendars() {
+        let germany = germany();
+        let holidays = germany.holidays_in_year(2025);
+        assert_eq!(holidays[1], Date::from(18, 4, 2025));
+        assert_eq!(
+            germany.holiday_name(&Date::from(29, 5, 2025)),
+            Some("Christi Himmelfahrt")
+        );
+        let us = us_federal();
+        let names: Vec<&str> = us
+            .holidays_in_year(2025)
+            .iter()
+            .filter_map(|date| us.holiday_name(date))
+            .collect();
+        assert_eq!(names.len(), 11);
+        assert_eq!(
+            us.holiday_name(&Date::from(20, 1, 2025)),
+            Some("Martin Luther King Jr. Day")
+        );
+        assert_eq!(
+            us.holiday_name(&Date::from(27, 11, 2025)),
+            Some("Thanksgiving Day")
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_dates() {
+        use crate::date_and_time::random::*;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let range = Date::from(1, 12, 2024)..=Date::from(31, 12, 2024);
+        let holidays = vec![Date::from(25, 12, 2024), Date::from(26, 12, 2024)];
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut again = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let date = random_date_in(range.clone(), &mut rng).unwrap();
+            assert!(range.contains(&date));
+            assert_eq!(date, random_date_in(range.clone(), &mut again).unwrap());
+            let day = random_business_day_in(range.clone(), &holidays, &mut rng).unwrap();
+            assert!(range.contains(&day) && holidays.is_business_day(&day));
+            random_business_day_in(range.clone(), &holidays, &mut again).unwrap();
+        }
+        // a weekend with a holiday calendar only has no business day
+        let weekend = Date::from(28, 12, 2024)..=Date::from(29, 12, 2024);
+        assert!(random_business_day_in(weekend, &holidays, &mut rng).is_err());
+        let empty = Date::from(2, 1, 2024)..=Date::from(1, 1, 2024);
+        assert!(random_date_in(empty, &mut rng).is_err());
+    }
+
+    // Returns the next pseudo random number of the xorshift state: &mut u64, the
+    // differential tests are reproducible without the feature rand
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    fn next_random(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    // Returns the libc strftime() of the format: &str for the broken down time: &libc::tm
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    fn libc_strftime(format: &str, tm: &libc::tm) -> String {
+        let format = std::ffi::CString::new(format).unwrap();
+        let mut buffer = [0 as libc::c_char; 256];
+        let len = unsafe { libc::strftime(buffer.as_mut_ptr(), buffer.len(), format.as_ptr(), tm) };
+        let bytes: Vec<u8> = buffer[..len].iter().map(|&c| c as u8).collect();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    #[test]
+    #[ignore = "differential test against libc, run it with: cargo test -- --ignored"]
+    fn test_differential_strftime() {
+        let placeholders = [
+            "%Y", "%C", "%y", "%G", "%g", "%b", "%B", "%m", "%U", "%V", "%W", "%j", "%d", "%e",
+            "%a", "%A", "%w", "%u", "%D", "%F",
+        ];
+        let mut state = 0x2024_0622_u64;
+        // the days from the year 1000 to the year 9999
+        let (first, last) = (
+            get_days_from_date(&Date::from(1, 1, 1000)),
+            get_days_from_date(&Date::from(31, 12, 9999)),
+        );
+        let mut failures: Vec<String> = Vec::new();
+        for n in 0..200_000 {
+            // the first days are around the turns of the years, where the week numbers are
+            // tricky
+            let days = if n < 2 * 366 {
+                get_days_from_date(&Date::from(1, 1, 1995 + n / 14)) + n as i64 % 14 - 7
+            } else {
+                first + (next_random(&mut state) % (last - first + 1) as u64) as i64
+            };
+            let date = get_date_from_days(days);
+            let secs: libc::time_t = days * 86_400;
+            let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+            assert!(!unsafe { libc::gmtime_r(&secs, &mut tm) }.is_null());
+            assert_eq!(
+                (tm.tm_year + 1900, tm.tm_mon + 1, tm.tm_mday),
+                (date.y, date.m as i32, date.d as i32),
+                "days {}",
+                days
+            );
+            for placeholder in placeholders {
+                let expected = libc_strftime(placeholder, &tm);
+                let result = date.as_formated_string(placeholder);
+                if result != expected {
+                    failures.push(format!(
+                        "{} of {}: {} (libc {})",
+                        placeholder,
+                        date.as_string(),
+                        result,
+                        expected
+                    ));
+                }
+            }
+        }
+        failures.truncate(20);
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
+
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    #[test]
+    #[ignore = "differential test against libc, run it with: cargo test -- --ignored"]
+    fn test_differential_mktime() {
+        use crate::date_and_time::timezone::*;
+        // the crate libc doesn't declare tzset() for Linux
+        extern "C" {
+            fn tzset();
+        }
+        // the zones with DST in both hemispheres, a 30 minutes DST and without DST
+        let zones = [
+            "Europe/Berlin",
+            "America/New_York",
+            "Australia/Sydney",
+            "Australia/Lord_Howe",
+            "Asia/Kolkata",
+        ];
+        let mut state = 0x1970_0101_u64;
+        let mut failures: Vec<String> = Vec::new();
+        let _timezone = lock_local_timezone();
+        let previous_tz = std::env::var_os("TZ");
+        for zone in zones {
+            let Ok(tz) = TimeZone::by_name(zone) else {
+                continue;
+            };
+            std::env::set_var("TZ", zone);
+            unsafe { tzset() };
+            for _ in 0..20_000 {
+                // the timestamps from 1970 until 2100
+                let secs = (next_random(&mut state) % 4_102_444_800) as i64;
+                let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+                assert!(!unsafe { libc::localtime_r(&secs, &mut tm) }.is_null());
+                let local = tz.utc_to_local(&DateTime::from_timestamp(secs));
+                let expected = DateTime::from(
+                    Date::from(tm.tm_mday as u8, (tm.tm_mon + 1) as u8, tm.tm_year + 1900),
+                    Time::from(tm.tm_hour, tm.tm_min as i8, tm.tm_sec as i8),
+                );
+                if local != expected || tz.is_dst_at_timestamp(secs) != (tm.tm_isdst > 0) {
+                    failures.push(format!("{} at {}: local {:?}", zone, secs, local));
+                }
+                // mktime() chooses one of the UTC times of an ambiguous local time
+                tm.tm_isdst = -1;
+                let utc = unsafe { libc::mktime(&mut tm) };
+                match tz.local_to_utc(&local) {
+                    LocalResult::Single(result) if result.as_timestamp() != utc => {
+                        failures.push(format!("{} mktime of {:?}: {}", zone, local, utc));
+                    }
+                    LocalResult::Ambiguous(a, b)
+                        if a.as_timestamp() != utc && b.as_timestamp() != utc =>
+                    {
+                        failures.push(format!("{} mktime of {:?}: {}", zone, local, utc));
+                    }
+                    LocalResult::None => {
+                        failures.push(format!("{} has no UTC for {:?}", zone, local));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        match previous_tz {
+            Some(tz) => std::env::set_var("TZ", tz),
+            None => std::env::remove_var("TZ"),
+        }
+        unsafe { tzset() };
+        failures.truncate(20);
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
 }
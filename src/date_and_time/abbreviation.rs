@@ -0,0 +1,207 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::error::*;
+use crate::date_and_time::offset::*;
+use crate::date_and_time::timezone::*;
+
+// The known abbreviations with the zone and the offset in seconds, some abbreviations
+// (e.g. CST, IST, AMT) are used by more than one zone
+const ABBREVIATIONS: [(&str, &str, i32); 50] = [
+    ("ACST", "Australia/Adelaide", 34_200),
+    ("AEDT", "Australia/Sydney", 39_600),
+    ("AEST", "Australia/Sydney", 36_000),
+    ("AKST", "America/Anchorage", -32_400),
+    ("AMT", "America/Manaus", -14_400),
+    ("AMT", "Asia/Yerevan", 14_400),
+    ("AST", "America/Halifax", -14_400),
+    ("AST", "Asia/Riyadh", 10_800),
+    ("AWST", "Australia/Perth", 28_800),
+    ("BST", "Europe/London", 3_600),
+    ("BST", "Asia/Dhaka", 21_600),
+    ("CAT", "Africa/Maputo", 7_200),
+    ("CDT", "America/Chicago", -18_000),
+    ("CDT", "America/Havana", -14_400),
+    ("CEST", "Europe/Berlin", 7_200),
+    ("CET", "Europe/Berlin", 3_600),
+    ("CST", "America/Chicago", -21_600),
+    ("CST", "Asia/Shanghai", 28_800),
+    ("CST", "America/Havana", -18_000),
+    ("EAT", "Africa/Nairobi", 10_800),
+    ("EDT", "America/New_York", -14_400),
+    ("EEST", "Europe/Athens", 10_800),
+    ("EET", "Europe/Athens", 7_200),
+    ("EST", "America/New_York", -18_000),
+    ("GMT", "Etc/GMT", 0),
+    ("GST", "Asia/Dubai", 14_400),
+    ("GST", "Atlantic/South_Georgia", -7_200),
+    ("HKT", "Asia/Hong_Kong", 28_800),
+    ("HST", "Pacific/Honolulu", -36_000),
+    ("IDT", "Asia/Jerusalem", 10_800),
+    ("IST", "Asia/Kolkata", 19_800),
+    ("IST", "Europe/Dublin", 3_600),
+    ("IST", "Asia/Jerusalem", 7_200),
+    ("JST", "Asia/Tokyo", 32_400),
+    ("KST", "Asia/Seoul", 32_400),
+    ("MDT", "America/Denver", -21_600),
+    ("MSK", "Europe/Moscow", 10_800),
+    ("MST", "America/Denver", -25_200),
+    ("NZDT", "Pacific/Auckland", 46_800),
+    ("NZST", "Pacific/Auckland", 43_200),
+    ("PDT", "America/Los_Angeles", -25_200),
+    ("PKT", "Asia/Karachi", 18_000),
+    ("PST", "America/Los_Angeles", -28_800),
+    ("SAST", "Africa/Johannesburg", 7_200),
+    ("SST", "Pacific/Pago_Pago", -39_600),
+    ("SST", "Asia/Singapore", 28_800),
+    ("UTC", "Etc/UTC", 0),
+    ("WAT", "Africa/Lagos", 3_600),
+    ("WEST", "Europe/Lisbon", 3_600),
+    ("WET", "Europe/Lisbon", 0),
+];
+
+/// The ZoneCandidate structure is one possible meaning of a timezone abbreviation, the
+/// ```zone``` is the IANA name of a zone which uses the abbreviation with the ```offset```.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ZoneCandidate {
+    pub abbreviation: &'static str,
+    pub zone: &'static str,
+    pub offset: UtcOffset,
+}
+
+#[allow(dead_code)]
+impl ZoneCandidate {
+    /// ```as_time_zone()``` returns a ```TimeZone``` with the fixed offset of the
+    /// candidate, named by the abbreviation.
+    ///
+    /// ```
+    /// use date_and_time::abbreviation::*;
+    /// let tz = AbbreviationTable::new().resolve("JST").unwrap().as_time_zone();
+    /// assert_eq!(tz.offset_at_timestamp(0), 32_400);
+    /// ```
+    pub fn as_time_zone(&self) -> TimeZone {
+        TimeZone::fixed(self.abbreviation, self.offset.as_seconds())
+    }
+}
+
+/// ```candidates(abbreviation)``` returns all zones which use the ```abbreviation```
+/// (case insensitive), the list is empty for an unknown abbreviation.
+///
+/// ```
+/// use date_and_time::abbreviation::*;
+/// let zones: Vec<&str> = candidates("ist").iter().map(|c| c.zone).collect();
+/// assert_eq!(zones, ["Asia/Kolkata", "Europe/Dublin", "Asia/Jerusalem"]);
+/// assert!(candidates("XYZ").is_empty());
+/// ```
+pub fn candidates(abbreviation: &str) -> Vec<ZoneCandidate> {
+    ABBREVIATIONS
+        .iter()
+        .filter(|(abbr, _, _)| abbr.eq_ignore_ascii_case(abbreviation.trim()))
+        .map(|&(abbr, zone, offset)| ZoneCandidate {
+            abbreviation: abbr,
+            zone,
+            offset: UtcOffset::from_seconds(offset),
+        })
+        .collect()
+}
+
+/// The AbbreviationTable structure resolves timezone abbreviations to a single zone. An
+/// abbreviation with more than one candidate is only resolved if a preferred zone was
+/// configured with ```prefer()```, otherwise ```resolve()``` returns an error instead of
+/// guessing. ```DateTime::parse_formatted_with_zone()``` resolves the ```%Z``` of a parsed
+/// text with the table.
+///
+/// ```
+/// use date_and_time::abbreviation::*;
+/// let mut table = AbbreviationTable::new();
+/// assert!(table.resolve("CST").is_err());
+/// table.prefer("CST", "Asia/Shanghai").unwrap();
+/// assert_eq!(table.resolve("CST").unwrap().offset.as_string(), "+08:00");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AbbreviationTable {
+    preferences: Vec<ZoneCandidate>,
+}
+
+#[allow(dead_code)]
+impl AbbreviationTable {
+    /// ```new()``` creates an ```AbbreviationTable``` without preferences.
+    ///
+    /// ```
+    /// use date_and_time::abbreviation::*;
+    /// assert_eq!(AbbreviationTable::new().resolve("PST").unwrap().zone, "America/Los_Angeles");
+    /// ```
+    pub fn new() -> AbbreviationTable {
+        AbbreviationTable {
+            preferences: Vec::new(),
+        }
+    }
+    /// ```prefer(abbreviation, zone)``` configures the ```zone``` as the meaning of the
+    /// ```abbreviation```, a former preference of the abbreviation is replaced. It returns
+    /// an error if the zone is not a candidate of the abbreviation.
+    ///
+    /// ```
+    /// use date_and_time::abbreviation::*;
+    /// let mut table = AbbreviationTable::new();
+    /// assert!(table.prefer("IST", "Europe/Dublin").is_ok());
+    /// assert!(table.prefer("IST", "Europe/Berlin").is_err());
+    /// ```
+    pub fn prefer(&mut self, abbreviation: &str, zone: &str) -> Result<(), DateTimeError> {
+        let Some(candidate) = candidates(abbreviation)
+            .into_iter()
+            .find(|c| c.zone.eq_ignore_ascii_case(zone.trim()))
+        else {
+            return Err(DateTimeError::OutOfRange(format!(
+                "'{}' is not a zone of the abbreviation '{}'",
+                zone, abbreviation
+            )));
+        };
+        self.preferences
+            .retain(|c| c.abbreviation != candidate.abbreviation);
+        self.preferences.push(candidate);
+        Ok(())
+    }
+    /// ```resolve(abbreviation)``` returns the zone of the ```abbreviation```. It is the
+    /// preferred zone, or the only candidate if the abbreviation is unique. An unknown
+    /// abbreviation returns a ```DateTimeError::Parse```, an ambiguous abbreviation
+    /// without preference returns a ```DateTimeError::Ambiguous``` with the candidates.
+    ///
+    /// ```
+    /// use date_and_time::abbreviation::*;
+    /// use date_and_time::error::*;
+    /// let table = AbbreviationTable::new();
+    /// assert_eq!(table.resolve("cet").unwrap().offset.as_seconds(), 3_600);
+    /// assert!(matches!(table.resolve("AMT"), Err(DateTimeError::Ambiguous(_))));
+    /// assert!(matches!(table.resolve("XYZ"), Err(DateTimeError::Parse(_))));
+    /// ```
+    pub fn resolve(&self, abbreviation: &str) -> Result<ZoneCandidate, DateTimeError> {
+        let found = candidates(abbreviation);
+        match found.len() {
+            0 => Err(DateTimeError::Parse(format!(
+                "unknown timezone abbreviation '{}'",
+                abbreviation
+            ))),
+            1 => Ok(found[0]),
+            _ => {
+                if let Some(preferred) = self
+                    .preferences
+                    .iter()
+                    .find(|c| c.abbreviation == found[0].abbreviation)
+                {
+                    return Ok(*preferred);
+                }
+                let zones: Vec<String> = found
+                    .iter()
+                    .map(|c| format!("{} ({})", c.zone, c.offset.as_string()))
+                    .collect();
+                Err(DateTimeError::Ambiguous(format!(
+                    "timezone abbreviation '{}' could be {}",
+                    abbreviation,
+                    zones.join(", ")
+                )))
+            }
+        }
+    }
+}
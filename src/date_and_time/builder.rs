@@ -0,0 +1,285 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::time::*;
+
+/// The DateBuilder structure builds a ```Date``` from the year, the month and the day, the
+/// fields which are not set are taken from the defaults (the 1st January 0 of
+/// ```Date::new()```, or the date of ```defaults()```). The functions ```year_opt()```,
+/// ```month_opt()``` and ```day_opt()``` set an optional field, e.g. of a form or a config
+/// file.
+///
+/// ```
+/// use date_and_time::builder::*;
+/// use date_and_time::date::*;
+/// let date = DateBuilder::new().year(2024).month(6).build().unwrap();
+/// assert_eq!(date, Date::from(1, 6, 2024));
+/// let form_day: Option<u8> = None;
+/// let date = DateBuilder::new()
+///     .defaults(Date::from(15, 3, 2024))
+///     .month(7)
+///     .day_opt(form_day)
+///     .build()
+///     .unwrap();
+/// assert_eq!(date, Date::from(15, 7, 2024));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DateBuilder {
+    year: Option<i32>,
+    month: Option<u8>,
+    day: Option<u8>,
+    defaults: Option<Date>,
+}
+
+#[allow(dead_code)]
+impl DateBuilder {
+    /// ```new()``` creates a ```DateBuilder``` without a field.
+    ///
+    /// ```
+    /// use date_and_time::builder::*;
+    /// use date_and_time::date::*;
+    /// assert_eq!(DateBuilder::new().build(), Ok(Date::new()));
+    /// ```
+    pub fn new() -> DateBuilder {
+        DateBuilder::default()
+    }
+    /// ```defaults(date)``` returns the ```DateBuilder``` with the ```date``` for the
+    /// fields which are not set, e.g. the ```Date::from_system_date()```.
+    pub fn defaults(self, date: Date) -> DateBuilder {
+        DateBuilder {
+            defaults: Some(date),
+            ..self
+        }
+    }
+    /// ```year(year)``` returns the ```DateBuilder``` with the ```year```.
+    pub fn year(self, year: i32) -> DateBuilder {
+        self.year_opt(Some(year))
+    }
+    /// ```month(month)``` returns the ```DateBuilder``` with the ```month``` (1 to 12).
+    pub fn month(self, month: u8) -> DateBuilder {
+        self.month_opt(Some(month))
+    }
+    /// ```day(day)``` returns the ```DateBuilder``` with the ```day``` of the month.
+    pub fn day(self, day: u8) -> DateBuilder {
+        self.day_opt(Some(day))
+    }
+    /// ```year_opt(year)``` returns the ```DateBuilder``` with the ```year```, None keeps
+    /// the year which was set before.
+    pub fn year_opt(self, year: Option<i32>) -> DateBuilder {
+        DateBuilder {
+            year: year.or(self.year),
+            ..self
+        }
+    }
+    /// ```month_opt(month)``` returns the ```DateBuilder``` with the ```month```, None
+    /// keeps the month which was set before.
+    pub fn month_opt(self, month: Option<u8>) -> DateBuilder {
+        DateBuilder {
+            month: month.or(self.month),
+            ..self
+        }
+    }
+    /// ```day_opt(day)``` returns the ```DateBuilder``` with the ```day```, None keeps the
+    /// day which was set before.
+    pub fn day_opt(self, day: Option<u8>) -> DateBuilder {
+        DateBuilder {
+            day: day.or(self.day),
+            ..self
+        }
+    }
+    /// ```build()``` creates the ```Date``` of the fields and the defaults, it returns an
+    /// error if the date is invalid.
+    ///
+    /// ```
+    /// use date_and_time::builder::*;
+    /// assert!(DateBuilder::new().year(2023).month(2).day(29).build().is_err());
+    /// ```
+    pub fn build(&self) -> Result<Date, DateTimeError> {
+        let defaults = self.defaults.unwrap_or_default();
+        get_checked_date(
+            self.day.unwrap_or(defaults.d),
+            self.month.unwrap_or(defaults.m),
+            self.year.unwrap_or(defaults.y),
+        )
+    }
+}
+
+/// The DateTimeBuilder structure builds a ```DateTime``` like the ```DateBuilder``` builds
+/// a ```Date```, the time fields which are not set are 0 (or taken from the date and time
+/// of ```defaults()```).
+///
+/// ```
+/// use date_and_time::builder::*;
+/// let dt = DateTimeBuilder::new()
+///     .year(2024)
+///     .month(6)
+///     .day(22)
+///     .hour(18)
+///     .minute(30)
+///     .build()
+///     .unwrap();
+/// assert_eq!(dt.as_string(), "2024-06-22T18:30:00");
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DateTimeBuilder {
+    date: DateBuilder,
+    hour: Option<u8>,
+    minute: Option<u8>,
+    second: Option<u8>,
+    nanos: Option<u32>,
+    defaults: Option<(u8, u8, u8, u32)>,
+}
+
+#[allow(dead_code)]
+impl DateTimeBuilder {
+    /// ```new()``` creates a ```DateTimeBuilder``` without a field.
+    ///
+    /// ```
+    /// use date_and_time::builder::*;
+    /// use date_and_time::datetime::*;
+    /// assert_eq!(DateTimeBuilder::new().build(), Ok(DateTime::new()));
+    /// ```
+    pub fn new() -> DateTimeBuilder {
+        DateTimeBuilder::default()
+    }
+    /// ```defaults(datetime)``` returns the ```DateTimeBuilder``` with the ```datetime```
+    /// for the fields which are not set.
+    ///
+    /// ```
+    /// use date_and_time::builder::*;
+    /// use date_and_time::datetime::*;
+    /// let now = DateTime::from_timestamp(1_719_081_045);
+    /// let dt = DateTimeBuilder::new().defaults(now).minute(0).second(0).build().unwrap();
+    /// assert_eq!(dt.as_string(), "2024-06-22T18:00:00");
+    /// ```
+    pub fn defaults(self, datetime: DateTime) -> DateTimeBuilder {
+        DateTimeBuilder {
+            date: self.date.defaults(datetime.date),
+            defaults: Some((
+                datetime.time.h as u8,
+                datetime.time.m as u8,
+                datetime.time.s as u8,
+                datetime.nanos,
+            )),
+            ..self
+        }
+    }
+    /// ```year(year)``` returns the ```DateTimeBuilder``` with the ```year```.
+    pub fn year(self, year: i32) -> DateTimeBuilder {
+        self.year_opt(Some(year))
+    }
+    /// ```month(month)``` returns the ```DateTimeBuilder``` with the ```month``` (1 to 12).
+    pub fn month(self, month: u8) -> DateTimeBuilder {
+        self.month_opt(Some(month))
+    }
+    /// ```day(day)``` returns the ```DateTimeBuilder``` with the ```day``` of the month.
+    pub fn day(self, day: u8) -> DateTimeBuilder {
+        self.day_opt(Some(day))
+    }
+    /// ```hour(hour)``` returns the ```DateTimeBuilder``` with the ```hour``` (0 to 23).
+    pub fn hour(self, hour: u8) -> DateTimeBuilder {
+        self.hour_opt(Some(hour))
+    }
+    /// ```minute(minute)``` returns the ```DateTimeBuilder``` with the ```minute``` (0 to
+    /// 59).
+    pub fn minute(self, minute: u8) -> DateTimeBuilder {
+        self.minute_opt(Some(minute))
+    }
+    /// ```second(second)``` returns the ```DateTimeBuilder``` with the ```second``` (0 to
+    /// 59).
+    pub fn second(self, second: u8) -> DateTimeBuilder {
+        self.second_opt(Some(second))
+    }
+    /// ```nanos(nanos)``` returns the ```DateTimeBuilder``` with the fractions of the
+    /// second in ```nanos``` (0 to 999999999).
+    pub fn nanos(self, nanos: u32) -> DateTimeBuilder {
+        self.nanos_opt(Some(nanos))
+    }
+    /// ```year_opt(year)``` returns the ```DateTimeBuilder``` with the ```year```, None
+    /// keeps the year which was set before.
+    pub fn year_opt(self, year: Option<i32>) -> DateTimeBuilder {
+        DateTimeBuilder {
+            date: self.date.year_opt(year),
+            ..self
+        }
+    }
+    /// ```month_opt(month)``` returns the ```DateTimeBuilder``` with the ```month```, None
+    /// keeps the month which was set before.
+    pub fn month_opt(self, month: Option<u8>) -> DateTimeBuilder {
+        DateTimeBuilder {
+            date: self.date.month_opt(month),
+            ..self
+        }
+    }
+    /// ```day_opt(day)``` returns the ```DateTimeBuilder``` with the ```day```, None keeps
+    /// the day which was set before.
+    pub fn day_opt(self, day: Option<u8>) -> DateTimeBuilder {
+        DateTimeBuilder {
+            date: self.date.day_opt(day),
+            ..self
+        }
+    }
+    /// ```hour_opt(hour)``` returns the ```DateTimeBuilder``` with the ```hour```, None
+    /// keeps the hour which was set before.
+    pub fn hour_opt(self, hour: Option<u8>) -> DateTimeBuilder {
+        DateTimeBuilder {
+            hour: hour.or(self.hour),
+            ..self
+        }
+    }
+    /// ```minute_opt(minute)``` returns the ```DateTimeBuilder``` with the ```minute```,
+    /// None keeps the minute which was set before.
+    pub fn minute_opt(self, minute: Option<u8>) -> DateTimeBuilder {
+        DateTimeBuilder {
+            minute: minute.or(self.minute),
+            ..self
+        }
+    }
+    /// ```second_opt(second)``` returns the ```DateTimeBuilder``` with the ```second```,
+    /// None keeps the second which was set before.
+    pub fn second_opt(self, second: Option<u8>) -> DateTimeBuilder {
+        DateTimeBuilder {
+            second: second.or(self.second),
+            ..self
+        }
+    }
+    /// ```nanos_opt(nanos)``` returns the ```DateTimeBuilder``` with the ```nanos```, None
+    /// keeps the nanoseconds which were set before.
+    pub fn nanos_opt(self, nanos: Option<u32>) -> DateTimeBuilder {
+        DateTimeBuilder {
+            nanos: nanos.or(self.nanos),
+            ..self
+        }
+    }
+    /// ```build()``` creates the ```DateTime``` of the fields and the defaults, it returns
+    /// an error if the date or the clock time is invalid.
+    ///
+    /// ```
+    /// use date_and_time::builder::*;
+    /// assert!(DateTimeBuilder::new().year(2024).hour(24).build().is_err());
+    /// assert!(DateTimeBuilder::new().nanos(1_000_000_000).build().is_err());
+    /// ```
+    pub fn build(&self) -> Result<DateTime, DateTimeError> {
+        let date = self.date.build()?;
+        let (hour, minute, second, nanos) = self.defaults.unwrap_or_default();
+        let hour = self.hour.unwrap_or(hour);
+        let minute = self.minute.unwrap_or(minute);
+        let second = self.second.unwrap_or(second);
+        let nanos = self.nanos.unwrap_or(nanos);
+        if hour > 23 || minute > 59 || second > 59 || nanos > 999_999_999 {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{:02}:{:02}:{:02}.{:09} is not a valid clock time",
+                hour, minute, second, nanos
+            )));
+        }
+        Ok(DateTime {
+            date,
+            time: Time::from(hour as i32, minute as i8, second as i8),
+            nanos,
+        })
+    }
+}
@@ -0,0 +1,148 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// The conversions between the structures of this crate and the naive types of the chrono
+// crate, they are only compiled with the feature chrono.
+//
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::time::*;
+
+impl From<NaiveDate> for Date {
+    /// Creates a ```Date``` from the chrono ```NaiveDate```. Because ```Date``` owns its
+    /// own ```from()``` function, use ```let date: Date = naive.into();```.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use date_and_time::date::*;
+    /// let date: Date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().into();
+    /// assert_eq!(date.as_string(), "2024-02-29");
+    /// ```
+    fn from(naive: NaiveDate) -> Date {
+        Date::from(naive.day() as u8, naive.month() as u8, naive.year())
+    }
+}
+
+impl TryFrom<Date> for NaiveDate {
+    type Error = DateTimeError;
+
+    /// Creates a chrono ```NaiveDate``` from the ```Date```, it returns an error for an
+    /// invalid date or a year outside of the range of chrono.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use date_and_time::date::*;
+    /// let naive = NaiveDate::try_from(Date::from(24, 12, 2024)).unwrap();
+    /// assert_eq!(naive, NaiveDate::from_ymd_opt(2024, 12, 24).unwrap());
+    /// assert!(NaiveDate::try_from(Date::from(30, 2, 2024)).is_err());
+    /// ```
+    fn try_from(date: Date) -> Result<NaiveDate, DateTimeError> {
+        NaiveDate::from_ymd_opt(date.y, date.m as u32, date.d as u32).ok_or_else(|| {
+            DateTimeError::OutOfRange(format!(
+                "the date {} can't be converted to a chrono NaiveDate",
+                date.as_string()
+            ))
+        })
+    }
+}
+
+impl From<NaiveTime> for Time {
+    /// Creates a ```Time``` from the chrono ```NaiveTime```, the fractions of the second
+    /// are truncated. Use ```let time: Time = naive.into();```.
+    ///
+    /// ```
+    /// use chrono::NaiveTime;
+    /// use date_and_time::time::*;
+    /// let time: Time = NaiveTime::from_hms_nano_opt(13, 45, 30, 500).unwrap().into();
+    /// assert_eq!(time, Time::from(13, 45, 30));
+    /// ```
+    fn from(naive: NaiveTime) -> Time {
+        Time::from(
+            naive.hour() as i32,
+            naive.minute() as i8,
+            naive.second() as i8,
+        )
+    }
+}
+
+impl TryFrom<Time> for NaiveTime {
+    type Error = DateTimeError;
+
+    /// Creates a chrono ```NaiveTime``` from the ```Time```, it returns an error for an
+    /// invalid time or a time of 24 hours and more.
+    ///
+    /// ```
+    /// use chrono::NaiveTime;
+    /// use date_and_time::time::*;
+    /// let naive = NaiveTime::try_from(Time::from(23, 59, 59)).unwrap();
+    /// assert_eq!(naive, NaiveTime::from_hms_opt(23, 59, 59).unwrap());
+    /// assert!(NaiveTime::try_from(Time::from(12, 60, 0)).is_err());
+    /// ```
+    fn try_from(time: Time) -> Result<NaiveTime, DateTimeError> {
+        naive_time(&time, 0)
+    }
+}
+
+impl From<NaiveDateTime> for DateTime {
+    /// Creates a ```DateTime``` from the chrono ```NaiveDateTime``` including the fractions
+    /// of the second, a leap second of chrono is counted as the second 59. Because
+    /// ```DateTime``` owns its own ```from()``` function, use ```let dt: DateTime =
+    /// naive.into();```.
+    ///
+    /// ```
+    /// use chrono::{NaiveDate, NaiveTime};
+    /// use date_and_time::datetime::*;
+    /// let date = NaiveDate::from_ymd_opt(2024, 6, 22).unwrap();
+    /// let naive = date.and_time(NaiveTime::from_hms_nano_opt(8, 30, 0, 250_000_000).unwrap());
+    /// let dt: DateTime = naive.into();
+    /// assert_eq!(dt.as_string(), "2024-06-22T08:30:00.250000000");
+    /// ```
+    fn from(naive: NaiveDateTime) -> DateTime {
+        DateTime {
+            date: naive.date().into(),
+            time: naive.time().into(),
+            nanos: naive.nanosecond() % 1_000_000_000,
+        }
+    }
+}
+
+impl TryFrom<DateTime> for NaiveDateTime {
+    type Error = DateTimeError;
+
+    /// Creates a chrono ```NaiveDateTime``` from the ```DateTime``` including the fractions
+    /// of the second, it returns an error if the date or the time can't be converted.
+    ///
+    /// ```
+    /// use chrono::NaiveDateTime;
+    /// use date_and_time::datetime::*;
+    /// let naive = NaiveDateTime::try_from(DateTime::from_timestamp(1_700_000_000)).unwrap();
+    /// let back: DateTime = naive.into();
+    /// assert_eq!(back, DateTime::from_timestamp(1_700_000_000));
+    /// ```
+    fn try_from(dt: DateTime) -> Result<NaiveDateTime, DateTimeError> {
+        Ok(NaiveDateTime::new(
+            NaiveDate::try_from(dt.date)?,
+            naive_time(&dt.time, dt.nanos)?,
+        ))
+    }
+}
+
+// Returns the chrono NaiveTime of the time: &Time and the nanos: u32, or an error
+fn naive_time(time: &Time, nanos: u32) -> Result<NaiveTime, DateTimeError> {
+    let error = || {
+        DateTimeError::OutOfRange(format!(
+            "the time {} can't be converted to a chrono NaiveTime",
+            time.as_string()
+        ))
+    };
+    if time.h < 0 || time.m < 0 || time.s < 0 || nanos >= 1_000_000_000 {
+        return Err(error());
+    }
+    NaiveTime::from_hms_nano_opt(time.h as u32, time.m as u32, time.s as u32, nanos)
+        .ok_or_else(error)
+}
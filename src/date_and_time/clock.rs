@@ -0,0 +1,221 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use std::cell::Cell;
+
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::time::*;
+
+// The frozen time and the time travel offset of the SystemClock in the current thread
+thread_local! {
+    static FROZEN: Cell<Option<DateTime>> = const { Cell::new(None) };
+    static TRAVEL: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// The ```Clock``` trait is the source of the current date and time. Code which gets the
+/// clock as a parameter (instead of calling ```from_system_clock()``` directly) can be
+/// tested with a ```FixedClock```.
+///
+pub trait Clock {
+    /// ```now()``` returns the current date and time in UTC.
+    fn now(&self) -> DateTime;
+}
+
+/// The ```SystemClock``` is the ```Clock``` of the operating system. In tests it can be
+/// frozen or moved with the functions ```freeze()```, ```travel()``` and ```reset()```,
+/// this only affects the current thread.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime {
+        if let Some(frozen) = FROZEN.with(|f| f.get()) {
+            return frozen;
+        }
+        DateTime::from_system_clock().add_duration(&TRAVEL.with(|t| t.get()))
+    }
+}
+
+/// The ```FixedClock``` is a ```Clock``` which always returns the same date and time until
+/// it is changed with ```set()``` or ```advance()```.
+///
+/// ```
+/// use date_and_time::clock::*;
+/// use date_and_time::date::*;
+/// use date_and_time::datetime::*;
+/// use date_and_time::duration::*;
+/// let mut clock = FixedClock::new(DateTime::from_timestamp(1_719_791_999));
+/// assert_eq!(Date::today_with(&clock), Date::from(30, 6, 2024));
+/// clock.advance(&Duration::from_seconds(1));
+/// assert_eq!(Date::today_with(&clock), Date::from(1, 7, 2024));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FixedClock {
+    pub now: DateTime,
+}
+
+#[allow(dead_code)]
+impl FixedClock {
+    /// ```new(now)``` creates a ```FixedClock``` which returns the date and time ```now```.
+    pub fn new(now: DateTime) -> FixedClock {
+        FixedClock { now }
+    }
+    /// ```set(now)``` sets the clock to the date and time ```now```.
+    pub fn set(&mut self, now: DateTime) {
+        self.now = now;
+    }
+    /// ```advance(&duration)``` moves the clock forward by the ```duration``` (backwards
+    /// with a negative duration).
+    pub fn advance(&mut self, duration: &Duration) {
+        self.now = self.now.add_duration(duration);
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime {
+        self.now
+    }
+}
+
+/// ```freeze(now)``` stops the ```SystemClock``` of the current thread at the date and time
+/// ```now```, until ```reset()``` is called.
+///
+/// ```
+/// use date_and_time::clock::*;
+/// use date_and_time::datetime::*;
+/// freeze(DateTime::from_timestamp(0));
+/// assert_eq!(SystemClock.now().as_string(), "1970-01-01T00:00:00");
+/// reset();
+/// ```
+pub fn freeze(now: DateTime) {
+    FROZEN.with(|f| f.set(Some(now)));
+}
+
+/// ```travel(offset)``` moves the ```SystemClock``` of the current thread by the
+/// ```offset``` into the future (or into the past with a negative offset), the clock keeps
+/// running. The offsets of several calls are added, ```reset()``` removes them.
+///
+/// ```
+/// use date_and_time::clock::*;
+/// use date_and_time::datetime::*;
+/// use date_and_time::duration::*;
+/// travel(Duration::from_seconds(86_400));
+/// let tomorrow = SystemClock.now();
+/// reset();
+/// assert!(tomorrow.diff(&DateTime::from_system_clock()).as_seconds() < -86_000);
+/// ```
+pub fn travel(offset: Duration) {
+    TRAVEL.with(|t| {
+        let total = t.get().as_nanoseconds() + offset.as_nanoseconds();
+        t.set(Duration::from_nanoseconds(total));
+    });
+}
+
+/// ```reset()``` removes the ```freeze()``` and the ```travel()``` of the ```SystemClock``` in
+/// the current thread.
+///
+/// ```
+/// use date_and_time::clock::*;
+/// use date_and_time::datetime::*;
+/// freeze(DateTime::from_timestamp(0));
+/// reset();
+/// assert!(SystemClock.now() > DateTime::from_timestamp(0));
+/// ```
+pub fn reset() {
+    FROZEN.with(|f| f.set(None));
+    TRAVEL.with(|t| t.set(Duration::ZERO));
+}
+
+#[allow(dead_code)]
+impl Date {
+    /// ```today_with(&clock)``` creates a ```Date``` structure with the current UTC date of
+    /// the ```clock```.
+    ///
+    /// ```
+    /// use date_and_time::clock::*;
+    /// use date_and_time::date::*;
+    /// let today = Date::today_with(&SystemClock);
+    /// println!("Today is {}", today.as_string());
+    /// ```
+    pub fn today_with(clock: &(impl Clock + ?Sized)) -> Date {
+        clock.now().date
+    }
+}
+
+#[allow(dead_code)]
+impl Time {
+    /// ```now_with(&clock)``` creates a ```Time``` structure with the current UTC time of
+    /// the ```clock```.
+    ///
+    /// ```
+    /// use date_and_time::clock::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// let clock = FixedClock::new(DateTime::from_timestamp(45_296));
+    /// assert_eq!(Time::now_with(&clock), Time::from(12, 34, 56));
+    /// ```
+    pub fn now_with(clock: &(impl Clock + ?Sized)) -> Time {
+        clock.now().time
+    }
+}
+
+#[allow(dead_code)]
+impl DateTime {
+    /// ```now_with(&clock)``` creates a ```DateTime``` structure with the current UTC date
+    /// and time of the ```clock```.
+    ///
+    /// ```
+    /// use date_and_time::clock::*;
+    /// use date_and_time::datetime::*;
+    /// let clock = FixedClock::new(DateTime::from_timestamp(86_400));
+    /// assert_eq!(DateTime::now_with(&clock).as_string(), "1970-01-02T00:00:00");
+    /// ```
+    pub fn now_with(clock: &(impl Clock + ?Sized)) -> DateTime {
+        clock.now()
+    }
+    /// ```is_before_now(&clock)``` returns true if the date and time is before the current
+    /// date and time of the ```clock```, e.g. to check if a token is expired.
+    ///
+    /// ```
+    /// use date_and_time::clock::*;
+    /// use date_and_time::datetime::*;
+    /// let clock = FixedClock::new(DateTime::from_timestamp(1_000));
+    /// let expires = DateTime::from_timestamp(999);
+    /// assert!(expires.is_before_now(&clock));
+    /// assert!(!clock.now.is_before_now(&clock));
+    /// ```
+    pub fn is_before_now(&self, clock: &(impl Clock + ?Sized)) -> bool {
+        *self < clock.now()
+    }
+    /// ```is_after_now(&clock)``` returns true if the date and time is after the current
+    /// date and time of the ```clock```, e.g. a scheduled job in the future.
+    ///
+    /// ```
+    /// use date_and_time::clock::*;
+    /// use date_and_time::datetime::*;
+    /// let clock = FixedClock::new(DateTime::from_timestamp(1_000));
+    /// assert!(DateTime::from_timestamp(1_001).is_after_now(&clock));
+    /// assert!(!clock.now.is_after_now(&clock));
+    /// ```
+    pub fn is_after_now(&self, clock: &(impl Clock + ?Sized)) -> bool {
+        *self > clock.now()
+    }
+    /// ```elapsed_since(&clock)``` returns the ```Duration``` from the date and time to the
+    /// current date and time of the ```clock```, it is negative if the date and time is in
+    /// the future.
+    ///
+    /// ```
+    /// use date_and_time::clock::*;
+    /// use date_and_time::datetime::*;
+    /// let clock = FixedClock::new(DateTime::from_timestamp(1_000));
+    /// let created = DateTime::from_timestamp(400);
+    /// assert_eq!(created.elapsed_since(&clock).as_seconds(), 600);
+    /// ```
+    pub fn elapsed_since(&self, clock: &(impl Clock + ?Sized)) -> Duration {
+        self.diff(&clock.now())
+    }
+}
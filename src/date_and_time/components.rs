@@ -0,0 +1,160 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::iso8601::*;
+use crate::date_and_time::time::*;
+
+/// The HasDate trait gives the named date components of all structures with a date, so
+/// formatters and parsers can be written once for all of them.
+///
+/// ```
+/// use date_and_time::components::*;
+/// use date_and_time::date::*;
+/// use date_and_time::datetime::*;
+/// fn label(value: &impl HasDate) -> String {
+///     format!("{}/{}/{}", value.month(), value.day(), value.year())
+/// }
+/// assert_eq!(label(&Date::from(22, 6, 2024)), "6/22/2024");
+/// assert_eq!(label(&DateTime::from_timestamp(0)), "1/1/1970");
+/// ```
+pub trait HasDate {
+    /// ```year()``` returns the year.
+    fn year(&self) -> i32;
+    /// ```month()``` returns the month from 1 to 12.
+    fn month(&self) -> u8;
+    /// ```day()``` returns the day of the month.
+    fn day(&self) -> u8;
+    /// ```as_date()``` returns the components as a ```Date``` structure.
+    ///
+    /// ```
+    /// use date_and_time::components::*;
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// assert_eq!(DateTime::from_timestamp(86_400).as_date(), Date::from(2, 1, 1970));
+    /// ```
+    fn as_date(&self) -> Date {
+        Date::from(self.day(), self.month(), self.year())
+    }
+}
+
+/// The HasTime trait gives the named time components of all structures with a clock
+/// time, so formatters and parsers can be written once for all of them.
+///
+/// ```
+/// use date_and_time::components::*;
+/// use date_and_time::datetime::*;
+/// use date_and_time::time::*;
+/// fn is_afternoon(value: &impl HasTime) -> bool {
+///     value.hour() >= 12
+/// }
+/// assert!(is_afternoon(&Time::from(13, 0, 0)));
+/// assert!(!is_afternoon(&DateTime::from_timestamp(3_600)));
+/// ```
+pub trait HasTime {
+    /// ```hour()``` returns the hours.
+    fn hour(&self) -> i32;
+    /// ```minute()``` returns the minutes.
+    fn minute(&self) -> i8;
+    /// ```second()``` returns the seconds.
+    fn second(&self) -> i8;
+    /// ```nanosecond()``` returns the fractions of the second in nanoseconds, it is zero
+    /// for structures without fractions.
+    fn nanosecond(&self) -> u32 {
+        0
+    }
+    /// ```as_time()``` returns the components as a ```Time``` structure, the fractions of
+    /// the second are truncated.
+    ///
+    /// ```
+    /// use date_and_time::components::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// assert_eq!(DateTime::from_timestamp(5_400).as_time(), Time::from(1, 30, 0));
+    /// ```
+    fn as_time(&self) -> Time {
+        Time::from(self.hour(), self.minute(), self.second())
+    }
+}
+
+impl HasDate for Date {
+    fn year(&self) -> i32 {
+        Date::year(self)
+    }
+    fn month(&self) -> u8 {
+        Date::month(self)
+    }
+    fn day(&self) -> u8 {
+        Date::day(self)
+    }
+}
+
+impl HasTime for Time {
+    fn hour(&self) -> i32 {
+        Time::hour(self)
+    }
+    fn minute(&self) -> i8 {
+        Time::minute(self)
+    }
+    fn second(&self) -> i8 {
+        Time::second(self)
+    }
+}
+
+impl HasDate for DateTime {
+    fn year(&self) -> i32 {
+        self.date.y
+    }
+    fn month(&self) -> u8 {
+        self.date.m
+    }
+    fn day(&self) -> u8 {
+        self.date.d
+    }
+}
+
+impl HasTime for DateTime {
+    fn hour(&self) -> i32 {
+        self.time.h
+    }
+    fn minute(&self) -> i8 {
+        self.time.m
+    }
+    fn second(&self) -> i8 {
+        self.time.s
+    }
+    fn nanosecond(&self) -> u32 {
+        self.nanos
+    }
+}
+
+impl HasDate for IsoDateTime {
+    // The components are the local date as written in the text, not the UTC date.
+    fn year(&self) -> i32 {
+        self.date_time.year()
+    }
+    fn month(&self) -> u8 {
+        self.date_time.month()
+    }
+    fn day(&self) -> u8 {
+        self.date_time.day()
+    }
+}
+
+impl HasTime for IsoDateTime {
+    // The components are the local time as written in the text, not the UTC time.
+    fn hour(&self) -> i32 {
+        self.date_time.hour()
+    }
+    fn minute(&self) -> i8 {
+        self.date_time.minute()
+    }
+    fn second(&self) -> i8 {
+        self.date_time.second()
+    }
+    fn nanosecond(&self) -> u32 {
+        self.date_time.nanos
+    }
+}
@@ -0,0 +1,110 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::clock::*;
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::time::*;
+
+/// The Countdown structure counts down to the UTC date and time ```target```. It can be
+/// build with the functions ```new()```, ```from()``` and ```from_duration()```.
+///
+/// The functions without a clock parameter use the ```SystemClock```, the functions with
+/// the suffix ```_with``` take the ```Clock``` as a parameter (e.g. a ```FixedClock``` in
+/// tests).
+///
+/// ```
+/// use date_and_time::clock::*;
+/// use date_and_time::countdown::*;
+/// use date_and_time::date::*;
+/// use date_and_time::datetime::*;
+/// use date_and_time::time::*;
+/// let launch = Countdown::from(Date::from(24, 12, 2024), Time::from(18, 0, 0));
+/// let clock = FixedClock::new(DateTime::from(Date::from(22, 12, 2024), Time::from(14, 44, 51)));
+/// assert_eq!(launch.as_string_with(&clock), "2d 03:15:09");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Countdown {
+    pub target: DateTime,
+}
+
+#[allow(dead_code)]
+impl Countdown {
+    /// ```new(target)``` creates a ```Countdown``` to the UTC date and time ```target```.
+    pub fn new(target: DateTime) -> Countdown {
+        Countdown { target }
+    }
+    /// ```from(date, time)``` creates a ```Countdown``` to the UTC ```date``` and
+    /// ```time```.
+    pub fn from(date: Date, time: Time) -> Countdown {
+        Countdown {
+            target: DateTime::from(date, time),
+        }
+    }
+    /// ```from_duration(&duration)``` creates a ```Countdown``` which expires after the
+    /// ```duration```, counted from now.
+    ///
+    /// ```
+    /// use date_and_time::countdown::*;
+    /// use date_and_time::duration::*;
+    /// let egg_timer = Countdown::from_duration(&Duration::from_seconds(300));
+    /// assert!(!egg_timer.is_expired());
+    /// assert!(egg_timer.remaining().as_seconds() <= 300);
+    /// ```
+    pub fn from_duration(duration: &Duration) -> Countdown {
+        Countdown::from_duration_with(duration, &SystemClock)
+    }
+    /// ```from_duration_with(&duration, &clock)``` creates a ```Countdown``` which expires
+    /// after the ```duration```, counted from the current time of the ```clock```.
+    pub fn from_duration_with(duration: &Duration, clock: &(impl Clock + ?Sized)) -> Countdown {
+        Countdown {
+            target: clock.now().add_duration(duration),
+        }
+    }
+    /// ```remaining()``` returns the time until the target, it is zero if the countdown is
+    /// expired.
+    pub fn remaining(&self) -> Duration {
+        self.remaining_with(&SystemClock)
+    }
+    /// ```remaining_with(&clock)``` returns the time from the current time of the
+    /// ```clock``` until the target, it is zero if the countdown is expired.
+    pub fn remaining_with(&self, clock: &(impl Clock + ?Sized)) -> Duration {
+        let remaining = clock.now().diff(&self.target);
+        if remaining.is_negative() {
+            return Duration::ZERO;
+        }
+        remaining
+    }
+    /// ```is_expired()``` returns true if the target is reached.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_with(&SystemClock)
+    }
+    /// ```is_expired_with(&clock)``` returns true if the target is reached at the current
+    /// time of the ```clock```.
+    pub fn is_expired_with(&self, clock: &(impl Clock + ?Sized)) -> bool {
+        clock.now() >= self.target
+    }
+    /// ```as_string()``` gets the remaining time as a string in the format
+    /// ```Dd HH:MM:SS```, the days are left out if they are zero. The fractions of the
+    /// second are rounded up, so the countdown shows 00:00:00 only when it is expired.
+    pub fn as_string(&self) -> String {
+        self.as_string_with(&SystemClock)
+    }
+    /// ```as_string_with(&clock)``` gets the remaining time at the current time of the
+    /// ```clock``` as a string, see ```as_string()```.
+    pub fn as_string_with(&self, clock: &(impl Clock + ?Sized)) -> String {
+        let remaining = self.remaining_with(clock);
+        let mut secs = remaining.as_seconds();
+        if remaining.subsec_nanoseconds() > 0 {
+            secs += 1;
+        }
+        let days = secs / 86_400;
+        let time = Time::from_seconds(secs % 86_400);
+        if days > 0 {
+            return format!("{}d {}", days, time.as_string());
+        }
+        time.as_string()
+    }
+}
@@ -0,0 +1,271 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::time::*;
+
+// The months of the 400 year cycle of the gregorian calendar, the last month to search
+const MONTHS_PER_CYCLE: u32 = 4_800;
+
+/// The CronSchedule structure is a schedule of a standard 5-field cron expression with the
+/// minute, hour, day of the month, month and day of the week, e.g. ```30 8 * * MON-FRI```.
+/// It can be build with the function ```parse()```.
+///
+/// Every field can be a ```*```, a number, a range (```1-5```), a step (```*/15``` or
+/// ```0-30/10```) or a list of these (```1,15,30```). The months and weekdays can be
+/// English abbreviations (```JAN``` or ```mon```), the weekdays are 0 (or 7) = Sunday to
+/// 6 = Saturday. Like in the classic cron, a date matches if the day of the month or the
+/// day of the week matches, when both fields are restricted (not starting with ```*```).
+/// The shortcuts ```@yearly```, ```@annually```, ```@monthly```, ```@weekly```,
+/// ```@daily```, ```@midnight``` and ```@hourly``` are supported, too.
+///
+/// The schedule has no timezone, it is calculated in the time of the given
+/// ```DateTime``` (e.g. UTC or a local time).
+///
+/// ```
+/// use date_and_time::cron::*;
+/// use date_and_time::date::*;
+/// use date_and_time::datetime::*;
+/// use date_and_time::time::*;
+/// let schedule = CronSchedule::parse("30 8 * * MON-FRI").unwrap();
+/// // Friday evening
+/// let now = DateTime::from(Date::from(5, 7, 2024), Time::from(18, 0, 0));
+/// let next = schedule.next_after(&now).unwrap();
+/// assert_eq!(next.as_string(), "2024-07-08T08:30:00");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+#[allow(dead_code)]
+impl CronSchedule {
+    /// ```parse(expression)``` parses a 5-field cron ```expression``` or one of the
+    /// shortcuts (e.g. ```@daily```), it returns an error for an invalid field.
+    ///
+    /// ```
+    /// use date_and_time::cron::*;
+    /// assert!(CronSchedule::parse("*/15 0-6,22,23 1,15 jan-jun *").is_ok());
+    /// assert!(CronSchedule::parse("@weekly").is_ok());
+    /// assert!(CronSchedule::parse("60 * * * *").is_err());
+    /// assert!(CronSchedule::parse("* * * *").is_err());
+    /// ```
+    pub fn parse(expression: &str) -> Result<CronSchedule, DateTimeError> {
+        let expression = expression.trim();
+        let fields = match expression.to_lowercase().as_str() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            _ => expression,
+        };
+        let fields: Vec<&str> = fields.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(DateTimeError::Parse(format!(
+                "'{}' is not a cron expression with 5 fields",
+                expression
+            )));
+        }
+        let field = |n: usize, min: u8, max: u8, names: &[&str], first_name: u8| {
+            get_cron_field(fields[n], min, max, names, first_name).ok_or_else(|| {
+                DateTimeError::Parse(format!(
+                    "'{}' is not a valid field in the cron expression '{}'",
+                    fields[n], expression
+                ))
+            })
+        };
+        let weekdays = field(4, 0, 7, &WEEKDAY_ABBREVIATE, 0)?;
+        Ok(CronSchedule {
+            expression: String::from(expression),
+            minutes: field(0, 0, 59, &[], 0)?,
+            hours: field(1, 0, 23, &[], 0)?,
+            days: field(2, 1, 31, &[], 0)?,
+            months: field(3, 1, 12, &MONTH_NAME_ABBREVIATE, 1)?,
+            // the Sunday can be 0 or 7
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            any_day: fields[2].starts_with('*'),
+            any_weekday: fields[4].starts_with('*'),
+        })
+    }
+    /// ```as_string()``` gets the cron expression of the schedule.
+    ///
+    /// ```
+    /// use date_and_time::cron::*;
+    /// assert_eq!(CronSchedule::parse(" 0 12 * * * ").unwrap().as_string(), "0 12 * * *");
+    /// ```
+    pub fn as_string(&self) -> String {
+        self.expression.clone()
+    }
+    /// ```matches(&datetime)``` returns true if the ```datetime``` is in the schedule, the
+    /// seconds are ignored. An invalid date or a time which is not a time of the clock
+    /// never matches.
+    ///
+    /// ```
+    /// use date_and_time::cron::*;
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// // the 13th of a month or any Friday
+    /// let schedule = CronSchedule::parse("0 0 13 * FRI").unwrap();
+    /// assert!(schedule.matches(&DateTime::from(Date::from(13, 8, 2024), Time::from(0, 0, 30))));
+    /// assert!(schedule.matches(&DateTime::from(Date::from(16, 8, 2024), Time::new())));
+    /// assert!(!schedule.matches(&DateTime::from(Date::from(15, 8, 2024), Time::new())));
+    /// ```
+    pub fn matches(&self, datetime: &DateTime) -> bool {
+        // a counting time or an invalid time is not a time of the clock
+        let time = &datetime.time;
+        if !is_time_valid(time) || !(0..86_400).contains(&time.as_seconds()) {
+            return false;
+        }
+        self.is_date_matching(&datetime.date)
+            && self.hours & 1 << datetime.time.h != 0
+            && self.minutes & 1 << datetime.time.m != 0
+    }
+    /// ```next_after(&datetime)``` returns the first date and time of the schedule after
+    /// the ```datetime```, or None if the schedule never matches (e.g. on the 30th
+    /// February) or the ```datetime``` has an invalid date.
+    ///
+    /// ```
+    /// use date_and_time::cron::*;
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// let leap_day = CronSchedule::parse("0 12 29 2 *").unwrap();
+    /// let now = DateTime::from(Date::from(1, 3, 2024), Time::new());
+    /// assert_eq!(leap_day.next_after(&now).unwrap().as_string(), "2028-02-29T12:00:00");
+    /// assert_eq!(CronSchedule::parse("0 0 30 2 *").unwrap().next_after(&now), None);
+    /// ```
+    pub fn next_after(&self, datetime: &DateTime) -> Option<DateTime> {
+        if !is_date_valid(&datetime.date) {
+            return None;
+        }
+        let minute = Time::from(datetime.time.h, datetime.time.m, 0);
+        let start = DateTime::from(datetime.date, minute).add_duration(&Duration::from_seconds(60));
+        let (mut year, mut month, mut first_day) = (start.date.y, start.date.m, start.date.d);
+        let mut first_minute = start.time.h as u32 * 60 + start.time.m as u32;
+        for _ in 0..=MONTHS_PER_CYCLE {
+            if self.months & 1 << month != 0 {
+                for d in first_day..=get_max_days_of_month(month, year) {
+                    let date = Date {
+                        d,
+                        m: month,
+                        y: year,
+                    };
+                    if self.is_date_matching(&date) {
+                        if let Some(time) = self.get_first_time(first_minute) {
+                            return Some(DateTime::from(date, time));
+                        }
+                    }
+                    first_minute = 0;
+                }
+            }
+            (first_day, first_minute) = (1, 0);
+            month += 1;
+            if month > 12 {
+                (year, month) = (year + 1, 1);
+            }
+        }
+        None
+    }
+    /// ```occurrences_after(&datetime)``` returns an iterator over the dates and times of
+    /// the schedule after the ```datetime```.
+    ///
+    /// ```
+    /// use date_and_time::cron::*;
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// let schedule = CronSchedule::parse("*/20 9 * * *").unwrap();
+    /// let now = DateTime::from(Date::from(1, 7, 2024), Time::from(9, 20, 0));
+    /// let times: Vec<String> = schedule
+    ///     .occurrences_after(&now)
+    ///     .take(3)
+    ///     .map(|dt| dt.as_string())
+    ///     .collect();
+    /// assert_eq!(times, ["2024-07-01T09:40:00", "2024-07-02T09:00:00", "2024-07-02T09:20:00"]);
+    /// ```
+    pub fn occurrences_after(&self, datetime: &DateTime) -> impl Iterator<Item = DateTime> + '_ {
+        let mut last = *datetime;
+        std::iter::from_fn(move || {
+            last = self.next_after(&last)?;
+            Some(last)
+        })
+    }
+    // Returns true if the month, the day of the month or the weekday of the date: &Date
+    // are in the schedule
+    fn is_date_matching(&self, date: &Date) -> bool {
+        if !is_date_valid(date) {
+            return false;
+        }
+        let day = self.days & 1 << date.d != 0;
+        let weekday = self.weekdays & 1 << date.get_weekday() != 0;
+        let day_matches = match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            (true, false) => weekday,
+            _ => day,
+        };
+        self.months & 1 << date.m != 0 && day_matches
+    }
+    // Returns the first time of the schedule at or after the minute: u32 of the day, or
+    // None if there is no such time on this day
+    fn get_first_time(&self, minute: u32) -> Option<Time> {
+        (minute / 60..24)
+            .filter(|h| self.hours & 1 << h != 0)
+            .find_map(|h| {
+                let first = if h == minute / 60 { minute % 60 } else { 0 };
+                let m = (self.minutes >> first).trailing_zeros() + first;
+                (m < 60).then(|| Time::from(h as i32, m as i8, 0))
+            })
+    }
+}
+
+// Returns the bits of the values of the cron field: &str with the values from min: u8 to
+// max: u8, the names: &[&str] are the values from first_name: u8, or None if it is invalid
+fn get_cron_field(field: &str, min: u8, max: u8, names: &[&str], first_name: u8) -> Option<u64> {
+    let value = |text: &str| -> Option<u8> {
+        let number = match names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(text))
+        {
+            Some(idx) => idx as u8 + first_name,
+            None if !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()) => {
+                text.parse().ok()?
+            }
+            None => return None,
+        };
+        (min..=max).contains(&number).then_some(number)
+    };
+    let mut bits: u64 = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u8>().ok().filter(|s| *s > 0)?),
+            None => (part, 1),
+        };
+        let (first, last) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((first, last)) => (value(first)?, value(last)?),
+            // a single value with a step runs until the maximum, e.g. 5/15
+            None if part.contains('/') => (value(range)?, max),
+            None => (value(range)?, value(range)?),
+        };
+        if first > last {
+            return None;
+        }
+        for n in (first..=last).step_by(step as usize) {
+            bits |= 1 << n;
+        }
+    }
+    Some(bits)
+}
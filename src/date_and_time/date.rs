@@ -2,15 +2,28 @@
 // (c) 2024 by markus dot mueller dot 73 at hotmail dot de
 // small crate to get some rudimentary date and time calculations
 // the license details are in the main library file.
-use std::time::SystemTime;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::formatted::*;
+use crate::date_and_time::invalid::*;
+use crate::date_and_time::locale::*;
+use crate::date_and_time::time::*;
+use std::fmt;
 
 // These constant arrays are private and only used for calculatons.
 const LAST_DAY_OF_MONTH_LEAP: [u8; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
 const LAST_DAY_OF_MONTH_COMMON: [u8; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
 
+// The Rata Die day number of the 1st January 1970 (the 1st January 1 is the day 1)
+const RATA_DIE_OF_EPOCH: i64 = 719_163;
+// The days since the epoch of Date::MIN and Date::MAX
+const MIN_DAYS: i64 = get_days_from_date(&Date::MIN);
+const MAX_DAYS: i64 = get_days_from_date(&Date::MAX);
+
 // These constants are placeholders, Rust has no direct methods to get the local
 // date and time format of the running system.
-const WEEKDAY_FULL: [&str; 7] = [
+pub(crate) const WEEKDAY_FULL: [&str; 7] = [
     "Sunday",
     "Monday",
     "Tuesday",
@@ -19,9 +32,9 @@ const WEEKDAY_FULL: [&str; 7] = [
     "Friday",
     "Saturday",
 ];
-const WEEKDAY_ABBREVIATE: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+pub(crate) const WEEKDAY_ABBREVIATE: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
 
-const MONTH_NAME_FULL: [&str; 12] = [
+pub(crate) const MONTH_NAME_FULL: [&str; 12] = [
     "January",
     "February",
     "March",
@@ -35,7 +48,7 @@ const MONTH_NAME_FULL: [&str; 12] = [
     "November",
     "December",
 ];
-const MONTH_NAME_ABBREVIATE: [&str; 12] = [
+pub(crate) const MONTH_NAME_ABBREVIATE: [&str; 12] = [
     "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
 ];
 
@@ -45,95 +58,669 @@ const MONTH_NAME_ABBREVIATE: [&str; 12] = [
 ///
 /// Take a look further into the methods.
 ///
-/// The structure owns the traits ```Copy```, ```Clone``` and ```PartialEq```. so you can
-/// compare two dates if they are equal or not.
+/// The structure owns the traits ```Copy```, ```Clone```, ```PartialEq``` and ```Ord```. so
+/// you can compare two dates and use them as keys of a ```BTreeMap``` or ```HashMap```.
+///
+/// The supported dates are all days of the years of an ```i32```, from ```Date::MIN```
+/// to ```Date::MAX```. A calculation with a result outside of this range returns the
+/// invalid date ```Date{d: 0, m: 0, y: 0}``` (or an error).
 ///
-#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Date {
     pub y: i32,
     pub m: u8,
     pub d: u8,
 }
 
+/// The handling of the 29th February in years without a leap day, see
+/// ```Date::add_years_with()```.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LeapDayPolicy {
+    /// The anniversary is the 28th February.
+    Feb28,
+    /// The anniversary is the 1st March.
+    Mar1,
+    /// There is no anniversary in the year.
+    Skip,
+}
+
+/// The handling of a day which is missing in the new month (e.g. the 31st February), see
+/// ```Date::add_months_with()```.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MonthEndPolicy {
+    /// The date is moved to the last day of the month.
+    Clamp,
+    /// The missing days are carried into the next month, e.g. the 31st February is the 2nd
+    /// or 3rd March.
+    Overflow,
+    /// The date is an error.
+    Error,
+}
+
+/// The parity of a week relative to an anchor week, see ```Date::week_parity()```.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WeekParity {
+    Even,
+    Odd,
+}
+
+/// The days of the week, the numbers (```Weekday::Monday as u8```) are the same as of
+/// ```Date::get_weekday()```.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Sunday = 0,
+    Monday = 1,
+    Tuesday = 2,
+    Wednesday = 3,
+    Thursday = 4,
+    Friday = 5,
+    Saturday = 6,
+}
+
+/// The ```WeekConfig``` defines the weeks of reports and calendars, at the moment the
+/// ```first_day``` of the week. The ISO 8601 weeks start on Monday, the US weeks on Sunday.
+///
+/// ```
+/// use date_and_time::date::*;
+/// let config = WeekConfig::us();
+/// assert_eq!(config.week_start(&Date::from(3, 7, 2024)).as_string(), "2024-06-30");
+/// assert_eq!(WeekConfig::default(), WeekConfig::iso());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WeekConfig {
+    pub first_day: Weekday,
+}
+
+#[allow(dead_code)]
+impl WeekConfig {
+    /// ```iso()``` returns the ISO 8601 week configuration, the weeks start on Monday.
+    pub const fn iso() -> WeekConfig {
+        WeekConfig {
+            first_day: Weekday::Monday,
+        }
+    }
+    /// ```us()``` returns the US week configuration, the weeks start on Sunday.
+    pub const fn us() -> WeekConfig {
+        WeekConfig {
+            first_day: Weekday::Sunday,
+        }
+    }
+    /// ```week_start(&date)``` returns the first day of the week of the ```date```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let monday = WeekConfig::iso().week_start(&Date::from(3, 7, 2024));
+    /// assert_eq!(monday.as_string(), "2024-07-01");
+    /// ```
+    pub fn week_start(&self, date: &Date) -> Date {
+        date.start_of_week(self.first_day)
+    }
+}
+
+impl Default for WeekConfig {
+    fn default() -> Self {
+        WeekConfig::iso()
+    }
+}
+
+impl Default for Date {
+    fn default() -> Self {
+        Date::new()
+    }
+}
+
 #[allow(dead_code)]
 impl Date {
+    /// The first supported date, the 1st January of the year ```i32::MIN```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::MIN.sub_days(1).m, 0);
+    /// ```
+    pub const MIN: Date = Date {
+        d: 1,
+        m: 1,
+        y: i32::MIN,
+    };
+    /// The last supported date, the 31st December of the year ```i32::MAX```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::MAX.add_days(1).m, 0);
+    /// assert_eq!(Date::from(1, 1, 2024).add_days(u64::MAX).m, 0);
+    /// ```
+    pub const MAX: Date = Date {
+        d: 31,
+        m: 12,
+        y: i32::MAX,
+    };
     /// ```new()``` creates a ```Date``` structure with the date 1st January 0 (year Null).
-    pub fn new() -> Date {
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::new().as_string(), "0000-01-01");
+    /// ```
+    pub const fn new() -> Date {
         Date { d: 1, m: 1, y: 0 }
     }
     /// ```from(day, month, year)``` creates a ```Date``` structure with the given date.
     /// The new ```Date``` will be checked for validity, if it was invalid, the returned date
     /// will be ```Date{d: 0, m: 0, y: 0}```. You can check against the day or month if you
     /// got a valid date.
-    pub fn from(day: u8, month: u8, year: i32) -> Date {
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let leap_day = Date::from(29, 2, 2024);
+    /// assert_eq!(leap_day.as_string(), "2024-02-29");
+    /// // 2023 was no leap year
+    /// assert_eq!(Date::from(29, 2, 2023).d, 0);
+    /// ```
+    pub const fn from(day: u8, month: u8, year: i32) -> Date {
         let new_date = Date {
             d: day,
             m: month,
             y: year,
         };
-        if is_date_valid(&new_date) == false {
+        if !is_date_valid(&new_date) {
             return Date { d: 0, m: 0, y: 0 };
         }
         new_date
     }
+    /// ```from_ymd(year, month, day)``` creates a ```Date``` structure like ```from()```
+    /// with the parameters in the ISO order. Both functions are ```const```, so a ```Date```
+    /// can be a constant or part of a ```static``` table.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// const RELEASE: Date = Date::from_ymd(2024, 6, 22);
+    /// static MILESTONES: [Date; 2] = [Date::from_ymd(2024, 3, 1), RELEASE];
+    /// assert_eq!(MILESTONES[1].as_string(), "2024-06-22");
+    /// // invalid dates are checked at compile time too
+    /// const INVALID: Date = Date::from_ymd(2023, 2, 29);
+    /// assert_eq!(INVALID.m, 0);
+    /// ```
+    pub const fn from_ymd(year: i32, month: u8, day: u8) -> Date {
+        Date::from(day, month, year)
+    }
+    /// ```from_ordinal(year, day_of_year)``` creates a ```Date``` structure from the day in
+    /// the year (range 1 - 365, or 366 in a leap year), it is the inverse of
+    /// ```get_day_of_year()```. It returns an error if the day is not in the year.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from_ordinal(2024, 61), Ok(Date::from(1, 3, 2024)));
+    /// assert_eq!(Date::from_ordinal(2024, 366), Ok(Date::from(31, 12, 2024)));
+    /// assert!(Date::from_ordinal(2023, 366).is_err());
+    /// assert!(Date::from_ordinal(2024, 0).is_err());
+    /// ```
+    pub fn from_ordinal(year: i32, day_of_year: u32) -> Result<Date, DateTimeError> {
+        let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+        if !(1..=days_in_year).contains(&day_of_year) {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{} is not a day of the year {} (1 to {})",
+                day_of_year, year, days_in_year
+            )));
+        }
+        let jan_1 = get_days_from_date(&Date {
+            d: 1,
+            m: 1,
+            y: year,
+        });
+        Ok(get_date_from_days(jan_1 + day_of_year as i64 - 1))
+    }
+    /// ```from_iso_week_date(year, week, weekday)``` creates a ```Date``` structure from
+    /// the ISO 8601 week-based ```year```, the ```week``` (range 1 - 52, or 53 in a long
+    /// year) and the ```weekday``` (1 = Monday to 7 = Sunday). It is the inverse of
+    /// ```get_iso_week()```, it returns an error if the week or the weekday doesn't exist or
+    /// the date is out of the range of ```Date::MIN``` to ```Date::MAX```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from_iso_week_date(2024, 26, 6), Ok(Date::from(29, 6, 2024)));
+    /// // the week 1 of 2025 starts in 2024
+    /// assert_eq!(Date::from_iso_week_date(2025, 1, 1), Ok(Date::from(30, 12, 2024)));
+    /// assert_eq!(Date::from_iso_week_date(2020, 53, 7), Ok(Date::from(3, 1, 2021)));
+    /// assert!(Date::from_iso_week_date(2024, 53, 1).is_err());
+    /// assert!(Date::from_iso_week_date(2024, 1, 0).is_err());
+    /// ```
+    pub fn from_iso_week_date(year: i32, week: u8, weekday: u8) -> Result<Date, DateTimeError> {
+        let weeks = Date {
+            d: 28,
+            m: 12,
+            y: year,
+        }
+        .get_iso_week()
+        .1;
+        if !(1..=weeks).contains(&week) || !(1..=7).contains(&weekday) {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{}-W{:02}-{} is not an ISO week date (weeks 1 to {}, weekdays 1 to 7)",
+                year, week, weekday, weeks
+            )));
+        }
+        let jan_4 = Date {
+            d: 4,
+            m: 1,
+            y: year,
+        };
+        let monday = get_days_from_date(&jan_4) - (jan_4.get_weekday() as i64 + 6) % 7;
+        let date = get_date_from_days(monday + (week as i64 - 1) * 7 + weekday as i64 - 1);
+        // the weeks at the ends of the years of an i32 can spill over Date::MIN or Date::MAX
+        if !is_date_valid(&date) {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{}-W{:02}-{} is out of the range of a date",
+                year, week, weekday
+            )));
+        }
+        Ok(date)
+    }
+    /// ```from_rata_die(rata_die)``` creates a ```Date``` structure from the Rata Die day
+    /// number, the days since the 31st December of the year 0 (the 1st January of the year
+    /// 1 is the day 1). The days since the 1st January 1 (e.g. of the .NET ```DateTime```)
+    /// are the Rata Die minus 1.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from_rata_die(1), Date::from(1, 1, 1));
+    /// assert_eq!(Date::from_rata_die(719_163), Date::from(1, 1, 1970));
+    /// // outside of Date::MIN and Date::MAX
+    /// assert_eq!(Date::from_rata_die(i64::MAX).m, 0);
+    /// ```
+    pub fn from_rata_die(rata_die: i64) -> Date {
+        get_date_from_days(rata_die.saturating_sub(RATA_DIE_OF_EPOCH))
+    }
+    /// ```to_rata_die()``` gets the Rata Die day number of the date, see
+    /// ```from_rata_die()```. It returns ```None``` for an invalid date, the day number 0
+    /// is the valid 31st December of the year 0.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(22, 6, 2024).to_rata_die(), Some(739_059));
+    /// assert_eq!(Date::from(31, 12, 0).to_rata_die(), Some(0));
+    /// assert_eq!(Date::from(31, 2, 2024).to_rata_die(), None);
+    /// // the .NET ticks of the day
+    /// let ticks = (Date::from(22, 6, 2024).to_rata_die().unwrap() - 1) * 864_000_000_000;
+    /// assert_eq!(ticks, 638_546_112_000_000_000);
+    /// ```
+    pub fn to_rata_die(&self) -> Option<i64> {
+        if !is_date_valid(self) {
+            return None;
+        }
+        Some(get_days_from_date(self) + RATA_DIE_OF_EPOCH)
+    }
+    /// ```from_epoch_days(days)``` creates a ```Date``` structure from the days since the
+    /// 1st January 1970, negative ```days``` are before it. Outside of ```Date::MIN``` and
+    /// ```Date::MAX``` the date is invalid.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// const LAUNCH: Date = Date::from_epoch_days(19_896);
+    /// assert_eq!(LAUNCH, Date::from(22, 6, 2024));
+    /// assert_eq!(Date::from_epoch_days(-1), Date::from(31, 12, 1969));
+    /// ```
+    pub const fn from_epoch_days(days: i64) -> Date {
+        get_date_from_days(days)
+    }
+    /// ```to_epoch_days()``` gets the days since the 1st January 1970 of the date, see
+    /// ```from_epoch_days()```. It returns ```None``` for an invalid date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// const DAYS: Option<i64> = Date::from(22, 6, 2024).to_epoch_days();
+    /// assert_eq!(DAYS, Some(19_896));
+    /// assert_eq!(Date::from(30, 2, 2024).to_epoch_days(), None);
+    /// ```
+    pub const fn to_epoch_days(&self) -> Option<i64> {
+        if !is_date_valid(self) {
+            return None;
+        }
+        Some(get_days_from_date(self))
+    }
     /// ```from_system_date()``` creates a ```Date``` structure with the current system date
     /// derived from UTC time.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let today = Date::from_system_date();
+    /// println!("Today is {}", today.as_string());
+    /// ```
     pub fn from_system_date() -> Date {
-        let sys_secs = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        get_date_from_days((sys_secs / 86_400) as i64)
+        let nanos = get_system_clock_nanoseconds();
+        get_date_from_days(nanos.div_euclid(86_400_000_000_000) as i64)
     }
     // TODO pub fn from_local_date() -> Date
     /// ```set(day, month, year)``` modified the ```Date``` structure to the given date.
     /// The new ```Date``` will be checked for validity, if it was invalid, the returned date
     /// will be ```Date{d: 0, m: 0, y: 0}```. You can check against the day or month if you
-    /// got a valid date.
+    /// got a valid date. Use ```with_day()```, ```with_month()``` or ```with_year()``` to
+    /// keep the date if the new one is invalid.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let mut date = Date::new();
+    /// date.set(24, 12, 2024);
+    /// assert_eq!(date, Date::from(24, 12, 2024));
+    /// date.set(32, 1, 2024);
+    /// assert_eq!(date.m, 0);
+    /// ```
     pub fn set(&mut self, day: u8, month: u8, year: i32) {
         self.d = day;
         self.m = month;
         self.y = year;
-        if is_date_valid(self) == false {
+        if !is_date_valid(self) {
             self.d = 0;
             self.m = 0;
             self.y = 0;
         }
     }
+    /// ```year()``` returns the year of the ```Date```, it is the same as the field ```y```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(24, 12, 2024).year(), 2024);
+    /// ```
+    pub fn year(&self) -> i32 {
+        self.y
+    }
+    /// ```month()``` returns the month (1 to 12) of the ```Date```, it is the same as the
+    /// field ```m```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(24, 12, 2024).month(), 12);
+    /// ```
+    pub fn month(&self) -> u8 {
+        self.m
+    }
+    /// ```day()``` returns the day of the month of the ```Date```, it is the same as the
+    /// field ```d```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(24, 12, 2024).day(), 24);
+    /// ```
+    pub fn day(&self) -> u8 {
+        self.d
+    }
+    /// ```with_day(day)``` returns a new ```Date``` structure with the ```day``` of the
+    /// month instead of the day of this date, or an error if the new date is invalid. The
+    /// date itself is not changed.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let date = Date::from(24, 2, 2023);
+    /// assert_eq!(date.with_day(15), Ok(Date::from(15, 2, 2023)));
+    /// assert!(date.with_day(29).is_err());
+    /// ```
+    pub fn with_day(&self, day: u8) -> Result<Date, DateTimeError> {
+        get_checked_date(day, self.m, self.y)
+    }
+    /// ```with_month(month)``` returns a new ```Date``` structure with the ```month```
+    /// instead of the month of this date, or an error if the new date is invalid (e.g. the
+    /// 31st in a month with 30 days).
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let date = Date::from(31, 5, 2024);
+    /// assert_eq!(date.with_month(7), Ok(Date::from(31, 7, 2024)));
+    /// assert!(date.with_month(6).is_err());
+    /// assert!(date.with_month(13).is_err());
+    /// ```
+    pub fn with_month(&self, month: u8) -> Result<Date, DateTimeError> {
+        get_checked_date(self.d, month, self.y)
+    }
+    /// ```with_year(year)``` returns a new ```Date``` structure with the ```year```
+    /// instead of the year of this date, or an error if the new date is invalid (the 29th
+    /// February in a year without leap day).
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let leap_day = Date::from(29, 2, 2024);
+    /// assert_eq!(leap_day.with_year(2028), Ok(Date::from(29, 2, 2028)));
+    /// assert!(leap_day.with_year(2025).is_err());
+    /// ```
+    pub fn with_year(&self, year: i32) -> Result<Date, DateTimeError> {
+        get_checked_date(self.d, self.m, year)
+    }
+    /// ```is_in_leap_year()``` returns true if the year of the ```Date``` is a leap year.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert!(Date::from(1, 3, 2024).is_in_leap_year());
+    /// assert!(!Date::from(1, 3, 2023).is_in_leap_year());
+    /// ```
+    pub const fn is_in_leap_year(&self) -> bool {
+        is_leap_year(self.y)
+    }
+    /// ```days_in_month()``` returns the number of days (28 to 31) of the month of the
+    /// ```Date```, or 0 for an invalid date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(10, 2, 2024).days_in_month(), 29);
+    /// assert_eq!(Date::from(30, 2, 2024).days_in_month(), 0);
+    /// ```
+    pub const fn days_in_month(&self) -> u8 {
+        if !is_date_valid(self) {
+            return 0;
+        }
+        get_max_days_of_month(self.m, self.y)
+    }
     /// ```get_day_of_year()``` gets the day in year from the Date structure as a number.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(1, 3, 2024).get_day_of_year(), 61);
+    /// assert_eq!(Date::from(1, 3, 2023).get_day_of_year(), 60);
+    /// ```
     pub fn get_day_of_year(&self) -> u32 {
         let mut d: u32 = self.d as u32;
         let m: usize = self.m as usize;
-        if is_leap_year(self.y) {
-            for n in 0..m - 1 {
-                d += LAST_DAY_OF_MONTH_LEAP[n as usize] as u32;
-            }
+        let days_of_month = if is_leap_year(self.y) {
+            &LAST_DAY_OF_MONTH_LEAP
         } else {
-            for n in 0..m - 1 {
-                d += LAST_DAY_OF_MONTH_COMMON[n as usize] as u32;
-            }
+            &LAST_DAY_OF_MONTH_COMMON
+        };
+        for days in days_of_month.iter().take(m - 1) {
+            d += *days as u32;
         }
         d
     }
-    /// ```get_iso_week_of_year()``` gets the number of the week in the year of the
-    /// Date structure as a number. This is the ISO 8601 weeknumber. The ISO weeks
-    /// starts with Monday.
-    pub fn get_iso_week_of_year(&self) -> u8 {
-        let jan1 = Date {
+    /// ```get_day_of_quarter()``` gets the day in the quarter (range 1 - 92) from the Date
+    /// structure as a number, or 0 for an invalid date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(1, 4, 2024).get_day_of_quarter(), 1);
+    /// assert_eq!(Date::from(31, 3, 2024).get_day_of_quarter(), 91);
+    /// ```
+    pub fn get_day_of_quarter(&self) -> u32 {
+        if !is_date_valid(self) {
+            return 0;
+        }
+        let first_month = (self.m - 1) / 3 * 3 + 1;
+        let first = Date {
             d: 1,
-            m: 1,
+            m: first_month,
             y: self.y,
         };
-        let current: Date = *self;
-        let days_jan1 = get_days_from_date(&jan1);
-        let days_date = get_days_from_date(&current);
-        let weeks = (days_date - days_jan1) / 7 + 1;
-        weeks as u8
+        self.get_day_of_year() - first.get_day_of_year() + 1
+    }
+    /// ```quarter()``` returns the quarter (1 to 4) of the Date structure, or 0 for an
+    /// invalid date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(31, 3, 2024).quarter(), 1);
+    /// assert_eq!(Date::from(1, 10, 2024).quarter(), 4);
+    /// ```
+    pub fn quarter(&self) -> u8 {
+        if !is_date_valid(self) {
+            return 0;
+        }
+        (self.m - 1) / 3 + 1
+    }
+    /// ```first_day_of_quarter()``` returns the first day of the quarter of the Date
+    /// structure. For an invalid date the result will be ```Date{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let date = Date::from(15, 8, 2024);
+    /// assert_eq!(date.first_day_of_quarter().as_string(), "2024-07-01");
+    /// ```
+    pub fn first_day_of_quarter(&self) -> Date {
+        match self.quarter() {
+            0 => Date { d: 0, m: 0, y: 0 },
+            quarter => Date {
+                d: 1,
+                m: quarter * 3 - 2,
+                y: self.y,
+            },
+        }
+    }
+    /// ```last_day_of_quarter()``` returns the last day of the quarter of the Date
+    /// structure. For an invalid date the result will be ```Date{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let date = Date::from(15, 8, 2024);
+    /// assert_eq!(date.last_day_of_quarter().as_string(), "2024-09-30");
+    /// ```
+    pub fn last_day_of_quarter(&self) -> Date {
+        match self.quarter() {
+            0 => Date { d: 0, m: 0, y: 0 },
+            quarter => Date {
+                d: get_max_days_of_month(quarter * 3, self.y),
+                m: quarter * 3,
+                y: self.y,
+            },
+        }
+    }
+    /// ```first_day_of_month()``` returns the first day of the month of the Date structure.
+    /// For an invalid date the result will be ```Date{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(15, 2, 2024).first_day_of_month().as_string(), "2024-02-01");
+    /// ```
+    pub fn first_day_of_month(&self) -> Date {
+        if !is_date_valid(self) {
+            return Date { d: 0, m: 0, y: 0 };
+        }
+        Date {
+            d: 1,
+            m: self.m,
+            y: self.y,
+        }
+    }
+    /// ```last_day_of_month()``` returns the last day of the month of the Date structure,
+    /// the leap years are respected. For an invalid date the result will be
+    /// ```Date{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(15, 2, 2024).last_day_of_month().as_string(), "2024-02-29");
+    /// assert_eq!(Date::from(15, 2, 2023).last_day_of_month().as_string(), "2023-02-28");
+    /// ```
+    pub fn last_day_of_month(&self) -> Date {
+        if !is_date_valid(self) {
+            return Date { d: 0, m: 0, y: 0 };
+        }
+        Date {
+            d: get_max_days_of_month(self.m, self.y),
+            m: self.m,
+            y: self.y,
+        }
+    }
+    /// ```get_days_remaining_in_year()``` gets the number of days after the Date structure
+    /// until the end of the year (range 0 - 365), or 0 for an invalid date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(24, 12, 2024).get_days_remaining_in_year(), 7);
+    /// assert_eq!(Date::from(1, 1, 2024).get_days_remaining_in_year(), 365);
+    /// ```
+    pub fn get_days_remaining_in_year(&self) -> u32 {
+        if !is_date_valid(self) {
+            return 0;
+        }
+        let days_in_year = if is_leap_year(self.y) { 366 } else { 365 };
+        days_in_year - self.get_day_of_year()
+    }
+    /// ```get_week_of_month()``` gets the number of the week in the month of the Date
+    /// structure (range 1 - 6), or 0 for an invalid date. The weeks start with Monday, the
+    /// first week is the week with the 1st of the month.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// // the 1st December 2024 was a Sunday
+    /// assert_eq!(Date::from(1, 12, 2024).get_week_of_month(), 1);
+    /// assert_eq!(Date::from(2, 12, 2024).get_week_of_month(), 2);
+    /// assert_eq!(Date::from(30, 12, 2024).get_week_of_month(), 6);
+    /// ```
+    pub fn get_week_of_month(&self) -> u8 {
+        if !is_date_valid(self) {
+            return 0;
+        }
+        let first = Date {
+            d: 1,
+            m: self.m,
+            y: self.y,
+        };
+        let offset = (first.get_weekday() + 6) % 7;
+        (self.d - 1 + offset) / 7 + 1
+    }
+    /// ```get_iso_week_of_year()``` gets the number of the week in the year of the
+    /// Date structure as a number (range 1 - 53). This is the ISO 8601 weeknumber. The ISO
+    /// weeks starts with Monday and the week 1 is the week with the first Thursday of the
+    /// year, so the first and the last days of a year can be in a week of the year before
+    /// or after it, see ```get_iso_week()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(8, 1, 2024).get_iso_week_of_year(), 2);
+    /// // the 1st January 2021 was a Friday
+    /// assert_eq!(Date::from(1, 1, 2021).get_iso_week_of_year(), 53);
+    /// assert_eq!(Date::from(30, 12, 2024).get_iso_week_of_year(), 1);
+    /// ```
+    pub fn get_iso_week_of_year(&self) -> u8 {
+        self.get_iso_week().1
+    }
+    /// ```get_iso_week()``` gets the ISO 8601 week-based year and the number of the week
+    /// (range 1 - 53) of the Date structure. The week-based year is the year of the
+    /// Thursday in the same week, it differs to the year of the date for some days at the
+    /// turn of the year. The last two days of ```Date::MAX``` are in the week 1 of the year
+    /// after ```i32::MAX```, their week-based year saturates at ```i32::MAX```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(1, 1, 2021).get_iso_week(), (2020, 53));
+    /// assert_eq!(Date::from(30, 12, 2024).get_iso_week(), (2025, 1));
+    /// assert_eq!(Date::from(28, 12, 2026).get_iso_week(), (2026, 53));
+    /// ```
+    pub fn get_iso_week(&self) -> (i32, u8) {
+        let days = get_days_from_date(self);
+        let iso_weekday = (self.get_weekday() as i64 + 6) % 7;
+        let thursday = get_date_from_days(days - iso_weekday + 3);
+        if !is_date_valid(&thursday) {
+            // the Thursday is after Date::MAX, so the date is in the week 1 of the next year
+            return (self.y.saturating_add(1), 1);
+        }
+        let week = (thursday.get_day_of_year() - 1) / 7 + 1;
+        (thursday.y, week as u8)
     }
     /// ```get_week_of_year()``` gets the number of the week in the year of the
     /// Date structure. The parameter ```firstweekday``` has to be ```== 0``` if the
     /// week starts with Sundays or has to be ```!= 0``` if the week starts with
     /// Mondays. The result can differs to the ISO week.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let date = Date::from(15, 3, 2023);
+    /// assert_eq!(date.get_week_of_year(0), 11);
+    /// assert_eq!(date.get_week_of_year(1), 11);
+    /// // the 1st January 2024 was a Monday before the first Sunday
+    /// assert_eq!(Date::from(1, 1, 2024).get_week_of_year(0), 0);
+    /// ```
     pub fn get_week_of_year(&self, firstweekday: u8) -> u8 {
         let mut wday: u32 = self.get_weekday() as u32;
         if firstweekday != 0 {
@@ -143,14 +730,21 @@ impl Date {
                 wday -= 1;
             }
         }
+        // the week 1 starts with the first firstweekday, the days before are in the week 0
         let yday: u32 = self.get_day_of_year();
-        let result: u32 = (yday + 7 - wday) / 7;
+        let result: u32 = (yday + 6 - wday) / 7;
         result as u8
     }
     /// ```get_weekday()``` gets a number for the day in the week of the Date structure.
     /// From 0 = Sunday to 6 = Saturday
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// // the 24th December 2024 was a Tuesday
+    /// assert_eq!(Date::from(24, 12, 2024).get_weekday(), 2);
+    /// ```
     pub fn get_weekday(&self) -> u8 {
-        let days: i64 = get_days_from_date(&self);
+        let days: i64 = get_days_from_date(self);
         let weekday = if days >= -4 {
             (days + 4) % 7
         } else {
@@ -158,12 +752,281 @@ impl Date {
         };
         weekday as u8
     }
+    /// ```is_weekend()``` returns true if the date is a Saturday or a Sunday, for other
+    /// weekends use ```is_weekend_with()```. It returns false for an invalid date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert!(Date::from(6, 7, 2024).is_weekend());
+    /// assert!(!Date::from(8, 7, 2024).is_weekend());
+    /// ```
+    pub fn is_weekend(&self) -> bool {
+        self.is_weekend_with(&[Weekday::Saturday, Weekday::Sunday])
+    }
+    /// ```is_weekend_with(&weekend)``` returns true if the weekday of the date is one of the
+    /// ```weekend``` days, e.g. Friday and Saturday. It returns false for an invalid date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let weekend = [Weekday::Friday, Weekday::Saturday];
+    /// assert!(Date::from(5, 7, 2024).is_weekend_with(&weekend));
+    /// assert!(!Date::from(7, 7, 2024).is_weekend_with(&weekend));
+    /// ```
+    pub fn is_weekend_with(&self, weekend: &[Weekday]) -> bool {
+        is_date_valid(self) && weekend.iter().any(|&day| day as u8 == self.get_weekday())
+    }
+    /// ```is_weekday()``` returns true if the date is a Monday to Friday, the opposite of
+    /// ```is_weekend()``` for a valid date. It returns false for an invalid date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert!(Date::from(8, 7, 2024).is_weekday());
+    /// assert!(!Date::from(7, 7, 2024).is_weekday());
+    /// assert!(!Date::from(31, 2, 2024).is_weekday());
+    /// ```
+    pub fn is_weekday(&self) -> bool {
+        is_date_valid(self) && !self.is_weekend()
+    }
+    /// ```week_parity(anchor)``` returns if the week of the date is an even or an odd
+    /// number of weeks away from the week of the ```anchor``` date, e.g. for schedules which
+    /// alternate every week. The week of the anchor is ```WeekParity::Even```, the weeks
+    /// start with Monday.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// // the recycling is picked up in the week of the 1st January 2024
+    /// let anchor = Date::from(1, 1, 2024);
+    /// assert_eq!(Date::from(12, 1, 2024).week_parity(anchor), WeekParity::Odd);
+    /// assert_eq!(Date::from(15, 1, 2024).week_parity(anchor), WeekParity::Even);
+    /// ```
+    pub fn week_parity(&self, anchor: Date) -> WeekParity {
+        if get_weeks_between(&anchor, self).rem_euclid(2) == 0 {
+            WeekParity::Even
+        } else {
+            WeekParity::Odd
+        }
+    }
+    /// ```start_of_week(week_start)``` returns the first day of the week of the date, the
+    /// weeks start with the ```week_start``` day. For an invalid date the result will be
+    /// ```Date{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// // the 22nd June 2024 was a Saturday
+    /// let date = Date::from(22, 6, 2024);
+    /// assert_eq!(date.start_of_week(Weekday::Monday).as_string(), "2024-06-17");
+    /// assert_eq!(date.start_of_week(Weekday::Sunday).as_string(), "2024-06-16");
+    /// assert_eq!(date.start_of_week(Weekday::Saturday), date);
+    /// ```
+    pub fn start_of_week(&self, week_start: Weekday) -> Date {
+        if !is_date_valid(self) {
+            return Date { d: 0, m: 0, y: 0 };
+        }
+        let days_since_start = (self.get_weekday() + 7 - week_start as u8) % 7;
+        get_date_from_days(get_days_from_date(self) - days_since_start as i64)
+    }
+    /// ```end_of_week(week_start)``` returns the last day of the week of the date, the
+    /// weeks start with the ```week_start``` day. For an invalid date the result will be
+    /// ```Date{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let date = Date::from(22, 6, 2024);
+    /// assert_eq!(date.end_of_week(Weekday::Monday).as_string(), "2024-06-23");
+    /// assert_eq!(date.end_of_week(Weekday::Sunday), date);
+    /// ```
+    pub fn end_of_week(&self, week_start: Weekday) -> Date {
+        if !is_date_valid(self) {
+            return Date { d: 0, m: 0, y: 0 };
+        }
+        get_date_from_days(get_days_from_date(&self.start_of_week(week_start)) + 6)
+    }
+    /// ```next_occurrence(weekday, inclusive)``` returns the next date on the ```weekday```
+    /// after the date, with ```inclusive``` the date itself if it is on the weekday. For an
+    /// invalid date the result will be ```Date{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// // the 17th June 2024 was a Monday
+    /// let monday = Date::from(17, 6, 2024);
+    /// assert_eq!(monday.next_occurrence(Weekday::Monday, false).as_string(), "2024-06-24");
+    /// assert_eq!(monday.next_occurrence(Weekday::Monday, true), monday);
+    /// assert_eq!(monday.next_occurrence(Weekday::Friday, false).as_string(), "2024-06-21");
+    /// ```
+    pub fn next_occurrence(&self, weekday: Weekday, inclusive: bool) -> Date {
+        if !is_date_valid(self) {
+            return Date { d: 0, m: 0, y: 0 };
+        }
+        let mut days = (weekday as u8 + 7 - self.get_weekday()) % 7;
+        if days == 0 && !inclusive {
+            days = 7;
+        }
+        get_date_from_days(get_days_from_date(self) + days as i64)
+    }
+    /// ```previous_occurrence(weekday, inclusive)``` returns the last date on the
+    /// ```weekday``` before the date, with ```inclusive``` the date itself if it is on the
+    /// weekday. For an invalid date the result will be ```Date{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let monday = Date::from(17, 6, 2024);
+    /// assert_eq!(monday.previous_occurrence(Weekday::Monday, false).as_string(), "2024-06-10");
+    /// assert_eq!(monday.previous_occurrence(Weekday::Monday, true), monday);
+    /// assert_eq!(monday.previous_occurrence(Weekday::Sunday, true).as_string(), "2024-06-16");
+    /// ```
+    pub fn previous_occurrence(&self, weekday: Weekday, inclusive: bool) -> Date {
+        if !is_date_valid(self) {
+            return Date { d: 0, m: 0, y: 0 };
+        }
+        let mut days = (self.get_weekday() + 7 - weekday as u8) % 7;
+        if days == 0 && !inclusive {
+            days = 7;
+        }
+        get_date_from_days(get_days_from_date(self) - days as i64)
+    }
+    /// ```nth_weekday_of_month(n, weekday)``` returns the ```n```th (1 to 5) ```weekday```
+    /// in the month of the date, see the function ```nth_weekday_of_month()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let june = Date::from(1, 6, 2024);
+    /// assert_eq!(june.nth_weekday_of_month(3, Weekday::Thursday), Some(Date::from(20, 6, 2024)));
+    /// ```
+    pub fn nth_weekday_of_month(&self, n: u8, weekday: Weekday) -> Option<Date> {
+        nth_weekday_of_month(self.y, self.m, n, weekday)
+    }
+    /// ```last_weekday_of_month(weekday)``` returns the last ```weekday``` in the month of
+    /// the date, see the function ```last_weekday_of_month()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let may = Date::from(1, 5, 2024);
+    /// assert_eq!(may.last_weekday_of_month(Weekday::Monday), Some(Date::from(27, 5, 2024)));
+    /// ```
+    pub fn last_weekday_of_month(&self, weekday: Weekday) -> Option<Date> {
+        last_weekday_of_month(self.y, self.m, weekday)
+    }
+    /// ```month_grid(week_start)``` returns the weeks of the month of the date as rows of
+    /// seven days, e.g. to show the month like the ```cal``` command. The weeks start with
+    /// the ```week_start``` day, the days of the neighbour months are ```None```. For an
+    /// invalid date the grid is empty.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// // the 1st June 2024 was a Saturday
+    /// let grid = Date::from(22, 6, 2024).month_grid(Weekday::Monday);
+    /// assert_eq!(grid.len(), 5);
+    /// assert_eq!(grid[0][4], None);
+    /// assert_eq!(grid[0][5], Some(Date::from(1, 6, 2024)));
+    /// assert_eq!(grid[4][6], Some(Date::from(30, 6, 2024)));
+    /// ```
+    pub fn month_grid(&self, week_start: Weekday) -> Vec<[Option<Date>; 7]> {
+        self.month_grid_padded(week_start)
+            .iter()
+            .map(|week| week.map(|date| (date.m == self.m).then_some(date)))
+            .collect()
+    }
+    /// ```month_grid_padded(week_start)``` returns the weeks of the month of the date like
+    /// ```month_grid()```, but with the days of the previous and the next month in the first
+    /// and the last week.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let grid = Date::from(1, 2, 2026).month_grid_padded(Weekday::Sunday);
+    /// assert_eq!(grid.len(), 4);
+    /// assert_eq!(grid[0][0], Date::from(1, 2, 2026));
+    /// let grid = Date::from(1, 3, 2026).month_grid_padded(Weekday::Monday);
+    /// assert_eq!(grid[0][0], Date::from(23, 2, 2026));
+    /// assert_eq!(grid[5][6], Date::from(5, 4, 2026));
+    /// ```
+    pub fn month_grid_padded(&self, week_start: Weekday) -> Vec<[Date; 7]> {
+        if !is_date_valid(self) {
+            return Vec::new();
+        }
+        let first = Date {
+            d: 1,
+            m: self.m,
+            y: self.y,
+        };
+        let last = get_days_from_date(&first) + get_max_days_of_month(self.m, self.y) as i64 - 1;
+        let mut week = get_days_from_date(&first.start_of_week(week_start));
+        let mut grid = Vec::new();
+        while week <= last {
+            grid.push(std::array::from_fn(|n| get_date_from_days(week + n as i64)));
+            week += 7;
+        }
+        grid
+    }
+    /// ```biweek_index(anchor)``` returns the number of the two week period of the date,
+    /// counted from the two weeks which start with the week of the ```anchor``` date (index
+    /// 0). Periods before the anchor have negative numbers, the weeks start with Monday.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let anchor = Date::from(1, 1, 2024);
+    /// assert_eq!(Date::from(14, 1, 2024).biweek_index(anchor), 0);
+    /// assert_eq!(Date::from(15, 1, 2024).biweek_index(anchor), 1);
+    /// assert_eq!(Date::from(31, 12, 2023).biweek_index(anchor), -1);
+    /// ```
+    pub fn biweek_index(&self, anchor: Date) -> i64 {
+        get_weeks_between(&anchor, self).div_euclid(2)
+    }
     /// ```diff_in_days(&other_date)``` gets the difference between the to dates in days.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let new_year = Date::from(1, 1, 2024);
+    /// assert_eq!(new_year.diff_in_days(&Date::from(1, 3, 2024)), 60);
+    /// assert_eq!(new_year.diff_in_days(&Date::from(31, 12, 2023)), -1);
+    /// ```
     pub fn diff_in_days(&self, date: &Date) -> i64 {
-        let d1_days = get_days_from_date(&self);
-        let d2_days = get_days_from_date(&date);
+        let d1_days = get_days_from_date(self);
+        let d2_days = get_days_from_date(date);
         d2_days - d1_days
     }
+    /// ```diff_ymd(&other_date)``` gets the difference between the two dates in years,
+    /// months and days like they are counted on a calendar, e.g. for an age. A month is
+    /// from a day of the month to the same day of the next month (or its last day, if the
+    /// day is missing). All values are negative if the ```other_date``` is before the date,
+    /// with an invalid date the result is ```(0, 0, 0)```. The years are an ```i64```, the
+    /// difference between ```Date::MIN``` and ```Date::MAX``` is more than ```i32::MAX```
+    /// years.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let start = Date::from(9, 5, 2021);
+    /// assert_eq!(start.diff_ymd(&Date::from(21, 8, 2023)), (2, 3, 12));
+    /// assert_eq!(Date::from(21, 8, 2023).diff_ymd(&start), (-2, -3, -12));
+    /// assert_eq!(Date::from(31, 1, 2024).diff_ymd(&Date::from(29, 2, 2024)), (0, 1, 0));
+    /// assert_eq!(Date::MIN.diff_ymd(&Date::MAX), (u32::MAX as i64, 11, 30));
+    /// ```
+    pub fn diff_ymd(&self, date: &Date) -> (i64, i32, i32) {
+        if !is_date_valid(self) || !is_date_valid(date) {
+            return (0, 0, 0);
+        }
+        if date < self {
+            let (years, months, days) = date.diff_ymd(self);
+            return (-years, -months, -days);
+        }
+        let mut months = (date.y as i64 - self.y as i64) * 12 + date.m as i64 - self.m as i64;
+        // the anchor is in a month between the two valid dates, so it is always in range
+        let Ok(mut anchor) = get_date_in_month(self, months, MonthEndPolicy::Clamp) else {
+            return (0, 0, 0);
+        };
+        if anchor > *date {
+            months -= 1;
+            let Ok(previous) = get_date_in_month(self, months, MonthEndPolicy::Clamp) else {
+                return (0, 0, 0);
+            };
+            anchor = previous;
+        }
+        // the anchor is less than a month before the date
+        (
+            months / 12,
+            (months % 12) as i32,
+            anchor.diff_in_days(date) as i32,
+        )
+    }
     /// ```add_date(&other_ate)``` adds the ```&other_date``` to the date and returns a new
     /// ```Date``` structure.
     ///
@@ -171,11 +1034,18 @@ impl Date {
     /// will be ```Date{d: 0, m: 0, y: 0}```. You can check against the day or month if you
     /// got a valid date.
     ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// // the other date is counted in days since the Linux epoch, the 2nd January 1970 is one day
+    /// let one_day = Date::from(2, 1, 1970);
+    /// assert_eq!(Date::from(31, 12, 2024).add_date(&one_day), Date::from(1, 1, 2025));
+    /// ```
+    ///
     pub fn add_date(&self, date: &Date) -> Date {
-        let d1_days = get_days_from_date(&self);
-        let d2_days = get_days_from_date(&date);
+        let d1_days = get_days_from_date(self);
+        let d2_days = get_days_from_date(date);
         let new_date: Date = get_date_from_days(d1_days + d2_days);
-        if is_date_valid(&new_date) == false {
+        if !is_date_valid(&new_date) {
             return Date { d: 0, m: 0, y: 0 };
         }
         new_date
@@ -187,74 +1057,187 @@ impl Date {
     /// will be ```Date{d: 0, m: 0, y: 0}```. You can check against the day or month if you
     /// got a valid date.
     ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let one_day = Date::from(2, 1, 1970);
+    /// assert_eq!(Date::from(1, 1, 2025).sub_date(&one_day), Date::from(31, 12, 2024));
+    /// ```
+    ///
     pub fn sub_date(&self, date: &Date) -> Date {
-        let d1_days = get_days_from_date(&self);
-        let d2_days = get_days_from_date(&date);
+        let d1_days = get_days_from_date(self);
+        let d2_days = get_days_from_date(date);
         let new_date: Date = get_date_from_days(d1_days - d2_days);
-        if is_date_valid(&new_date) == false {
+        if !is_date_valid(&new_date) {
             return Date { d: 0, m: 0, y: 0 };
         }
         new_date
     }
     /// ```add_years(years as u32)``` adds the years to the date and returns a new
     /// ```Date``` structure.
+    ///
+    /// The new ```Date``` will be checked for validity, if it was invalid (the 29th February
+    /// in a year without leap day), the returned date will be ```Date{d: 0, m: 0, y: 0}```.
+    /// Use ```checked_add_years()``` to get an error or ```add_years_with()``` to move the
+    /// leap day.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(15, 6, 2020).add_years(4), Date::from(15, 6, 2024));
+    /// assert_eq!(Date::from(29, 2, 2024).add_years(1).m, 0);
+    /// ```
     pub fn add_years(&self, years: u32) -> Date {
-        let new_date = Date {
-            d: self.d,
-            m: self.m,
-            y: self.y + years as i32,
-        };
-        new_date
+        self.checked_add_years(years)
+            .unwrap_or(Date { d: 0, m: 0, y: 0 })
     }
     /// ```sub_years(years as u32)``` substract the years from the date and returns a new
     /// ```Date``` structure.
+    ///
+    /// The new ```Date``` will be checked for validity like in ```add_years()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(15, 6, 2024).sub_years(4), Date::from(15, 6, 2020));
+    /// ```
     pub fn sub_years(&self, years: u32) -> Date {
-        let new_date = Date {
-            d: self.d,
-            m: self.m,
-            y: self.y - years as i32,
-        };
-        new_date
+        self.checked_sub_years(years)
+            .unwrap_or(Date { d: 0, m: 0, y: 0 })
     }
-    /// ```add_months(months as u32)``` adds the months to the date and returns a new
-    /// ```Date``` structure.
-    pub fn add_months(&self, months: u32) -> Date {
-        let mon: u32 = self.m as u32 + months;
-        let mut new_date = Date {
-            d: self.d,
-            m: self.m,
-            y: self.y,
-        };
-        if mon > 12 {
-            new_date.y += mon as i32 / 12;
-            new_date.m = (mon % 12) as u8;
-        } else {
-            new_date.m = mon as u8;
-        }
-        new_date
+    /// ```checked_add_years(years)``` adds the years to the date and returns a new
+    /// ```Date``` structure, or an error if the new date is invalid or the year is out of
+    /// range.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(29, 2, 2024).checked_add_years(4), Ok(Date::from(29, 2, 2028)));
+    /// assert!(Date::from(29, 2, 2024).checked_add_years(1).is_err());
+    /// ```
+    pub fn checked_add_years(&self, years: u32) -> Result<Date, DateTimeError> {
+        let year = i32::try_from(years)
+            .ok()
+            .and_then(|years| self.y.checked_add(years));
+        get_checked_date_in_year(self, year)
     }
-    /// ```sub_months(years as u32)``` substract the months from the date and returns a new
-    /// ```Date``` structure.
-    pub fn sub_months(&self, months: u32) -> Date {
-        let mut new_date = Date {
-            d: self.d,
-            m: self.m,
-            y: self.y,
-        };
-        let mon: i32;
-        if months > 12 {
-            new_date.y -= months as i32 / 12;
-            mon = months as i32 % 12;
-        } else {
-            mon = months as i32;
-        }
-        if mon >= new_date.m as i32 {
-            new_date.y -= 1;
-            new_date.m = 12 - (mon - new_date.m as i32) as u8;
-        } else {
-            new_date.m -= mon as u8;
-        }
-        new_date
+    /// ```checked_sub_years(years)``` substract the years from the date and returns a new
+    /// ```Date``` structure, or an error if the new date is invalid or the year is out of
+    /// range.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(1, 3, 2024).checked_sub_years(1), Ok(Date::from(1, 3, 2023)));
+    /// assert!(Date::from(29, 2, 2024).checked_sub_years(1).is_err());
+    /// ```
+    pub fn checked_sub_years(&self, years: u32) -> Result<Date, DateTimeError> {
+        let year = i32::try_from(years)
+            .ok()
+            .and_then(|years| self.y.checked_sub(years));
+        get_checked_date_in_year(self, year)
+    }
+    /// ```add_years_with(years, policy)``` adds the years to the date and returns a new
+    /// ```Date``` structure. The 29th February is moved by the ```policy``` if the new
+    /// year has no leap day, with ```LeapDayPolicy::Skip``` the result is ```None```.
+    /// The result is ```None``` too if the year is out of range.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let leap_day = Date::from(29, 2, 2024);
+    /// assert_eq!(leap_day.add_years_with(1, LeapDayPolicy::Feb28), Some(Date::from(28, 2, 2025)));
+    /// assert_eq!(leap_day.add_years_with(1, LeapDayPolicy::Mar1), Some(Date::from(1, 3, 2025)));
+    /// assert_eq!(leap_day.add_years_with(1, LeapDayPolicy::Skip), None);
+    /// assert_eq!(leap_day.add_years_with(4, LeapDayPolicy::Skip), Some(Date::from(29, 2, 2028)));
+    /// ```
+    pub fn add_years_with(&self, years: u32, policy: LeapDayPolicy) -> Option<Date> {
+        let year = self.y.checked_add(i32::try_from(years).ok()?)?;
+        get_date_in_year(self, year, policy)
+    }
+    /// ```sub_years_with(years, policy)``` substract the years from the date and returns a
+    /// new ```Date``` structure, the 29th February is handled like in
+    /// ```add_years_with()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let leap_day = Date::from(29, 2, 2024);
+    /// assert_eq!(leap_day.sub_years_with(1, LeapDayPolicy::Mar1), Some(Date::from(1, 3, 2023)));
+    /// ```
+    pub fn sub_years_with(&self, years: u32, policy: LeapDayPolicy) -> Option<Date> {
+        let year = self.y.checked_sub(i32::try_from(years).ok()?)?;
+        get_date_in_year(self, year, policy)
+    }
+    /// ```add_months(months)``` adds the months to the date and returns a new ```Date```
+    /// structure, a negative number of ```months``` is substracted.
+    ///
+    /// The new ```Date``` will be checked for validity, if it was invalid (e.g. the 31st in
+    /// a month with 30 days), the returned date will be ```Date{d: 0, m: 0, y: 0}```. Use
+    /// ```add_months_with()``` to handle a day which is missing in the new month.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(15, 11, 2024).add_months(3), Date::from(15, 2, 2025));
+    /// assert_eq!(Date::from(15, 11, 2024).add_months(1), Date::from(15, 12, 2024));
+    /// assert_eq!(Date::from(15, 1, 2025).add_months(-13), Date::from(15, 12, 2023));
+    /// assert_eq!(Date::from(31, 1, 2024).add_months(1).m, 0);
+    /// ```
+    pub fn add_months(&self, months: i32) -> Date {
+        get_date_in_month(self, months as i64, MonthEndPolicy::Error).unwrap_or(Date {
+            d: 0,
+            m: 0,
+            y: 0,
+        })
+    }
+    /// ```sub_months(months)``` substract the months from the date and returns a new
+    /// ```Date``` structure, a negative number of ```months``` is added. The new ```Date```
+    /// will be checked for validity like in ```add_months()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(15, 2, 2024).sub_months(3), Date::from(15, 11, 2023));
+    /// assert_eq!(Date::from(15, 12, 2024).sub_months(12), Date::from(15, 12, 2023));
+    /// assert_eq!(Date::from(15, 12, 2024).sub_months(-1), Date::from(15, 1, 2025));
+    /// ```
+    pub fn sub_months(&self, months: i32) -> Date {
+        get_date_in_month(self, -(months as i64), MonthEndPolicy::Error).unwrap_or(Date {
+            d: 0,
+            m: 0,
+            y: 0,
+        })
+    }
+    /// ```add_months_with(months, policy)``` adds the months to the date and returns a new
+    /// ```Date``` structure. A day which is missing in the new month (e.g. the 31st in
+    /// February) is handled by the ```policy```, a negative number of ```months``` is
+    /// substracted. It returns an error for an invalid date, a year out of range or a
+    /// missing day with ```MonthEndPolicy::Error```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let end_of_january = Date::from(31, 1, 2024);
+    /// assert_eq!(end_of_january.add_months_with(1, MonthEndPolicy::Clamp), Ok(Date::from(29, 2, 2024)));
+    /// assert_eq!(end_of_january.add_months_with(1, MonthEndPolicy::Overflow), Ok(Date::from(2, 3, 2024)));
+    /// assert!(end_of_january.add_months_with(1, MonthEndPolicy::Error).is_err());
+    /// assert_eq!(end_of_january.add_months_with(2, MonthEndPolicy::Error), Ok(Date::from(31, 3, 2024)));
+    /// ```
+    pub fn add_months_with(
+        &self,
+        months: i32,
+        policy: MonthEndPolicy,
+    ) -> Result<Date, DateTimeError> {
+        get_date_in_month(self, months as i64, policy)
+    }
+    /// ```sub_months_with(months, policy)``` substract the months from the date and returns
+    /// a new ```Date``` structure, a missing day is handled like in ```add_months_with()```.
+    /// A negative number of ```months``` is added.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let end_of_may = Date::from(31, 5, 2023);
+    /// assert_eq!(end_of_may.sub_months_with(3, MonthEndPolicy::Clamp), Ok(Date::from(28, 2, 2023)));
+    /// assert_eq!(end_of_may.sub_months_with(1, MonthEndPolicy::Overflow), Ok(Date::from(1, 5, 2023)));
+    /// assert_eq!(end_of_may.sub_months_with(-1, MonthEndPolicy::Clamp), Ok(Date::from(30, 6, 2023)));
+    /// ```
+    pub fn sub_months_with(
+        &self,
+        months: i32,
+        policy: MonthEndPolicy,
+    ) -> Result<Date, DateTimeError> {
+        get_date_in_month(self, -(months as i64), policy)
     }
     /// ```add_days(days as u64)``` adds the days to the date and returns a new
     /// ```Date``` structure.
@@ -263,10 +1246,15 @@ impl Date {
     /// will be ```Date{d: 0, m: 0, y: 0}```. You can check against the day or month if you
     /// got a valid date.
     ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(28, 2, 2024).add_days(2), Date::from(1, 3, 2024));
+    /// ```
+    ///
     pub fn add_days(&self, days: u64) -> Date {
-        let d_days = get_days_from_date(&self);
-        let new_date: Date = get_date_from_days(d_days + days as i64);
-        if is_date_valid(&new_date) == false {
+        let d_days = get_days_from_date(self);
+        let new_date: Date = get_date_from_days(d_days.saturating_add_unsigned(days));
+        if !is_date_valid(&new_date) {
             return Date { d: 0, m: 0, y: 0 };
         }
         new_date
@@ -278,18 +1266,62 @@ impl Date {
     /// will be ```Date{d: 0, m: 0, y: 0}```. You can check against the day or month if you
     /// got a valid date.
     ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(1, 3, 2023).sub_days(1), Date::from(28, 2, 2023));
+    /// ```
+    ///
     pub fn sub_days(&self, days: u64) -> Date {
-        let d_days = get_days_from_date(&self);
-        let new_date: Date = get_date_from_days(d_days - days as i64);
-        if is_date_valid(&new_date) == false {
+        let d_days = get_days_from_date(self);
+        let new_date: Date = get_date_from_days(d_days.saturating_sub_unsigned(days));
+        if !is_date_valid(&new_date) {
             return Date { d: 0, m: 0, y: 0 };
         }
         new_date
     }
+    /// ```add_duration(&duration)``` adds the ```duration``` to the midnight of the date and
+    /// returns a ```DateTime``` structure, the full days of the duration move the date and
+    /// the rest becomes the time of the day. A negative duration goes back from the
+    /// midnight, e.g. -1 hour is 23:00 of the day before.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::duration::*;
+    /// let date = Date::from(31, 12, 2024);
+    /// let dt = date.add_duration(&Duration::from_seconds(36 * 3_600));
+    /// assert_eq!(dt.as_string(), "2025-01-01T12:00:00");
+    /// let dt = date.add_duration(&Duration::from_seconds(-3_600));
+    /// assert_eq!(dt.as_string(), "2024-12-30T23:00:00");
+    /// ```
+    pub fn add_duration(&self, duration: &Duration) -> DateTime {
+        DateTime::from(*self, Time::new()).add_duration(duration)
+    }
+    /// ```as_option()``` returns the date, or ```None``` if it is invalid (e.g. the result
+    /// ```Date{d: 0, m: 0, y: 0}``` of ```from()```).
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(9, 5, 2024).as_option(), Some(Date::from(9, 5, 2024)));
+    /// assert_eq!(Date::from(31, 4, 2024).as_option(), None);
+    /// ```
+    pub fn as_option(&self) -> Option<Date> {
+        is_date_valid(self).then_some(*self)
+    }
     /// ```as_string()``` gets the ```Date``` structure as a string in the format: YYYY-MM-DD
-    /// (ISO 8601 date format)
+    /// (ISO 8601 date format). An invalid date is printed as the text of
+    /// ```invalid::set_invalid_display()```, if it was set.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// assert_eq!(Date::from(9, 5, 2024).as_string(), "2024-05-09");
+    /// ```
     pub fn as_string(&self) -> String {
-        String::from(format!("{:04}-{:02}-{:02}", self.y, self.m, self.d))
+        if !is_date_valid(self) {
+            if let Some(text) = get_invalid_display() {
+                return text;
+            }
+        }
+        format!("{:04}-{:02}-{:02}", self.y, self.m, self.d)
     }
     /// ```as_formated_string(date_format)``` gets the ```Date``` structure as a string in
     /// the ```date_format``` parameter.
@@ -314,136 +1346,301 @@ impl Date {
     /// | V | writes ISO 8601 week of the year (range 01 - 53). In ISO 8601 weeks begin with Monday. |
     /// | W | writes week of the year as a decimal number (Monday is the first day of the week) (range 00-53). |
     /// | j | writes day of the year as a decimal number (range 001-366)
+    /// | J | writes the days remaining in the year as a decimal number (range 0-365) |
+    /// | Q | writes day of the quarter as a decimal number (range 1-92) |
+    /// | K | writes week of the month as a decimal number (Monday is the first day of the week) (range 1-6) |
     /// | d | writes day of the month as a decimal number (range 01-31)
     /// | e | writes day of the month as a decimal number (range 1-31). Single digit is preceded by a space. |
+    /// | o | writes day of the month as an ordinal number, e.g. 1st or 22nd. |
     /// | a | writes abbreviated weekday name, e.g. Fri. |
     /// | A | writes full weekday name, e.g. Friday.
     /// | w | writes weekday as a decimal number, where Sunday is 0. |
     /// | u | writes weekday as a decimal number, where Monday is 1 (ISO 8601 format). |
     /// | D | equivalent to "%m/%d/%y" |
     /// | F | equivalent to "%Y-%m-%d" (the ISO 8601 date format) |
+    /// | s | writes the seconds since the Linux epoch (01. Jan 1970 UTC) at midnight of the date |
+    /// | x | writes the preferred date format of the locale, e.g. 12/24/2024 |
+    ///
+    /// A number can have a modifier between the % and the placeholder, %-d writes it without
+    /// the padding (e.g. 5), %_d pads it with spaces (e.g. " 5") and %0e with zeros (e.g.
+    /// 05).
     ///
-    /// The result of the week and month names are only in english atm.
+    /// The week and month names are English, ```as_formated_string_localized()``` writes
+    /// them in the language of a ```Locale```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let date = Date::from(24, 12, 2024);
+    /// assert_eq!(date.as_formated_string("%A, %d. %B %Y"), "Tuesday, 24. December 2024");
+    /// assert_eq!(date.as_formated_string("%a %e %b"), "Tue 24 Dec");
+    /// assert_eq!(date.as_formated_string("%F, day %j"), "2024-12-24, day 359");
+    /// assert_eq!(date.as_formated_string("week %K, day %Q of Q4, %J left"), "week 5, day 85 of Q4, 7 left");
+    /// assert_eq!(date.as_formated_string("the %o of %B"), "the 24th of December");
+    /// let date = Date::from(5, 6, 2024);
+    /// assert_eq!(date.as_formated_string("%-d.%-m.%Y"), "5.6.2024");
+    /// assert_eq!(date.as_formated_string("%_d/%0e"), " 5/05");
+    /// assert_eq!(Date::from(1, 1, 2000).as_formated_string("%F = %s"), "2000-01-01 = 946684800");
+    /// assert_eq!(Date::from(1, 1, 2000).as_formated_string("%x"), "01/01/2000");
+    /// ```
     ///
     pub fn as_formated_string(&self, date_format: &str) -> String {
-        let mut chars = date_format.chars();
-        let mut result = String::default();
-        while let Some(c) = chars.next() {
-            if c == '%' {
-                let Some(cn) = chars.next() else {
-                    continue;
-                };
-                match cn {
-                    '%' => result.push(c),
-                    'a' => {
-                        let wd: usize = self.get_weekday() as usize;
-                        let s = String::from(format!("{}", WEEKDAY_ABBREVIATE[wd]));
-                        result.push_str(&s)
-                    }
-                    'A' => {
-                        let wd: usize = self.get_weekday() as usize;
-                        let s = String::from(format!("{}", WEEKDAY_FULL[wd]));
-                        result.push_str(&s)
-                    }
-                    'b' => {
-                        let mn: usize = self.m as usize;
-                        let s = String::from(format!("{}", MONTH_NAME_ABBREVIATE[mn]));
-                        result.push_str(&s)
-                    }
-                    'B' => {
-                        let mn: usize = self.m as usize;
-                        let s = String::from(format!("{}", MONTH_NAME_FULL[mn]));
-                        result.push_str(&s)
-                    }
-                    'C' => {
-                        let s = String::from(format!("{:02}", self.y / 100));
-                        result.push_str(&s)
-                    }
-                    'd' => {
-                        let s = String::from(format!("{:02}", self.d));
-                        result.push_str(&s)
-                    }
-                    'D' => {
-                        let s = String::from(format!("{:02}/{:02}/{:02}", self.m, self.d, self.y));
-                        result.push_str(&s)
-                    }
-                    'e' => {
-                        let s = String::from(format!("{:2}", self.d));
-                        result.push_str(&s)
-                    }
-                    'F' => {
-                        let s = String::from(format!("{:04}-{:02}-{:02}", self.y, self.m, self.d));
-                        result.push_str(&s)
-                    }
-                    'g' => {
-                        let s = String::from(format!("{:02}", self.y));
-                        result.push_str(&s)
-                    }
-                    'G' => {
-                        let s = String::from(format!("{:04}", self.y));
-                        result.push_str(&s)
-                    }
-                    'j' => {
-                        let s = String::from(format!("{:03}", self.get_day_of_year()));
-                        result.push_str(&s)
-                    }
-                    'm' => {
-                        let s = String::from(format!("{:02}", self.m));
-                        result.push_str(&s)
-                    }
-                    'n' => result.push('\n'),
-                    't' => result.push('\t'),
-                    'u' => {
-                        let mut wd = self.get_weekday();
-                        if wd == 0 {
-                            wd = 7;
-                        }
-                        let s = String::from(format!("{}", wd));
-                        result.push_str(&s)
-                    }
-                    'U' => {
-                        let s = String::from(format!("{}", self.get_week_of_year(0)));
-                        result.push_str(&s)
-                    }
-                    'V' => {
-                        let s = String::from(format!("{}", self.get_iso_week_of_year()));
-                        result.push_str(&s)
-                    }
-                    'W' => {
-                        let s = String::from(format!("{}", self.get_week_of_year(1)));
-                        result.push_str(&s)
-                    }
-                    'w' => {
-                        let s = String::from(format!("{}", self.get_weekday()));
-                        result.push_str(&s)
-                    }
-                    'y' => {
-                        let s = String::from(format!("{:02}", self.y));
-                        result.push_str(&s)
-                    }
-                    'Y' => {
-                        let s = String::from(format!("{:04}", self.y));
-                        result.push_str(&s)
-                    }
-                    _ => result.push(cn),
-                }
-            } else {
-                result.push(c)
+        self.as_formated_string_localized(date_format, &English)
+    }
+    /// ```try_as_formated_string(date_format)``` gets the ```Date``` structure as a string
+    /// like ```as_formated_string()```, but it returns an error with the placeholder and its
+    /// position (the index of the % in the characters of the format, starting at 0) if the
+    /// ```date_format``` has a placeholder which is unknown for a date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let date = Date::from(24, 12, 2024);
+    /// assert_eq!(date.try_as_formated_string("%d.%m.%Y"), Ok(String::from("24.12.2024")));
+    /// let error = date.try_as_formated_string("%d. %h %Y").unwrap_err();
+    /// assert_eq!(error.to_string(), "parse error: the format '%d. %h %Y' has the unknown placeholder %h at position 4");
+    /// // the time placeholders are unknown for a date
+    /// assert!(date.try_as_formated_string("%F %H:%M").is_err());
+    /// ```
+    pub fn try_as_formated_string(&self, date_format: &str) -> Result<String, DateTimeError> {
+        check_format(date_format, true, false)?;
+        Ok(self.as_formated_string(date_format))
+    }
+    /// ```as_formated_string_localized(date_format, &locale)``` gets the ```Date```
+    /// structure as a string in the ```date_format``` parameter like
+    /// ```as_formated_string()```, the names of %a, %A, %b and %B and the suffix of %o are
+    /// taken from the ```locale```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::locale::*;
+    /// let date = Date::from(24, 12, 2024);
+    /// let text = date.as_formated_string_localized(English.date_format(), &English);
+    /// assert_eq!(text, "12/24/2024");
+    /// ```
+    pub fn as_formated_string_localized(
+        &self,
+        date_format: &str,
+        locale: &(impl Locale + ?Sized),
+    ) -> String {
+        if !is_date_valid(self) {
+            if let Some(text) = get_invalid_display() {
+                return text;
             }
         }
-        result
+        get_formated_string(Some(self), None, None, date_format, locale)
+    }
+    /// ```format_into(&mut out, date_format)``` writes the ```Date``` structure in the
+    /// ```date_format``` parameter of ```as_formated_string()``` into ```out```, e.g. a
+    /// ```String``` or a buffer on the stack, without the allocation of a new ```String```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let mut line = String::from("date: ");
+    /// Date::from(24, 12, 2024).format_into(&mut line, "%d.%m.%Y").unwrap();
+    /// assert_eq!(line, "date: 24.12.2024");
+    /// ```
+    pub fn format_into(
+        &self,
+        out: &mut (impl fmt::Write + ?Sized),
+        date_format: &str,
+    ) -> fmt::Result {
+        if !is_date_valid(self) {
+            if let Some(text) = get_invalid_display() {
+                return out.write_str(&text);
+            }
+        }
+        write_formated_string(out, Some(self), None, None, date_format, &English)
     }
 }
 
-// Returns: true if year: i32 is a leap year, else false
-fn is_leap_year(year: i32) -> bool {
+// Returns the number of weeks from the week of the date: &Date to the week of the other:
+// &Date, the weeks start with Monday
+fn get_weeks_between(date: &Date, other: &Date) -> i64 {
+    let monday = |d: &Date| get_days_from_date(d) - (d.get_weekday() as i64 + 6) % 7;
+    (monday(other) - monday(date)) / 7
+}
+
+// Returns the day and month of the date: &Date in the year: i32, the 29th February in a
+// year without leap day is handled by the policy: LeapDayPolicy
+fn get_date_in_year(date: &Date, year: i32, policy: LeapDayPolicy) -> Option<Date> {
+    if date.d == 29 && date.m == 2 && !is_leap_year(year) {
+        return match policy {
+            LeapDayPolicy::Feb28 => Some(Date {
+                d: 28,
+                m: 2,
+                y: year,
+            }),
+            LeapDayPolicy::Mar1 => Some(Date {
+                d: 1,
+                m: 3,
+                y: year,
+            }),
+            LeapDayPolicy::Skip => None,
+        };
+    }
+    Some(Date {
+        d: date.d,
+        m: date.m,
+        y: year,
+    })
+}
+
+// Returns the day of the date: &Date in the month which is months: i64 after it, a missing
+// day is handled by the policy: MonthEndPolicy, or an error if there is no such date
+fn get_date_in_month(
+    date: &Date,
+    months: i64,
+    policy: MonthEndPolicy,
+) -> Result<Date, DateTimeError> {
+    if !is_date_valid(date) {
+        return Err(DateTimeError::OutOfRange(format!(
+            "{:04}-{:02}-{:02} is not a valid date",
+            date.y, date.m, date.d
+        )));
+    }
+    let month = date.y as i64 * 12 + date.m as i64 - 1 + months;
+    let Ok(y) = i32::try_from(month.div_euclid(12)) else {
+        return Err(DateTimeError::OutOfRange(format!(
+            "the year of {} is out of range",
+            date.as_string()
+        )));
+    };
+    let m = month.rem_euclid(12) as u8 + 1;
+    let max_days = get_max_days_of_month(m, y);
+    if date.d <= max_days {
+        return Ok(Date { d: date.d, m, y });
+    }
+    match policy {
+        MonthEndPolicy::Clamp => Ok(Date { d: max_days, m, y }),
+        MonthEndPolicy::Overflow => Ok(get_date_from_days(
+            get_days_from_date(&Date { d: max_days, m, y }) + (date.d - max_days) as i64,
+        )),
+        MonthEndPolicy::Error => get_checked_date(date.d, m, y),
+    }
+}
+
+// Returns the Date of the day: u8, month: u8 and year: i32, or an error if it is invalid
+pub(crate) fn get_checked_date(day: u8, month: u8, year: i32) -> Result<Date, DateTimeError> {
+    let date = Date {
+        d: day,
+        m: month,
+        y: year,
+    };
+    if !is_date_valid(&date) {
+        return Err(DateTimeError::OutOfRange(format!(
+            "{:04}-{:02}-{:02} is not a valid date",
+            year, month, day
+        )));
+    }
+    Ok(date)
+}
+
+// Returns the day and month of the date: &Date in the year: Option<i32>, or an error if
+// the year is None or the new date is invalid
+fn get_checked_date_in_year(date: &Date, year: Option<i32>) -> Result<Date, DateTimeError> {
+    let Some(year) = year else {
+        return Err(DateTimeError::OutOfRange(format!(
+            "the year of {} is out of range",
+            date.as_string()
+        )));
+    };
+    get_checked_date(date.d, date.m, year)
+}
+
+/// ```days_in_month(year, month)``` returns the number of days (28 to 31) of the
+/// ```month``` (1 to 12) in the ```year```, or 0 for an invalid month.
+///
+/// ```
+/// use date_and_time::date::*;
+/// assert_eq!(days_in_month(2023, 2), 28);
+/// assert_eq!(days_in_month(2000, 2), 29);
+/// assert_eq!(days_in_month(2024, 13), 0);
+/// ```
+pub const fn days_in_month(year: i32, month: u8) -> u8 {
+    if month < 1 || month > 12 {
+        return 0;
+    }
+    get_max_days_of_month(month, year)
+}
+
+/// ```nth_weekday_of_month(year, month, n, weekday)``` returns the ```n```th (1 to 5)
+/// ```weekday``` in the ```month``` of the ```year```, e.g. the third Thursday of June. It
+/// returns ```None``` for an invalid month or ```n```, or if the month has only four of
+/// the weekdays.
+///
+/// ```
+/// use date_and_time::date::*;
+/// // Thanksgiving is the fourth Thursday of November
+/// let thanksgiving = nth_weekday_of_month(2024, 11, 4, Weekday::Thursday).unwrap();
+/// assert_eq!(thanksgiving.as_string(), "2024-11-28");
+/// assert_eq!(nth_weekday_of_month(2024, 11, 5, Weekday::Thursday), None);
+/// ```
+pub fn nth_weekday_of_month(year: i32, month: u8, n: u8, weekday: Weekday) -> Option<Date> {
+    if !(1..=5).contains(&n) || days_in_month(year, month) == 0 {
+        return None;
+    }
+    let first = Date {
+        d: 1,
+        m: month,
+        y: year,
+    };
+    let day = 1 + (weekday as u8 + 7 - first.get_weekday()) % 7 + (n - 1) * 7;
+    if day > get_max_days_of_month(month, year) {
+        return None;
+    }
+    Some(Date {
+        d: day,
+        m: month,
+        y: year,
+    })
+}
+
+/// ```last_weekday_of_month(year, month, weekday)``` returns the last ```weekday``` in the
+/// ```month``` of the ```year```, e.g. the last Monday of May. It returns ```None``` for an
+/// invalid month.
+///
+/// ```
+/// use date_and_time::date::*;
+/// let last_sunday = last_weekday_of_month(2024, 10, Weekday::Sunday).unwrap();
+/// assert_eq!(last_sunday.as_string(), "2024-10-27");
+/// ```
+pub fn last_weekday_of_month(year: i32, month: u8, weekday: Weekday) -> Option<Date> {
+    let last_day = days_in_month(year, month);
+    if last_day == 0 {
+        return None;
+    }
+    let last = Date {
+        d: last_day,
+        m: month,
+        y: year,
+    };
+    let day = last_day - (last.get_weekday() + 7 - weekday as u8) % 7;
+    Some(Date {
+        d: day,
+        m: month,
+        y: year,
+    })
+}
+
+/// ```is_leap_year(year)``` returns true if the ```year``` is a leap year in the
+/// Gregorian calendar, i.e. it is divisible by 4 but not by 100, or divisible by 400.
+///
+/// ```
+/// use date_and_time::date::*;
+/// assert!(is_leap_year(2024));
+/// assert!(!is_leap_year(1900));
+/// assert!(is_leap_year(2000));
+/// ```
+pub const fn is_leap_year(year: i32) -> bool {
     if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) {
         return true;
     }
     false
 }
 // Returns a Date in the civil calendar from the days: u64
-fn get_date_from_days(days: i64) -> Date {
+pub(crate) const fn get_date_from_days(days: i64) -> Date {
+    if days < MIN_DAYS || days > MAX_DAYS {
+        return Date { d: 0, m: 0, y: 0 };
+    }
     let z: i64 = days + 719_468;
     let era = if z >= 0 {
         z / 146_097
@@ -463,7 +1660,7 @@ fn get_date_from_days(days: i64) -> Date {
         y: year as i32,
     };
     if mon <= 2 {
-        result.y = (year + mon) as i32;
+        result.y = (year + 1) as i32;
     }
     result
 }
@@ -476,7 +1673,7 @@ fn get_date_from_days(days: i64) -> Date {
 
 // Returns the number of days since civil 1970-01-01.
 // Negative values indicate days prior to 1970-01-01.
-fn get_days_from_date(date: &Date) -> i64 {
+pub(crate) const fn get_days_from_date(date: &Date) -> i64 {
     let mut y: i64 = date.y as i64;
     let m = date.m as i64;
     let d = date.d as i64;
@@ -496,7 +1693,7 @@ fn get_days_from_date(date: &Date) -> i64 {
 }
 
 // Returns the maximal number days of the given month: u8 in the given year: i32
-fn get_max_days_of_month(month: u8, year: i32) -> u8 {
+pub(crate) const fn get_max_days_of_month(month: u8, year: i32) -> u8 {
     let m: usize = (month - 1) as usize;
     if is_leap_year(year) {
         LAST_DAY_OF_MONTH_LEAP[m]
@@ -506,7 +1703,7 @@ fn get_max_days_of_month(month: u8, year: i32) -> u8 {
 }
 
 // Returns true if date: &Date is a valid date, else false
-fn is_date_valid(date: &Date) -> bool {
+pub(crate) const fn is_date_valid(date: &Date) -> bool {
     if date.m < 1 || date.m > 12 {
         return false;
     }
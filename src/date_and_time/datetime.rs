@@ -0,0 +1,698 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use std::fmt;
+use std::time::SystemTime;
+
+use crate::date_and_time::date::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::formatted::*;
+use crate::date_and_time::invalid::*;
+use crate::date_and_time::locale::*;
+use crate::date_and_time::offset::*;
+use crate::date_and_time::time::*;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_DAY: i128 = 86_400 * NANOS_PER_SECOND;
+// The 100 nanosecond intervals of a Windows FILETIME from the 1st January 1601 to the epoch
+const FILETIME_OF_EPOCH: i128 = 116_444_736_000_000_000;
+// The seconds since the Linux epoch of the first and after the last day with an i32 year
+const MIN_EPOCH_SECONDS: f64 = get_days_from_date(&Date::MIN) as f64 * 86_400.0;
+const MAX_EPOCH_SECONDS: f64 = (get_days_from_date(&Date::MAX) + 1) as f64 * 86_400.0;
+
+/// The rounding of a value to full nanoseconds, e.g. in ```DateTime::from_epoch_f64()```.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round to the earlier nanosecond.
+    Floor,
+    /// Round to the later nanosecond.
+    Ceil,
+    /// Round to the nearest nanosecond, a half nanosecond is rounded to the later one.
+    Nearest,
+    /// Round to the nanosecond nearer to the epoch.
+    TowardZero,
+}
+
+/// The DateTimeDiff structure is the difference between two date and times in years,
+/// months, days, hours, minutes, seconds and nanoseconds of ```DateTime::diff_breakdown()```.
+/// All fields have the same sign.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DateTimeDiff {
+    pub years: i64,
+    pub months: i32,
+    pub days: i32,
+    pub hours: i32,
+    pub minutes: i32,
+    pub seconds: i32,
+    pub nanos: i32,
+}
+
+/// The DateTime structure combines a ```Date``` and a clock ```Time``` (0:00:00 to 23:59:59)
+/// with the fractions of a second in ```nanos```. It can be build with the functions
+/// ```new()```, ```from()```, ```from_timestamp()``` and ```from_system_clock()```.
+///
+/// The DateTime has no timezone, the functions which are using the system clock return
+/// the UTC time.
+///
+/// The structure owns the traits ```Copy```, ```Clone```, ```PartialEq``` and
+/// ```PartialOrd```, so you can compare two date and times.
+///
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+    pub nanos: u32,
+}
+
+impl Default for DateTime {
+    fn default() -> Self {
+        DateTime::new()
+    }
+}
+
+#[allow(dead_code)]
+impl DateTime {
+    /// ```new()``` creates a ```DateTime``` structure with the 1st January 0 (year Null)
+    /// at 0:00:00.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// assert_eq!(DateTime::new().as_string(), "0000-01-01T00:00:00");
+    /// ```
+    pub fn new() -> DateTime {
+        DateTime {
+            date: Date::new(),
+            time: Time::new(),
+            nanos: 0,
+        }
+    }
+    /// ```from(date, time)``` creates a ```DateTime``` structure from the ```date``` and
+    /// the ```time```. Times of more than 24 hours are carried into the date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// let dt = DateTime::from(Date::from(31, 12, 2024), Time::from(25, 0, 0));
+    /// assert_eq!(dt.as_string(), "2025-01-01T01:00:00");
+    /// ```
+    pub fn from(date: Date, time: Time) -> DateTime {
+        let secs: i64 = time.as_seconds();
+        DateTime::from_nanoseconds(
+            get_days_from_date(&date) as i128 * NANOS_PER_DAY + secs as i128 * NANOS_PER_SECOND,
+        )
+    }
+    /// ```from_timestamp(unix_secs)``` creates a ```DateTime``` structure from the seconds
+    /// since the Linux epoch (01. Jan 1970 UTC).
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// assert_eq!(DateTime::from_timestamp(0).as_string(), "1970-01-01T00:00:00");
+    /// assert_eq!(DateTime::from_timestamp(-1).as_string(), "1969-12-31T23:59:59");
+    /// ```
+    pub fn from_timestamp(unix_secs: i64) -> DateTime {
+        DateTime::from_nanoseconds(unix_secs as i128 * NANOS_PER_SECOND)
+    }
+    /// ```from_epoch_f64(secs, rounding)``` creates a ```DateTime``` structure from the
+    /// fractional seconds since the Linux epoch (e.g. from a JSON API), the fraction is
+    /// rounded to nanoseconds with the ```rounding```. It returns an error for NaN, the
+    /// infinities and seconds outside of the years of the ```Date```.
+    ///
+    /// An ```f64``` has about 16 significant digits, so a timestamp of today is only exact
+    /// to some hundred nanoseconds.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let dt = DateTime::from_epoch_f64(1_718_000_000.25, RoundingMode::Nearest).unwrap();
+    /// assert_eq!(dt.as_string(), "2024-06-10T06:13:20.250000000");
+    /// let dt = DateTime::from_epoch_f64(-0.5, RoundingMode::Floor).unwrap();
+    /// assert_eq!(dt.as_string(), "1969-12-31T23:59:59.500000000");
+    /// assert!(DateTime::from_epoch_f64(f64::NAN, RoundingMode::Floor).is_err());
+    /// ```
+    pub fn from_epoch_f64(secs: f64, rounding: RoundingMode) -> Result<DateTime, DateTimeError> {
+        if !secs.is_finite() {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{} is not a finite number of seconds",
+                secs
+            )));
+        }
+        if !(MIN_EPOCH_SECONDS..MAX_EPOCH_SECONDS).contains(&secs) {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{} seconds since the epoch are outside of the supported years",
+                secs
+            )));
+        }
+        let whole = secs.floor();
+        let fraction = (secs - whole) * NANOS_PER_SECOND as f64;
+        let nanos = match rounding {
+            RoundingMode::Floor => fraction.floor(),
+            RoundingMode::Ceil => fraction.ceil(),
+            RoundingMode::Nearest => (fraction + 0.5).floor(),
+            RoundingMode::TowardZero if secs < 0.0 => fraction.ceil(),
+            RoundingMode::TowardZero => fraction.floor(),
+        };
+        let total = whole as i128 * NANOS_PER_SECOND + nanos as i128;
+        trace_event!(
+            secs,
+            ?rounding,
+            fraction,
+            nanos,
+            "rounded the epoch seconds"
+        );
+        if total >= MAX_EPOCH_SECONDS as i128 * NANOS_PER_SECOND {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{} seconds since the epoch are outside of the supported years",
+                secs
+            )));
+        }
+        Ok(DateTime::from_nanoseconds(total))
+    }
+    /// ```from_epoch_millis(millis)``` creates a ```DateTime``` structure from the
+    /// milliseconds since the Linux epoch. Every ```i64``` is in the years of the
+    /// ```Date```, so the function can't fail.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let dt = DateTime::from_epoch_millis(1_719_081_000_123);
+    /// assert_eq!(dt.as_string(), "2024-06-22T18:30:00.123000000");
+    /// ```
+    pub fn from_epoch_millis(millis: i64) -> DateTime {
+        DateTime::from_nanoseconds(millis as i128 * 1_000_000)
+    }
+    /// ```from_epoch_micros(micros)``` creates a ```DateTime``` structure from the
+    /// microseconds since the Linux epoch.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let dt = DateTime::from_epoch_micros(-1);
+    /// assert_eq!(dt.as_string(), "1969-12-31T23:59:59.999999000");
+    /// ```
+    pub fn from_epoch_micros(micros: i64) -> DateTime {
+        DateTime::from_nanoseconds(micros as i128 * 1_000)
+    }
+    /// ```from_epoch_nanos(nanos)``` creates a ```DateTime``` structure from the
+    /// nanoseconds since the Linux epoch, an ```i64``` covers the years 1677 to 2262.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let dt = DateTime::from_epoch_nanos(i64::MAX);
+    /// assert_eq!(dt.as_string(), "2262-04-11T23:47:16.854775807");
+    /// ```
+    pub fn from_epoch_nanos(nanos: i64) -> DateTime {
+        DateTime::from_nanoseconds(nanos as i128)
+    }
+    /// ```from_system_clock()``` creates a ```DateTime``` structure from the systems clock.
+    /// The result is in UTC time and will probably be different from your local time.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let now = DateTime::from_system_clock();
+    /// println!("It is {} UTC", now.as_string());
+    /// ```
+    pub fn from_system_clock() -> DateTime {
+        DateTime::from_nanoseconds(get_system_clock_nanoseconds())
+    }
+    /// ```as_timestamp()``` returns the seconds since the Linux epoch (01. Jan 1970 UTC),
+    /// the fractions of the second are truncated.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// let y2k = DateTime::from(Date::from(1, 1, 2000), Time::new());
+    /// assert_eq!(y2k.as_timestamp(), 946_684_800);
+    /// ```
+    pub fn as_timestamp(&self) -> i64 {
+        self.as_nanoseconds().div_euclid(NANOS_PER_SECOND) as i64
+    }
+    /// ```to_epoch_millis()``` returns the milliseconds since the Linux epoch, the
+    /// fractions of a millisecond are cut towards the earlier time. It returns an error
+    /// for an invalid date or if the milliseconds don't fit in an ```i64```.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let dt = DateTime::from_epoch_nanos(-1);
+    /// assert_eq!(dt.to_epoch_millis().unwrap(), -1);
+    /// ```
+    pub fn to_epoch_millis(&self) -> Result<i64, DateTimeError> {
+        self.get_epoch_units(1_000_000, "milliseconds")
+    }
+    /// ```to_epoch_micros()``` returns the microseconds since the Linux epoch, the
+    /// fractions of a microsecond are cut towards the earlier time. It returns an error
+    /// for an invalid date or if the microseconds don't fit in an ```i64```.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let dt = DateTime::from_epoch_nanos(1_719_081_000_123_456_789);
+    /// assert_eq!(dt.to_epoch_micros().unwrap(), 1_719_081_000_123_456);
+    /// ```
+    pub fn to_epoch_micros(&self) -> Result<i64, DateTimeError> {
+        self.get_epoch_units(1_000, "microseconds")
+    }
+    /// ```to_epoch_nanos()``` returns the nanoseconds since the Linux epoch. It returns an
+    /// error for an invalid date or a date and time outside of the years 1677 to 2262,
+    /// which don't fit in an ```i64```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// let dt = DateTime::from(Date::from(22, 6, 2024), Time::from(18, 30, 0));
+    /// assert_eq!(dt.to_epoch_nanos().unwrap(), 1_719_081_000_000_000_000);
+    /// let dt = DateTime::from(Date::from(1, 1, 2263), Time::from(0, 0, 0));
+    /// assert!(dt.to_epoch_nanos().is_err());
+    /// ```
+    pub fn to_epoch_nanos(&self) -> Result<i64, DateTimeError> {
+        self.get_epoch_units(1, "nanoseconds")
+    }
+    /// ```from_filetime(filetime)``` creates a ```DateTime``` structure from a Windows
+    /// ```FILETIME```, the 100 nanosecond intervals since the 1st January 1601 UTC (e.g.
+    /// of the file metadata or the event log).
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// assert_eq!(DateTime::from_filetime(0).as_string(), "1601-01-01T00:00:00");
+    /// let dt = DateTime::from_filetime(133_635_546_001_234_567);
+    /// assert_eq!(dt.as_string(), "2024-06-22T18:30:00.123456700");
+    /// ```
+    pub fn from_filetime(filetime: u64) -> DateTime {
+        DateTime::from_nanoseconds((filetime as i128 - FILETIME_OF_EPOCH) * 100)
+    }
+    /// ```to_filetime()``` returns the Windows ```FILETIME``` of the date and time, the
+    /// fractions of 100 nanoseconds are truncated. It returns an error for an invalid date
+    /// or a date and time before the year 1601 or after the year 60056.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let dt = DateTime::from_timestamp(1_719_081_000);
+    /// assert_eq!(dt.to_filetime().unwrap(), 133_635_546_000_000_000);
+    /// assert!(DateTime::from_timestamp(-11_644_473_601).to_filetime().is_err());
+    /// ```
+    pub fn to_filetime(&self) -> Result<u64, DateTimeError> {
+        if !is_date_valid(&self.date) {
+            return Err(DateTimeError::OutOfRange(String::from(
+                "the date and time has an invalid date",
+            )));
+        }
+        let filetime = self.as_nanoseconds().div_euclid(100) + FILETIME_OF_EPOCH;
+        u64::try_from(filetime).map_err(|_| {
+            DateTimeError::OutOfRange(format!(
+                "{} is outside of the range of a FILETIME",
+                self.as_string()
+            ))
+        })
+    }
+    /// ```diff(&other)``` gets the difference between the two date and times as a
+    /// ```Duration```.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let start = DateTime::from_timestamp(1_000);
+    /// let end = DateTime::from_timestamp(1_090);
+    /// assert_eq!(start.diff(&end).as_seconds(), 90);
+    /// ```
+    pub fn diff(&self, other: &DateTime) -> Duration {
+        Duration::from_nanoseconds(other.as_nanoseconds() - self.as_nanoseconds())
+    }
+    /// ```diff_in_seconds(&other)``` gets the difference between the two date and times in
+    /// whole seconds, the fractional part is truncated. Like the POSIX time every day has
+    /// 86400 seconds, the leap seconds are counted with the feature ```leap-seconds``` in
+    /// ```diff_in_real_seconds()```.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let start = DateTime::from_timestamp(1_000);
+    /// let end = DateTime::from_timestamp(4_600);
+    /// assert_eq!(start.diff_in_seconds(&end), 3_600);
+    /// assert_eq!(end.diff_in_seconds(&start), -3_600);
+    /// ```
+    pub fn diff_in_seconds(&self, other: &DateTime) -> i64 {
+        self.diff(other).as_seconds()
+    }
+    /// ```diff_breakdown(&other)``` gets the difference between the two date and times in
+    /// years, months and days like ```Date::diff_ymd()``` and the rest in hours, minutes,
+    /// seconds and nanoseconds. All values are negative if the ```other``` is before the
+    /// date and time.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// let start = DateTime::from(Date::from(1, 3, 2024), Time::from(18, 0, 0));
+    /// let end = DateTime::from(Date::from(15, 4, 2024), Time::from(9, 30, 0));
+    /// let diff = start.diff_breakdown(&end);
+    /// assert_eq!((diff.years, diff.months, diff.days), (0, 1, 13));
+    /// assert_eq!((diff.hours, diff.minutes, diff.seconds), (15, 30, 0));
+    /// assert_eq!(end.diff_breakdown(&start).days, -13);
+    /// ```
+    pub fn diff_breakdown(&self, other: &DateTime) -> DateTimeDiff {
+        if other.as_nanoseconds() < self.as_nanoseconds() {
+            let diff = other.diff_breakdown(self);
+            return DateTimeDiff {
+                years: -diff.years,
+                months: -diff.months,
+                days: -diff.days,
+                hours: -diff.hours,
+                minutes: -diff.minutes,
+                seconds: -diff.seconds,
+                nanos: -diff.nanos,
+            };
+        }
+        // the time of the day is borrowed from the day before the end
+        let start_of_day = self.as_nanoseconds().rem_euclid(NANOS_PER_DAY);
+        let mut rest = other.as_nanoseconds().rem_euclid(NANOS_PER_DAY) - start_of_day;
+        let mut end = other.date;
+        if rest < 0 {
+            rest += NANOS_PER_DAY;
+            end = end.sub_days(1);
+        }
+        let (years, months, days) = self.date.diff_ymd(&end);
+        let secs = (rest / NANOS_PER_SECOND) as i32;
+        DateTimeDiff {
+            years,
+            months,
+            days,
+            hours: secs / 3_600,
+            minutes: secs / 60 % 60,
+            seconds: secs % 60,
+            nanos: (rest % NANOS_PER_SECOND) as i32,
+        }
+    }
+    /// ```approx_eq(&other, tolerance)``` returns true if the two date and times differ by
+    /// not more than the ```tolerance``` (in both directions), e.g. for timestamps of two
+    /// different clocks.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::duration::*;
+    /// let server = DateTime::from_timestamp(1_700_000_000);
+    /// let client = DateTime::from_timestamp(1_700_000_002);
+    /// assert!(server.approx_eq(&client, Duration::from_seconds(2)));
+    /// assert!(!server.approx_eq(&client, Duration::from_milliseconds(1_999)));
+    /// ```
+    pub fn approx_eq(&self, other: &DateTime, tolerance: Duration) -> bool {
+        self.diff(other).as_nanoseconds().abs() <= tolerance.as_nanoseconds().abs()
+    }
+    /// ```add_duration(&duration)``` adds the ```duration``` to the date and time and
+    /// returns a new ```DateTime``` structure.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::duration::*;
+    /// let dt = DateTime::from_timestamp(0).add_duration(&Duration::from_milliseconds(1_500));
+    /// assert_eq!(dt.as_string(), "1970-01-01T00:00:01.500000000");
+    /// ```
+    pub fn add_duration(&self, duration: &Duration) -> DateTime {
+        DateTime::from_nanoseconds(self.as_nanoseconds() + duration.as_nanoseconds())
+    }
+    /// ```sub_duration(&duration)``` substract the ```duration``` from the date and time and
+    /// returns a new ```DateTime``` structure.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::duration::*;
+    /// let dt = DateTime::from_timestamp(0).sub_duration(&Duration::from_seconds(60));
+    /// assert_eq!(dt.as_string(), "1969-12-31T23:59:00");
+    /// ```
+    pub fn sub_duration(&self, duration: &Duration) -> DateTime {
+        DateTime::from_nanoseconds(self.as_nanoseconds() - duration.as_nanoseconds())
+    }
+    /// ```split()``` returns the date, the time and the UTC offset of the date and time. The
+    /// ```DateTime``` has no timezone, so the offset is always ```None``` (see
+    /// ```IsoDateTime::split()```), and the ```Time``` has no fractions of a second.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// let dt = DateTime::from(Date::from(22, 6, 2024), Time::from(13, 45, 30));
+    /// let (date, time, offset) = dt.split();
+    /// assert_eq!(date, Date::from(22, 6, 2024));
+    /// assert_eq!(time.as_string(), "13:45:30");
+    /// assert_eq!(offset, None);
+    /// ```
+    pub fn split(&self) -> (Date, Time, Option<UtcOffset>) {
+        (self.date, self.time, None)
+    }
+    /// ```round_to_tick(ticks_per_second)``` rounds the date and time to the nearest tick of
+    /// a grid with ```ticks_per_second``` ticks (e.g. 48000 for audio samples) and returns a
+    /// new ```DateTime``` structure. The grid starts at every full second.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let mut dt = DateTime::from_timestamp(59);
+    /// dt.nanos = 999_999_700;
+    /// assert_eq!(dt.round_to_tick(1_000).as_string(), "1970-01-01T00:01:00");
+    /// ```
+    pub fn round_to_tick(&self, ticks_per_second: u32) -> DateTime {
+        let rounded =
+            Duration::from_nanoseconds(self.nanos as i128).round_to_ticks(ticks_per_second);
+        DateTime::from_nanoseconds(
+            self.as_nanoseconds() - self.nanos as i128 + rounded.as_nanoseconds(),
+        )
+    }
+    /// ```as_string()``` gets the ```DateTime``` structure as a string in the format
+    /// YYYY-MM-DDTHH:MM:SS (ISO 8601), the fractions of the second are added if they are
+    /// not zero. A date and time with an invalid date is printed as the text of
+    /// ```invalid::set_invalid_display()```, if it was set.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let mut dt = DateTime::from_timestamp(1_700_000_000);
+    /// assert_eq!(dt.as_string(), "2023-11-14T22:13:20");
+    /// dt.nanos = 250_000_000;
+    /// assert_eq!(dt.as_string(), "2023-11-14T22:13:20.250000000");
+    /// ```
+    pub fn as_string(&self) -> String {
+        if !is_date_valid(&self.date) {
+            if let Some(text) = get_invalid_display() {
+                return text;
+            }
+        }
+        let mut s = format!("{}T{}", self.date.as_string(), self.time.as_string());
+        if self.nanos > 0 {
+            s.push_str(&format!(".{:09}", self.nanos));
+        }
+        s
+    }
+    /// ```as_formated_string(format)``` gets the ```DateTime``` structure as a string in the
+    /// ```format``` parameter, it has the placeholders of ```Date::as_formated_string()```
+    /// and of ```Time::as_formated_string()```, %c writes the preferred date and time
+    /// format of the locale and %s the seconds since the Linux epoch like
+    /// ```as_timestamp()```. %z, %:z and %Z write the offset and the abbreviation of
+    /// the local timezone (see ```as_formated_string_with_offset()```). A date and time with
+    /// an invalid date is printed as the text of ```invalid::set_invalid_display()```, if it
+    /// was set.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let dt = DateTime::from_timestamp(1_719_081_045);
+    /// assert_eq!(dt.as_formated_string("%Y-%m-%d %H:%M:%S"), "2024-06-22 18:30:45");
+    /// assert_eq!(dt.as_formated_string("%A, %o %B at %-I:%M %p"), "Saturday, 22nd June at 6:30 p.m.");
+    /// assert_eq!(dt.as_formated_string("%FT%T (%s)"), "2024-06-22T18:30:45 (1719081045)");
+    /// assert_eq!(dt.as_formated_string("%c"), "06/22/2024 06:30:45 p.m.");
+    /// ```
+    pub fn as_formated_string(&self, format: &str) -> String {
+        self.as_formated_string_localized(format, &English)
+    }
+    /// ```try_as_formated_string(format)``` gets the ```DateTime``` structure as a string
+    /// like ```as_formated_string()```, but it returns an error with the placeholder and its
+    /// position if the ```format``` has an unknown placeholder (see
+    /// ```Date::try_as_formated_string()```).
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let dt = DateTime::from_timestamp(1_719_081_045);
+    /// assert!(dt.try_as_formated_string("%F %T%:z").is_ok());
+    /// let error = dt.try_as_formated_string("%Y-%m-%d %H:%M:%S%").unwrap_err();
+    /// assert!(error.to_string().ends_with("has an incomplete placeholder % at position 17"));
+    /// ```
+    pub fn try_as_formated_string(&self, format: &str) -> Result<String, DateTimeError> {
+        check_format(format, true, true)?;
+        Ok(self.as_formated_string(format))
+    }
+    /// ```as_formated_string_localized(format, &locale)``` gets the ```DateTime``` structure
+    /// as a string in the ```format``` parameter like ```as_formated_string()```, the names
+    /// are taken from the ```locale```.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::locale::*;
+    /// let dt = DateTime::from_timestamp(1_719_081_045);
+    /// let format = format!("{} {}", English.date_format(), English.time_format());
+    /// assert_eq!(dt.as_formated_string_localized(&format, &English), "06/22/2024 06:30:45 p.m.");
+    /// ```
+    pub fn as_formated_string_localized(
+        &self,
+        format: &str,
+        locale: &(impl Locale + ?Sized),
+    ) -> String {
+        if !is_date_valid(&self.date) {
+            if let Some(text) = get_invalid_display() {
+                return text;
+            }
+        }
+        get_formated_string(Some(&self.date), Some(&self.time), None, format, locale)
+    }
+    /// ```format_into(&mut out, format)``` writes the ```DateTime``` structure in the
+    /// ```format``` parameter of ```as_formated_string()``` into ```out```, without the
+    /// allocation of a new ```String```. Only %Z allocates the abbreviation of the local
+    /// timezone.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use std::fmt::Write;
+    /// let mut log = String::new();
+    /// for timestamp in [1_719_081_045, 1_719_081_046] {
+    ///     DateTime::from_timestamp(timestamp).format_into(&mut log, "%F %T").unwrap();
+    ///     writeln!(log, " event").unwrap();
+    /// }
+    /// assert_eq!(log, "2024-06-22 18:30:45 event\n2024-06-22 18:30:46 event\n");
+    /// ```
+    pub fn format_into(&self, out: &mut (impl fmt::Write + ?Sized), format: &str) -> fmt::Result {
+        if !is_date_valid(&self.date) {
+            if let Some(text) = get_invalid_display() {
+                return out.write_str(&text);
+            }
+        }
+        let (date, time) = (Some(&self.date), Some(&self.time));
+        write_formated_string(out, date, time, None, format, &English)
+    }
+    /// ```as_formated_string_with_offset(format, offset)``` gets the ```DateTime``` structure
+    /// as a string in the ```format``` parameter like ```as_formated_string()```, but %z
+    /// and %:z write the ```offset``` instead of the offset of the local timezone. %Z writes
+    /// UTC for the offset zero, else the offset as +HH:MM.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::offset::*;
+    /// let dt = DateTime::from_timestamp(1_719_081_045);
+    /// let offset = UtcOffset::from_hours_minutes(2, 0);
+    /// assert_eq!(dt.as_formated_string_with_offset("%FT%T%:z", offset), "2024-06-22T18:30:45+02:00");
+    /// assert_eq!(dt.as_formated_string_with_offset("%d/%b/%Y:%T %z", offset), "22/Jun/2024:18:30:45 +0200");
+    /// let utc = UtcOffset::from_seconds(0);
+    /// assert_eq!(dt.as_formated_string_with_offset("%T %Z", utc), "18:30:45 UTC");
+    /// ```
+    pub fn as_formated_string_with_offset(&self, format: &str, offset: UtcOffset) -> String {
+        if !is_date_valid(&self.date) {
+            if let Some(text) = get_invalid_display() {
+                return text;
+            }
+        }
+        let name = match offset.as_seconds() {
+            0 => String::from("UTC"),
+            _ => offset.as_string(),
+        };
+        let zone = Some((offset, name.as_str()));
+        get_formated_string(Some(&self.date), Some(&self.time), zone, format, &English)
+    }
+    // Returns the units of nanos_per_unit: i128 nanoseconds since the Linux epoch, or an
+    // error with the name: &str of the units
+    fn get_epoch_units(&self, nanos_per_unit: i128, name: &str) -> Result<i64, DateTimeError> {
+        if !is_date_valid(&self.date) {
+            return Err(DateTimeError::OutOfRange(String::from(
+                "the date and time has an invalid date",
+            )));
+        }
+        i64::try_from(self.as_nanoseconds().div_euclid(nanos_per_unit)).map_err(|_| {
+            DateTimeError::OutOfRange(format!(
+                "the {} since the epoch of {} don't fit in an i64",
+                name,
+                self.as_string()
+            ))
+        })
+    }
+    // Returns the nanoseconds since the Linux epoch
+    pub(crate) fn as_nanoseconds(&self) -> i128 {
+        let secs: i64 = self.time.h as i64 * 3_600 + self.time.m as i64 * 60 + self.time.s as i64;
+        get_days_from_date(&self.date) as i128 * NANOS_PER_DAY
+            + secs as i128 * NANOS_PER_SECOND
+            + self.nanos as i128
+    }
+    // Returns the DateTime of the nanoseconds since the Linux epoch
+    pub(crate) fn from_nanoseconds(nanos: i128) -> DateTime {
+        let days = nanos.div_euclid(NANOS_PER_DAY);
+        let rest = nanos.rem_euclid(NANOS_PER_DAY);
+        DateTime {
+            date: get_date_from_days(i64::try_from(days).unwrap_or(i64::MAX)),
+            time: Time::from_seconds((rest / NANOS_PER_SECOND) as i64),
+            nanos: (rest % NANOS_PER_SECOND) as u32,
+        }
+    }
+}
+
+// Returns the nanoseconds of the system clock since the Linux epoch, in the browser the
+// clock of JavaScript is used because SystemTime::now() panics on wasm32-unknown-unknown
+pub(crate) fn get_system_clock_nanoseconds() -> i128 {
+    let result: i128;
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        // the JavaScript clock has milliseconds
+        result = js_sys::Date::now() as i128 * 1_000_000;
+    }
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    {
+        let now: DateTime = SystemTime::now().into();
+        result = now.as_nanoseconds();
+    }
+    result
+}
+
+impl From<SystemTime> for DateTime {
+    /// Creates a UTC ```DateTime``` from the ```SystemTime``` (e.g. the modification time
+    /// of a file), times before the Linux epoch are supported. Because ```DateTime``` owns
+    /// its own ```from()``` function, use ```let dt: DateTime = st.into();```.
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use date_and_time::datetime::*;
+    /// let dt: DateTime = (SystemTime::UNIX_EPOCH - Duration::from_millis(500)).into();
+    /// assert_eq!(dt.as_string(), "1969-12-31T23:59:59.500000000");
+    /// ```
+    fn from(st: SystemTime) -> DateTime {
+        match st.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => DateTime::from_nanoseconds(after.as_nanos() as i128),
+            Err(before) => DateTime::from_nanoseconds(-(before.duration().as_nanos() as i128)),
+        }
+    }
+}
+
+impl TryFrom<DateTime> for SystemTime {
+    type Error = DateTimeError;
+
+    /// Creates a ```SystemTime``` from the UTC ```DateTime```, it returns an error if the
+    /// date and time is outside of the range of the ```SystemTime``` of the platform (e.g.
+    /// the years 1601 to 30827 on Windows).
+    ///
+    /// ```
+    /// use std::time::SystemTime;
+    /// use date_and_time::datetime::*;
+    /// let st = SystemTime::try_from(DateTime::from_timestamp(1_700_000_000)).unwrap();
+    /// let back: DateTime = st.into();
+    /// assert_eq!(back.as_timestamp(), 1_700_000_000);
+    /// ```
+    fn try_from(dt: DateTime) -> Result<SystemTime, DateTimeError> {
+        let nanos = dt.as_nanoseconds();
+        let span = u64::try_from(nanos.unsigned_abs() / NANOS_PER_SECOND as u128)
+            .ok()
+            .map(|secs| {
+                std::time::Duration::new(
+                    secs,
+                    (nanos.unsigned_abs() % NANOS_PER_SECOND as u128) as u32,
+                )
+            });
+        let st = match span {
+            Some(span) if nanos >= 0 => SystemTime::UNIX_EPOCH.checked_add(span),
+            Some(span) => SystemTime::UNIX_EPOCH.checked_sub(span),
+            None => None,
+        };
+        st.ok_or_else(|| {
+            DateTimeError::OutOfRange(format!(
+                "the date and time {} can't be converted to a SystemTime",
+                dt.as_string()
+            ))
+        })
+    }
+}
@@ -0,0 +1,105 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+
+const NANOS_PER_SECOND: f64 = 1_000_000_000.0;
+
+/// The DriftReport structure is the result of ```estimate_drift()```. The ```offset``` is
+/// the difference of the local clock to the reference clock at the reference time ```at```
+/// (positive if the local clock is ahead), the ```rate``` are the seconds the local clock
+/// gains per second (negative if it is slow).
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DriftReport {
+    pub offset: Duration,
+    pub rate: f64,
+    pub at: DateTime,
+    pub samples: usize,
+}
+
+#[allow(dead_code)]
+impl DriftReport {
+    /// ```rate_ppm()``` returns the ```rate``` in parts per million (microseconds per
+    /// second), the usual unit for the accuracy of quartz clocks.
+    pub fn rate_ppm(&self) -> f64 {
+        self.rate * 1_000_000.0
+    }
+    /// ```offset_at(&reference)``` returns the expected offset of the local clock at the
+    /// ```reference``` time, extrapolated with the ```rate```.
+    pub fn offset_at(&self, reference: &DateTime) -> Duration {
+        let elapsed = self.at.diff(reference).as_float();
+        let drift = (elapsed * self.rate * NANOS_PER_SECOND).round() as i128;
+        Duration::from_nanoseconds(self.offset.as_nanoseconds() + drift)
+    }
+}
+
+/// ```estimate_drift(samples)``` estimates the offset and the drift rate of a local clock
+/// from pairs of ```(local, reference)``` times which were read at the same moment, e.g.
+/// the local clock and the time of a NTP server. The rate is the slope of a least squares
+/// fit, so single bad samples are smoothed. The ```offset``` is given at the reference time
+/// of the latest sample.
+///
+/// With a single sample (or samples of the same reference time) the rate is zero, without
+/// any sample an error is returned.
+///
+/// ```
+/// use date_and_time::datetime::*;
+/// use date_and_time::drift::*;
+/// use date_and_time::duration::*;
+/// // the local clock is 2 seconds ahead and gains 1 millisecond per 100 seconds
+/// let samples: Vec<(DateTime, DateTime)> = (0..5)
+///     .map(|n| {
+///         let reference = DateTime::from_timestamp(1_700_000_000 + n * 100);
+///         let offset = Duration::from_milliseconds(2_000 + n);
+///         (reference.add_duration(&offset), reference)
+///     })
+///     .collect();
+/// let report = estimate_drift(&samples).unwrap();
+/// assert_eq!(report.offset.as_milliseconds(), 2_004);
+/// assert!((report.rate_ppm() - 10.0).abs() < 0.001);
+/// ```
+pub fn estimate_drift(samples: &[(DateTime, DateTime)]) -> Result<DriftReport, DateTimeError> {
+    let Some(first) = samples.first() else {
+        return Err(DateTimeError::OutOfRange(
+            "no samples to estimate the drift".to_string(),
+        ));
+    };
+    let origin = first.1;
+    // x: the reference time since the first sample, y: the offset of the local clock, both
+    // in seconds
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|(local, reference)| {
+            (
+                origin.diff(reference).as_float(),
+                reference.diff(local).as_float(),
+            )
+        })
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let mut sxx = 0.0;
+    let mut sxy = 0.0;
+    for (x, y) in &points {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+    }
+    let rate = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    let latest = samples
+        .iter()
+        .map(|(_, reference)| *reference)
+        .fold(origin, |a, b| if b > a { b } else { a });
+    let x_latest = origin.diff(&latest).as_float();
+    let offset = mean_y + rate * (x_latest - mean_x);
+    Ok(DriftReport {
+        offset: Duration::from_nanoseconds((offset * NANOS_PER_SECOND).round() as i128),
+        rate,
+        at: latest,
+        samples: samples.len(),
+    })
+}
@@ -0,0 +1,296 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::error::*;
+use crate::date_and_time::time::*;
+use crate::date_and_time::timecode::*;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// The Duration structure is a signed span of time with nanosecond precision. It can be
+/// build with the functions ```new()```, ```from_seconds()```, ```from_milliseconds()```,
+/// ```from_nanoseconds()```, ```from_time()``` and ```parse_clock()```.
+///
+/// Unlike the ```Time``` structure the Duration keeps the fractions of a second, so it can
+/// be used for sports timing or the length of media files.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration {
+    nanos: i128,
+}
+
+#[allow(dead_code)]
+impl Duration {
+    /// A ```Duration``` with the length zero.
+    pub const ZERO: Duration = Duration { nanos: 0 };
+
+    /// ```new()``` creates a ```Duration``` with the length zero.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::new().as_nanoseconds(), 0);
+    /// ```
+    pub fn new() -> Duration {
+        Duration { nanos: 0 }
+    }
+    /// ```from_seconds(seconds)``` creates a ```Duration``` from the ```seconds```.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::from_seconds(90).as_milliseconds(), 90_000);
+    /// ```
+    pub fn from_seconds(seconds: i64) -> Duration {
+        Duration {
+            nanos: seconds as i128 * NANOS_PER_SECOND,
+        }
+    }
+    /// ```from_milliseconds(milliseconds)``` creates a ```Duration``` from the
+    /// ```milliseconds```.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::from_milliseconds(1_500).as_float(), 1.5);
+    /// ```
+    pub fn from_milliseconds(milliseconds: i64) -> Duration {
+        Duration {
+            nanos: milliseconds as i128 * 1_000_000,
+        }
+    }
+    /// ```from_nanoseconds(nanoseconds)``` creates a ```Duration``` from the
+    /// ```nanoseconds```.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::from_nanoseconds(1_000_000).as_milliseconds(), 1);
+    /// ```
+    pub fn from_nanoseconds(nanoseconds: i128) -> Duration {
+        Duration { nanos: nanoseconds }
+    }
+    /// ```from_time(&time)``` creates a ```Duration``` from the hours, minutes and seconds
+    /// of the ```Time``` structure.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// use date_and_time::time::*;
+    /// assert_eq!(Duration::from_time(&Time::from(1, 30, 0)).as_seconds(), 5_400);
+    /// assert_eq!(Duration::from_time(&Time::from(-1, 30, 0)).as_seconds(), -5_400);
+    /// ```
+    pub fn from_time(time: &Time) -> Duration {
+        Duration::from_seconds(time.as_seconds())
+    }
+    /// ```parse_clock(text)``` parses a clock like text in the format ```H:MM:SS.fff```,
+    /// ```M:SS.fff``` or ```S.fff``` into a ```Duration```.
+    ///
+    /// The leading component can have any number of digits (e.g. ```90:00``` are 90
+    /// minutes), all following components must have two digits and must be lower than 60.
+    /// The fractional part of the seconds is optional and can have up to 9 digits. A
+    /// leading ```-``` or ```+``` is allowed for time differences.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// let lap = Duration::parse_clock("1:02.345").unwrap();
+    /// assert_eq!(lap.as_milliseconds(), 62_345);
+    /// assert_eq!(Duration::parse_clock("90:00").unwrap().as_seconds(), 5_400);
+    /// assert!(Duration::parse_clock("1:60").is_err());
+    /// ```
+    ///
+    pub fn parse_clock(text: &str) -> Result<Duration, DateTimeError> {
+        let text = text.trim();
+        let (negative, body) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (clock, fraction) = match body.split_once('.') {
+            Some((clock, fraction)) => (clock, Some(fraction)),
+            None => (body, None),
+        };
+        let parts: Vec<&str> = clock.split(':').collect();
+        if parts.len() > 3 {
+            return Err(DateTimeError::Parse(format!(
+                "too many components in clock value '{}'",
+                text
+            )));
+        }
+        let mut secs: i128 = 0;
+        for (n, part) in parts.iter().enumerate() {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(DateTimeError::Parse(format!(
+                    "invalid component '{}' in clock value '{}'",
+                    part, text
+                )));
+            }
+            let Ok(value) = part.parse::<i128>() else {
+                return Err(DateTimeError::Parse(format!(
+                    "component '{}' in clock value '{}' is too large",
+                    part, text
+                )));
+            };
+            if n > 0 && (part.len() != 2 || value >= 60) {
+                return Err(DateTimeError::Parse(format!(
+                    "minutes and seconds must be two digits below 60 in clock value '{}'",
+                    text
+                )));
+            }
+            secs = secs
+                .checked_mul(60)
+                .and_then(|secs| secs.checked_add(value))
+                .ok_or_else(|| get_clock_overflow_error(text))?;
+        }
+        let mut nanos: i128 = secs
+            .checked_mul(NANOS_PER_SECOND)
+            .ok_or_else(|| get_clock_overflow_error(text))?;
+        if let Some(fraction) = fraction {
+            if fraction.is_empty()
+                || fraction.len() > 9
+                || !fraction.bytes().all(|b| b.is_ascii_digit())
+            {
+                return Err(DateTimeError::Parse(format!(
+                    "invalid fraction of a second in clock value '{}'",
+                    text
+                )));
+            }
+            let digits: i128 = fraction.parse().unwrap_or(0);
+            nanos = nanos
+                .checked_add(digits * 10_i128.pow(9 - fraction.len() as u32))
+                .ok_or_else(|| get_clock_overflow_error(text))?;
+        }
+        if negative {
+            nanos = -nanos;
+        }
+        Ok(Duration { nanos })
+    }
+    /// ```as_seconds()``` returns the whole seconds of the ```Duration```, the fractional
+    /// part is truncated.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::from_milliseconds(-1_500).as_seconds(), -1);
+    /// ```
+    pub fn as_seconds(&self) -> i64 {
+        (self.nanos / NANOS_PER_SECOND) as i64
+    }
+    /// ```as_milliseconds()``` returns the whole milliseconds of the ```Duration```, the
+    /// fractional part is truncated.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::from_nanoseconds(2_999_999).as_milliseconds(), 2);
+    /// ```
+    pub fn as_milliseconds(&self) -> i64 {
+        (self.nanos / 1_000_000) as i64
+    }
+    /// ```as_nanoseconds()``` returns the ```Duration``` in nanoseconds.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::from_seconds(2).as_nanoseconds(), 2_000_000_000);
+    /// ```
+    pub fn as_nanoseconds(&self) -> i128 {
+        self.nanos
+    }
+    /// ```as_float()``` returns the ```Duration``` in seconds as a float.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::from_milliseconds(250).as_float(), 0.25);
+    /// ```
+    pub fn as_float(&self) -> f64 {
+        self.nanos as f64 / NANOS_PER_SECOND as f64
+    }
+    /// ```subsec_nanoseconds()``` returns the fractional part of the second in nanoseconds.
+    /// The result is negative for a negative ```Duration```.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::from_milliseconds(1_250).subsec_nanoseconds(), 250_000_000);
+    /// assert_eq!(Duration::from_milliseconds(-1_250).subsec_nanoseconds(), -250_000_000);
+    /// ```
+    pub fn subsec_nanoseconds(&self) -> i32 {
+        (self.nanos % NANOS_PER_SECOND) as i32
+    }
+    /// ```is_negative()``` returns true if the ```Duration``` is lower than zero.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert!(Duration::from_seconds(-1).is_negative());
+    /// assert!(!Duration::new().is_negative());
+    /// ```
+    pub fn is_negative(&self) -> bool {
+        self.nanos < 0
+    }
+    /// ```round_to_ticks(ticks_per_second)``` rounds the ```Duration``` to the nearest tick
+    /// of a grid with ```ticks_per_second``` ticks (e.g. 960 MIDI ticks or 44100 audio
+    /// samples) and returns a new ```Duration```. Halfway values are rounded away from zero,
+    /// with ```0``` ticks per second the ```Duration``` is returned unchanged. A rounding
+    /// beyond the largest ```Duration``` saturates.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// // 10 ms are 441 samples at 44.1 kHz
+    /// let rounded = Duration::from_nanoseconds(10_000_004).round_to_ticks(44_100);
+    /// assert_eq!(rounded, Duration::from_milliseconds(10));
+    /// ```
+    pub fn round_to_ticks(&self, ticks_per_second: u32) -> Duration {
+        if ticks_per_second == 0 {
+            return *self;
+        }
+        let tps = ticks_per_second as i128;
+        // the whole seconds are exactly ticks_per_second ticks, only the rest is rounded
+        let rest = self.nanos % NANOS_PER_SECOND;
+        let ticks = round_div(rest * tps, NANOS_PER_SECOND);
+        let rounded = round_div(ticks * NANOS_PER_SECOND, tps);
+        Duration {
+            nanos: (self.nanos - rest).saturating_add(rounded),
+        }
+    }
+    /// ```round_to_frame(&rate)``` rounds the ```Duration``` to the nearest frame of the
+    /// frame ```rate``` and returns a new ```Duration```.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// use date_and_time::timecode::*;
+    /// let rounded = Duration::from_milliseconds(1_030).round_to_frame(&FrameRate::FPS_25);
+    /// assert_eq!(rounded, Duration::from_milliseconds(1_040));
+    /// ```
+    pub fn round_to_frame(&self, rate: &FrameRate) -> Duration {
+        if rate.numerator == 0 || rate.denominator == 0 {
+            return *self;
+        }
+        let num = rate.numerator as i128;
+        let den = rate.denominator as i128;
+        // the denominator seconds are exactly numerator frames, only the rest is rounded
+        let period = den * NANOS_PER_SECOND;
+        let rest = self.nanos % period;
+        let frames = round_div(rest * num, period);
+        Duration {
+            nanos: self.nanos - rest + round_div(frames * period, num),
+        }
+    }
+    /// ```as_time()``` returns the ```Duration``` as a ```Time``` structure, the
+    /// fractional part of the second is truncated.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// use date_and_time::time::*;
+    /// assert_eq!(Duration::from_milliseconds(5_400_999).as_time(), Time::from(1, 30, 0));
+    /// ```
+    pub fn as_time(&self) -> Time {
+        Time::from_seconds(self.as_seconds())
+    }
+}
+
+// Returns the quotient of value: i128 and divisor: i128 (positive) rounded to the nearest
+// integer, halfway values are rounded away from zero
+fn round_div(value: i128, divisor: i128) -> i128 {
+    if value >= 0 {
+        (2 * value + divisor) / (2 * divisor)
+    } else {
+        -((-2 * value + divisor) / (2 * divisor))
+    }
+}
+
+// Returns the parse error for a clock value which does not fit into a Duration
+fn get_clock_overflow_error(text: &str) -> DateTimeError {
+    DateTimeError::Parse(format!("clock value '{}' is too large", text))
+}
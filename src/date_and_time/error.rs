@@ -0,0 +1,33 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use std::fmt;
+
+/// The ```DateTimeError``` is returned by the functions of this crate which can fail and
+/// return a ```Result``` instead of an invalid structure.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DateTimeError {
+    /// The text could not be parsed, the ```String``` contains the reason.
+    Parse(String),
+    /// A file could not be read, the ```String``` contains the reason.
+    Io(String),
+    /// A value is outside of its valid range, the ```String``` contains the reason.
+    OutOfRange(String),
+    /// A value has more than one meaning (e.g. a timezone abbreviation), the ```String```
+    /// contains the possible meanings.
+    Ambiguous(String),
+}
+
+impl fmt::Display for DateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateTimeError::Parse(reason) => write!(f, "parse error: {}", reason),
+            DateTimeError::Io(reason) => write!(f, "io error: {}", reason),
+            DateTimeError::OutOfRange(reason) => write!(f, "out of range: {}", reason),
+            DateTimeError::Ambiguous(reason) => write!(f, "ambiguous: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for DateTimeError {}
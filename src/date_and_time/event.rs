@@ -0,0 +1,113 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use std::cmp::Ordering;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::time::Instant;
+
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+
+// The sequence number of the next EventStamp in this process
+static NEXT_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// The ```EventStamp``` structure marks the moment of an event with the wall clock time
+/// (```wall```, UTC) and a monotonic ```Instant``` with a sequence number. It can be build
+/// with the functions ```now()``` and ```from_wall()```.
+///
+/// The wall clock can step backwards (e.g. by NTP adjustments), so the stamps are compared
+/// and ordered by the monotonic part only. Two stamps of the same ```Instant``` are ordered
+/// by their sequence number, which is unique in the process. The monotonic part is only
+/// meaningful inside the process which created the stamps.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct EventStamp {
+    pub wall: DateTime,
+    instant: Instant,
+    sequence: u64,
+}
+
+#[allow(dead_code)]
+impl EventStamp {
+    /// ```now()``` creates an ```EventStamp``` with the current UTC time of the systems
+    /// clock and the current ```Instant```.
+    ///
+    /// ```
+    /// use date_and_time::event::*;
+    /// let first = EventStamp::now();
+    /// let second = EventStamp::now();
+    /// assert!(first < second);
+    /// ```
+    pub fn now() -> EventStamp {
+        EventStamp::from_wall(DateTime::from_system_clock())
+    }
+    /// ```from_wall(wall)``` creates an ```EventStamp``` with the given wall clock time
+    /// ```wall``` (e.g. of another clock source) and the current ```Instant```.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::event::*;
+    /// // the wall clock was set back between the two events
+    /// let first = EventStamp::from_wall(DateTime::from_timestamp(1_700_000_010));
+    /// let second = EventStamp::from_wall(DateTime::from_timestamp(1_700_000_000));
+    /// assert!(first < second);
+    /// ```
+    pub fn from_wall(wall: DateTime) -> EventStamp {
+        EventStamp {
+            wall,
+            instant: Instant::now(),
+            sequence: NEXT_SEQUENCE.fetch_add(1, AtomicOrdering::Relaxed),
+        }
+    }
+    /// ```sequence()``` returns the sequence number of the ```EventStamp```, the numbers
+    /// start with 0 and are unique in the process.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+    /// ```elapsed_since(&earlier)``` returns the monotonic time between the ```earlier```
+    /// stamp and this stamp as a ```Duration```, it is negative if ```earlier``` is the
+    /// later stamp. Unlike the difference of the wall clock times it can't be disturbed by
+    /// clock adjustments.
+    ///
+    /// ```
+    /// use date_and_time::event::*;
+    /// let start = EventStamp::now();
+    /// let end = EventStamp::now();
+    /// assert!(!end.elapsed_since(&start).is_negative());
+    /// ```
+    pub fn elapsed_since(&self, earlier: &EventStamp) -> Duration {
+        if self.instant >= earlier.instant {
+            Duration::from_nanoseconds((self.instant - earlier.instant).as_nanos() as i128)
+        } else {
+            Duration::from_nanoseconds(-((earlier.instant - self.instant).as_nanos() as i128))
+        }
+    }
+    /// ```as_string()``` gets the ```EventStamp``` as a string in the format
+    /// YYYY-MM-DDTHH:MM:SS[.nnnnnnnnn]#sequence.
+    pub fn as_string(&self) -> String {
+        format!("{}#{}", self.wall.as_string(), self.sequence)
+    }
+}
+
+impl PartialEq for EventStamp {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for EventStamp {}
+
+impl PartialOrd for EventStamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EventStamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.instant
+            .cmp(&other.instant)
+            .then(self.sequence.cmp(&other.sequence))
+    }
+}
@@ -0,0 +1,988 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// The formatter of the date and time placeholders of as_formated_string() and the parsers of
+// the formatted strings, the inverse of as_formated_string(). A format is round-trip safe, if
+// every valid value which is formatted with it is parsed back to the same value. The rules
+// and the normalization are documented at parse_formatted().
+//
+use crate::date_and_time::abbreviation::*;
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::invalid::*;
+use crate::date_and_time::local::*;
+use crate::date_and_time::locale::*;
+use crate::date_and_time::offset::*;
+use crate::date_and_time::time::*;
+use std::fmt;
+
+// The placeholders which can be parsed by Date::parse_formatted() and Time::parse_formatted()
+const DATE_PLACEHOLDERS: &str = "YmdejbBaAuw";
+const TIME_PLACEHOLDERS: &str = "HIMSp";
+// The depth of the composite placeholders %c, %x and %X in the formats of a locale
+const MAX_COMPOSITE_DEPTH: u8 = 2;
+
+// A part of a format, a text between the placeholders or a placeholder with its modifier
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FormatToken<T> {
+    Text(T),
+    Placeholder(char, Option<char>),
+}
+
+// The tokens of the rest of a format
+struct FormatTokens<'a> {
+    rest: &'a str,
+}
+
+// The date, the time and the zone of the placeholders
+#[derive(Copy, Clone)]
+struct FormatValues<'a> {
+    date: Option<&'a Date>,
+    time: Option<&'a Time>,
+    zone: Option<(UtcOffset, &'a str)>,
+}
+
+// A writer which discards the text, check_format() writes the placeholders into it
+struct NullWriter;
+
+impl fmt::Write for NullWriter {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+// A small text on the stack for the padding of the numbers, a longer text can't be written
+struct NumberBuffer {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl NumberBuffer {
+    fn new() -> NumberBuffer {
+        NumberBuffer {
+            bytes: [0; 64],
+            len: 0,
+        }
+    }
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+    }
+}
+
+impl fmt::Write for NumberBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+// The parsed fields of a formatted string
+#[derive(Default)]
+struct Fields {
+    year: Option<i32>,
+    month: Option<u8>,
+    day: Option<u8>,
+    day_of_year: Option<u16>,
+    weekday: Option<u8>,
+    hour: Option<i32>,
+    hour_12: Option<i32>,
+    pm: Option<bool>,
+    minute: Option<i8>,
+    second: Option<i8>,
+    zone: Option<String>,
+}
+
+/// ```normalize_format(format)``` returns the ```format``` with the composite placeholders
+/// replaced by their parts: ```%F``` is ```%Y-%m-%d```, ```%D``` is ```%m/%d/%y```,
+/// ```%T``` is ```%H:%M:%S```, ```%R``` is ```%H:%M``` and ```%r``` is ```%I:%M:%S %p```.
+/// This is the first step of ```parse_formatted()```.
+///
+/// ```
+/// use date_and_time::formatted::*;
+/// assert_eq!(normalize_format("%F %T"), "%Y-%m-%d %H:%M:%S");
+/// assert_eq!(normalize_format("100%% at %R"), "100%% at %H:%M");
+/// ```
+pub fn normalize_format(format: &str) -> String {
+    let mut result = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('F') => result.push_str("%Y-%m-%d"),
+            Some('D') => result.push_str("%m/%d/%y"),
+            Some('T') => result.push_str("%H:%M:%S"),
+            Some('R') => result.push_str("%H:%M"),
+            Some('r') => result.push_str("%I:%M:%S %p"),
+            Some(cn) => {
+                result.push('%');
+                result.push(cn);
+            }
+            None => {}
+        }
+    }
+    result
+}
+
+/// ```is_round_trip_date_format(date_format)``` returns true if every valid ```Date``` with
+/// a year from -99999999 to 999999999 (-999 to 9999 if a number follows the year directly,
+/// e.g. in ```%Y%m%d```) which is formatted with the ```date_format``` (by
+/// ```Date::as_formated_string()```) is parsed back to the same date by
+/// ```Date::parse_formatted()```. This is the case if the format has only placeholders of
+/// ```parse_formatted()``` and it contains the year (```%Y```) and the month (```%m```,
+/// ```%b``` or ```%B```) with the day (```%d``` or ```%e```) or the day of the year
+/// (```%j```).
+///
+/// ```
+/// use date_and_time::formatted::*;
+/// assert!(is_round_trip_date_format("%F"));
+/// assert!(is_round_trip_date_format("%A, %e. %B %Y"));
+/// assert!(is_round_trip_date_format("%Y%j"));
+/// // the century is missing
+/// assert!(!is_round_trip_date_format("%d.%m.%y"));
+/// ```
+pub fn is_round_trip_date_format(date_format: &str) -> bool {
+    let Some(found) = get_placeholders(date_format, DATE_PLACEHOLDERS) else {
+        return false;
+    };
+    let has = |placeholders: &str| found.chars().any(|c| placeholders.contains(c));
+    has("Y") && (has("mbB") && has("de") || has("j"))
+}
+
+/// ```is_round_trip_time_format(time_format)``` returns true if every valid clock ```Time```
+/// (00:00:00 to 23:59:59) which is formatted with the ```time_format``` (by
+/// ```Time::as_formated_string()```) is parsed back to the same time by
+/// ```Time::parse_formatted()```. This is the case if the format has only placeholders of
+/// ```parse_formatted()``` and it contains the hour (```%H```, or ```%I``` with ```%p```),
+/// the minute (```%M```) and the second (```%S```).
+///
+/// ```
+/// use date_and_time::formatted::*;
+/// assert!(is_round_trip_time_format("%T"));
+/// assert!(is_round_trip_time_format("%r"));
+/// // the seconds are missing
+/// assert!(!is_round_trip_time_format("%H:%M"));
+/// ```
+pub fn is_round_trip_time_format(time_format: &str) -> bool {
+    let Some(found) = get_placeholders(time_format, TIME_PLACEHOLDERS) else {
+        return false;
+    };
+    let has = |placeholder: char| found.contains(placeholder);
+    (has('H') || has('I') && has('p')) && has('M') && has('S')
+}
+
+/// The FormatSpec structure is a format of ```as_formated_string()``` which is parsed only
+/// once into its texts and placeholders, e.g. for a logger which writes many dates with the
+/// same format. ```format()```, ```format_time()``` and ```format_datetime()``` write the
+/// same text as ```as_formated_string()``` of ```Date```, ```Time``` and ```DateTime```.
+///
+/// ```
+/// use date_and_time::datetime::*;
+/// use date_and_time::formatted::*;
+/// let spec = FormatSpec::parse("%Y-%m-%d %H:%M:%S");
+/// let dt = DateTime::from_timestamp(1_719_081_045);
+/// assert_eq!(spec.format_datetime(&dt), "2024-06-22 18:30:45");
+/// assert_eq!(spec.format_datetime(&dt), dt.as_formated_string("%Y-%m-%d %H:%M:%S"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatSpec {
+    tokens: Vec<FormatToken<String>>,
+}
+
+#[allow(dead_code)]
+impl FormatSpec {
+    /// ```parse(format)``` creates the ```FormatSpec``` of the ```format``` with the
+    /// placeholders of ```DateTime::as_formated_string()```.
+    pub fn parse(format: &str) -> FormatSpec {
+        let mut tokens: Vec<FormatToken<String>> = Vec::new();
+        for token in get_format_tokens(format) {
+            match (tokens.last_mut(), token) {
+                (Some(FormatToken::Text(text)), FormatToken::Text(next)) => text.push_str(next),
+                (_, FormatToken::Text(next)) => tokens.push(FormatToken::Text(String::from(next))),
+                (_, FormatToken::Placeholder(cn, modifier)) => {
+                    tokens.push(FormatToken::Placeholder(cn, modifier))
+                }
+            }
+        }
+        FormatSpec { tokens }
+    }
+    // Returns the text of the date, the time and the zone of the values in the format
+    fn get_formated_values(&self, values: FormatValues) -> String {
+        let tokens = self.tokens.iter().map(|token| match token {
+            FormatToken::Text(text) => FormatToken::Text(text.as_str()),
+            FormatToken::Placeholder(cn, modifier) => FormatToken::Placeholder(*cn, *modifier),
+        });
+        let mut result = String::new();
+        // writing into a String doesn't fail
+        let _ = write_formated_tokens(&mut result, values, tokens, &English, 0);
+        result
+    }
+    /// ```format(&date)``` returns the ```date``` as a string in the format, like
+    /// ```Date::as_formated_string()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::formatted::*;
+    /// let spec = FormatSpec::parse("%A, %o %B %Y");
+    /// assert_eq!(spec.format(&Date::from(22, 6, 2024)), "Saturday, 22nd June 2024");
+    /// ```
+    pub fn format(&self, date: &Date) -> String {
+        if !is_date_valid(date) {
+            if let Some(text) = get_invalid_display() {
+                return text;
+            }
+        }
+        self.get_formated_values(FormatValues {
+            date: Some(date),
+            time: None,
+            zone: None,
+        })
+    }
+    /// ```format_time(&time)``` returns the ```time``` as a string in the format, like
+    /// ```Time::as_formated_string()```.
+    ///
+    /// ```
+    /// use date_and_time::formatted::*;
+    /// use date_and_time::time::*;
+    /// let spec = FormatSpec::parse("%-I:%M %p");
+    /// assert_eq!(spec.format_time(&Time::from(21, 5, 0)), "9:05 p.m.");
+    /// ```
+    pub fn format_time(&self, time: &Time) -> String {
+        if !is_time_valid(time) {
+            if let Some(text) = get_invalid_display() {
+                return text;
+            }
+        }
+        self.get_formated_values(FormatValues {
+            date: None,
+            time: Some(time),
+            zone: None,
+        })
+    }
+    /// ```format_datetime(&datetime)``` returns the ```datetime``` as a string in the
+    /// format, like ```DateTime::as_formated_string()```.
+    pub fn format_datetime(&self, datetime: &DateTime) -> String {
+        if !is_date_valid(&datetime.date) {
+            if let Some(text) = get_invalid_display() {
+                return text;
+            }
+        }
+        self.get_formated_values(FormatValues {
+            date: Some(&datetime.date),
+            time: Some(&datetime.time),
+            zone: None,
+        })
+    }
+}
+
+#[allow(dead_code)]
+impl Date {
+    /// ```parse_formatted(text, date_format)``` parses the ```text``` which was formatted
+    /// with the ```date_format``` of ```as_formated_string()```, it is the inverse of
+    /// ```as_formated_string()``` for the formats of ```is_round_trip_date_format()```.
+    ///
+    /// The placeholders ```%Y```, ```%m```, ```%d```, ```%e```, ```%j```, ```%b```,
+    /// ```%B```, ```%a```, ```%A```, ```%u```, ```%w``` and ```%F``` can be parsed, the
+    /// weekdays are only checked. Before parsing, the format is normalized by
+    /// ```normalize_format()```, the text is matched with these rules:
+    /// - the numbers may have less digits (e.g. ```7``` for ```%d```) or leading spaces,
+    /// - the year (```%Y```) can have a sign and more than 4 digits, it has at most 4
+    ///   characters with the sign if a number follows it directly (e.g. in ```%Y%m%d```),
+    /// - the names of the months and weekdays are English, in any upper- or lowercase,
+    /// - all other characters must be the same as in the format.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let date = Date::from(24, 12, 2024);
+    /// let text = date.as_formated_string("%A, %e. %B %Y");
+    /// assert_eq!(Date::parse_formatted(&text, "%A, %e. %B %Y"), Ok(date));
+    /// assert_eq!(Date::parse_formatted("20241224", "%Y%m%d"), Ok(date));
+    /// assert_eq!(Date::parse_formatted("24 dec 2024", "%d %b %Y"), Ok(date));
+    /// // the 24th December 2024 was a Tuesday
+    /// assert!(Date::parse_formatted("Mon 2024-12-24", "%a %F").is_err());
+    /// ```
+    pub fn parse_formatted(text: &str, date_format: &str) -> Result<Date, DateTimeError> {
+        let fields = parse_fields(text, date_format, DATE_PLACEHOLDERS)?;
+        get_fields_date(&fields).map_err(|reason| get_fields_error(text, date_format, reason))
+    }
+}
+
+#[allow(dead_code)]
+impl Time {
+    /// ```parse_formatted(text, time_format)``` parses the ```text``` which was formatted
+    /// with the ```time_format``` of ```as_formated_string()```, it is the inverse of
+    /// ```as_formated_string()``` for the formats of ```is_round_trip_time_format()```.
+    ///
+    /// The placeholders ```%H```, ```%I```, ```%M```, ```%S```, ```%p```, ```%T```,
+    /// ```%R``` and ```%r``` can be parsed, the text is matched with the rules of
+    /// ```Date::parse_formatted()```. The hours are 0 to 23 (```%H```) or 1 to 12 with
+    /// ```%p``` (```a.m.```, ```p.m.```, ```AM``` or ```PM```), without seconds the time
+    /// has 0 seconds.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let time = Time::from(21, 5, 0);
+    /// assert_eq!(Time::parse_formatted("09:05 p.m.", "%I:%M %p"), Ok(time));
+    /// assert_eq!(Time::parse_formatted(" 9:05:00 PM", "%r"), Ok(time));
+    /// assert_eq!(Time::parse_formatted("21:05", "%R"), Ok(time));
+    /// assert!(Time::parse_formatted("24:00", "%R").is_err());
+    /// ```
+    pub fn parse_formatted(text: &str, time_format: &str) -> Result<Time, DateTimeError> {
+        let fields = parse_fields(text, time_format, TIME_PLACEHOLDERS)?;
+        get_fields_time(&fields).map_err(|reason| get_fields_error(text, time_format, reason))
+    }
+}
+
+#[allow(dead_code)]
+impl DateTime {
+    /// ```parse_formatted_with_zone(text, format, abbreviations)``` parses the ```text```
+    /// with a date, a time and a timezone abbreviation (```%Z```) which was formatted with
+    /// the ```format```, the placeholders and rules are the same as of
+    /// ```Date::parse_formatted()``` and ```Time::parse_formatted()```. The abbreviation is
+    /// a word of ASCII letters, it is resolved by the ```abbreviations```: an unknown
+    /// abbreviation is an error, an ambiguous one returns the preferred zone or a
+    /// ```DateTimeError::Ambiguous``` instead of guessing. It returns the local date and
+    /// time and the zone of the abbreviation.
+    ///
+    /// ```
+    /// use date_and_time::abbreviation::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::error::*;
+    /// let mut table = AbbreviationTable::new();
+    /// let text = "2024-06-22 18:30 CST";
+    /// let result = DateTime::parse_formatted_with_zone(text, "%F %R %Z", &table);
+    /// assert!(matches!(result, Err(DateTimeError::Ambiguous(_))));
+    /// table.prefer("CST", "Asia/Shanghai").unwrap();
+    /// let (local, zone) = DateTime::parse_formatted_with_zone(text, "%F %R %Z", &table).unwrap();
+    /// assert_eq!(local.as_string(), "2024-06-22T18:30:00");
+    /// assert_eq!(zone.offset.as_seconds(), 28_800);
+    /// ```
+    pub fn parse_formatted_with_zone(
+        text: &str,
+        format: &str,
+        abbreviations: &AbbreviationTable,
+    ) -> Result<(DateTime, ZoneCandidate), DateTimeError> {
+        let allowed = format!("{}{}Z", DATE_PLACEHOLDERS, TIME_PLACEHOLDERS);
+        let fields = parse_fields(text, format, &allowed)?;
+        let error = |reason: &str| get_fields_error(text, format, reason);
+        let date = get_fields_date(&fields).map_err(error)?;
+        let time = get_fields_time(&fields).map_err(error)?;
+        let zone = fields
+            .zone
+            .as_deref()
+            .ok_or_else(|| error("has no timezone abbreviation (%Z)"))?;
+        Ok((DateTime::from(date, time), abbreviations.resolve(zone)?))
+    }
+}
+
+// Returns the text of the format: &str with the placeholders of the date and the time, the
+// placeholders of a missing date or time are written as their character (e.g. "%Y" as "Y").
+// The offset and the abbreviation of %z and %Z are of the zone, or of the local timezone if
+// there is no zone.
+pub(crate) fn get_formated_string(
+    date: Option<&Date>,
+    time: Option<&Time>,
+    zone: Option<(UtcOffset, &str)>,
+    format: &str,
+    locale: &(impl Locale + ?Sized),
+) -> String {
+    let mut result = String::new();
+    // writing into a String doesn't fail
+    let _ = write_formated_string(&mut result, date, time, zone, format, locale);
+    result
+}
+
+// Writes the text of the format: &str like get_formated_string() into the out: &mut impl
+// fmt::Write, without an allocation (except of the abbreviation of the local timezone)
+pub(crate) fn write_formated_string<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    date: Option<&Date>,
+    time: Option<&Time>,
+    zone: Option<(UtcOffset, &str)>,
+    format: &str,
+    locale: &(impl Locale + ?Sized),
+) -> fmt::Result {
+    let values = FormatValues { date, time, zone };
+    write_formated_tokens(out, values, get_format_tokens(format), locale, 0)
+}
+
+// Returns an error with the first unknown placeholder of the format: &str and its position
+// (the index of the % in the characters of the format), the placeholders of the date and
+// of the time are known if has_date: bool and has_time: bool
+pub(crate) fn check_format(
+    format: &str,
+    has_date: bool,
+    has_time: bool,
+) -> Result<(), DateTimeError> {
+    let error = |position: usize, reason: String| {
+        DateTimeError::Parse(format!(
+            "the format '{}' has {} at position {}",
+            format, reason, position
+        ))
+    };
+    // the placeholders are known if the formatter writes them for these values, the zone
+    // is given, so the local timezone isn't read
+    let date = Date::new();
+    let time = Time::new();
+    let values = FormatValues {
+        date: has_date.then_some(&date),
+        time: has_time.then_some(&time),
+        zone: Some((UtcOffset::default(), "UTC")),
+    };
+    let mut chars = format.char_indices().enumerate();
+    while let Some((position, (start, c))) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        let Some((_, (_, cn))) = chars.next() else {
+            return Err(error(position, String::from("an incomplete placeholder %")));
+        };
+        if matches!(cn, '-' | '_' | '0' | ':') && chars.next().is_none() {
+            return Err(error(
+                position,
+                format!("an incomplete placeholder %{}", cn),
+            ));
+        }
+        let end = chars
+            .clone()
+            .next()
+            .map_or(format.len(), |(_, (idx, _))| idx);
+        let placeholder = &format[start..end];
+        let known = match get_format_tokens(placeholder).next() {
+            // %%, %n and %t are texts, a colon with another placeholder than z is written
+            // as a text too
+            Some(FormatToken::Text(_)) => !placeholder.starts_with("%:"),
+            Some(FormatToken::Placeholder(cn, modifier)) => {
+                write_placeholder(&mut NullWriter, values, cn, modifier, &English, 0)
+                    .unwrap_or(false)
+            }
+            None => false,
+        };
+        if !known {
+            return Err(error(
+                position,
+                format!("the unknown placeholder {}", placeholder),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Returns the tokens of the format: &str, an iterator which doesn't allocate
+fn get_format_tokens(format: &str) -> FormatTokens<'_> {
+    FormatTokens { rest: format }
+}
+
+impl<'a> Iterator for FormatTokens<'a> {
+    type Item = FormatToken<&'a str>;
+    fn next(&mut self) -> Option<FormatToken<&'a str>> {
+        let text = match self.rest.find('%') {
+            Some(0) => "",
+            Some(n) => &self.rest[..n],
+            None => self.rest,
+        };
+        if !text.is_empty() {
+            self.rest = &self.rest[text.len()..];
+            return Some(FormatToken::Text(text));
+        }
+        let placeholder = self.rest.get(1..)?;
+        let mut chars = placeholder.chars();
+        let Some(mut cn) = chars.next() else {
+            self.rest = "";
+            return None;
+        };
+        // the padding modifiers %-d (no padding), %_d (spaces) and %0e (zeros) and the
+        // colon of %:z
+        let modifier = match cn {
+            '-' | '_' | '0' | ':' => Some(cn),
+            _ => None,
+        };
+        if modifier.is_some() {
+            let Some(next) = chars.next() else {
+                self.rest = "";
+                return (modifier == Some(':')).then_some(FormatToken::Text(":"));
+            };
+            cn = next;
+        }
+        self.rest = chars.as_str();
+        let token = match (cn, modifier) {
+            ('%', _) => FormatToken::Text("%"),
+            ('n', _) => FormatToken::Text("\n"),
+            ('t', _) => FormatToken::Text("\t"),
+            // only %:z has a colon
+            (_, Some(':')) if cn != 'z' => FormatToken::Text(&placeholder[..1 + cn.len_utf8()]),
+            _ => FormatToken::Placeholder(cn, modifier),
+        };
+        Some(token)
+    }
+}
+
+// Writes the tokens like get_formated_string() into the out: &mut impl fmt::Write, the
+// depth: u8 counts the composite placeholders %c, %x and %X of the locale which are
+// expanded in each other
+fn write_formated_tokens<'a, W: fmt::Write + ?Sized>(
+    out: &mut W,
+    values: FormatValues,
+    tokens: impl Iterator<Item = FormatToken<&'a str>>,
+    locale: &(impl Locale + ?Sized),
+    depth: u8,
+) -> fmt::Result {
+    for token in tokens {
+        match token {
+            FormatToken::Text(text) => out.write_str(text)?,
+            FormatToken::Placeholder(cn, Some(modifier @ ('-' | '_' | '0'))) => {
+                // the number is padded in a buffer on the stack, a longer text isn't a number
+                let mut buffer = NumberBuffer::new();
+                match write_placeholder(&mut buffer, values, cn, None, locale, depth) {
+                    Ok(true) => write_padded_number(out, buffer.as_str(), modifier)?,
+                    Ok(false) => out.write_char(cn)?,
+                    Err(_) => {
+                        write_placeholder(out, values, cn, None, locale, depth)?;
+                    }
+                }
+            }
+            FormatToken::Placeholder(cn, modifier) => {
+                if !write_placeholder(out, values, cn, modifier, locale, depth)? {
+                    out.write_char(cn)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// Writes the text of the placeholder: char with the modifier: Option<char> of the values into
+// the out: &mut impl fmt::Write, returns false and writes nothing if the placeholder is
+// unknown or its date or time is missing
+fn write_placeholder<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    values: FormatValues,
+    placeholder: char,
+    modifier: Option<char>,
+    locale: &(impl Locale + ?Sized),
+    depth: u8,
+) -> Result<bool, fmt::Error> {
+    let FormatValues { date, time, zone } = values;
+    let composite = depth < MAX_COMPOSITE_DEPTH;
+    match placeholder {
+        'z' => {
+            let offset = zone.map_or_else(get_gmt_offset, |(offset, _)| offset);
+            let separator = if modifier == Some(':') { ":" } else { "" };
+            write_offset(out, offset, separator)?
+        }
+        'Z' => match zone {
+            Some((_, name)) => out.write_str(name)?,
+            None => out.write_str(&get_timezone_abbreviation())?,
+        },
+        // the preferred formats of the locale, a format which contains itself is expanded
+        // only twice
+        'c' if composite && date.is_some() && time.is_some() => {
+            let tokens = get_format_tokens(locale.datetime_format());
+            write_formated_tokens(out, values, tokens, locale, depth + 1)?
+        }
+        'x' if composite && date.is_some() => {
+            let tokens = get_format_tokens(locale.date_format());
+            write_formated_tokens(out, values, tokens, locale, depth + 1)?
+        }
+        'X' if composite && time.is_some() => {
+            let tokens = get_format_tokens(locale.time_format());
+            write_formated_tokens(out, values, tokens, locale, depth + 1)?
+        }
+        // the seconds since the Linux epoch of the date (at midnight) and the time
+        's' if date.is_some() => {
+            let days = date.map_or(0, get_days_from_date);
+            write!(out, "{}", days * 86_400 + time.map_or(0, time_to_secs))?
+        }
+        _ => {
+            if let Some(date) = date {
+                if write_date_placeholder(out, date, placeholder, locale)? {
+                    return Ok(true);
+                }
+            }
+            if let Some(time) = time {
+                if write_time_placeholder(out, time, placeholder, locale)? {
+                    return Ok(true);
+                }
+            }
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+// Writes the offset: UtcOffset as +HHMM, or +HH:MM with the separator: &str ":"
+fn write_offset<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    offset: UtcOffset,
+    separator: &str,
+) -> fmt::Result {
+    let sign = if offset.as_seconds() < 0 { '-' } else { '+' };
+    let hours = offset.offset_hours().abs();
+    let minutes = offset.offset_minutes().abs();
+    write!(out, "{}{:02}{}{:02}", sign, hours, separator, minutes)
+}
+
+// Writes the date placeholder: char of the date: &Date, returns false if it isn't a date
+// placeholder
+fn write_date_placeholder<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    date: &Date,
+    placeholder: char,
+    locale: &(impl Locale + ?Sized),
+) -> Result<bool, fmt::Error> {
+    let month = ((date.m as usize + 11) % 12 + 1) as u8;
+    match placeholder {
+        'a' => out.write_str(locale.weekday_abbreviation(date.get_weekday()))?,
+        'A' => out.write_str(locale.weekday_name(date.get_weekday()))?,
+        'b' => out.write_str(locale.month_abbreviation(month))?,
+        'B' => out.write_str(locale.month_name(month))?,
+        'C' => write!(out, "{:02}", date.y / 100)?,
+        'd' => write!(out, "{:02}", date.d)?,
+        'D' => write!(
+            out,
+            "{:02}/{:02}/{:02}",
+            date.m,
+            date.d,
+            date.y.rem_euclid(100)
+        )?,
+        'e' => write!(out, "{:2}", date.d)?,
+        'F' => write!(out, "{:04}-{:02}-{:02}", date.y, date.m, date.d)?,
+        'g' => write!(out, "{:02}", date.get_iso_week().0.rem_euclid(100))?,
+        'G' => write!(out, "{:04}", date.get_iso_week().0)?,
+        'j' => write!(out, "{:03}", date.get_day_of_year())?,
+        'J' => write!(out, "{}", date.get_days_remaining_in_year())?,
+        'K' => write!(out, "{}", date.get_week_of_month())?,
+        'm' => write!(out, "{:02}", date.m)?,
+        'o' => write!(out, "{}{}", date.d, locale.ordinal_suffix(date.d))?,
+        'Q' => write!(out, "{}", date.get_day_of_quarter())?,
+        'u' => match date.get_weekday() {
+            0 => out.write_char('7')?,
+            wd => write!(out, "{}", wd)?,
+        },
+        'U' => write!(out, "{:02}", date.get_week_of_year(0))?,
+        'V' => write!(out, "{:02}", date.get_iso_week_of_year())?,
+        'W' => write!(out, "{:02}", date.get_week_of_year(1))?,
+        'w' => write!(out, "{}", date.get_weekday())?,
+        'y' => write!(out, "{:02}", date.y.rem_euclid(100))?,
+        'Y' => write!(out, "{:04}", date.y)?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+// Writes the time placeholder: char of the time: &Time, returns false if it isn't a time
+// placeholder
+fn write_time_placeholder<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    time: &Time,
+    placeholder: char,
+    locale: &(impl Locale + ?Sized),
+) -> Result<bool, fmt::Error> {
+    // a negative time has the minus in front of the hours
+    let (sign, abs) = match is_time_valid(time) && time.is_negative() {
+        true => ("-", time.abs()),
+        false => ("", *time),
+    };
+    match placeholder {
+        'H' => write!(out, "{}{:02}", sign, abs.h)?,
+        'I' => {
+            let hour = if time.h == 0 {
+                12
+            } else if time.h > 12 {
+                time.h - 12
+            } else {
+                time.h
+            };
+            write!(out, "{:02}", hour)?
+        }
+        'M' => write!(out, "{:02}", abs.m)?,
+        'p' => match time.h >= 12 {
+            true => out.write_str(locale.meridiem(Meridiem::PM))?,
+            false => out.write_str(locale.meridiem(Meridiem::AM))?,
+        },
+        'r' => {
+            let mut hour = time.h;
+            if hour == 0 {
+                hour = 24
+            };
+            if hour > 12 {
+                hour -= 12
+            };
+            let h_12 = match time.h >= 12 {
+                true => locale.meridiem_short(Meridiem::PM),
+                false => locale.meridiem_short(Meridiem::AM),
+            };
+            write!(out, "{:2}:{:02}:{:02} {}", hour, time.m, time.s, h_12)?
+        }
+        'R' => write!(out, "{}{:02}:{:02}", sign, abs.h, abs.m)?,
+        'S' => write!(out, "{:02}", abs.s)?,
+        'T' => write!(out, "{}{:02}:{:02}:{:02}", sign, abs.h, abs.m, abs.s)?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+// Writes the number in the text of a placeholder with the padding of the modifier: char,
+// '-' removes the padding, '_' pads with spaces and '0' with zeros to the width of the text.
+// A text which isn't a number (e.g. a name) is written unchanged.
+fn write_padded_number<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    text: &str,
+    modifier: char,
+) -> fmt::Result {
+    let (sign, number) = match text.strip_prefix('-') {
+        Some(number) => ("-", number),
+        None => ("", text),
+    };
+    if !number.chars().all(|c| c.is_ascii_digit() || c == ' ')
+        || !number.chars().any(|c| c.is_ascii_digit())
+    {
+        return out.write_str(text);
+    }
+    let digits = number.trim_start_matches([' ', '0']);
+    let digits = if digits.is_empty() { "0" } else { digits };
+    let padding = number.len() - digits.len();
+    match modifier {
+        '_' => {
+            for _ in 0..padding {
+                out.write_char(' ')?;
+            }
+            write!(out, "{}{}", sign, digits)
+        }
+        '0' => {
+            out.write_str(sign)?;
+            for _ in 0..padding {
+                out.write_char('0')?;
+            }
+            out.write_str(digits)
+        }
+        _ => write!(out, "{}{}", sign, digits),
+    }
+}
+
+// Returns the placeholders of the normalized format: &str as a string, or None if one of
+// them is not in the allowed: &str placeholders
+fn get_placeholders(format: &str, allowed: &str) -> Option<String> {
+    let mut found = String::new();
+    let mut chars = normalize_format(format)
+        .chars()
+        .collect::<Vec<char>>()
+        .into_iter();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        match chars.next() {
+            Some('%' | 'n' | 't') | None => {}
+            Some(cn) if allowed.contains(cn) => found.push(cn),
+            Some(_) => return None,
+        }
+    }
+    Some(found)
+}
+
+// Returns the fields of the text: &str which was formatted with the format: &str, or an
+// error if it doesn't match or a placeholder isn't one of the allowed: &str placeholders
+fn parse_fields(text: &str, format: &str, allowed: &str) -> Result<Fields, DateTimeError> {
+    let error = |reason: String| {
+        DateTimeError::Parse(format!(
+            "'{}' with the format '{}' {}",
+            text, format, reason
+        ))
+    };
+    let format: Vec<char> = normalize_format(format).chars().collect();
+    let mut rest = text;
+    let mut fields = Fields::default();
+    let mut n = 0;
+    while n < format.len() {
+        let c = format[n];
+        n += 1;
+        let literal = match c {
+            '%' => match format.get(n) {
+                Some('%') => Some('%'),
+                Some('n') => Some('\n'),
+                Some('t') => Some('\t'),
+                _ => None,
+            },
+            c => Some(c),
+        };
+        if let Some(literal) = literal {
+            n += (c == '%') as usize;
+            rest = rest
+                .strip_prefix(literal)
+                .ok_or_else(|| error(format!("has no '{}' at '{}'", literal, rest)))?;
+            continue;
+        }
+        let Some(&placeholder) = format.get(n) else {
+            break;
+        };
+        n += 1;
+        if !allowed.contains(placeholder) {
+            return Err(error(format!(
+                "has the unsupported placeholder %{}",
+                placeholder
+            )));
+        }
+        // the year has 4 digits if the next placeholder is a number, e.g. in %Y%m%d
+        let next_is_number = format.get(n) == Some(&'%')
+            && format
+                .get(n + 1)
+                .is_some_and(|next| "YmdejuwHIMS".contains(*next));
+        let (value, after) = match placeholder {
+            'b' => get_name(rest, &MONTH_NAME_ABBREVIATE),
+            'B' => get_name(rest, &MONTH_NAME_FULL),
+            'a' => get_name(rest, &WEEKDAY_ABBREVIATE),
+            'A' => get_name(rest, &WEEKDAY_FULL),
+            'p' => get_meridiem(rest),
+            'Z' => get_zone_name(rest),
+            'Y' => get_number(rest, if next_is_number { 4 } else { 9 }, true),
+            'j' => get_number(rest, 3, false),
+            'u' | 'w' => get_number(rest, 1, false),
+            _ => get_number(rest, 2, false),
+        }
+        .ok_or_else(|| error(format!("doesn't match %{} at '{}'", placeholder, rest)))?;
+        let parsed = &rest[..rest.len() - after.len()];
+        rest = after;
+        match placeholder {
+            'Y' => fields.year = Some(value as i32),
+            'm' => fields.month = Some(value as u8),
+            'b' | 'B' => fields.month = Some(value as u8 + 1),
+            'd' | 'e' => fields.day = Some(value as u8),
+            'j' => fields.day_of_year = Some(value as u16),
+            'a' | 'A' | 'w' => fields.weekday = Some(value as u8),
+            'u' => fields.weekday = Some(value as u8 % 7),
+            'H' => fields.hour = Some(value as i32),
+            'I' => fields.hour_12 = Some(value as i32),
+            'p' => fields.pm = Some(value == 1),
+            'M' => fields.minute = Some(value as i8),
+            'Z' => fields.zone = Some(String::from(parsed)),
+            _ => fields.second = Some(value as i8),
+        }
+    }
+    if !rest.is_empty() {
+        return Err(error(format!("has the rest '{}'", rest)));
+    }
+    Ok(fields)
+}
+
+// Returns the Date of the parsed fields: &Fields, or the reason why they are no valid date
+fn get_fields_date(fields: &Fields) -> Result<Date, &'static str> {
+    let year = fields.year.ok_or("has no year (%Y)")?;
+    let date = match (fields.month, fields.day, fields.day_of_year) {
+        (Some(m), Some(d), _) => Date { d, m, y: year },
+        (_, _, Some(day_of_year)) => Date::from_ordinal(year, day_of_year as u32)
+            .map_err(|_| "has an invalid day of the year")?,
+        _ => return Err("has no month and day or day of the year"),
+    };
+    if !is_date_valid(&date) || fields.month.is_some_and(|m| m != date.m) {
+        return Err("is not a valid date");
+    }
+    if fields.weekday.is_some_and(|wd| wd != date.get_weekday()) {
+        return Err("has a wrong weekday");
+    }
+    Ok(date)
+}
+
+// Returns the Time of the parsed fields: &Fields, or the reason why they are no valid time
+fn get_fields_time(fields: &Fields) -> Result<Time, &'static str> {
+    let hour = match (fields.hour, fields.hour_12, fields.pm) {
+        (Some(hour), None, _) if hour < 24 => hour,
+        (None, Some(hour), Some(pm)) if (1..=12).contains(&hour) => hour % 12 + pm as i32 * 12,
+        (None, None, _) | (None, Some(_), None) => return Err("has no hour"),
+        _ => return Err("has an invalid hour"),
+    };
+    let minute = fields.minute.ok_or("has no minute (%M)")?;
+    let time = Time {
+        h: hour,
+        m: minute,
+        s: fields.second.unwrap_or(0),
+    };
+    if time.m > 59 || time.s > 59 {
+        return Err("is not a valid time");
+    }
+    Ok(time)
+}
+
+// Returns the parse error of the text: &str with the format: &str and the reason: &str
+fn get_fields_error(text: &str, format: &str, reason: &str) -> DateTimeError {
+    DateTimeError::Parse(format!(
+        "'{}' with the format '{}' {}",
+        text, format, reason
+    ))
+}
+
+// Returns the number at the start of the text: &str with at most width: usize characters
+// (leading spaces or a sign if signed: bool is true) and the rest of the text
+fn get_number(text: &str, width: usize, signed: bool) -> Option<(i64, &str)> {
+    let mut chars = text.char_indices().peekable();
+    let mut used = 0;
+    while used < width - 1 && chars.next_if(|(_, c)| *c == ' ').is_some() {
+        used += 1;
+    }
+    let negative = signed && chars.next_if(|(_, c)| *c == '-').is_some();
+    if negative || signed && chars.next_if(|(_, c)| *c == '+').is_some() {
+        used += 1;
+    }
+    let mut value: i64 = 0;
+    let mut digits = 0;
+    while used < width {
+        let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit()) else {
+            break;
+        };
+        value = value * 10 + c.to_digit(10)? as i64;
+        digits += 1;
+        used += 1;
+    }
+    if digits == 0 {
+        return None;
+    }
+    let end = chars.peek().map_or(text.len(), |(idx, _)| *idx);
+    Some((if negative { -value } else { value }, &text[end..]))
+}
+
+// Returns the index of the longest of the names: &[&str] at the start of the text: &str (in
+// any case) and the rest of the text
+fn get_name<'a>(text: &'a str, names: &[&str]) -> Option<(i64, &'a str)> {
+    names
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| {
+            text.get(..name.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(name))
+        })
+        .max_by_key(|(_, name)| name.len())
+        .map(|(idx, name)| (idx as i64, &text[name.len()..]))
+}
+
+// Returns the timezone abbreviation (the ASCII letters) at the start of the text: &str and
+// the rest of the text, the value is unused
+fn get_zone_name(text: &str) -> Option<(i64, &str)> {
+    let len = text.bytes().take_while(|b| b.is_ascii_alphabetic()).count();
+    if len == 0 {
+        return None;
+    }
+    Some((0, &text[len..]))
+}
+
+// Returns 1 for p.m. or PM and 0 for a.m. or AM (in any case) at the start of the text: &str
+// and the rest of the text
+fn get_meridiem(text: &str) -> Option<(i64, &str)> {
+    ["a.m.", "p.m.", "am", "pm"]
+        .iter()
+        .enumerate()
+        .find(|(_, name)| {
+            text.get(..name.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(name))
+        })
+        .map(|(idx, name)| (idx as i64 % 2, &text[name.len()..]))
+}
@@ -0,0 +1,117 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use std::collections::BTreeMap;
+
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+
+// The ASCII characters of the sparkline from the lowest (no count) to the highest level
+const LEVELS: [char; 10] = [' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+/// ```render_histogram(&counts, width)``` renders the ```counts``` per date as an ASCII
+/// sparkline with at most ```width``` columns and an axis with the first and the last date
+/// (in the format ```%F```) below. If there are more days than columns, every column sums
+/// up a range of days. The days without a count are empty, the highest column is ```@```.
+/// Invalid dates are skipped.
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use date_and_time::date::*;
+/// use date_and_time::histogram::*;
+/// let mut logins = BTreeMap::new();
+/// for (day, count) in [(1, 4), (2, 8), (3, 1), (5, 8)] {
+///     logins.insert(Date::from(day, 6, 2024), count);
+/// }
+/// let chart = render_histogram(&logins, 40);
+/// assert_eq!(chart, "+@: @\n2024-06-01\n2024-06-05");
+/// ```
+pub fn render_histogram(counts: &BTreeMap<Date, u32>, width: usize) -> String {
+    render_histogram_with(counts, width, "%F")
+}
+
+/// ```render_histogram_with(&counts, width, date_format)``` renders the ```counts``` like
+/// ```render_histogram()```, the dates of the axis are formatted with the ```date_format```
+/// of ```Date::as_formated_string()```. The axis is one line if both dates fit below the
+/// sparkline.
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use date_and_time::date::*;
+/// use date_and_time::histogram::*;
+/// let mut sales = BTreeMap::new();
+/// for day in 1..=30 {
+///     sales.insert(Date::from(day, 6, 2024), day as u32);
+/// }
+/// let chart = render_histogram_with(&sales, 15, "%d %b");
+/// assert_eq!(chart, ".::--=++**#%%@@\n01 Jun   30 Jun");
+/// ```
+pub fn render_histogram_with(
+    counts: &BTreeMap<Date, u32>,
+    width: usize,
+    date_format: &str,
+) -> String {
+    // the invalid dates are skipped, they have no position on the axis
+    let mut valid = counts.iter().filter(|(date, _)| is_date_valid(date));
+    let (Some((first, _)), last) = (valid.next(), valid.next_back()) else {
+        return String::new();
+    };
+    let last = last.map_or(first, |(date, _)| date);
+    if width == 0 {
+        return String::new();
+    }
+    let days = first.diff_in_days(last) as usize + 1;
+    let columns = width.min(days);
+    let mut sums: Vec<u64> = vec![0; columns];
+    for (date, count) in counts.iter().filter(|(date, _)| is_date_valid(date)) {
+        let column = first.diff_in_days(date) as usize * columns / days;
+        sums[column] += *count as u64;
+    }
+    let max = sums.iter().copied().max().unwrap_or(0);
+    let top = (LEVELS.len() - 1) as u64;
+    let mut result: String = sums
+        .iter()
+        .map(|sum| match max {
+            0 => LEVELS[0],
+            _ => LEVELS[(sum * top).div_ceil(max) as usize],
+        })
+        .collect();
+    let start = first.as_formated_string(date_format);
+    let end = last.as_formated_string(date_format);
+    result.push('\n');
+    if first == last {
+        result.push_str(&start);
+    } else if start.chars().count() + end.chars().count() < columns {
+        let gap = columns - start.chars().count() - end.chars().count();
+        result.push_str(&format!("{}{}{}", start, " ".repeat(gap), end));
+    } else {
+        result.push_str(&format!("{}\n{}", start, end));
+    }
+    result
+}
+
+/// ```weekday_hour_counts(date_times)``` counts the ```date_times``` per weekday and hour
+/// of the day, e.g. for an activity heatmap. The rows are the weekdays from 0 = Sunday to
+/// 6 = Saturday (like ```Date::get_weekday()```) and the columns the hours from 0 to 23.
+///
+/// ```
+/// use date_and_time::datetime::*;
+/// use date_and_time::histogram::*;
+/// // Tuesday 2nd July 2024 at 09:05 and 09:55, Wednesday at 17:30
+/// let logins = [1_719_911_100, 1_719_914_100, 1_720_027_800].map(DateTime::from_timestamp);
+/// let counts = weekday_hour_counts(logins);
+/// assert_eq!(counts[2][9], 2);
+/// assert_eq!(counts[3][17], 1);
+/// assert_eq!(counts.iter().flatten().sum::<u32>(), 3);
+/// ```
+pub fn weekday_hour_counts<I>(date_times: I) -> [[u32; 24]; 7]
+where
+    I: IntoIterator<Item = DateTime>,
+{
+    let mut counts = [[0; 24]; 7];
+    for dt in date_times {
+        counts[dt.date.get_weekday() as usize % 7][dt.time.bucket(60) as usize] += 1;
+    }
+    counts
+}
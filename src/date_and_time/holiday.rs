@@ -0,0 +1,379 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::julian::*;
+
+/// The ```HolidayCalendar``` trait decides which dates are holidays. It is implemented for
+/// a list of dates (```[Date]``` and ```Vec<Date>```), so a calendar can be as simple as
+/// ```vec![Date::from(25, 12, 2024), Date::from(26, 12, 2024)]```.
+///
+/// A business day is a day from Monday to Friday which is not a holiday. The holidays by
+/// rules (fixed dates, weekdays of a month and days relative to Easter) are in the
+/// ```RuleCalendar```.
+///
+pub trait HolidayCalendar {
+    /// ```is_holiday(&date)``` returns true if the ```date``` is a holiday.
+    fn is_holiday(&self, date: &Date) -> bool;
+    /// ```is_business_day(&date)``` returns true if the ```date``` is a weekday from Monday
+    /// to Friday and not a holiday.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::holiday::*;
+    /// let holidays = vec![Date::from(25, 12, 2024), Date::from(26, 12, 2024)];
+    /// assert!(holidays.is_business_day(&Date::from(24, 12, 2024)));
+    /// assert!(!holidays.is_business_day(&Date::from(25, 12, 2024)));
+    /// // a Saturday
+    /// assert!(!holidays.is_business_day(&Date::from(28, 12, 2024)));
+    /// ```
+    fn is_business_day(&self, date: &Date) -> bool {
+        let wd = date.get_weekday();
+        wd != 0 && wd != 6 && !self.is_holiday(date)
+    }
+    /// ```holidays_in_year(year)``` returns the sorted holidays in the ```year```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::holiday::*;
+    /// let holidays = vec![Date::from(26, 12, 2024), Date::from(1, 1, 2025), Date::from(25, 12, 2024)];
+    /// assert_eq!(holidays.holidays_in_year(2024), [Date::from(25, 12, 2024), Date::from(26, 12, 2024)]);
+    /// ```
+    fn holidays_in_year(&self, year: i32) -> Vec<Date> {
+        let first = get_days_from_date(&Date {
+            d: 1,
+            m: 1,
+            y: year,
+        });
+        let last = get_days_from_date(&Date {
+            d: 31,
+            m: 12,
+            y: year,
+        });
+        (first..=last)
+            .map(get_date_from_days)
+            .filter(|date| self.is_holiday(date))
+            .collect()
+    }
+}
+
+impl HolidayCalendar for [Date] {
+    fn is_holiday(&self, date: &Date) -> bool {
+        self.contains(date)
+    }
+    fn holidays_in_year(&self, year: i32) -> Vec<Date> {
+        let mut dates: Vec<Date> = self.iter().filter(|date| date.y == year).copied().collect();
+        dates.sort();
+        dates.dedup();
+        dates
+    }
+}
+
+impl HolidayCalendar for Vec<Date> {
+    fn is_holiday(&self, date: &Date) -> bool {
+        self.as_slice().is_holiday(date)
+    }
+    fn holidays_in_year(&self, year: i32) -> Vec<Date> {
+        self.as_slice().holidays_in_year(year)
+    }
+}
+
+/// The rules of the holidays in a ```RuleCalendar```, the holidays are in every year.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HolidayRule {
+    /// A fixed date, e.g. the 25th December. A 29th February is only in the leap years.
+    Fixed { month: u8, day: u8 },
+    /// The ```n```th (1 to 5) ```weekday``` of the ```month```, e.g. the fourth Thursday
+    /// in November. In the years without this weekday there is no holiday.
+    NthWeekday { month: u8, n: u8, weekday: Weekday },
+    /// The last ```weekday``` of the ```month```, e.g. the last Monday in May.
+    LastWeekday { month: u8, weekday: Weekday },
+    /// The days after (or before, if negative) the Easter Sunday, e.g. -2 for Good Friday.
+    Easter(i16),
+    /// The days after (or before, if negative) the Orthodox Easter Sunday.
+    OrthodoxEaster(i16),
+}
+
+#[allow(dead_code)]
+impl HolidayRule {
+    /// ```date_in_year(year)``` returns the date of the holiday in the ```year```, or
+    /// ```None``` if there is no such date in the year.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::holiday::*;
+    /// let pentecost_monday = HolidayRule::Easter(50);
+    /// assert_eq!(pentecost_monday.date_in_year(2024), Some(Date::from(20, 5, 2024)));
+    /// let leap_day = HolidayRule::Fixed { month: 2, day: 29 };
+    /// assert_eq!(leap_day.date_in_year(2023), None);
+    /// ```
+    pub fn date_in_year(&self, year: i32) -> Option<Date> {
+        match *self {
+            HolidayRule::Fixed { month, day } => {
+                let date = Date {
+                    d: day,
+                    m: month,
+                    y: year,
+                };
+                is_date_valid(&date).then_some(date)
+            }
+            HolidayRule::NthWeekday { month, n, weekday } => {
+                nth_weekday_of_month(year, month, n, weekday)
+            }
+            HolidayRule::LastWeekday { month, weekday } => {
+                last_weekday_of_month(year, month, weekday)
+            }
+            HolidayRule::Easter(days) => get_date_after(easter_sunday(year), days),
+            HolidayRule::OrthodoxEaster(days) => get_date_after(orthodox_easter_sunday(year), days),
+        }
+    }
+}
+
+// Returns the date: Date moved by the days: i16, or None if it is in another year
+fn get_date_after(date: Date, days: i16) -> Option<Date> {
+    let moved = get_date_from_days(get_days_from_date(&date) + days as i64);
+    (moved.y == date.y).then_some(moved)
+}
+
+/// The ```RuleCalendar``` is a ```HolidayCalendar``` with named holidays from
+/// ```HolidayRule```s, it is build with ```new()``` and ```add()```. With the feature
+/// ```holiday-calendars``` the calendars ```germany()``` and ```us_federal()``` are
+/// available as examples.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::holiday::*;
+/// let mut calendar = RuleCalendar::new();
+/// calendar
+///     .add("New Year's Day", HolidayRule::Fixed { month: 1, day: 1 })
+///     .add("Good Friday", HolidayRule::Easter(-2))
+///     .add("Thanksgiving", HolidayRule::NthWeekday { month: 11, n: 4, weekday: Weekday::Thursday });
+/// assert!(calendar.is_holiday(&Date::from(29, 3, 2024)));
+/// assert_eq!(calendar.holiday_name(&Date::from(28, 11, 2024)), Some("Thanksgiving"));
+/// assert_eq!(calendar.holidays_in_year(2024).len(), 3);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RuleCalendar {
+    holidays: Vec<(String, HolidayRule)>,
+}
+
+#[allow(dead_code)]
+impl RuleCalendar {
+    /// ```new()``` creates a ```RuleCalendar``` without holidays.
+    ///
+    /// ```
+    /// use date_and_time::holiday::*;
+    /// assert!(RuleCalendar::new().holidays_in_year(2024).is_empty());
+    /// ```
+    pub fn new() -> RuleCalendar {
+        RuleCalendar {
+            holidays: Vec::new(),
+        }
+    }
+    /// ```add(name, rule)``` adds the holiday ```name``` with the ```rule``` to the
+    /// calendar.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::holiday::*;
+    /// let mut calendar = RuleCalendar::new();
+    /// calendar.add("Christmas Day", HolidayRule::Fixed { month: 12, day: 25 });
+    /// assert!(calendar.is_holiday(&Date::from(25, 12, 1999)));
+    /// ```
+    pub fn add(&mut self, name: &str, rule: HolidayRule) -> &mut RuleCalendar {
+        self.holidays.push((name.to_string(), rule));
+        self
+    }
+    /// ```holiday_name(&date)``` returns the name of the holiday at the ```date```, if two
+    /// holidays are at the same date the name of the first added one.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::holiday::*;
+    /// let mut calendar = RuleCalendar::new();
+    /// calendar.add("Easter Monday", HolidayRule::Easter(1));
+    /// assert_eq!(calendar.holiday_name(&Date::from(1, 4, 2024)), Some("Easter Monday"));
+    /// assert_eq!(calendar.holiday_name(&Date::from(2, 4, 2024)), None);
+    /// ```
+    pub fn holiday_name(&self, date: &Date) -> Option<&str> {
+        self.holidays
+            .iter()
+            .find(|(_, rule)| rule.date_in_year(date.y) == Some(*date))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+impl HolidayCalendar for RuleCalendar {
+    fn is_holiday(&self, date: &Date) -> bool {
+        self.holiday_name(date).is_some()
+    }
+    fn holidays_in_year(&self, year: i32) -> Vec<Date> {
+        let mut dates: Vec<Date> = self
+            .holidays
+            .iter()
+            .filter_map(|(_, rule)| rule.date_in_year(year))
+            .collect();
+        dates.sort();
+        dates.dedup();
+        dates
+    }
+}
+
+/// ```easter_sunday(year)``` returns the date of the Easter Sunday in the ```year``` of the
+/// Gregorian calendar (the anonymous Gregorian algorithm).
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::holiday::*;
+/// assert_eq!(easter_sunday(2024), Date::from(31, 3, 2024));
+/// assert_eq!(easter_sunday(2025), Date::from(20, 4, 2025));
+/// ```
+pub fn easter_sunday(year: i32) -> Date {
+    let y = year as i64;
+    let a = y.rem_euclid(19);
+    let (b, c) = (y.div_euclid(100), y.rem_euclid(100));
+    let (d, e) = (b.div_euclid(4), b.rem_euclid(4));
+    let f = (b + 8).div_euclid(25);
+    let g = (b - f + 1).div_euclid(3);
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    Date {
+        d: day as u8,
+        m: month as u8,
+        y: year,
+    }
+}
+
+/// ```orthodox_easter_sunday(year)``` returns the date of the Orthodox Easter Sunday in the
+/// ```year```, it is calculated in the Julian calendar (Meeus' Julian algorithm) and
+/// returned as a date of the Gregorian calendar, like all dates of the crate.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::holiday::*;
+/// assert_eq!(orthodox_easter_sunday(2024), Date::from(5, 5, 2024));
+/// // in some years both Easter Sundays are at the same date
+/// assert_eq!(orthodox_easter_sunday(2025), easter_sunday(2025));
+/// ```
+pub fn orthodox_easter_sunday(year: i32) -> Date {
+    let y = year as i64;
+    let (a, b, c) = (y.rem_euclid(4), y.rem_euclid(7), y.rem_euclid(19));
+    let d = (19 * c + 15) % 30;
+    let e = (2 * a + 4 * b - d + 34).rem_euclid(7);
+    let julian = JulianDate {
+        y: year,
+        m: ((d + e + 114) / 31) as u8,
+        d: ((d + e + 114) % 31 + 1) as u8,
+    };
+    julian.to_gregorian()
+}
+
+/// ```germany()``` returns the nationwide public holidays in Germany, the holidays of the
+/// federal states are not included.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::holiday::*;
+/// let holidays = germany().holidays_in_year(2024);
+/// assert_eq!(holidays.len(), 9);
+/// assert!(holidays.contains(&Date::from(3, 10, 2024)));
+/// ```
+#[cfg(feature = "holiday-calendars")]
+pub fn germany() -> RuleCalendar {
+    let mut calendar = RuleCalendar::new();
+    calendar
+        .add("Neujahr", HolidayRule::Fixed { month: 1, day: 1 })
+        .add("Karfreitag", HolidayRule::Easter(-2))
+        .add("Ostermontag", HolidayRule::Easter(1))
+        .add("Tag der Arbeit", HolidayRule::Fixed { month: 5, day: 1 })
+        .add("Christi Himmelfahrt", HolidayRule::Easter(39))
+        .add("Pfingstmontag", HolidayRule::Easter(50))
+        .add(
+            "Tag der Deutschen Einheit",
+            HolidayRule::Fixed { month: 10, day: 3 },
+        )
+        .add(
+            "1. Weihnachtstag",
+            HolidayRule::Fixed { month: 12, day: 25 },
+        )
+        .add(
+            "2. Weihnachtstag",
+            HolidayRule::Fixed { month: 12, day: 26 },
+        );
+    calendar
+}
+
+/// ```us_federal()``` returns the federal holidays of the United States. The holidays are
+/// at their legal dates, the observed days (e.g. the Friday before a holiday on Saturday)
+/// are not included.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::holiday::*;
+/// let calendar = us_federal();
+/// assert_eq!(calendar.holiday_name(&Date::from(27, 5, 2024)), Some("Memorial Day"));
+/// assert_eq!(calendar.holidays_in_year(2024).len(), 11);
+/// ```
+#[cfg(feature = "holiday-calendars")]
+pub fn us_federal() -> RuleCalendar {
+    let mut calendar = RuleCalendar::new();
+    calendar
+        .add("New Year's Day", HolidayRule::Fixed { month: 1, day: 1 })
+        .add(
+            "Martin Luther King Jr. Day",
+            HolidayRule::NthWeekday {
+                month: 1,
+                n: 3,
+                weekday: Weekday::Monday,
+            },
+        )
+        .add(
+            "Washington's Birthday",
+            HolidayRule::NthWeekday {
+                month: 2,
+                n: 3,
+                weekday: Weekday::Monday,
+            },
+        )
+        .add(
+            "Memorial Day",
+            HolidayRule::LastWeekday {
+                month: 5,
+                weekday: Weekday::Monday,
+            },
+        )
+        .add("Juneteenth", HolidayRule::Fixed { month: 6, day: 19 })
+        .add("Independence Day", HolidayRule::Fixed { month: 7, day: 4 })
+        .add(
+            "Labor Day",
+            HolidayRule::NthWeekday {
+                month: 9,
+                n: 1,
+                weekday: Weekday::Monday,
+            },
+        )
+        .add(
+            "Columbus Day",
+            HolidayRule::NthWeekday {
+                month: 10,
+                n: 2,
+                weekday: Weekday::Monday,
+            },
+        )
+        .add("Veterans Day", HolidayRule::Fixed { month: 11, day: 11 })
+        .add(
+            "Thanksgiving Day",
+            HolidayRule::NthWeekday {
+                month: 11,
+                n: 4,
+                weekday: Weekday::Thursday,
+            },
+        )
+        .add("Christmas Day", HolidayRule::Fixed { month: 12, day: 25 });
+    calendar
+}
@@ -0,0 +1,408 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::time::*;
+
+/// The ```IcalValue``` enum is a DATE or DATE-TIME value of the iCalendar format (RFC
+/// 5545), e.g. of the ```DTSTART``` and ```DTEND``` properties of an event. It can be
+/// build with the functions ```parse()``` and ```parse_property()``` or directly. The
+/// fractions of a second are not a part of the iCalendar format, they are dropped.
+///
+/// ```
+/// use date_and_time::datetime::*;
+/// use date_and_time::ical::*;
+/// let start = IcalValue::Utc(DateTime::from_timestamp(1_719_081_000));
+/// assert_eq!(start.as_string(), "20240622T183000Z");
+/// assert_eq!(start.dtstart(), "DTSTART:20240622T183000Z");
+/// assert_eq!(IcalValue::parse("20240622T183000Z"), Ok(start));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum IcalValue {
+    /// A whole day, e.g. ```20240622```.
+    Date(Date),
+    /// A date and time without a timezone, it is the same local time everywhere, e.g.
+    /// ```20240622T183000```.
+    Floating(DateTime),
+    /// A date and time in UTC, e.g. ```20240622T183000Z```.
+    Utc(DateTime),
+    /// A local date and time in the timezone with the identifier ```tzid``` (the
+    /// ```TZID``` parameter), e.g. ```DTSTART;TZID=Europe/Berlin:20240622T183000```.
+    Zoned { value: DateTime, tzid: String },
+}
+
+#[allow(dead_code)]
+impl IcalValue {
+    /// ```parse(text)``` parses a DATE (```YYYYMMDD```), a floating DATE-TIME
+    /// (```YYYYMMDDThhmmss```) or a DATE-TIME in UTC (```YYYYMMDDThhmmssZ```). A timezone
+    /// is a parameter of the property, see ```parse_property()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::ical::*;
+    /// assert_eq!(IcalValue::parse("20240622"), Ok(IcalValue::Date(Date::from(22, 6, 2024))));
+    /// assert!(matches!(IcalValue::parse("20240622T183000"), Ok(IcalValue::Floating(_))));
+    /// assert!(IcalValue::parse("2024-06-22T18:30:00Z").is_err());
+    /// assert!(IcalValue::parse("20240622T243000Z").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<IcalValue, DateTimeError> {
+        let text = text.trim();
+        let Some((date, time)) = text.split_once('T') else {
+            return Ok(IcalValue::Date(get_ical_date(text)?));
+        };
+        let (time, utc) = match time.strip_suffix('Z') {
+            Some(time) => (time, true),
+            None => (time, false),
+        };
+        let value = DateTime::from(get_ical_date(date)?, get_ical_time(time)?);
+        Ok(match utc {
+            true => IcalValue::Utc(value),
+            false => IcalValue::Floating(value),
+        })
+    }
+    /// ```parse_property(line)``` parses a (unfolded) property line with a date or date and
+    /// time, e.g. ```DTSTART;TZID=Europe/Berlin:20240622T183000```, and returns the name
+    /// of the property with its value. The parameters ```TZID``` and ```VALUE``` (```DATE```
+    /// or ```DATE-TIME```) are checked, other parameters are ignored.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::ical::*;
+    /// let (name, value) = IcalValue::parse_property("DTSTART;VALUE=DATE:20240622").unwrap();
+    /// assert_eq!(name, "DTSTART");
+    /// assert_eq!(value, IcalValue::Date(Date::from(22, 6, 2024)));
+    /// let (_, value) = IcalValue::parse_property("DTEND;TZID=\"Europe/Berlin\":20240622T200000").unwrap();
+    /// assert_eq!(value.tzid(), Some("Europe/Berlin"));
+    /// assert!(IcalValue::parse_property("DTSTART;VALUE=DATE:20240622T200000").is_err());
+    /// ```
+    pub fn parse_property(line: &str) -> Result<(String, IcalValue), DateTimeError> {
+        let line = line.trim();
+        let error = |reason: &str| DateTimeError::Parse(format!("'{}' {}", line, reason));
+        // the colon and the semicolons can be inside a quoted parameter value
+        let colon = find_unquoted(line, ':').ok_or_else(|| error("has no value"))?;
+        let mut parts = split_unquoted(&line[..colon], ';');
+        let name = parts.remove(0).to_uppercase();
+        if name.is_empty() {
+            return Err(error("has no property name"));
+        }
+        let mut tzid: Option<String> = None;
+        let mut value_type: Option<String> = None;
+        for parameter in parts {
+            let (key, parameter_value) = parameter
+                .split_once('=')
+                .ok_or_else(|| error("has a parameter without a value"))?;
+            let parameter_value = parameter_value.trim_matches('"');
+            match key.to_uppercase().as_str() {
+                "TZID" => tzid = Some(String::from(parameter_value)),
+                "VALUE" => value_type = Some(parameter_value.to_uppercase()),
+                _ => {}
+            }
+        }
+        let value = IcalValue::parse(&line[colon + 1..])?;
+        let is_date = matches!(value, IcalValue::Date(_));
+        let value = match (value, value_type.as_deref(), tzid) {
+            (_, Some(other), _) if other != "DATE" && other != "DATE-TIME" => {
+                return Err(error("has an unsupported VALUE parameter"));
+            }
+            (_, Some(other), _) if is_date != (other == "DATE") => {
+                return Err(error("doesn't match its VALUE parameter"));
+            }
+            (IcalValue::Floating(value), _, Some(tzid)) => IcalValue::Zoned { value, tzid },
+            (value, _, None) => value,
+            _ => return Err(error("has a TZID parameter with a date or UTC value")),
+        };
+        Ok((name, value))
+    }
+    /// ```as_string()``` gets the value in the iCalendar format, without the timezone
+    /// of a ```Zoned``` value.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::ical::*;
+    /// assert_eq!(IcalValue::Date(Date::from(1, 7, 2024)).as_string(), "20240701");
+    /// ```
+    pub fn as_string(&self) -> String {
+        match self {
+            IcalValue::Date(date) => get_ical_date_string(date),
+            IcalValue::Floating(value) | IcalValue::Zoned { value, .. } => {
+                get_ical_date_time_string(value)
+            }
+            IcalValue::Utc(value) => format!("{}Z", get_ical_date_time_string(value)),
+        }
+    }
+    /// ```as_property(name)``` gets the value as a property line with the ```name```,
+    /// including the ```VALUE=DATE``` or ```TZID``` parameter.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::ical::*;
+    /// let date = IcalValue::Date(Date::from(1, 7, 2024));
+    /// assert_eq!(date.as_property("RDATE"), "RDATE;VALUE=DATE:20240701");
+    /// ```
+    pub fn as_property(&self, name: &str) -> String {
+        match self {
+            IcalValue::Date(_) => format!("{};VALUE=DATE:{}", name, self.as_string()),
+            IcalValue::Zoned { tzid, .. } if tzid.contains([';', ':', ',']) => {
+                format!("{};TZID=\"{}\":{}", name, tzid, self.as_string())
+            }
+            IcalValue::Zoned { tzid, .. } => {
+                format!("{};TZID={}:{}", name, tzid, self.as_string())
+            }
+            _ => format!("{}:{}", name, self.as_string()),
+        }
+    }
+    /// ```dtstart()``` gets the value as the ```DTSTART``` property of an event.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::ical::*;
+    /// let start = IcalValue::Zoned {
+    ///     value: DateTime::from_timestamp(1_719_081_000),
+    ///     tzid: String::from("Europe/Berlin"),
+    /// };
+    /// assert_eq!(start.dtstart(), "DTSTART;TZID=Europe/Berlin:20240622T183000");
+    /// ```
+    pub fn dtstart(&self) -> String {
+        self.as_property("DTSTART")
+    }
+    /// ```dtend()``` gets the value as the ```DTEND``` property of an event, the end is
+    /// not a part of the event (e.g. the day after an all-day event).
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::duration::*;
+    /// use date_and_time::ical::*;
+    /// let start = IcalValue::Date(Date::from(30, 6, 2024));
+    /// let end = start.add_duration(&Duration::from_seconds(86_400));
+    /// assert_eq!(end.dtend(), "DTEND;VALUE=DATE:20240701");
+    /// ```
+    pub fn dtend(&self) -> String {
+        self.as_property("DTEND")
+    }
+    /// ```add_duration(&duration)``` returns the value moved by the ```duration``` with the
+    /// same kind and timezone, a ```Date``` is moved by the whole days of the
+    /// ```duration```. The time of a ```Zoned``` value is moved as a local time.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::duration::*;
+    /// use date_and_time::ical::*;
+    /// let start = IcalValue::Utc(DateTime::from_timestamp(1_719_081_000));
+    /// let end = start.add_duration(&Duration::parse_ical("PT1H30M").unwrap());
+    /// assert_eq!(end.as_string(), "20240622T200000Z");
+    /// ```
+    pub fn add_duration(&self, duration: &Duration) -> IcalValue {
+        match self {
+            IcalValue::Date(date) => {
+                let days = duration.as_seconds().div_euclid(86_400);
+                IcalValue::Date(get_date_from_days(get_days_from_date(date) + days))
+            }
+            IcalValue::Floating(value) => IcalValue::Floating(value.add_duration(duration)),
+            IcalValue::Utc(value) => IcalValue::Utc(value.add_duration(duration)),
+            IcalValue::Zoned { value, tzid } => IcalValue::Zoned {
+                value: value.add_duration(duration),
+                tzid: tzid.clone(),
+            },
+        }
+    }
+    /// ```tzid()``` returns the timezone identifier of a ```Zoned``` value.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::ical::*;
+    /// assert_eq!(IcalValue::Date(Date::from(1, 7, 2024)).tzid(), None);
+    /// ```
+    pub fn tzid(&self) -> Option<&str> {
+        match self {
+            IcalValue::Zoned { tzid, .. } => Some(tzid),
+            _ => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Duration {
+    /// ```parse_ical(text)``` parses an iCalendar DURATION (RFC 5545), e.g. ```PT1H30M```,
+    /// ```P1D```, ```P2W``` or ```-PT15M```. A day has 24 hours.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::parse_ical("P1DT2H").unwrap().as_seconds(), 93_600);
+    /// assert_eq!(Duration::parse_ical("-PT15M").unwrap().as_seconds(), -900);
+    /// assert!(Duration::parse_ical("PT").is_err());
+    /// assert!(Duration::parse_ical("P1H").is_err());
+    /// ```
+    pub fn parse_ical(text: &str) -> Result<Duration, DateTimeError> {
+        let text = text.trim();
+        let error = || DateTimeError::Parse(format!("'{}' is not an iCalendar duration", text));
+        let (negative, body) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let body = body.strip_prefix('P').ok_or_else(error)?;
+        let (date_part, time_part) = match body.split_once('T') {
+            Some((date_part, time_part)) => (date_part, Some(time_part)),
+            None => (body, None),
+        };
+        let date_units: &[(char, i64)] = match time_part {
+            None if date_part.ends_with('W') => &[('W', 604_800)],
+            _ => &[('D', 86_400)],
+        };
+        let mut seconds = get_ical_units(date_part, date_units).ok_or_else(error)?;
+        if let Some(time_part) = time_part {
+            let time_units = [('H', 3_600), ('M', 60), ('S', 1)];
+            match get_ical_units(time_part, &time_units) {
+                Some(time_seconds) if !time_part.is_empty() => {
+                    seconds = seconds.checked_add(time_seconds).ok_or_else(error)?;
+                }
+                _ => return Err(error()),
+            }
+        } else if date_part.is_empty() {
+            return Err(error());
+        }
+        Ok(Duration::from_seconds(if negative {
+            -seconds
+        } else {
+            seconds
+        }))
+    }
+    /// ```as_ical_string()``` gets the ```Duration``` as an iCalendar DURATION, whole weeks
+    /// are written in weeks (```P2W```), a zero duration is ```PT0S```. The fractions of a
+    /// second are dropped.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// assert_eq!(Duration::from_seconds(5_400).as_ical_string(), "PT1H30M");
+    /// assert_eq!(Duration::from_seconds(-93_605).as_ical_string(), "-P1DT2H0M5S");
+    /// assert_eq!(Duration::from_seconds(1_209_600).as_ical_string(), "P2W");
+    /// ```
+    pub fn as_ical_string(&self) -> String {
+        let total = self.as_seconds();
+        let sign = if total < 0 { "-" } else { "" };
+        let seconds = total.unsigned_abs();
+        if seconds == 0 {
+            return String::from("PT0S");
+        }
+        if seconds.is_multiple_of(604_800) {
+            return format!("{}P{}W", sign, seconds / 604_800);
+        }
+        let mut s = format!("{}P", sign);
+        if seconds >= 86_400 {
+            s.push_str(&format!("{}D", seconds / 86_400));
+        }
+        let (h, m, sec) = (seconds % 86_400 / 3_600, seconds % 3_600 / 60, seconds % 60);
+        if h + m + sec > 0 {
+            s.push('T');
+        }
+        // the grammar has no gaps between the hours, minutes and seconds
+        if h > 0 {
+            s.push_str(&format!("{}H", h));
+        }
+        if m > 0 || h > 0 && sec > 0 {
+            s.push_str(&format!("{}M", m));
+        }
+        if sec > 0 {
+            s.push_str(&format!("{}S", sec));
+        }
+        s
+    }
+}
+
+// Returns the date of the text: &str in the format YYYYMMDD
+fn get_ical_date(text: &str) -> Result<Date, DateTimeError> {
+    let error = || DateTimeError::Parse(format!("'{}' is not an iCalendar date", text));
+    if text.len() != 8 || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(error());
+    }
+    let date = Date {
+        y: text[..4].parse().map_err(|_| error())?,
+        m: text[4..6].parse().map_err(|_| error())?,
+        d: text[6..].parse().map_err(|_| error())?,
+    };
+    if !is_date_valid(&date) {
+        return Err(error());
+    }
+    Ok(date)
+}
+
+// Returns the time of the text: &str in the format hhmmss
+fn get_ical_time(text: &str) -> Result<Time, DateTimeError> {
+    let error = || DateTimeError::Parse(format!("'{}' is not an iCalendar time", text));
+    if text.len() != 6 || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(error());
+    }
+    let number = |range: std::ops::Range<usize>| text[range].parse::<i8>().map_err(|_| error());
+    let (h, m, s) = (number(0..2)?, number(2..4)?, number(4..6)?);
+    if h > 23 || m > 59 || s > 59 {
+        return Err(error());
+    }
+    Ok(Time::from(h as i32, m, s))
+}
+
+// Returns the date: &Date in the format YYYYMMDD
+fn get_ical_date_string(date: &Date) -> String {
+    format!("{:04}{:02}{:02}", date.y, date.m, date.d)
+}
+
+// Returns the date and time: &DateTime in the format YYYYMMDDThhmmss
+fn get_ical_date_time_string(value: &DateTime) -> String {
+    format!(
+        "{}T{:02}{:02}{:02}",
+        get_ical_date_string(&value.date),
+        value.time.h,
+        value.time.m,
+        value.time.s
+    )
+}
+
+// Returns the byte position of the first separator: char in the text: &str which is not
+// inside double quotes
+fn find_unquoted(text: &str, separator: char) -> Option<usize> {
+    let mut quoted = false;
+    text.char_indices()
+        .find(|&(_, c)| {
+            quoted ^= c == '"';
+            c == separator && !quoted
+        })
+        .map(|(idx, _)| idx)
+}
+
+// Returns the parts of the text: &str split at every separator: char which is not inside
+// double quotes, there is at least one part
+fn split_unquoted(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(pos) = find_unquoted(rest, separator) {
+        parts.push(&rest[..pos]);
+        rest = &rest[pos + separator.len_utf8()..];
+    }
+    parts.push(rest);
+    parts
+}
+
+// Returns the seconds of the text: &str with numbers and the units: &[(char, i64)] (the
+// letter and its seconds) in this order, or None if it doesn't match
+fn get_ical_units(text: &str, units: &[(char, i64)]) -> Option<i64> {
+    let mut seconds: i64 = 0;
+    let mut rest = text;
+    let mut next_unit = 0;
+    while !rest.is_empty() {
+        let digits = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+        let letter = rest[digits..].chars().next()?;
+        let position = units[next_unit..]
+            .iter()
+            .position(|(unit, _)| *unit == letter)?;
+        if digits == 0 {
+            return None;
+        }
+        let value: i64 = rest[..digits].parse().ok()?;
+        seconds = seconds.checked_add(value.checked_mul(units[next_unit + position].1)?)?;
+        next_unit += position + 1;
+        rest = &rest[digits + 1..];
+    }
+    Some(seconds)
+}
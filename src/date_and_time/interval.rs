@@ -0,0 +1,179 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::time::*;
+
+/// The DateInterval structure is a span of whole days from the ```start``` to the ```end```
+/// date, both dates are included. It can be build with the function ```new()```, which
+/// checks that both dates are valid and in order.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::interval::*;
+/// let vacation = DateInterval::new(Date::from(22, 7, 2024), Date::from(2, 8, 2024)).unwrap();
+/// assert_eq!(vacation.days(), 12);
+/// assert!(vacation.contains(&Date::from(1, 8, 2024)));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DateInterval {
+    pub start: Date,
+    pub end: Date,
+}
+
+#[allow(dead_code)]
+impl DateInterval {
+    /// ```new(start, end)``` creates a ```DateInterval``` from the ```start``` to the
+    /// ```end``` date (included). It returns an error if a date is invalid or the end is
+    /// before the start.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// let day = Date::from(22, 6, 2024);
+    /// assert_eq!(DateInterval::new(day, day).unwrap().days(), 1);
+    /// assert!(DateInterval::new(day, Date::from(21, 6, 2024)).is_err());
+    /// ```
+    pub fn new(start: Date, end: Date) -> Result<DateInterval, DateTimeError> {
+        if !is_date_valid(&start) || !is_date_valid(&end) {
+            return Err(DateTimeError::OutOfRange(String::from(
+                "the interval has an invalid date",
+            )));
+        }
+        if end < start {
+            return Err(DateTimeError::OutOfRange(format!(
+                "the end {} is before the start {}",
+                end.as_string(),
+                start.as_string()
+            )));
+        }
+        Ok(DateInterval { start, end })
+    }
+    /// ```days()``` returns the number of days of the interval, the start and the end date
+    /// are counted.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// let february = DateInterval::new(Date::from(1, 2, 2024), Date::from(29, 2, 2024)).unwrap();
+    /// assert_eq!(february.days(), 29);
+    /// ```
+    pub fn days(&self) -> i64 {
+        self.start.diff_in_days(&self.end) + 1
+    }
+    /// ```contains(&date)``` returns true if the ```date``` is in the interval.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// let week = DateInterval::new(Date::from(17, 6, 2024), Date::from(23, 6, 2024)).unwrap();
+    /// assert!(week.contains(&Date::from(23, 6, 2024)));
+    /// assert!(!week.contains(&Date::from(24, 6, 2024)));
+    /// ```
+    pub fn contains(&self, date: &Date) -> bool {
+        self.start <= *date && *date <= self.end
+    }
+    /// ```intersection(&other)``` returns the days which are in both intervals, or
+    /// ```None``` if the intervals don't overlap.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// let june = DateInterval::new(Date::from(1, 6, 2024), Date::from(30, 6, 2024)).unwrap();
+    /// let trip = DateInterval::new(Date::from(28, 6, 2024), Date::from(3, 7, 2024)).unwrap();
+    /// assert_eq!(june.intersection(&trip).unwrap().days(), 3);
+    /// ```
+    pub fn intersection(&self, other: &DateInterval) -> Option<DateInterval> {
+        let start = if self.start < other.start {
+            other.start
+        } else {
+            self.start
+        };
+        let end = if self.end < other.end {
+            self.end
+        } else {
+            other.end
+        };
+        if end < start {
+            return None;
+        }
+        Some(DateInterval { start, end })
+    }
+    /// ```union_span(&other)``` returns the interval from the earlier start to the later
+    /// end of both intervals, the days between two separate intervals are included.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// let a = DateInterval::new(Date::from(1, 6, 2024), Date::from(5, 6, 2024)).unwrap();
+    /// let b = DateInterval::new(Date::from(10, 6, 2024), Date::from(12, 6, 2024)).unwrap();
+    /// assert_eq!(a.union_span(&b).days(), 12);
+    /// ```
+    pub fn union_span(&self, other: &DateInterval) -> DateInterval {
+        DateInterval {
+            start: if other.start < self.start {
+                other.start
+            } else {
+                self.start
+            },
+            end: if self.end < other.end {
+                other.end
+            } else {
+                self.end
+            },
+        }
+    }
+    /// ```progress_at(&now)``` returns the elapsed part of the interval at the date and
+    /// time ```now``` from 0.0 (before the start date) to 1.0 (after the end date), e.g. for
+    /// a progress bar of a sprint. The interval begins at 0:00 of the start date and ends
+    /// at 24:00 of the end date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::interval::*;
+    /// use date_and_time::time::*;
+    /// let sprint = DateInterval::new(Date::from(3, 6, 2024), Date::from(16, 6, 2024)).unwrap();
+    /// let now = DateTime::from(Date::from(10, 6, 2024), Time::from(0, 0, 0));
+    /// assert_eq!(sprint.progress_at(&now), 0.5);
+    /// ```
+    pub fn progress_at(&self, now: &DateTime) -> f64 {
+        let (start, end) = self.get_bounds();
+        let elapsed = start.diff(now).as_nanoseconds() as f64;
+        let length = start.diff(&end).as_nanoseconds() as f64;
+        (elapsed / length).clamp(0.0, 1.0)
+    }
+    /// ```remaining(&now)``` returns the duration from the date and time ```now``` until the
+    /// end of the interval (24:00 of the end date), or ```Duration::ZERO``` after the end.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::interval::*;
+    /// use date_and_time::time::*;
+    /// let trial = DateInterval::new(Date::from(1, 6, 2024), Date::from(14, 6, 2024)).unwrap();
+    /// let now = DateTime::from(Date::from(14, 6, 2024), Time::from(18, 0, 0));
+    /// assert_eq!(trial.remaining(&now).as_seconds(), 6 * 3_600);
+    /// ```
+    pub fn remaining(&self, now: &DateTime) -> Duration {
+        let (_, end) = self.get_bounds();
+        let remaining = now.diff(&end);
+        if remaining.is_negative() {
+            return Duration::ZERO;
+        }
+        remaining
+    }
+    // Returns the start: DateTime and the end: DateTime (after the last day) of the interval
+    fn get_bounds(&self) -> (DateTime, DateTime) {
+        let midnight = Time::from(0, 0, 0);
+        let after_end = get_date_from_days(get_days_from_date(&self.end) + 1);
+        (
+            DateTime::from(self.start, midnight),
+            DateTime::from(after_end, midnight),
+        )
+    }
+}
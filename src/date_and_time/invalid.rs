@@ -0,0 +1,54 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use std::sync::RwLock;
+
+// The text of the invalid dates and times in the as_string() functions, None prints the
+// fields of the invalid value (e.g. 0000-00-00)
+static INVALID_DISPLAY: RwLock<Option<String>> = RwLock::new(None);
+
+/// ```set_invalid_display(text)``` sets the ```text``` which the functions ```as_string()```
+/// and ```as_formated_string()``` of ```Date```, ```Time``` and ```DateTime``` return for
+/// an invalid value (e.g. ```Date{d: 0, m: 0, y: 0}```), instead of printing its fields.
+/// The setting is for the whole program, so it should be set once at the start. To check a
+/// value use ```Date::as_option()``` or ```Time::as_option()```.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::invalid::*;
+/// let invalid = Date::from(31, 2, 2024);
+/// assert_eq!(invalid.as_string(), "0000-00-00");
+/// set_invalid_display("n/a");
+/// assert_eq!(invalid.as_string(), "n/a");
+/// assert_eq!(invalid.as_formated_string("%d.%m.%Y"), "n/a");
+/// assert_eq!(Date::from(1, 2, 2024).as_string(), "2024-02-01");
+/// clear_invalid_display();
+/// assert_eq!(invalid.as_string(), "0000-00-00");
+/// ```
+pub fn set_invalid_display(text: &str) {
+    let mut display = INVALID_DISPLAY.write().unwrap_or_else(|e| e.into_inner());
+    *display = Some(text.to_string());
+}
+
+/// ```clear_invalid_display()``` removes the text of ```set_invalid_display()```, the
+/// invalid values are printed with their fields again.
+///
+/// ```
+/// use date_and_time::invalid::*;
+/// use date_and_time::time::*;
+/// set_invalid_display("--:--");
+/// clear_invalid_display();
+/// assert_eq!(Time { h: 0, m: -1, s: -1 }.as_string(), "00:-1:-1");
+/// ```
+pub fn clear_invalid_display() {
+    let mut display = INVALID_DISPLAY.write().unwrap_or_else(|e| e.into_inner());
+    *display = None;
+}
+
+// Returns the text of set_invalid_display(), or None if the invalid values are printed with
+// their fields
+pub(crate) fn get_invalid_display() -> Option<String> {
+    let display = INVALID_DISPLAY.read().unwrap_or_else(|e| e.into_inner());
+    display.clone()
+}
@@ -0,0 +1,405 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// The formats are described in RFC 3339 (section 5.6) and in ISO 8601-1:2019, the
+// profiles accept years with four digits or, if enabled, the expanded years with a sign
+// and an agreed number of digits (ISO 8601-1:2019, section 4.3.2), but no leap seconds.
+//
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::offset::*;
+use crate::date_and_time::time::*;
+
+/// The conformance level of the ```IsoParser```, every profile accepts all shapes of the
+/// profiles before it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IsoProfile {
+    /// Only RFC 3339: ```2024-06-22T13:45:30.5+02:00```, the seconds and the offset are
+    /// required.
+    Rfc3339,
+    /// The ISO 8601 extended format with calendar dates: the seconds and the offset are
+    /// optional, the offset can be written as ```+02```, a comma can be the decimal sign.
+    Extended,
+    /// The full ISO 8601: additionally ordinal dates (```2024-174```), week dates
+    /// (```2024-W25-6```) and the basic format (```20240622T134530+0200```).
+    Full,
+}
+
+/// The IsoDateTime structure is the result of the ```IsoParser```, the ```date_time``` is
+/// the local date and time as written in the text and the ```offset``` is the UTC offset
+/// of the text, if it had one.
+///
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct IsoDateTime {
+    pub date_time: DateTime,
+    pub offset: Option<UtcOffset>,
+}
+
+#[allow(dead_code)]
+impl IsoDateTime {
+    /// ```as_utc()``` returns the date and time in UTC, without an offset the date and time
+    /// is returned unchanged.
+    ///
+    /// ```
+    /// use date_and_time::iso8601::*;
+    /// let parser = IsoParser::new(IsoProfile::Rfc3339);
+    /// let parsed = parser.parse("2024-06-22T13:45:30+02:00").unwrap();
+    /// assert_eq!(parsed.as_utc().as_string(), "2024-06-22T11:45:30");
+    /// ```
+    pub fn as_utc(&self) -> DateTime {
+        match self.offset {
+            Some(offset) => self
+                .date_time
+                .sub_duration(&Duration::from_seconds(offset.as_seconds() as i64)),
+            None => self.date_time,
+        }
+    }
+    /// ```split()``` returns the local date, the local time and the UTC offset of the text,
+    /// like ```DateTime::split()```.
+    ///
+    /// ```
+    /// use date_and_time::iso8601::*;
+    /// let parser = IsoParser::new(IsoProfile::Rfc3339);
+    /// let (date, time, offset) = parser.parse("2024-06-22T13:45:30+02:00").unwrap().split();
+    /// assert_eq!(date.as_string(), "2024-06-22");
+    /// assert_eq!(time.as_string(), "13:45:30");
+    /// assert_eq!(offset.unwrap().as_seconds(), 7_200);
+    /// ```
+    pub fn split(&self) -> (Date, Time, Option<UtcOffset>) {
+        (self.date_time.date, self.date_time.time, self.offset)
+    }
+}
+
+/// The IsoParser structure parses dates and date and times in the shapes of its
+/// ```profile```, e.g. a strict API server can accept only RFC 3339 while a command line
+/// tool accepts all ISO 8601 shapes.
+///
+/// ```
+/// use date_and_time::iso8601::*;
+/// let strict = IsoParser::new(IsoProfile::Rfc3339);
+/// let full = IsoParser::new(IsoProfile::Full);
+/// assert!(strict.parse("2024-W25-6T12:00Z").is_err());
+/// assert_eq!(full.parse("2024-W25-6T12:00Z").unwrap().date_time.as_string(), "2024-06-22T12:00:00");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IsoParser {
+    pub profile: IsoProfile,
+    pub expanded_years: Option<u8>,
+}
+
+#[allow(dead_code)]
+impl IsoParser {
+    /// ```new(profile)``` creates an ```IsoParser``` for the ```profile```.
+    ///
+    /// ```
+    /// use date_and_time::iso8601::*;
+    /// assert_eq!(IsoParser::new(IsoProfile::Extended).profile, IsoProfile::Extended);
+    /// ```
+    pub fn new(profile: IsoProfile) -> IsoParser {
+        IsoParser {
+            profile,
+            expanded_years: None,
+        }
+    }
+    /// ```with_expanded_years(digits)``` returns the ```IsoParser``` for the expanded year
+    /// representation: every year has a required sign and exactly the number of
+    /// ```digits``` (4 to 9), e.g. ```+0123456-01-01``` with 7 digits or ```-0001-12-31```
+    /// with 4 digits. The parser and the sender of the text must agree on the digits.
+    ///
+    /// ```
+    /// use date_and_time::iso8601::*;
+    /// let parser = IsoParser::new(IsoProfile::Extended).with_expanded_years(7);
+    /// let date = parser.parse_date("+0123456-01-01").unwrap();
+    /// assert_eq!(date.y, 123_456);
+    /// assert_eq!(parser.format_date(&date).unwrap(), "+0123456-01-01");
+    /// assert!(parser.parse_date("0123456-01-01").is_err());
+    /// ```
+    pub fn with_expanded_years(self, digits: u8) -> IsoParser {
+        IsoParser {
+            expanded_years: Some(digits.clamp(4, 9)),
+            ..self
+        }
+    }
+    /// ```parse_date(text)``` parses a date without time. All profiles accept the calendar
+    /// date ```YYYY-MM-DD```, the ```IsoProfile::Full``` accepts additionally
+    /// ```YYYY-DDD```, ```YYYY-Www-D```, ```YYYYMMDD```, ```YYYYDDD``` and ```YYYYWwwD```.
+    ///
+    /// ```
+    /// use date_and_time::iso8601::*;
+    /// let parser = IsoParser::new(IsoProfile::Full);
+    /// assert_eq!(parser.parse_date("2024-174").unwrap().as_string(), "2024-06-22");
+    /// assert_eq!(parser.parse_date("2020W537").unwrap().as_string(), "2021-01-03");
+    /// assert!(parser.parse_date("2024-02-30").is_err());
+    /// ```
+    pub fn parse_date(&self, text: &str) -> Result<Date, DateTimeError> {
+        let text = text.trim();
+        enter_trace_span!("IsoParser::parse_date", text, profile = ?self.profile);
+        let (year, rest) = self.split_year(text)?;
+        let b = rest.as_bytes();
+        let full = self.profile == IsoProfile::Full;
+        let (shape, date) = if b.len() == 6 && b[0] == b'-' && b[3] == b'-' && b[1] != b'W' {
+            let (month, day) = (digits(rest, 1, 2)?, digits(rest, 4, 2)?);
+            ("date", Date::from(day as u8, month as u8, year))
+        } else if full && b.len() == 6 && rest.starts_with("-W") && b[4] == b'-' {
+            let (week, weekday) = (digits(rest, 2, 2)?, digits(rest, 5, 1)?);
+            ("week date", week_date(year, week, weekday))
+        } else if full && b.len() == 4 && b[0] == b'W' {
+            let (week, weekday) = (digits(rest, 1, 2)?, digits(rest, 3, 1)?);
+            ("week date", week_date(year, week, weekday))
+        } else if full && b.len() == 4 && b[0] == b'-' {
+            ("ordinal date", ordinal_date(year, digits(rest, 1, 3)?))
+        } else if full && b.len() == 4 {
+            let (month, day) = (digits(rest, 0, 2)?, digits(rest, 2, 2)?);
+            ("date", Date::from(day as u8, month as u8, year))
+        } else if full && b.len() == 3 {
+            ("ordinal date", ordinal_date(year, digits(rest, 0, 3)?))
+        } else {
+            return Err(self.error("date", text));
+        };
+        trace_event!(shape, year, ?date, "parsed the date");
+        if date.m == 0 {
+            return Err(DateTimeError::OutOfRange(format!(
+                "'{}' is not a valid {}",
+                text, shape
+            )));
+        }
+        Ok(date)
+    }
+    /// ```parse(text)``` parses a date and time, the date and the time are separated by a
+    /// ```T```. The time is ```HH:MM:SS``` with an optional fraction of the second and the
+    /// offset is ```Z``` or ```+HH:MM```. The ```IsoProfile::Extended``` accepts the time
+    /// without seconds or offset, the ```IsoProfile::Full``` accepts all date shapes of
+    /// ```parse_date()``` and the basic format ```HHMMSS+HHMM```.
+    ///
+    /// ```
+    /// use date_and_time::iso8601::*;
+    /// let parser = IsoParser::new(IsoProfile::Extended);
+    /// let parsed = parser.parse("2024-06-22T13:45").unwrap();
+    /// assert_eq!(parsed.date_time.as_string(), "2024-06-22T13:45:00");
+    /// assert_eq!(parsed.offset, None);
+    /// assert!(IsoParser::new(IsoProfile::Rfc3339).parse("2024-06-22T13:45").is_err());
+    /// ```
+    pub fn parse(&self, text: &str) -> Result<IsoDateTime, DateTimeError> {
+        let text = text.trim();
+        enter_trace_span!("IsoParser::parse", text, profile = ?self.profile);
+        let Some((date, rest)) = text.split_once(['T', 't']) else {
+            return Err(self.error("date and time", text));
+        };
+        let date = self.parse_date(date)?;
+        let (clock, offset) = self.split_offset(rest)?;
+        if offset.is_none() && self.profile == IsoProfile::Rfc3339 {
+            return Err(self.error("date and time without offset", text));
+        }
+        let (clock, fraction) = match clock.split_once(['.', ',']) {
+            Some((_, _)) if clock.contains(',') && self.profile == IsoProfile::Rfc3339 => {
+                return Err(self.error("decimal comma", text));
+            }
+            Some((clock, fraction)) => (clock, Some(fraction)),
+            None => (clock, None),
+        };
+        let fields: Vec<&str> = if clock.contains(':') {
+            clock.split(':').collect()
+        } else if self.profile == IsoProfile::Full && clock.is_ascii() && clock.len() % 2 == 0 {
+            (0..clock.len())
+                .step_by(2)
+                .map(|n| &clock[n..n + 2])
+                .collect()
+        } else {
+            return Err(self.error("time", text));
+        };
+        if fields.len() < 2 || fields.len() > 3 || fields.iter().any(|f| f.len() != 2) {
+            return Err(self.error("time", text));
+        }
+        if fields.len() == 2 && self.profile == IsoProfile::Rfc3339 {
+            return Err(self.error("time without seconds", text));
+        }
+        if fields.len() == 2 && fraction.is_some() {
+            return Err(self.error("fraction of a minute", text));
+        }
+        let mut values = [0; 3];
+        for (n, field) in fields.iter().enumerate() {
+            values[n] = digits(field, 0, 2)?;
+        }
+        let time = Time::from(values[0] as i32, values[1] as i8, values[2] as i8);
+        if values[0] > 23 || time.m < 0 {
+            return Err(DateTimeError::OutOfRange(format!(
+                "'{}' is not a valid time",
+                text
+            )));
+        }
+        let nanos = match fraction {
+            Some(f) if f.is_empty() || f.len() > 9 => {
+                return Err(DateTimeError::Parse(format!(
+                    "invalid fraction of a second in '{}'",
+                    text
+                )));
+            }
+            Some(f) => digits(f, 0, f.len())? * 10_u32.pow(9 - f.len() as u32),
+            None => 0,
+        };
+        trace_event!(?time, nanos, ?offset, "parsed the time and the offset");
+        Ok(IsoDateTime {
+            date_time: DateTime { date, time, nanos },
+            offset,
+        })
+    }
+    /// ```format_date(&date)``` formats the ```date``` as ```YYYY-MM-DD```, with expanded
+    /// years as ```±Y...Y-MM-DD```. It returns an error for an invalid date or a year which
+    /// doesn't fit in the digits.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::iso8601::*;
+    /// let parser = IsoParser::new(IsoProfile::Extended);
+    /// assert_eq!(parser.format_date(&Date::from(22, 6, 2024)).unwrap(), "2024-06-22");
+    /// assert!(parser.format_date(&Date::from(31, 12, -1)).is_err());
+    /// let expanded = parser.with_expanded_years(4);
+    /// assert_eq!(expanded.format_date(&Date::from(31, 12, -1)).unwrap(), "-0001-12-31");
+    /// ```
+    pub fn format_date(&self, date: &Date) -> Result<String, DateTimeError> {
+        if date.m == 0 {
+            return Err(DateTimeError::OutOfRange(String::from(
+                "can't format an invalid date",
+            )));
+        }
+        let len = self.expanded_years.unwrap_or(4) as usize;
+        let mut year = format!("{:0len$}", date.y.unsigned_abs(), len = len);
+        if year.len() > len || (self.expanded_years.is_none() && date.y < 0) {
+            return Err(DateTimeError::OutOfRange(format!(
+                "the year {} doesn't fit in the ISO 8601 format",
+                date.y
+            )));
+        }
+        if self.expanded_years.is_some() {
+            year.insert(0, if date.y < 0 { '-' } else { '+' });
+        }
+        Ok(format!("{}-{:02}-{:02}", year, date.m, date.d))
+    }
+    /// ```format(&value)``` formats the ```value``` in the extended format, which can be
+    /// parsed again by the ```IsoParser```. The offset 0 is written as ```Z```, the
+    /// ```IsoProfile::Rfc3339``` returns an error without an offset.
+    ///
+    /// ```
+    /// use date_and_time::iso8601::*;
+    /// let parser = IsoParser::new(IsoProfile::Rfc3339).with_expanded_years(6);
+    /// let parsed = parser.parse("-012345-03-01T10:00:00.5+01:00").unwrap();
+    /// assert_eq!(parser.format(&parsed).unwrap(), "-012345-03-01T10:00:00.500000000+01:00");
+    /// ```
+    pub fn format(&self, value: &IsoDateTime) -> Result<String, DateTimeError> {
+        let date_time = &value.date_time;
+        let mut s = format!(
+            "{}T{}",
+            self.format_date(&date_time.date)?,
+            date_time.time.as_string()
+        );
+        if date_time.nanos > 0 {
+            s.push_str(&format!(".{:09}", date_time.nanos));
+        }
+        match value.offset {
+            Some(offset) if offset.as_seconds() % 60 != 0 => {
+                return Err(DateTimeError::OutOfRange(format!(
+                    "the offset {} has seconds",
+                    offset.as_string()
+                )));
+            }
+            Some(offset) if offset.as_seconds() == 0 => s.push('Z'),
+            Some(offset) => s.push_str(&offset.as_string()),
+            None if self.profile == IsoProfile::Rfc3339 => {
+                return Err(self.error("date and time without offset", &s));
+            }
+            None => {}
+        }
+        Ok(s)
+    }
+    // Returns the year: i32 and the rest: &str of the date text: &str
+    fn split_year<'a>(&self, text: &'a str) -> Result<(i32, &'a str), DateTimeError> {
+        let Some(len) = self.expanded_years else {
+            if text.starts_with(['+', '-']) {
+                return Err(self.error("expanded year", text));
+            }
+            return Ok((digits(text, 0, 4)? as i32, &text[4..]));
+        };
+        let len = len as usize;
+        let year = match text.as_bytes().first() {
+            Some(b'+') => digits(text, 1, len)? as i32,
+            Some(b'-') => -(digits(text, 1, len)? as i32),
+            _ => return Err(self.error("year without sign", text)),
+        };
+        Ok((year, &text[1 + len..]))
+    }
+    // Returns the clock: &str and the offset: Option<UtcOffset> of the time part text: &str
+    fn split_offset<'a>(
+        &self,
+        text: &'a str,
+    ) -> Result<(&'a str, Option<UtcOffset>), DateTimeError> {
+        if let Some(clock) = text.strip_suffix(['Z', 'z']) {
+            return Ok((clock, Some(UtcOffset::default())));
+        }
+        let Some(pos) = text.find(['+', '-']) else {
+            return Ok((text, None));
+        };
+        let (clock, zone) = (&text[..pos], &text[pos + 1..]);
+        let b = zone.as_bytes();
+        let (hours, minutes) = if b.len() == 5 && b[2] == b':' {
+            (digits(zone, 0, 2)?, digits(zone, 3, 2)?)
+        } else if b.len() == 2 && self.profile != IsoProfile::Rfc3339 {
+            (digits(zone, 0, 2)?, 0)
+        } else if b.len() == 4 && self.profile == IsoProfile::Full {
+            (digits(zone, 0, 2)?, digits(zone, 2, 2)?)
+        } else {
+            return Err(self.error("offset", text));
+        };
+        if hours > 23 || minutes > 59 {
+            return Err(DateTimeError::OutOfRange(format!(
+                "'{}' is not a valid offset",
+                text
+            )));
+        }
+        let seconds = (hours * 3_600 + minutes * 60) as i32;
+        let offset = match text.as_bytes()[pos] {
+            b'-' => UtcOffset::from_seconds(-seconds),
+            _ => UtcOffset::from_seconds(seconds),
+        };
+        Ok((clock, Some(offset)))
+    }
+    // Returns the parse error for the shape: &str of the text: &str
+    fn error(&self, shape: &str, text: &str) -> DateTimeError {
+        let profile = match self.profile {
+            IsoProfile::Rfc3339 => "RFC 3339",
+            IsoProfile::Extended => "ISO 8601 extended",
+            IsoProfile::Full => "ISO 8601 full",
+        };
+        DateTimeError::Parse(format!(
+            "unsupported {} '{}' in the {} profile",
+            shape, text, profile
+        ))
+    }
+}
+
+// Returns the number of the len: usize ASCII digits at the position pos: usize of the
+// text: &str
+fn digits(text: &str, pos: usize, len: usize) -> Result<u32, DateTimeError> {
+    match text.get(pos..pos + len) {
+        Some(part) if part.bytes().all(|b| b.is_ascii_digit()) => Ok(part.parse().unwrap_or(0)),
+        _ => Err(DateTimeError::Parse(format!(
+            "expected {} digits at position {} of '{}'",
+            len, pos, text
+        ))),
+    }
+}
+
+// Returns the Date of the day: u32 of the year: i32, or the invalid Date
+fn ordinal_date(year: i32, day: u32) -> Date {
+    Date::from_ordinal(year, day).unwrap_or(Date { d: 0, m: 0, y: 0 })
+}
+
+// Returns the Date of the weekday: u32 (1 = Monday) in the ISO week: u32 of the year: i32,
+// or the invalid Date
+fn week_date(year: i32, week: u32, weekday: u32) -> Date {
+    // the week has 2 digits and the weekday 1 digit
+    Date::from_iso_week_date(year, week as u8, weekday as u8).unwrap_or(Date { d: 0, m: 0, y: 0 })
+}
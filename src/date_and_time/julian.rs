@@ -0,0 +1,138 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::error::*;
+
+// The Julian Day Number of the 1st January 1970
+const JULIAN_DAY_OF_EPOCH: i64 = 2_440_588;
+
+/// The JulianDate structure is a date of the (proleptic) Julian calendar, which has a leap
+/// year every 4 years. It is used for historical dates before the calendar reform (1582 in
+/// many countries) and by some Orthodox churches. The ```Date``` structure is always a date
+/// of the Gregorian calendar, they can be converted with ```to_gregorian()``` and
+/// ```Date::to_julian_calendar()```.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::julian::*;
+/// // the last day of the Julian calendar in Rome was followed by the 15th October 1582
+/// let last_day = JulianDate::new(1582, 10, 4).unwrap();
+/// assert_eq!(last_day.to_gregorian(), Date::from(14, 10, 1582));
+/// assert_eq!(Date::from(7, 1, 2025).to_julian_calendar().as_string(), "2024-12-25");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JulianDate {
+    pub y: i32,
+    pub m: u8,
+    pub d: u8,
+}
+
+#[allow(dead_code)]
+impl JulianDate {
+    /// ```new(year, month, day)``` creates a ```JulianDate```, it returns an error if the
+    /// date doesn't exist in the Julian calendar.
+    ///
+    /// ```
+    /// use date_and_time::julian::*;
+    /// // 1900 was a leap year in the Julian calendar
+    /// assert!(JulianDate::new(1900, 2, 29).is_ok());
+    /// assert!(JulianDate::new(1901, 2, 29).is_err());
+    /// ```
+    pub fn new(year: i32, month: u8, day: u8) -> Result<JulianDate, DateTimeError> {
+        let days_in_month = match month {
+            2 if is_julian_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => 0,
+        };
+        if !(1..=days_in_month).contains(&day) {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{:04}-{:02}-{:02} is not a date of the Julian calendar",
+                year, month, day
+            )));
+        }
+        Ok(JulianDate {
+            y: year,
+            m: month,
+            d: day,
+        })
+    }
+    /// ```to_gregorian()``` returns the same day as a ```Date``` of the Gregorian calendar.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::julian::*;
+    /// let christmas = JulianDate::new(2024, 12, 25).unwrap();
+    /// assert_eq!(christmas.to_gregorian(), Date::from(7, 1, 2025));
+    /// ```
+    pub fn to_gregorian(&self) -> Date {
+        // the months start with March, so the leap day is the last day of the year
+        let a = (14 - self.m as i64) / 12;
+        let y = self.y as i64 + 4_800 - a;
+        let m = self.m as i64 + 12 * a - 3;
+        let julian_day = self.d as i64 + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4) - 32_083;
+        get_date_from_days(julian_day - JULIAN_DAY_OF_EPOCH)
+    }
+    /// ```as_string()``` gets the date as a string in the format YYYY-MM-DD.
+    ///
+    /// ```
+    /// use date_and_time::julian::*;
+    /// assert_eq!(JulianDate::new(325, 5, 20).unwrap().as_string(), "0325-05-20");
+    /// ```
+    pub fn as_string(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.y, self.m, self.d)
+    }
+}
+
+#[allow(dead_code)]
+impl Date {
+    /// ```to_julian_calendar()``` returns the same day as a ```JulianDate``` of the Julian
+    /// calendar. For an invalid date the result will be ```JulianDate{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// // the Gregorian leap day 2000 was the 16th February in the Julian calendar
+    /// assert_eq!(Date::from(29, 2, 2000).to_julian_calendar().as_string(), "2000-02-16");
+    /// ```
+    pub fn to_julian_calendar(&self) -> JulianDate {
+        if !is_date_valid(self) {
+            return JulianDate { d: 0, m: 0, y: 0 };
+        }
+        let c = get_days_from_date(self) + JULIAN_DAY_OF_EPOCH + 32_082;
+        let d = (4 * c + 3).div_euclid(1_461);
+        let e = c - (1_461 * d).div_euclid(4);
+        let m = (5 * e + 2) / 153;
+        JulianDate {
+            y: (d - 4_800 + m / 10) as i32,
+            m: (m + 3 - 12 * (m / 10)) as u8,
+            d: (e - (153 * m + 2) / 5 + 1) as u8,
+        }
+    }
+    /// ```from_julian_calendar(year, month, day)``` creates a ```Date``` of the Gregorian
+    /// calendar from a date of the Julian calendar, it returns an error if the date doesn't
+    /// exist in the Julian calendar.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// // the October Revolution on the 25th October 1917 (Julian)
+    /// assert_eq!(Date::from_julian_calendar(1917, 10, 25), Ok(Date::from(7, 11, 1917)));
+    /// ```
+    pub fn from_julian_calendar(year: i32, month: u8, day: u8) -> Result<Date, DateTimeError> {
+        Ok(JulianDate::new(year, month, day)?.to_gregorian())
+    }
+}
+
+/// ```is_julian_leap_year(year)``` returns true if the ```year``` is a leap year in the
+/// Julian calendar, this is every 4th year.
+///
+/// ```
+/// use date_and_time::julian::*;
+/// assert!(is_julian_leap_year(1700));
+/// assert!(!is_julian_leap_year(1701));
+/// ```
+pub fn is_julian_leap_year(year: i32) -> bool {
+    year.rem_euclid(4) == 0
+}
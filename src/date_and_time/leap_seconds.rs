@@ -0,0 +1,475 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use std::sync::RwLock;
+
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+// The seconds from the 1st January 1900 (the epoch of the NTP) to the Linux epoch
+const NTP_SECONDS_OF_EPOCH: i64 = 2_208_988_800;
+// The seconds the GPS time is behind the TAI, since the start of the GPS time in 1980
+const TAI_MINUS_GPS: i128 = 19;
+// The first days (year, month) of the UTC with a new difference TAI - UTC in seconds
+const LEAP_SECONDS: [(i32, u8, i32); 28] = [
+    (1972, 1, 10),
+    (1972, 7, 11),
+    (1973, 1, 12),
+    (1974, 1, 13),
+    (1975, 1, 14),
+    (1976, 1, 15),
+    (1977, 1, 16),
+    (1978, 1, 17),
+    (1979, 1, 18),
+    (1980, 1, 19),
+    (1981, 7, 20),
+    (1982, 7, 21),
+    (1983, 7, 22),
+    (1985, 7, 23),
+    (1988, 1, 24),
+    (1990, 1, 25),
+    (1991, 1, 26),
+    (1992, 7, 27),
+    (1993, 7, 28),
+    (1994, 7, 29),
+    (1996, 1, 30),
+    (1997, 7, 31),
+    (1999, 1, 32),
+    (2006, 1, 33),
+    (2009, 1, 34),
+    (2012, 7, 35),
+    (2015, 7, 36),
+    (2017, 1, 37),
+];
+
+// The table of set_leap_second_table(), None uses the built-in table
+static LEAP_SECOND_TABLE: RwLock<Option<LeapSecondTable>> = RwLock::new(None);
+
+/// The ElapsedSeconds structure is the difference between two UTC date and times in whole
+/// seconds of ```LeapSecondTable::diff_in_seconds()``` and
+/// ```DateTime::diff_in_real_seconds()```, the ```posix``` seconds have 86400 seconds per
+/// day and the ```real``` seconds contain the inserted leap seconds, too.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ElapsedSeconds {
+    pub posix: i64,
+    pub real: i64,
+}
+
+/// The LeapSecondTable structure contains the differences between the TAI (International
+/// Atomic Time) and the UTC since 1972, every entry is the first second of the UTC with a
+/// new difference. It can be build with the functions ```builtin()``` (the leap seconds
+/// until 2017) and ```parse_leap_seconds_list()``` (the file ```leap-seconds.list``` of
+/// the IERS or the IANA time zone database).
+///
+/// The UTC has no leap seconds before 1972, so the difference of 1972 (10 seconds) is used
+/// for the earlier dates. A ```DateTime``` can't hold the leap second 23:59:60, it is
+/// converted to 23:59:59.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::datetime::*;
+/// use date_and_time::leap_seconds::*;
+/// use date_and_time::time::*;
+/// let table = LeapSecondTable::builtin();
+/// let utc = DateTime::from(Date::from(22, 6, 2024), Time::from(12, 0, 0));
+/// assert_eq!(table.tai_minus_utc(&utc), 37);
+/// assert_eq!(table.utc_to_tai(&utc).as_string(), "2024-06-22T12:00:37");
+/// assert_eq!(table.utc_to_gps(&utc).as_string(), "2024-06-22T12:00:18");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeapSecondTable {
+    // the seconds since the Linux epoch of the first UTC second with the new TAI - UTC
+    entries: Vec<(i64, i32)>,
+    expires: Option<i64>,
+}
+
+impl Default for LeapSecondTable {
+    fn default() -> Self {
+        LeapSecondTable::builtin()
+    }
+}
+
+#[allow(dead_code)]
+impl LeapSecondTable {
+    /// ```builtin()``` creates a ```LeapSecondTable``` with the leap seconds which are
+    /// built into the crate, the last one was inserted at the end of 2016 (TAI - UTC = 37
+    /// seconds). The table has no expiry date.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::leap_seconds::*;
+    /// let table = LeapSecondTable::builtin();
+    /// assert_eq!(table.tai_minus_utc(&DateTime::from_timestamp(0)), 10);
+    /// assert_eq!(table.expires(), None);
+    /// ```
+    pub fn builtin() -> LeapSecondTable {
+        LeapSecondTable {
+            entries: LEAP_SECONDS
+                .iter()
+                .map(|(y, m, offset)| {
+                    (
+                        get_days_from_date(&Date::from_ymd(*y, *m, 1)) * 86_400,
+                        *offset,
+                    )
+                })
+                .collect(),
+            expires: None,
+        }
+    }
+    /// ```parse_leap_seconds_list(text)``` creates a ```LeapSecondTable``` from the
+    /// ```text``` of a ```leap-seconds.list``` file, with a line of the NTP seconds (since
+    /// 1900) and the TAI - UTC for every entry. The comments start with ```#```, the
+    /// expiry date is read from the line ```#@```. It returns an error for an invalid line
+    /// or a text without an entry.
+    ///
+    /// ```
+    /// use date_and_time::leap_seconds::*;
+    /// let text = "#@\t3960057600\n\
+    ///             2272060800\t10\t# 1 Jan 1972\n\
+    ///             3692217600\t37\t# 1 Jan 2017\n";
+    /// let table = LeapSecondTable::parse_leap_seconds_list(text).unwrap();
+    /// assert_eq!(table.expires().unwrap().as_string(), "2025-06-28T00:00:00");
+    /// assert!(LeapSecondTable::parse_leap_seconds_list("# empty").is_err());
+    /// ```
+    pub fn parse_leap_seconds_list(text: &str) -> Result<LeapSecondTable, DateTimeError> {
+        let mut table = LeapSecondTable {
+            entries: Vec::new(),
+            expires: None,
+        };
+        for line in text.lines().map(str::trim) {
+            let invalid = || DateTimeError::Parse(format!("'{}' is not a leap second entry", line));
+            if let Some(expires) = line.strip_prefix("#@") {
+                let ntp: i64 = expires.trim().parse().map_err(|_| invalid())?;
+                table.expires = Some(ntp.checked_sub(NTP_SECONDS_OF_EPOCH).ok_or_else(invalid)?);
+                continue;
+            }
+            let entry = line.split('#').next().unwrap_or("");
+            if entry.trim().is_empty() {
+                continue;
+            }
+            let mut fields = entry.split_whitespace();
+            let ntp: i64 = fields
+                .next()
+                .and_then(|f| f.parse().ok())
+                .ok_or_else(invalid)?;
+            let offset: i32 = fields
+                .next()
+                .and_then(|f| f.parse().ok())
+                .ok_or_else(invalid)?;
+            if fields.next().is_some() {
+                return Err(invalid());
+            }
+            let timestamp = ntp.checked_sub(NTP_SECONDS_OF_EPOCH).ok_or_else(invalid)?;
+            table.add(DateTime::from_timestamp(timestamp), offset);
+        }
+        if table.entries.is_empty() {
+            return Err(DateTimeError::Parse(String::from(
+                "the leap seconds list has no entries",
+            )));
+        }
+        Ok(table)
+    }
+    /// ```add(utc, tai_minus_utc)``` adds a new leap second to the table, the ```utc``` is
+    /// the first second with the new ```tai_minus_utc``` (e.g. 0:00:00 of the 1st
+    /// January). An entry with the same ```utc``` is replaced.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::leap_seconds::*;
+    /// use date_and_time::time::*;
+    /// let mut table = LeapSecondTable::builtin();
+    /// let new_year = DateTime::from(Date::from(1, 1, 2030), Time::new());
+    /// table.add(new_year, 38);
+    /// assert_eq!(table.tai_minus_utc(&new_year), 38);
+    /// ```
+    pub fn add(&mut self, utc: DateTime, tai_minus_utc: i32) -> &mut Self {
+        let secs = utc.as_timestamp();
+        match self
+            .entries
+            .binary_search_by_key(&secs, |(start, _)| *start)
+        {
+            Ok(idx) => self.entries[idx].1 = tai_minus_utc,
+            Err(idx) => self.entries.insert(idx, (secs, tai_minus_utc)),
+        }
+        self
+    }
+    /// ```expires()``` returns the expiry date of the table, or None if it is unknown. A
+    /// leap second after this date may be missing in the table.
+    ///
+    /// ```
+    /// use date_and_time::leap_seconds::*;
+    /// assert_eq!(LeapSecondTable::builtin().expires(), None);
+    /// ```
+    pub fn expires(&self) -> Option<DateTime> {
+        self.expires.map(DateTime::from_timestamp)
+    }
+    /// ```tai_minus_utc(&utc)``` returns the seconds the TAI is ahead of the UTC at the
+    /// ```utc``` date and time.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::leap_seconds::*;
+    /// use date_and_time::time::*;
+    /// let table = LeapSecondTable::builtin();
+    /// let before = DateTime::from(Date::from(31, 12, 2016), Time::from(23, 59, 59));
+    /// let after = DateTime::from(Date::from(1, 1, 2017), Time::new());
+    /// assert_eq!(table.tai_minus_utc(&before), 36);
+    /// assert_eq!(table.tai_minus_utc(&after), 37);
+    /// ```
+    pub fn tai_minus_utc(&self, utc: &DateTime) -> i32 {
+        let secs = utc.as_timestamp();
+        let idx = self.entries.partition_point(|(start, _)| *start <= secs);
+        match (idx, self.entries.first()) {
+            (0, Some((_, offset))) => *offset,
+            (0, None) => 0,
+            _ => self.entries[idx - 1].1,
+        }
+    }
+    /// ```leap_seconds_between(&start, &end)``` returns the number of the leap seconds
+    /// which were inserted between the two UTC date and times, it is negative if the
+    /// ```end``` is before the ```start```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::leap_seconds::*;
+    /// use date_and_time::time::*;
+    /// let table = LeapSecondTable::builtin();
+    /// let start = DateTime::from(Date::from(1, 1, 2000), Time::new());
+    /// let end = DateTime::from(Date::from(1, 1, 2020), Time::new());
+    /// assert_eq!(table.leap_seconds_between(&start, &end), 5);
+    /// assert_eq!(table.leap_seconds_between(&end, &start), -5);
+    /// ```
+    pub fn leap_seconds_between(&self, start: &DateTime, end: &DateTime) -> i64 {
+        self.tai_minus_utc(end) as i64 - self.tai_minus_utc(start) as i64
+    }
+    /// ```diff_in_seconds(&start, &end)``` gets the difference between the two UTC date and
+    /// times in whole seconds, without (POSIX) and with the inserted leap seconds (real).
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::leap_seconds::*;
+    /// use date_and_time::time::*;
+    /// let table = LeapSecondTable::builtin();
+    /// let start = DateTime::from(Date::from(30, 6, 2015), Time::from(23, 59, 0));
+    /// let end = DateTime::from(Date::from(1, 7, 2015), Time::from(0, 1, 0));
+    /// let seconds = table.diff_in_seconds(&start, &end);
+    /// assert_eq!(seconds, ElapsedSeconds { posix: 120, real: 121 });
+    /// ```
+    pub fn diff_in_seconds(&self, start: &DateTime, end: &DateTime) -> ElapsedSeconds {
+        let nanos = start.diff(end).as_nanoseconds();
+        let leap_seconds = self.leap_seconds_between(start, end) as i128 * NANOS_PER_SECOND;
+        ElapsedSeconds {
+            posix: (nanos / NANOS_PER_SECOND) as i64,
+            real: ((nanos + leap_seconds) / NANOS_PER_SECOND) as i64,
+        }
+    }
+    /// ```utc_to_tai(&utc)``` converts the ```utc``` date and time to the TAI. A date and
+    /// time with an invalid date is returned unchanged.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::leap_seconds::*;
+    /// use date_and_time::time::*;
+    /// let utc = DateTime::from(Date::from(1, 1, 1999), Time::new());
+    /// let tai = LeapSecondTable::builtin().utc_to_tai(&utc);
+    /// assert_eq!(tai.as_string(), "1999-01-01T00:00:32");
+    /// ```
+    pub fn utc_to_tai(&self, utc: &DateTime) -> DateTime {
+        if !is_date_valid(&utc.date) {
+            return *utc;
+        }
+        let offset = Duration::from_seconds(self.tai_minus_utc(utc) as i64);
+        utc.add_duration(&offset)
+    }
+    /// ```tai_to_utc(&tai)``` converts the ```tai``` date and time to the UTC, the TAI
+    /// during an inserted leap second (UTC 23:59:60) is converted to 23:59:59. A date and
+    /// time with an invalid date is returned unchanged.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::leap_seconds::*;
+    /// use date_and_time::time::*;
+    /// let table = LeapSecondTable::builtin();
+    /// // the leap second at the end of 2016
+    /// let tai = DateTime::from(Date::from(1, 1, 2017), Time::from(0, 0, 36));
+    /// assert_eq!(table.tai_to_utc(&tai).as_string(), "2016-12-31T23:59:59");
+    /// let tai = DateTime::from(Date::from(1, 1, 2017), Time::from(0, 0, 37));
+    /// assert_eq!(table.tai_to_utc(&tai).as_string(), "2017-01-01T00:00:00");
+    /// ```
+    pub fn tai_to_utc(&self, tai: &DateTime) -> DateTime {
+        if !is_date_valid(&tai.date) {
+            return *tai;
+        }
+        let nanos = tai.as_nanoseconds();
+        for (idx, (start, offset)) in self.entries.iter().enumerate().rev() {
+            let utc = nanos - *offset as i128 * NANOS_PER_SECOND;
+            // the inserted leap seconds before the start belong to the new offset
+            let inserted = match idx {
+                0 => 0,
+                _ => (*offset - self.entries[idx - 1].1).max(0) as i128,
+            };
+            if utc >= (*start as i128 - inserted) * NANOS_PER_SECOND {
+                return DateTime::from_nanoseconds(utc);
+            }
+        }
+        let offset = self.entries.first().map_or(0, |(_, offset)| *offset);
+        DateTime::from_nanoseconds(nanos - offset as i128 * NANOS_PER_SECOND)
+    }
+    /// ```utc_to_gps(&utc)``` converts the ```utc``` date and time to the GPS time, which
+    /// is 19 seconds behind the TAI.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::leap_seconds::*;
+    /// use date_and_time::time::*;
+    /// // the start of the GPS time
+    /// let utc = DateTime::from(Date::from(6, 1, 1980), Time::new());
+    /// let gps = LeapSecondTable::builtin().utc_to_gps(&utc);
+    /// assert_eq!(gps, utc);
+    /// ```
+    pub fn utc_to_gps(&self, utc: &DateTime) -> DateTime {
+        let tai = self.utc_to_tai(utc);
+        if !is_date_valid(&tai.date) {
+            return tai;
+        }
+        DateTime::from_nanoseconds(tai.as_nanoseconds() - TAI_MINUS_GPS * NANOS_PER_SECOND)
+    }
+    /// ```gps_to_utc(&gps)``` converts the ```gps``` date and time to the UTC, like
+    /// ```tai_to_utc()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::leap_seconds::*;
+    /// use date_and_time::time::*;
+    /// let gps = DateTime::from(Date::from(22, 6, 2024), Time::from(12, 0, 18));
+    /// let utc = LeapSecondTable::builtin().gps_to_utc(&gps);
+    /// assert_eq!(utc.as_string(), "2024-06-22T12:00:00");
+    /// ```
+    pub fn gps_to_utc(&self, gps: &DateTime) -> DateTime {
+        if !is_date_valid(&gps.date) {
+            return *gps;
+        }
+        let tai =
+            DateTime::from_nanoseconds(gps.as_nanoseconds() + TAI_MINUS_GPS * NANOS_PER_SECOND);
+        self.tai_to_utc(&tai)
+    }
+}
+
+#[allow(dead_code)]
+impl DateTime {
+    /// ```to_tai()``` converts the date and time from the UTC to the TAI with the leap
+    /// second table of ```set_leap_second_table()``` (or the built-in table).
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let utc = DateTime::from_timestamp(1_719_057_600);
+    /// assert_eq!(utc.to_tai().as_string(), "2024-06-22T12:00:37");
+    /// ```
+    pub fn to_tai(&self) -> DateTime {
+        get_leap_second_table().utc_to_tai(self)
+    }
+    /// ```from_tai(&tai)``` converts the ```tai``` date and time to the UTC with the leap
+    /// second table of ```set_leap_second_table()``` (or the built-in table).
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let utc = DateTime::from_timestamp(1_719_057_600);
+    /// assert_eq!(DateTime::from_tai(&utc.to_tai()), utc);
+    /// ```
+    pub fn from_tai(tai: &DateTime) -> DateTime {
+        get_leap_second_table().tai_to_utc(tai)
+    }
+    /// ```to_gps()``` converts the date and time from the UTC to the GPS time with the leap
+    /// second table of ```set_leap_second_table()``` (or the built-in table).
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let utc = DateTime::from_timestamp(1_719_057_600);
+    /// assert_eq!(utc.to_gps().as_string(), "2024-06-22T12:00:18");
+    /// ```
+    pub fn to_gps(&self) -> DateTime {
+        get_leap_second_table().utc_to_gps(self)
+    }
+    /// ```from_gps(&gps)``` converts the ```gps``` date and time to the UTC with the leap
+    /// second table of ```set_leap_second_table()``` (or the built-in table).
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let utc = DateTime::from_timestamp(1_719_057_600);
+    /// assert_eq!(DateTime::from_gps(&utc.to_gps()), utc);
+    /// ```
+    pub fn from_gps(gps: &DateTime) -> DateTime {
+        get_leap_second_table().gps_to_utc(gps)
+    }
+    /// ```diff_in_real_seconds(&other)``` gets the difference between the two UTC date and
+    /// times in whole seconds like ```diff_in_seconds()``` (POSIX) and with the leap seconds
+    /// of the table of ```set_leap_second_table()``` (real), e.g. for the elapsed time
+    /// between two log entries.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::time::*;
+    /// let start = DateTime::from(Date::from(1, 1, 1972), Time::new());
+    /// let end = DateTime::from(Date::from(1, 1, 2017), Time::new());
+    /// let seconds = start.diff_in_real_seconds(&end);
+    /// assert_eq!(seconds.posix, start.diff_in_seconds(&end));
+    /// assert_eq!(seconds.real - seconds.posix, 27);
+    /// ```
+    pub fn diff_in_real_seconds(&self, other: &DateTime) -> ElapsedSeconds {
+        get_leap_second_table().diff_in_seconds(self, other)
+    }
+}
+
+/// ```set_leap_second_table(table)``` sets the leap second ```table``` of the functions
+/// ```to_tai()```, ```from_tai()```, ```to_gps()``` and ```from_gps()``` of a
+/// ```DateTime```, e.g. a newer ```leap-seconds.list```. The setting is for the whole
+/// program.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::datetime::*;
+/// use date_and_time::leap_seconds::*;
+/// use date_and_time::time::*;
+/// let new_year = DateTime::from(Date::from(1, 1, 2035), Time::new());
+/// let mut table = LeapSecondTable::builtin();
+/// table.add(new_year, 38);
+/// set_leap_second_table(table);
+/// assert_eq!(new_year.to_tai().as_string(), "2035-01-01T00:00:38");
+/// reset_leap_second_table();
+/// assert_eq!(new_year.to_tai().as_string(), "2035-01-01T00:00:37");
+/// ```
+pub fn set_leap_second_table(table: LeapSecondTable) {
+    let mut current = LEAP_SECOND_TABLE.write().unwrap_or_else(|e| e.into_inner());
+    *current = Some(table);
+}
+
+/// ```reset_leap_second_table()``` removes the table of ```set_leap_second_table()```, the
+/// built-in table is used again.
+///
+/// ```
+/// use date_and_time::leap_seconds::*;
+/// reset_leap_second_table();
+/// ```
+pub fn reset_leap_second_table() {
+    let mut current = LEAP_SECOND_TABLE.write().unwrap_or_else(|e| e.into_inner());
+    *current = None;
+}
+
+// Returns the table of set_leap_second_table() or the built-in table
+pub(crate) fn get_leap_second_table() -> LeapSecondTable {
+    let current = LEAP_SECOND_TABLE.read().unwrap_or_else(|e| e.into_inner());
+    current.clone().unwrap_or_default()
+}
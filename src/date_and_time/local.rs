@@ -2,183 +2,597 @@
 // (c) 2024 by markus dot mueller dot 73 at hotmail dot de
 // small crate to get some rudimentary date and time calculations
 // the license details are in the main library file.
-
+//
+// The functions use the API of the OS (libc on Linux, windows-sys on Windows) with the cargo
+// feature local, which is enabled by default. Without the feature or on other platforms the
+// local timezone is taken from the TZ environment variable or /etc/localtime, if neither is
+// available the UTC time is returned. In the browser (wasm32 with the feature wasm) the
+// timezone and the offset are taken from JavaScript.
+//
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+#[cfg(all(feature = "local", target_os = "windows"))]
+use crate::date_and_time::error::*;
+use crate::date_and_time::offset::*;
 use crate::date_and_time::time::*;
-#[cfg(target_os = "linux")]
+#[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+use crate::date_and_time::timezone::*;
+#[cfg(all(feature = "local", target_os = "linux"))]
 use libc::{localtime_r, time, time_t, tm};
+#[cfg(all(feature = "local", target_os = "linux"))]
+use std::ffi::CStr;
 
-#[cfg(target_os = "windows")]
+#[cfg(all(feature = "local", target_os = "windows"))]
 use windows_sys::Win32::Foundation::SYSTEMTIME;
+#[cfg(all(feature = "local", target_os = "windows"))]
 use windows_sys::Win32::System::SystemInformation::GetLocalTime;
+#[cfg(all(feature = "local", target_os = "windows"))]
 use windows_sys::Win32::System::Time::{GetTimeZoneInformation, TIME_ZONE_INFORMATION};
 
+/// ```get_local_time()``` returns the current time of the local timezone, in contrast to
+/// ```Time::from_system_clock()``` which returns the UTC time.
+///
+/// ```
+/// use date_and_time::local::*;
+/// let now = get_local_time();
+/// println!("It is {} o'clock", now.as_formated_string("%R"));
+/// ```
 pub fn get_local_time() -> Time {
-    let mut result = Time::new();
-    #[cfg(target_os = "linux")]
+    let result: Time;
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    {
+        result = Time::from_tm(&get_local_tm());
+    }
+    #[cfg(all(feature = "local", target_os = "windows"))]
     unsafe {
-        let mut t: time_t = 0;
-        let t_ptr: *mut time_t = &mut t;
-        t = time(t_ptr);
-        let mut ltm = tm {
-            tm_sec: 0,
-            tm_min: 0,
-            tm_hour: 0,
-            tm_mday: 0,
-            tm_mon: 0,
-            tm_year: 0,
-            tm_wday: 0,
-            tm_yday: 0,
-            tm_isdst: 0,
-            tm_gmtoff: 0,
-            tm_zone: std::ptr::null(),
-        };
-        let ltm_ptr: *mut tm = &mut ltm;
-        localtime_r(&t, ltm_ptr);
-        result.set(ltm.tm_hour as i32, ltm.tm_min as i8, ltm.tm_sec as i8);
+        let mut lt = get_empty_systemtime();
+        let lt_ptr: *mut SYSTEMTIME = &mut lt;
+        GetLocalTime(lt_ptr);
+        result = lt.into();
+    }
+    #[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+    {
+        result = get_fallback_datetime().time;
     }
-    #[cfg(target_os = "windows")]
+    result
+}
+
+/// ```get_local_date()``` returns the current date of the local timezone, in contrast to
+/// ```Date::from_system_date()``` which returns the UTC date.
+///
+/// ```
+/// use date_and_time::local::*;
+/// let today = get_local_date();
+/// println!("Today is {}", today.as_formated_string("%A, %d. %B %Y"));
+/// ```
+pub fn get_local_date() -> Date {
+    let result: Date;
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    {
+        result = Date::from_tm(&get_local_tm());
+    }
+    #[cfg(all(feature = "local", target_os = "windows"))]
     unsafe {
-        let mut lt = SYSTEMTIME {
-            wYear: 0,
-            wMonth: 0,
-            wDayOfWeek: 0,
-            wDay: 0,
-            wHour: 0,
-            wMinute: 0,
-            wSecond: 0,
-            wMilliseconds: 0,
-        };
+        let mut lt = get_empty_systemtime();
         let lt_ptr: *mut SYSTEMTIME = &mut lt;
         GetLocalTime(lt_ptr);
-        result.set(lt.wHour as i32, lt.wMinute as i8, lt.wSecond as i8);
+        result = lt.into();
+    }
+    #[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+    {
+        result = get_fallback_datetime().date;
     }
     result
 }
 
-pub fn is_daylight_saving() -> bool {
-    let mut result: bool = false;
-    #[cfg(target_os = "linux")]
+/// ```get_local_datetime()``` returns the current date and time of the local timezone. The
+/// clock of the OS is read only once, so the date and the time are consistent, even at
+/// midnight (in contrast to calling ```get_local_date()``` and ```get_local_time()```).
+///
+/// ```
+/// use date_and_time::local::*;
+/// let now = get_local_datetime();
+/// println!("{} {}", now.date.as_string(), now.time.as_string());
+/// ```
+pub fn get_local_datetime() -> DateTime {
+    let result: DateTime;
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    {
+        result = get_local_datetime_at(get_system_clock_nanoseconds());
+    }
+    #[cfg(all(feature = "local", target_os = "windows"))]
     unsafe {
-        let mut t: time_t = 0;
-        let t_ptr: *mut time_t = &mut t;
-        t = time(t_ptr);
-        let mut ltm = tm {
-            tm_sec: 0,
-            tm_min: 0,
-            tm_hour: 0,
-            tm_mday: 0,
-            tm_mon: 0,
-            tm_year: 0,
-            tm_wday: 0,
-            tm_yday: 0,
-            tm_isdst: 0,
-            tm_gmtoff: 0,
-            tm_zone: std::ptr::null(),
-        };
-        let ltm_ptr: *mut tm = &mut ltm;
-        localtime_r(&t, ltm_ptr);
-        if ltm.tm_isdst != 0 {
-            result = true;
+        let mut lt = get_empty_systemtime();
+        let lt_ptr: *mut SYSTEMTIME = &mut lt;
+        GetLocalTime(lt_ptr);
+        result = lt.into();
+    }
+    #[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+    {
+        result = get_fallback_datetime();
+    }
+    result
+}
+
+/// ```is_daylight_saving()``` returns true if the daylight saving time is currently active
+/// in the local timezone.
+///
+/// ```
+/// use date_and_time::local::*;
+/// if is_daylight_saving() {
+///     println!("It's summer time");
+/// }
+/// ```
+pub fn is_daylight_saving() -> bool {
+    let result: bool;
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    {
+        result = get_local_tm().tm_isdst > 0;
+    }
+    #[cfg(all(feature = "local", target_os = "windows"))]
+    {
+        result = get_time_zone_information().1;
+    }
+    #[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+    {
+        result = get_fallback_is_dst();
+    }
+    result
+}
+
+/// ```get_gmt_offset()``` returns the current offset of the local timezone to UTC, the
+/// daylight saving time is included.
+///
+/// ```
+/// use date_and_time::local::*;
+/// let offset = get_gmt_offset();
+/// println!("The offset to UTC is {}", offset.as_string());
+/// assert!(offset.offset_hours().abs() <= 14);
+/// ```
+pub fn get_gmt_offset() -> UtcOffset {
+    let result: UtcOffset;
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    {
+        result = UtcOffset::from_seconds(get_local_tm().tm_gmtoff as i32);
+    }
+    #[cfg(all(feature = "local", target_os = "windows"))]
+    {
+        let (tzi, is_dst) = get_time_zone_information();
+        let mut bias = tzi.Bias;
+        if is_dst {
+            bias += tzi.DaylightBias;
         }
+        // the bias is in minutes west of Greenwich
+        result = UtcOffset::from_seconds(-bias * 60);
     }
-    #[cfg(target_os = "windows")]
-    unsafe {
-        let mut tzi = TIME_ZONE_INFORMATION {
-            Bias: 0,
-            StandardName: [0; 32],
-            StandardDate: SYSTEMTIME {
-                wYear: 0,
-                wMonth: 0,
-                wDayOfWeek: 0,
-                wDay: 0,
-                wHour: 0,
-                wMinute: 0,
-                wSecond: 0,
-                wMilliseconds: 0,
-            },
-            StandardBias: 0,
-            DaylightName: [0; 32],
-            DaylightDate: SYSTEMTIME {
-                wYear: 0,
-                wMonth: 0,
-                wDayOfWeek: 0,
-                wDay: 0,
-                wHour: 0,
-                wMinute: 0,
-                wSecond: 0,
-                wMilliseconds: 0,
-            },
-            DaylightBias: 0,
+    #[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+    {
+        let now = DateTime::from_system_clock().as_timestamp();
+        result = UtcOffset::from_seconds(get_fallback_zone().offset_at_timestamp(now));
+    }
+    result
+}
+
+/// ```get_timezone_name()``` returns the name of the local timezone, e.g.
+/// ```"Europe/Berlin"```. On Linux the name is taken from the ```TZ``` environment variable,
+/// the link ```/etc/localtime``` or the file ```/etc/timezone```, if none of them names
+/// the timezone the abbreviation is returned. On Windows it is the name of the standard
+/// time (e.g. ```"W. Europe Standard Time"```).
+///
+/// ```
+/// use date_and_time::local::*;
+/// println!("The local timezone is {}", get_timezone_name());
+/// ```
+pub fn get_timezone_name() -> String {
+    let result: String;
+    #[cfg(all(feature = "local", target_os = "windows"))]
+    {
+        result = utf16_to_string(&get_time_zone_information().0.StandardName);
+    }
+    #[cfg(not(all(feature = "local", target_os = "windows")))]
+    {
+        result = get_system_timezone_name().unwrap_or_else(get_timezone_abbreviation);
+    }
+    result
+}
+
+/// ```get_timezone_abbreviation()``` returns the current abbreviation of the local
+/// timezone, e.g. ```"CEST"``` in the summer and ```"CET"``` in the winter in Berlin.
+/// Windows knows no abbreviations, there the name of the current standard or daylight time
+/// is returned (e.g. ```"W. Europe Daylight Time"```).
+///
+/// ```
+/// use date_and_time::local::*;
+/// let now = get_local_time();
+/// println!("{} {}", now.as_string(), get_timezone_abbreviation());
+/// ```
+pub fn get_timezone_abbreviation() -> String {
+    let result: String;
+    #[cfg(all(feature = "local", target_os = "linux"))]
+    {
+        let ltm = get_local_tm();
+        result = if ltm.tm_zone.is_null() {
+            String::from("UTC")
+        } else {
+            unsafe { CStr::from_ptr(ltm.tm_zone) }
+                .to_string_lossy()
+                .into_owned()
+        };
+    }
+    #[cfg(all(feature = "local", target_os = "windows"))]
+    {
+        let (tzi, is_dst) = get_time_zone_information();
+        result = if is_dst {
+            utf16_to_string(&tzi.DaylightName)
+        } else {
+            utf16_to_string(&tzi.StandardName)
         };
-        let tzi_ptr: *mut TIME_ZONE_INFORMATION = &mut tzi;
-        let is_dst = GetTimeZoneInformation(tzi_ptr);
-        if is_dst == 2 {
-            result = true;
-        }
+    }
+    #[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+    {
+        result = get_fallback_zone()
+            .abbreviation_at_timestamp(DateTime::from_system_clock().as_timestamp());
     }
     result
 }
 
-pub fn get_gmt_offset() -> i8 {
-    let result: i8;
-    #[cfg(target_os = "linux")]
-    unsafe {
-        let mut t: time_t = 0;
-        let t_ptr: *mut time_t = &mut t;
-        t = time(t_ptr);
-        let mut ltm = tm {
-            tm_sec: 0,
-            tm_min: 0,
-            tm_hour: 0,
-            tm_mday: 0,
-            tm_mon: 0,
-            tm_year: 0,
-            tm_wday: 0,
-            tm_yday: 0,
-            tm_isdst: 0,
-            tm_gmtoff: 0,
-            tm_zone: std::ptr::null(),
+#[cfg(all(feature = "local", target_os = "linux"))]
+#[allow(dead_code)]
+impl Date {
+    /// ```from_tm(&tm)``` creates a ```Date``` structure from the date of the C structure
+    /// ```struct tm```. The new ```Date``` will be checked for validity, if it was invalid,
+    /// the returned date will be ```Date{d: 0, m: 0, y: 0}```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let tm = Date::from(24, 12, 2024).to_tm();
+    /// assert_eq!(Date::from_tm(&tm), Date::from(24, 12, 2024));
+    /// ```
+    pub fn from_tm(t: &tm) -> Date {
+        if !(1..=31).contains(&t.tm_mday) || !(0..=11).contains(&t.tm_mon) {
+            return Date { d: 0, m: 0, y: 0 };
+        }
+        Date::from(t.tm_mday as u8, (t.tm_mon + 1) as u8, t.tm_year + 1_900)
+    }
+    /// ```to_tm()``` returns the ```Date``` as a C structure ```struct tm```, the weekday
+    /// and the day of the year are filled in, the time is midnight. An invalid date or a
+    /// year before ```i32::MIN + 1900``` returns a zeroed ```struct tm```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let tm = Date::from(24, 12, 2024).to_tm();
+    /// assert_eq!((tm.tm_year, tm.tm_mon, tm.tm_mday, tm.tm_wday), (124, 11, 24, 2));
+    /// assert_eq!(Date::from(30, 2, 2024).to_tm().tm_mday, 0);
+    /// ```
+    pub fn to_tm(&self) -> tm {
+        let mut t = get_empty_tm();
+        let Some(year) = self.y.checked_sub(1_900).filter(|_| is_date_valid(self)) else {
+            return t;
         };
-        let ltm_ptr: *mut tm = &mut ltm;
-        localtime_r(&t, ltm_ptr);
-        result = ltm.tm_gmtoff as i8;
+        t.tm_mday = self.d as i32;
+        t.tm_mon = self.m as i32 - 1;
+        t.tm_year = year;
+        t.tm_wday = self.get_weekday() as i32;
+        t.tm_yday = self.get_day_of_year() as i32 - 1;
+        t
+    }
+}
+
+#[cfg(all(feature = "local", target_os = "linux"))]
+#[allow(dead_code)]
+impl Time {
+    /// ```from_tm(&tm)``` creates a ```Time``` structure from the time of the C structure
+    /// ```struct tm```. A leap second (```tm_sec == 60```) is reduced to the second 59.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let tm = Time::from(13, 45, 30).to_tm();
+    /// assert_eq!(Time::from_tm(&tm), Time::from(13, 45, 30));
+    /// ```
+    pub fn from_tm(t: &tm) -> Time {
+        Time::from(t.tm_hour, t.tm_min as i8, t.tm_sec.min(59) as i8)
     }
-    #[cfg(target_os = "windows")]
+    /// ```to_tm()``` returns the ```Time``` as a C structure ```struct tm```, the date
+    /// fields are zero.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let tm = Time::from(13, 45, 30).to_tm();
+    /// assert_eq!((tm.tm_hour, tm.tm_min, tm.tm_sec), (13, 45, 30));
+    /// ```
+    pub fn to_tm(&self) -> tm {
+        let mut t = get_empty_tm();
+        t.tm_hour = self.h;
+        t.tm_min = self.m as i32;
+        t.tm_sec = self.s as i32;
+        t
+    }
+}
+
+#[cfg(all(feature = "local", target_os = "linux"))]
+#[allow(dead_code)]
+impl DateTime {
+    /// ```from_tm(&tm)``` creates a ```DateTime``` structure from the C structure
+    /// ```struct tm```. The offset to UTC in ```tm_gmtoff``` is ignored.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let tm = DateTime::from_timestamp(1_700_000_000).to_tm();
+    /// assert_eq!(DateTime::from_tm(&tm), DateTime::from_timestamp(1_700_000_000));
+    /// ```
+    pub fn from_tm(t: &tm) -> DateTime {
+        DateTime {
+            date: Date::from_tm(t),
+            time: Time::from_tm(t),
+            nanos: 0,
+        }
+    }
+    /// ```to_tm()``` returns the ```DateTime``` as a C structure ```struct tm```.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let tm = DateTime::from_timestamp(1_700_000_000).to_tm();
+    /// assert_eq!((tm.tm_yday, tm.tm_hour), (317, 22));
+    /// ```
+    pub fn to_tm(&self) -> tm {
+        let mut t = self.date.to_tm();
+        t.tm_hour = self.time.h;
+        t.tm_min = self.time.m as i32;
+        t.tm_sec = self.time.s as i32;
+        t
+    }
+}
+
+// Returns a struct tm with all fields zero
+#[cfg(all(feature = "local", target_os = "linux"))]
+fn get_empty_tm() -> tm {
+    tm {
+        tm_sec: 0,
+        tm_min: 0,
+        tm_hour: 0,
+        tm_mday: 0,
+        tm_mon: 0,
+        tm_year: 0,
+        tm_wday: 0,
+        tm_yday: 0,
+        tm_isdst: 0,
+        tm_gmtoff: 0,
+        tm_zone: std::ptr::null(),
+    }
+}
+
+// Returns the struct tm of the current local time
+#[cfg(all(feature = "local", target_os = "linux"))]
+fn get_local_tm() -> tm {
+    let t: time_t = unsafe { time(std::ptr::null_mut()) };
+    get_local_tm_at(t)
+}
+
+// Returns the local date and time at the nanoseconds since the Linux epoch, the times before
+// the epoch are split with the euclidean division like in DateTime::from_system_clock()
+#[cfg(all(feature = "local", target_os = "linux"))]
+pub(crate) fn get_local_datetime_at(nanos: i128) -> DateTime {
+    let secs = nanos.div_euclid(1_000_000_000);
+    let ltm = get_local_tm_at(secs as time_t);
+    DateTime {
+        date: Date::from_tm(&ltm),
+        time: Time::from_tm(&ltm),
+        nanos: nanos.rem_euclid(1_000_000_000) as u32,
+    }
+}
+
+// Returns the struct tm of the local time at t: time_t (seconds since the Linux epoch)
+#[cfg(all(feature = "local", target_os = "linux"))]
+fn get_local_tm_at(t: time_t) -> tm {
+    let mut ltm = get_empty_tm();
     unsafe {
-        let mut tzi = TIME_ZONE_INFORMATION {
-            Bias: 0,
-            StandardName: [0; 32],
-            StandardDate: SYSTEMTIME {
-                wYear: 0,
-                wMonth: 0,
-                wDayOfWeek: 0,
-                wDay: 0,
-                wHour: 0,
-                wMinute: 0,
-                wSecond: 0,
-                wMilliseconds: 0,
-            },
-            StandardBias: 0,
-            DaylightName: [0; 32],
-            DaylightDate: SYSTEMTIME {
-                wYear: 0,
-                wMonth: 0,
-                wDayOfWeek: 0,
-                wDay: 0,
-                wHour: 0,
-                wMinute: 0,
-                wSecond: 0,
-                wMilliseconds: 0,
-            },
-            DaylightBias: 0,
-        };
-        let tzi_ptr: *mut TIME_ZONE_INFORMATION = &mut tzi;
-        let is_dst = GetTimeZoneInformation(tzi_ptr);
-        let mut bias = tzi.Bias;
-        if is_dst == 2 {
-            bias += tzi.DaylightBias;
+        localtime_r(&t, &mut ltm);
+    }
+    ltm
+}
+
+// Returns the IANA name of the local timezone from the TZ environment variable, the link
+// /etc/localtime or the file /etc/timezone
+#[cfg(not(all(feature = "local", target_os = "windows")))]
+fn get_system_timezone_name() -> Option<String> {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        // the browser knows the IANA name of the timezone of the user
+        let format =
+            js_sys::Intl::DateTimeFormat::new(&js_sys::Array::new(), &js_sys::Object::new());
+        let options = format.resolved_options();
+        let name = js_sys::Reflect::get(&options, &js_sys::JsString::from("timeZone")).ok()?;
+        return name.as_string().filter(|name| !name.is_empty());
+    }
+    #[allow(unreachable_code)]
+    let tz = std::env::var("TZ").unwrap_or_default();
+    let name = tz.trim_start_matches(':');
+    if !name.is_empty() {
+        let name = name.split_once("zoneinfo/").map_or(name, |(_, n)| n);
+        return Some(name.to_string());
+    }
+    if let Ok(path) = std::fs::read_link("/etc/localtime") {
+        if let Some((_, name)) = path.to_string_lossy().split_once("zoneinfo/") {
+            return Some(name.to_string());
         }
-        result = (bias * -1) as i8;
+    }
+    std::fs::read_to_string("/etc/timezone")
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+// Returns the local timezone without the API of the OS, the TZ environment variable or
+// /etc/localtime is used if it is available, else UTC
+#[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+fn get_fallback_zone() -> TimeZone {
+    let result: TimeZone;
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        // the browser gives only the current offset in minutes west of Greenwich
+        let offset = -js_sys::Date::new_0().get_timezone_offset() as i32 * 60;
+        let name = get_system_timezone_name()
+            .unwrap_or_else(|| format!("UTC{}", UtcOffset::from_seconds(offset).as_string()));
+        result = TimeZone::fixed(&name, offset);
+    }
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    {
+        result = TimeZone::from_system().unwrap_or_else(|_| TimeZone::utc());
+    }
+    result
+}
+
+// Returns true if the daylight saving time is currently active in the fallback timezone, in
+// the browser the current offset is compared with the offsets in January and July
+#[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+fn get_fallback_is_dst() -> bool {
+    let result: bool;
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        let now = js_sys::Date::new_0();
+        let year = now.get_full_year();
+        let january = js_sys::Date::new_with_year_month_day(year, 0, 1).get_timezone_offset();
+        let july = js_sys::Date::new_with_year_month_day(year, 6, 1).get_timezone_offset();
+        // the offsets are west of Greenwich, the daylight saving time has the lower one
+        result = january != july && now.get_timezone_offset() == january.min(july);
+    }
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    {
+        result =
+            get_fallback_zone().is_dst_at_timestamp(DateTime::from_system_clock().as_timestamp());
     }
     result
 }
+
+// Returns the current local date and time of the fallback timezone
+#[cfg(not(all(feature = "local", any(target_os = "linux", target_os = "windows"))))]
+fn get_fallback_datetime() -> DateTime {
+    get_fallback_zone().utc_to_local(&DateTime::from_system_clock())
+}
+
+#[cfg(all(feature = "local", target_os = "windows"))]
+impl From<SYSTEMTIME> for Date {
+    /// Creates a ```Date``` from the date of the Win32 ```SYSTEMTIME```. Because ```Date```
+    /// owns its own ```from()``` function, use ```let date: Date = st.into();```.
+    fn from(st: SYSTEMTIME) -> Date {
+        Date::from(st.wDay as u8, st.wMonth as u8, st.wYear as i32)
+    }
+}
+
+#[cfg(all(feature = "local", target_os = "windows"))]
+impl TryFrom<Date> for SYSTEMTIME {
+    type Error = DateTimeError;
+
+    /// Creates a Win32 ```SYSTEMTIME``` from the ```Date```, the time is midnight. The
+    /// ```SYSTEMTIME``` only supports the years 1601 to 30827, it returns an error for the
+    /// other years and an invalid date.
+    fn try_from(date: Date) -> Result<SYSTEMTIME, DateTimeError> {
+        if !is_date_valid(&date) {
+            return Err(DateTimeError::OutOfRange(String::from(
+                "the SYSTEMTIME has an invalid date",
+            )));
+        }
+        if !(1_601..=30_827).contains(&date.y) {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{} is outside of the range of a SYSTEMTIME",
+                date.as_string()
+            )));
+        }
+        let mut st = get_empty_systemtime();
+        st.wYear = date.y as u16;
+        st.wMonth = date.m as u16;
+        st.wDay = date.d as u16;
+        st.wDayOfWeek = date.get_weekday() as u16;
+        Ok(st)
+    }
+}
+
+#[cfg(all(feature = "local", target_os = "windows"))]
+impl From<SYSTEMTIME> for Time {
+    /// Creates a ```Time``` from the time of the Win32 ```SYSTEMTIME```, the milliseconds
+    /// are truncated. Use ```let time: Time = st.into();```.
+    fn from(st: SYSTEMTIME) -> Time {
+        Time::from(st.wHour as i32, st.wMinute as i8, st.wSecond as i8)
+    }
+}
+
+#[cfg(all(feature = "local", target_os = "windows"))]
+impl From<Time> for SYSTEMTIME {
+    /// Creates a Win32 ```SYSTEMTIME``` from the ```Time```, the date fields are zero.
+    fn from(time: Time) -> SYSTEMTIME {
+        let mut st = get_empty_systemtime();
+        st.wHour = time.h as u16;
+        st.wMinute = time.m as u16;
+        st.wSecond = time.s as u16;
+        st
+    }
+}
+
+#[cfg(all(feature = "local", target_os = "windows"))]
+impl From<SYSTEMTIME> for DateTime {
+    /// Creates a ```DateTime``` from the Win32 ```SYSTEMTIME``` including the milliseconds.
+    fn from(st: SYSTEMTIME) -> DateTime {
+        DateTime {
+            date: st.into(),
+            time: st.into(),
+            nanos: st.wMilliseconds as u32 * 1_000_000,
+        }
+    }
+}
+
+#[cfg(all(feature = "local", target_os = "windows"))]
+impl TryFrom<DateTime> for SYSTEMTIME {
+    type Error = DateTimeError;
+
+    /// Creates a Win32 ```SYSTEMTIME``` from the ```DateTime```, the fractions of the
+    /// second are truncated to milliseconds. It returns an error like
+    /// ```SYSTEMTIME::try_from(date)```.
+    fn try_from(dt: DateTime) -> Result<SYSTEMTIME, DateTimeError> {
+        let mut st = SYSTEMTIME::try_from(dt.date)?;
+        st.wHour = dt.time.h as u16;
+        st.wMinute = dt.time.m as u16;
+        st.wSecond = dt.time.s as u16;
+        st.wMilliseconds = (dt.nanos / 1_000_000) as u16;
+        Ok(st)
+    }
+}
+
+// Returns the TIME_ZONE_INFORMATION of the local timezone and true if the daylight saving
+// time is active
+#[cfg(all(feature = "local", target_os = "windows"))]
+fn get_time_zone_information() -> (TIME_ZONE_INFORMATION, bool) {
+    let mut tzi = TIME_ZONE_INFORMATION {
+        Bias: 0,
+        StandardName: [0; 32],
+        StandardDate: get_empty_systemtime(),
+        StandardBias: 0,
+        DaylightName: [0; 32],
+        DaylightDate: get_empty_systemtime(),
+        DaylightBias: 0,
+    };
+    let tzi_ptr: *mut TIME_ZONE_INFORMATION = &mut tzi;
+    let is_dst = unsafe { GetTimeZoneInformation(tzi_ptr) };
+    (tzi, is_dst == 2)
+}
+
+// Returns the String of the zero terminated UTF-16 text: &[u16]
+#[cfg(all(feature = "local", target_os = "windows"))]
+fn utf16_to_string(text: &[u16]) -> String {
+    let len = text.iter().position(|c| *c == 0).unwrap_or(text.len());
+    String::from_utf16_lossy(&text[..len])
+}
+
+// Returns a SYSTEMTIME with all fields zero
+#[cfg(all(feature = "local", target_os = "windows"))]
+fn get_empty_systemtime() -> SYSTEMTIME {
+    SYSTEMTIME {
+        wYear: 0,
+        wMonth: 0,
+        wDayOfWeek: 0,
+        wDay: 0,
+        wHour: 0,
+        wMinute: 0,
+        wSecond: 0,
+        wMilliseconds: 0,
+    }
+}
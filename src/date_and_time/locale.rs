@@ -0,0 +1,844 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::time::*;
+#[cfg(all(feature = "local", target_os = "linux"))]
+use libc::{freelocale, newlocale, nl_item, nl_langinfo_l, LC_TIME_MASK};
+#[cfg(all(feature = "local", target_os = "linux"))]
+use libc::{ABDAY_1, ABMON_1, AM_STR, DAY_1, D_FMT, D_T_FMT, MON_1, PM_STR, T_FMT};
+#[cfg(all(feature = "local", target_os = "linux"))]
+use std::ffi::CStr;
+
+#[cfg(all(feature = "local", target_os = "windows"))]
+use windows_sys::Win32::Globalization::{
+    GetLocaleInfoEx, LOCALE_S1159, LOCALE_S2359, LOCALE_SABBREVDAYNAME1, LOCALE_SABBREVMONTHNAME1,
+    LOCALE_SDAYNAME1, LOCALE_SMONTHNAME1, LOCALE_SSHORTDATE, LOCALE_STIMEFORMAT,
+};
+
+// The names of the bundled locales, the abbreviations are the usual ones of the language.
+#[cfg(feature = "locales")]
+const GERMAN_MONTHS: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+#[cfg(feature = "locales")]
+const GERMAN_MONTHS_ABBREVIATE: [&str; 12] = [
+    "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+];
+#[cfg(feature = "locales")]
+const GERMAN_WEEKDAYS: [&str; 7] = [
+    "Sonntag",
+    "Montag",
+    "Dienstag",
+    "Mittwoch",
+    "Donnerstag",
+    "Freitag",
+    "Samstag",
+];
+#[cfg(feature = "locales")]
+const GERMAN_WEEKDAYS_ABBREVIATE: [&str; 7] = ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"];
+
+#[cfg(feature = "locales")]
+const FRENCH_MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+#[cfg(feature = "locales")]
+const FRENCH_MONTHS_ABBREVIATE: [&str; 12] = [
+    "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+    "déc.",
+];
+#[cfg(feature = "locales")]
+const FRENCH_WEEKDAYS: [&str; 7] = [
+    "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+];
+#[cfg(feature = "locales")]
+const FRENCH_WEEKDAYS_ABBREVIATE: [&str; 7] =
+    ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."];
+
+#[cfg(feature = "locales")]
+const SPANISH_MONTHS: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+#[cfg(feature = "locales")]
+const SPANISH_MONTHS_ABBREVIATE: [&str; 12] = [
+    "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+];
+#[cfg(feature = "locales")]
+const SPANISH_WEEKDAYS: [&str; 7] = [
+    "domingo",
+    "lunes",
+    "martes",
+    "miércoles",
+    "jueves",
+    "viernes",
+    "sábado",
+];
+#[cfg(feature = "locales")]
+const SPANISH_WEEKDAYS_ABBREVIATE: [&str; 7] = ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"];
+
+#[cfg(feature = "locales")]
+const ITALIAN_MONTHS: [&str; 12] = [
+    "gennaio",
+    "febbraio",
+    "marzo",
+    "aprile",
+    "maggio",
+    "giugno",
+    "luglio",
+    "agosto",
+    "settembre",
+    "ottobre",
+    "novembre",
+    "dicembre",
+];
+#[cfg(feature = "locales")]
+const ITALIAN_MONTHS_ABBREVIATE: [&str; 12] = [
+    "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+];
+#[cfg(feature = "locales")]
+const ITALIAN_WEEKDAYS: [&str; 7] = [
+    "domenica",
+    "lunedì",
+    "martedì",
+    "mercoledì",
+    "giovedì",
+    "venerdì",
+    "sabato",
+];
+#[cfg(feature = "locales")]
+const ITALIAN_WEEKDAYS_ABBREVIATE: [&str; 7] = ["dom", "lun", "mar", "mer", "gio", "ven", "sab"];
+
+#[cfg(feature = "locales")]
+const DUTCH_MONTHS: [&str; 12] = [
+    "januari",
+    "februari",
+    "maart",
+    "april",
+    "mei",
+    "juni",
+    "juli",
+    "augustus",
+    "september",
+    "oktober",
+    "november",
+    "december",
+];
+#[cfg(feature = "locales")]
+const DUTCH_MONTHS_ABBREVIATE: [&str; 12] = [
+    "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+];
+#[cfg(feature = "locales")]
+const DUTCH_WEEKDAYS: [&str; 7] = [
+    "zondag",
+    "maandag",
+    "dinsdag",
+    "woensdag",
+    "donderdag",
+    "vrijdag",
+    "zaterdag",
+];
+#[cfg(feature = "locales")]
+const DUTCH_WEEKDAYS_ABBREVIATE: [&str; 7] = ["zo", "ma", "di", "wo", "do", "vr", "za"];
+
+/// The ```Locale``` trait provides the names which are used in human readable texts, e.g.
+/// by ```format_range()``` and ```as_formated_string_localized()``` of ```Date``` and
+/// ```Time```. The crate contains the locale ```English``` and with the feature ```locales```
+/// the locales ```German```, ```French```, ```Spanish```, ```Italian``` and ```Dutch```,
+/// implement the trait for your own translations. The weekday names, the a.m./p.m.
+/// strings and the default formats are English, if they are not implemented.
+///
+/// The English defaults are the US English conventions, not the ones of the C (POSIX)
+/// locale of ```strftime()```: %x is ```%m/%d/%Y``` (C: ```%m/%d/%y```), %X is
+/// ```%I:%M:%S %p``` (C: ```%H:%M:%S```), %c is ```%x %X``` (C: ```%a %b %e %H:%M:%S %Y```)
+/// and %p is ```a.m.``` or ```p.m.``` (C: ```AM``` or ```PM```), but %r has ```AM``` or
+/// ```PM``` like in C. A locale with the C formats only has to implement these methods.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::locale::*;
+/// struct Swedish;
+/// impl Locale for Swedish {
+///     fn month_name(&self, month: u8) -> &str {
+///         ["januari", "februari", "mars", "april", "maj", "juni", "juli", "augusti",
+///          "september", "oktober", "november", "december"][month as usize - 1]
+///     }
+///     fn month_abbreviation(&self, month: u8) -> &str {
+///         &self.month_name(month)[..3]
+///     }
+///     fn weekday_name(&self, weekday: u8) -> &str {
+///         ["söndag", "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag"]
+///             [weekday as usize]
+///     }
+///     fn date_format(&self) -> &str {
+///         "%Y-%m-%d"
+///     }
+/// }
+/// let date = Date::from(22, 6, 2024);
+/// let text = date.as_formated_string_localized("%A den %e %B %Y", &Swedish);
+/// assert_eq!(text, "lördag den 22 juni 2024");
+/// let text = date.as_formated_string_localized(Swedish.date_format(), &Swedish);
+/// assert_eq!(text, "2024-06-22");
+/// ```
+pub trait Locale {
+    /// ```month_name(month)``` returns the full name of the ```month``` (1 to 12).
+    fn month_name(&self, month: u8) -> &str;
+    /// ```month_abbreviation(month)``` returns the abbreviated name of the ```month``` (1
+    /// to 12).
+    fn month_abbreviation(&self, month: u8) -> &str;
+    /// ```weekday_name(weekday)``` returns the full name of the ```weekday``` (0 = Sunday
+    /// to 6 = Saturday, like ```Date::get_weekday()```).
+    fn weekday_name(&self, weekday: u8) -> &str {
+        WEEKDAY_FULL.get(weekday as usize).copied().unwrap_or("")
+    }
+    /// ```weekday_abbreviation(weekday)``` returns the abbreviated name of the
+    /// ```weekday``` (0 = Sunday to 6 = Saturday).
+    fn weekday_abbreviation(&self, weekday: u8) -> &str {
+        WEEKDAY_ABBREVIATE
+            .get(weekday as usize)
+            .copied()
+            .unwrap_or("")
+    }
+    /// ```meridiem(meridiem)``` returns the string of the placeholder %p, e.g. "a.m." and
+    /// "p.m.".
+    fn meridiem(&self, meridiem: Meridiem) -> &str {
+        match meridiem {
+            Meridiem::AM => "a.m.",
+            Meridiem::PM => "p.m.",
+        }
+    }
+    /// ```meridiem_short(meridiem)``` returns the string of the placeholder %r, like
+    /// ```strftime()``` it is "AM" and "PM" for English.
+    fn meridiem_short(&self, meridiem: Meridiem) -> &str {
+        match meridiem {
+            Meridiem::AM => "AM",
+            Meridiem::PM => "PM",
+        }
+    }
+    /// ```ordinal_suffix(day)``` returns the suffix of the placeholder %o which is written
+    /// after the ```day``` of the month, e.g. "st" of 1st and "nd" of 22nd.
+    fn ordinal_suffix(&self, day: u8) -> &str {
+        match (day % 10, day % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        }
+    }
+    /// ```date_format()``` returns the usual date format of the locale, it is written by
+    /// the placeholder %x.
+    fn date_format(&self) -> &str {
+        "%m/%d/%Y"
+    }
+    /// ```time_format()``` returns the usual time format of the locale, it is written by
+    /// the placeholder %X.
+    fn time_format(&self) -> &str {
+        "%I:%M:%S %p"
+    }
+    /// ```datetime_format()``` returns the usual format of the date and the time of the
+    /// locale, it is written by the placeholder %c.
+    fn datetime_format(&self) -> &str {
+        "%x %X"
+    }
+}
+
+/// The English ```Locale```, the names of ```as_formated_string()``` with the US English
+/// formats, see the differences to the C locale at ```Locale```.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct English;
+
+impl Locale for English {
+    fn month_name(&self, month: u8) -> &str {
+        let idx = (month as usize).wrapping_sub(1);
+        MONTH_NAME_FULL.get(idx).copied().unwrap_or("")
+    }
+    fn month_abbreviation(&self, month: u8) -> &str {
+        let idx = (month as usize).wrapping_sub(1);
+        MONTH_NAME_ABBREVIATE.get(idx).copied().unwrap_or("")
+    }
+}
+
+/// The German ```Locale```, the date format is "%d.%m.%Y". The locales ```German```,
+/// ```French```, ```Spanish```, ```Italian``` and ```Dutch``` need the feature
+/// ```locales```.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::locale::*;
+/// let date = Date::from(22, 6, 2024);
+/// let text = date.as_formated_string_localized("%A, %e. %B %Y", &German);
+/// assert_eq!(text, "Samstag, 22. Juni 2024");
+/// assert_eq!(format_range(date, Date::from(25, 6, 2024), &German), "22–25 Juni 2024");
+/// ```
+#[cfg(feature = "locales")]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct German;
+
+#[cfg(feature = "locales")]
+impl Locale for German {
+    fn month_name(&self, month: u8) -> &str {
+        get_locale_name(&GERMAN_MONTHS, (month as usize).wrapping_sub(1))
+    }
+    fn month_abbreviation(&self, month: u8) -> &str {
+        get_locale_name(&GERMAN_MONTHS_ABBREVIATE, (month as usize).wrapping_sub(1))
+    }
+    fn weekday_name(&self, weekday: u8) -> &str {
+        get_locale_name(&GERMAN_WEEKDAYS, weekday as usize)
+    }
+    fn weekday_abbreviation(&self, weekday: u8) -> &str {
+        get_locale_name(&GERMAN_WEEKDAYS_ABBREVIATE, weekday as usize)
+    }
+    fn meridiem(&self, meridiem: Meridiem) -> &str {
+        match meridiem {
+            Meridiem::AM => "vorm.",
+            Meridiem::PM => "nachm.",
+        }
+    }
+    fn meridiem_short(&self, meridiem: Meridiem) -> &str {
+        self.meridiem(meridiem)
+    }
+    fn ordinal_suffix(&self, _: u8) -> &str {
+        "."
+    }
+    fn date_format(&self) -> &str {
+        "%d.%m.%Y"
+    }
+    fn time_format(&self) -> &str {
+        "%H:%M:%S"
+    }
+}
+
+/// The French ```Locale```, the date format is "%d/%m/%Y".
+#[cfg(feature = "locales")]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct French;
+
+#[cfg(feature = "locales")]
+impl Locale for French {
+    fn month_name(&self, month: u8) -> &str {
+        get_locale_name(&FRENCH_MONTHS, (month as usize).wrapping_sub(1))
+    }
+    fn month_abbreviation(&self, month: u8) -> &str {
+        get_locale_name(&FRENCH_MONTHS_ABBREVIATE, (month as usize).wrapping_sub(1))
+    }
+    fn weekday_name(&self, weekday: u8) -> &str {
+        get_locale_name(&FRENCH_WEEKDAYS, weekday as usize)
+    }
+    fn weekday_abbreviation(&self, weekday: u8) -> &str {
+        get_locale_name(&FRENCH_WEEKDAYS_ABBREVIATE, weekday as usize)
+    }
+    fn meridiem(&self, meridiem: Meridiem) -> &str {
+        match meridiem {
+            Meridiem::AM => "AM",
+            Meridiem::PM => "PM",
+        }
+    }
+    fn ordinal_suffix(&self, day: u8) -> &str {
+        if day == 1 {
+            "er"
+        } else {
+            ""
+        }
+    }
+    fn date_format(&self) -> &str {
+        "%d/%m/%Y"
+    }
+    fn time_format(&self) -> &str {
+        "%H:%M:%S"
+    }
+}
+
+/// The Spanish ```Locale```, the date format is "%d/%m/%Y".
+#[cfg(feature = "locales")]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Spanish;
+
+#[cfg(feature = "locales")]
+impl Locale for Spanish {
+    fn month_name(&self, month: u8) -> &str {
+        get_locale_name(&SPANISH_MONTHS, (month as usize).wrapping_sub(1))
+    }
+    fn month_abbreviation(&self, month: u8) -> &str {
+        get_locale_name(&SPANISH_MONTHS_ABBREVIATE, (month as usize).wrapping_sub(1))
+    }
+    fn weekday_name(&self, weekday: u8) -> &str {
+        get_locale_name(&SPANISH_WEEKDAYS, weekday as usize)
+    }
+    fn weekday_abbreviation(&self, weekday: u8) -> &str {
+        get_locale_name(&SPANISH_WEEKDAYS_ABBREVIATE, weekday as usize)
+    }
+    fn meridiem(&self, meridiem: Meridiem) -> &str {
+        match meridiem {
+            Meridiem::AM => "a. m.",
+            Meridiem::PM => "p. m.",
+        }
+    }
+    fn meridiem_short(&self, meridiem: Meridiem) -> &str {
+        self.meridiem(meridiem)
+    }
+    fn ordinal_suffix(&self, day: u8) -> &str {
+        if day == 1 {
+            "º"
+        } else {
+            ""
+        }
+    }
+    fn date_format(&self) -> &str {
+        "%d/%m/%Y"
+    }
+    fn time_format(&self) -> &str {
+        "%H:%M:%S"
+    }
+}
+
+/// The Italian ```Locale```, the date format is "%d/%m/%Y".
+#[cfg(feature = "locales")]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Italian;
+
+#[cfg(feature = "locales")]
+impl Locale for Italian {
+    fn month_name(&self, month: u8) -> &str {
+        get_locale_name(&ITALIAN_MONTHS, (month as usize).wrapping_sub(1))
+    }
+    fn month_abbreviation(&self, month: u8) -> &str {
+        get_locale_name(&ITALIAN_MONTHS_ABBREVIATE, (month as usize).wrapping_sub(1))
+    }
+    fn weekday_name(&self, weekday: u8) -> &str {
+        get_locale_name(&ITALIAN_WEEKDAYS, weekday as usize)
+    }
+    fn weekday_abbreviation(&self, weekday: u8) -> &str {
+        get_locale_name(&ITALIAN_WEEKDAYS_ABBREVIATE, weekday as usize)
+    }
+    fn meridiem(&self, meridiem: Meridiem) -> &str {
+        match meridiem {
+            Meridiem::AM => "AM",
+            Meridiem::PM => "PM",
+        }
+    }
+    fn ordinal_suffix(&self, day: u8) -> &str {
+        if day == 1 {
+            "º"
+        } else {
+            ""
+        }
+    }
+    fn date_format(&self) -> &str {
+        "%d/%m/%Y"
+    }
+    fn time_format(&self) -> &str {
+        "%H:%M:%S"
+    }
+}
+
+/// The Dutch ```Locale```, the date format is "%d-%m-%Y".
+#[cfg(feature = "locales")]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Dutch;
+
+#[cfg(feature = "locales")]
+impl Locale for Dutch {
+    fn month_name(&self, month: u8) -> &str {
+        get_locale_name(&DUTCH_MONTHS, (month as usize).wrapping_sub(1))
+    }
+    fn month_abbreviation(&self, month: u8) -> &str {
+        get_locale_name(&DUTCH_MONTHS_ABBREVIATE, (month as usize).wrapping_sub(1))
+    }
+    fn weekday_name(&self, weekday: u8) -> &str {
+        get_locale_name(&DUTCH_WEEKDAYS, weekday as usize)
+    }
+    fn weekday_abbreviation(&self, weekday: u8) -> &str {
+        get_locale_name(&DUTCH_WEEKDAYS_ABBREVIATE, weekday as usize)
+    }
+    fn meridiem(&self, meridiem: Meridiem) -> &str {
+        match meridiem {
+            Meridiem::AM => "a.m.",
+            Meridiem::PM => "p.m.",
+        }
+    }
+    fn meridiem_short(&self, meridiem: Meridiem) -> &str {
+        self.meridiem(meridiem)
+    }
+    fn ordinal_suffix(&self, _: u8) -> &str {
+        "e"
+    }
+    fn date_format(&self) -> &str {
+        "%d-%m-%Y"
+    }
+    fn time_format(&self) -> &str {
+        "%H:%M:%S"
+    }
+}
+
+/// The ```SystemLocale``` contains the names and the formats of the locale of the running
+/// system. With the feature ```local``` ```from_system()``` reads them from the ```LC_TIME```
+/// locale of the environment (```nl_langinfo()```) on Linux and from the user locale
+/// (```GetLocaleInfoEx()```) on Windows, on other systems and for the texts which the system
+/// doesn't provide the English names and formats are used.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::locale::*;
+/// let locale = SystemLocale::from_system();
+/// let date = Date::from(22, 6, 2024);
+/// println!("{}", date.as_formated_string_localized(locale.date_format(), &locale));
+/// println!("{}", date.as_formated_string_localized("%A, %e %B %Y", &locale));
+/// assert!(!locale.month_name(6).is_empty());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SystemLocale {
+    months: [String; 12],
+    month_abbreviations: [String; 12],
+    weekdays: [String; 7],
+    weekday_abbreviations: [String; 7],
+    am: String,
+    pm: String,
+    date_format: String,
+    time_format: String,
+    datetime_format: String,
+}
+
+#[allow(dead_code)]
+impl SystemLocale {
+    /// ```from_system()``` creates a ```SystemLocale``` with the names and the formats of
+    /// the locale of the running system. Like the other functions of the feature ```local```
+    /// it only queries Linux and Windows, on macOS, the BSDs and the other Unix systems
+    /// (which have ```nl_langinfo_l()``` too) the locale is English.
+    pub fn from_system() -> SystemLocale {
+        // the locale is only changed on the systems with the feature local
+        #[allow(unused_mut)]
+        let mut locale = SystemLocale {
+            months: MONTH_NAME_FULL.map(String::from),
+            month_abbreviations: MONTH_NAME_ABBREVIATE.map(String::from),
+            weekdays: WEEKDAY_FULL.map(String::from),
+            weekday_abbreviations: WEEKDAY_ABBREVIATE.map(String::from),
+            am: String::from(English.meridiem(Meridiem::AM)),
+            pm: String::from(English.meridiem(Meridiem::PM)),
+            date_format: String::from(English.date_format()),
+            time_format: String::from(English.time_format()),
+            datetime_format: String::from(English.datetime_format()),
+        };
+        #[cfg(all(feature = "local", target_os = "linux"))]
+        {
+            // a locale of the environment (LC_ALL, LC_TIME or LANG) for this thread only,
+            // setlocale() would change the locale of the whole process
+            let loc = unsafe { newlocale(LC_TIME_MASK, c"".as_ptr(), std::ptr::null_mut()) };
+            if !loc.is_null() {
+                let text = |item: nl_item| {
+                    let ptr = unsafe { nl_langinfo_l(item, loc) };
+                    if ptr.is_null() {
+                        return None;
+                    }
+                    let text = unsafe { CStr::from_ptr(ptr) }.to_string_lossy();
+                    Some(String::from(text))
+                };
+                for n in 0..12 {
+                    set_system_text(&mut locale.months[n], text(MON_1 + n as nl_item));
+                    set_system_text(
+                        &mut locale.month_abbreviations[n],
+                        text(ABMON_1 + n as nl_item),
+                    );
+                }
+                for n in 0..7 {
+                    set_system_text(&mut locale.weekdays[n], text(DAY_1 + n as nl_item));
+                    set_system_text(
+                        &mut locale.weekday_abbreviations[n],
+                        text(ABDAY_1 + n as nl_item),
+                    );
+                }
+                set_system_text(&mut locale.am, text(AM_STR));
+                set_system_text(&mut locale.pm, text(PM_STR));
+                set_system_text(&mut locale.date_format, text(D_FMT));
+                set_system_text(&mut locale.time_format, text(T_FMT));
+                set_system_text(&mut locale.datetime_format, text(D_T_FMT));
+                unsafe { freelocale(loc) };
+            }
+        }
+        #[cfg(all(feature = "local", target_os = "windows"))]
+        {
+            for n in 0..12 {
+                set_system_text(
+                    &mut locale.months[n],
+                    get_locale_info(LOCALE_SMONTHNAME1 + n as u32),
+                );
+                set_system_text(
+                    &mut locale.month_abbreviations[n],
+                    get_locale_info(LOCALE_SABBREVMONTHNAME1 + n as u32),
+                );
+            }
+            // the first day of the names of Windows is Monday
+            for n in 0..7 {
+                let day = (n as u32 + 6) % 7;
+                set_system_text(
+                    &mut locale.weekdays[n],
+                    get_locale_info(LOCALE_SDAYNAME1 + day),
+                );
+                set_system_text(
+                    &mut locale.weekday_abbreviations[n],
+                    get_locale_info(LOCALE_SABBREVDAYNAME1 + day),
+                );
+            }
+            set_system_text(&mut locale.am, get_locale_info(LOCALE_S1159));
+            set_system_text(&mut locale.pm, get_locale_info(LOCALE_S2359));
+            set_system_text(
+                &mut locale.date_format,
+                get_locale_info(LOCALE_SSHORTDATE).map(|f| get_strftime_format(&f)),
+            );
+            set_system_text(
+                &mut locale.time_format,
+                get_locale_info(LOCALE_STIMEFORMAT).map(|f| get_strftime_format(&f)),
+            );
+        }
+        locale
+    }
+}
+
+impl Locale for SystemLocale {
+    fn month_name(&self, month: u8) -> &str {
+        let idx = (month as usize).wrapping_sub(1);
+        self.months.get(idx).map_or("", |s| s.as_str())
+    }
+    fn month_abbreviation(&self, month: u8) -> &str {
+        let idx = (month as usize).wrapping_sub(1);
+        self.month_abbreviations.get(idx).map_or("", |s| s.as_str())
+    }
+    fn weekday_name(&self, weekday: u8) -> &str {
+        self.weekdays
+            .get(weekday as usize)
+            .map_or("", |s| s.as_str())
+    }
+    fn weekday_abbreviation(&self, weekday: u8) -> &str {
+        self.weekday_abbreviations
+            .get(weekday as usize)
+            .map_or("", |s| s.as_str())
+    }
+    fn meridiem(&self, meridiem: Meridiem) -> &str {
+        match meridiem {
+            Meridiem::AM => &self.am,
+            Meridiem::PM => &self.pm,
+        }
+    }
+    fn meridiem_short(&self, meridiem: Meridiem) -> &str {
+        self.meridiem(meridiem)
+    }
+    fn date_format(&self) -> &str {
+        &self.date_format
+    }
+    fn time_format(&self) -> &str {
+        &self.time_format
+    }
+    fn datetime_format(&self) -> &str {
+        &self.datetime_format
+    }
+}
+
+/// ```format_range(a, b, &locale)``` returns a short label for the dates from ```a``` to
+/// ```b``` (both included), the parts which both dates have in common are written only
+/// once. Ranges of whole months or years are written without the days.
+///
+/// | Range | Label |
+/// | ----- | ----- |
+/// | 2024-06-22 to 2024-06-25 | 22–25 June 2024 |
+/// | 2024-06-22 to 2024-07-03 | 22 June – 3 July 2024 |
+/// | 2024-12-28 to 2025-01-03 | 28 Dec 2024 – 3 Jan 2025 |
+/// | 2024-06-01 to 2024-07-31 | June–July 2024 |
+/// | 2024-12-01 to 2025-01-31 | Dec 2024 – Jan 2025 |
+/// | 2024-01-01 to 2024-12-31 | 2024 |
+///
+/// The order of ```a``` and ```b``` doesn't matter. If one of the dates is invalid, both
+/// dates are written in the ISO 8601 format.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::locale::*;
+/// let label = format_range(Date::from(22, 6, 2024), Date::from(25, 6, 2024), &English);
+/// assert_eq!(label, "22–25 June 2024");
+/// let label = format_range(Date::from(1, 12, 2024), Date::from(31, 1, 2025), &English);
+/// assert_eq!(label, "Dec 2024 – Jan 2025");
+/// ```
+pub fn format_range(a: Date, b: Date, locale: &(impl Locale + ?Sized)) -> String {
+    if !is_date_valid(&a) || !is_date_valid(&b) {
+        return format!("{} – {}", a.as_string(), b.as_string());
+    }
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+    let whole_months = a.d == 1 && b.d == get_max_days_of_month(b.m, b.y);
+    if whole_months {
+        if a.m == 1 && b.m == 12 {
+            if a.y == b.y {
+                return format!("{}", a.y);
+            }
+            return format!("{}–{}", a.y, b.y);
+        }
+        if a.y != b.y {
+            return format!(
+                "{} {} – {} {}",
+                locale.month_abbreviation(a.m),
+                a.y,
+                locale.month_abbreviation(b.m),
+                b.y
+            );
+        }
+        if a.m == b.m {
+            return format!("{} {}", locale.month_name(a.m), a.y);
+        }
+        return format!(
+            "{}–{} {}",
+            locale.month_name(a.m),
+            locale.month_name(b.m),
+            a.y
+        );
+    }
+    if a.y != b.y {
+        return format!(
+            "{} {} {} – {} {} {}",
+            a.d,
+            locale.month_abbreviation(a.m),
+            a.y,
+            b.d,
+            locale.month_abbreviation(b.m),
+            b.y
+        );
+    }
+    if a.m != b.m {
+        return format!(
+            "{} {} – {} {} {}",
+            a.d,
+            locale.month_name(a.m),
+            b.d,
+            locale.month_name(b.m),
+            b.y
+        );
+    }
+    if a.d != b.d {
+        return format!("{}–{} {} {}", a.d, b.d, locale.month_name(b.m), b.y);
+    }
+    format!("{} {} {}", a.d, locale.month_name(a.m), a.y)
+}
+
+// Returns the name at the idx of the names, or an empty string if the idx is out of range
+#[cfg(feature = "locales")]
+fn get_locale_name(names: &'static [&'static str], idx: usize) -> &'static str {
+    names.get(idx).copied().unwrap_or("")
+}
+
+// Sets the target to the text of the system, if the system provides a text which is not
+// empty
+#[cfg(all(feature = "local", any(target_os = "linux", target_os = "windows")))]
+fn set_system_text(target: &mut String, text: Option<String>) {
+    if let Some(text) = text {
+        if !text.is_empty() {
+            *target = text;
+        }
+    }
+}
+
+// Returns the text of the lctype of the user locale of Windows, None if there is no text
+#[cfg(all(feature = "local", target_os = "windows"))]
+fn get_locale_info(lctype: u32) -> Option<String> {
+    let mut buffer = [0u16; 128];
+    let len = unsafe {
+        GetLocaleInfoEx(
+            std::ptr::null(),
+            lctype,
+            buffer.as_mut_ptr(),
+            buffer.len() as i32,
+        )
+    };
+    // the length includes the terminating zero
+    if len <= 1 {
+        return None;
+    }
+    Some(String::from_utf16_lossy(&buffer[..len as usize - 1]))
+}
+
+// Returns the strftime() format of a date or time picture of Windows, e.g. "%d.%m.%Y" of
+// "dd.MM.yyyy" and "%I:%M:%S %p" of "hh:mm:ss tt", the text in single quotes is literal
+#[cfg(any(test, all(feature = "local", target_os = "windows")))]
+pub(crate) fn get_strftime_format(picture: &str) -> String {
+    let mut result = String::new();
+    let mut chars = picture.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut count = 1;
+        while chars.peek() == Some(&c) && c != '\'' {
+            chars.next();
+            count += 1;
+        }
+        let placeholder = match (c, count) {
+            ('d', 1..=2) => "%d",
+            ('d', 3) => "%a",
+            ('d', _) => "%A",
+            ('M', 1..=2) => "%m",
+            ('M', 3) => "%b",
+            ('M', _) => "%B",
+            ('y', 1..=2) => "%y",
+            ('y', _) => "%Y",
+            ('H', _) => "%H",
+            ('h', _) => "%I",
+            ('m', _) => "%M",
+            ('s', _) => "%S",
+            ('t', _) => "%p",
+            // the era, e.g. "A.D."
+            ('g', _) => "",
+            ('\'', _) => {
+                // two single quotes are a literal single quote
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                    result.push('\'');
+                    continue;
+                }
+                for l in chars.by_ref() {
+                    match l {
+                        '\'' => break,
+                        '%' => result.push_str("%%"),
+                        _ => result.push(l),
+                    }
+                }
+                ""
+            }
+            ('%', _) => {
+                result.push_str(&"%%".repeat(count));
+                ""
+            }
+            _ => {
+                result.push_str(&c.to_string().repeat(count));
+                ""
+            }
+        };
+        result.push_str(placeholder);
+    }
+    result
+}
@@ -0,0 +1,89 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+
+/// The UtcOffset structure is the offset of a local time to UTC in seconds, positive east
+/// of Greenwich. It can be build with the functions ```from_seconds()``` and
+/// ```from_hours_minutes()```.
+///
+/// Not all timezones have whole hours (e.g. India +05:30, Nepal +05:45), use
+/// ```offset_hours()``` together with ```offset_minutes()``` or ```as_seconds()```.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UtcOffset {
+    seconds: i32,
+}
+
+#[allow(dead_code)]
+impl UtcOffset {
+    /// ```from_seconds(seconds)``` creates an ```UtcOffset``` of the ```seconds``` east of
+    /// Greenwich.
+    ///
+    /// ```
+    /// use date_and_time::offset::*;
+    /// assert_eq!(UtcOffset::from_seconds(19_800).as_string(), "+05:30");
+    /// ```
+    pub fn from_seconds(seconds: i32) -> UtcOffset {
+        UtcOffset { seconds }
+    }
+    /// ```from_hours_minutes(hours, minutes)``` creates an ```UtcOffset``` of the
+    /// ```hours``` and ```minutes```, both must have the same sign for offsets west of
+    /// Greenwich.
+    ///
+    /// ```
+    /// use date_and_time::offset::*;
+    /// assert_eq!(UtcOffset::from_hours_minutes(-3, -30).as_seconds(), -12_600);
+    /// ```
+    pub fn from_hours_minutes(hours: i32, minutes: i32) -> UtcOffset {
+        UtcOffset {
+            seconds: hours * 3_600 + minutes * 60,
+        }
+    }
+    /// ```as_seconds()``` returns the offset in seconds.
+    ///
+    /// ```
+    /// use date_and_time::offset::*;
+    /// assert_eq!(UtcOffset::from_hours_minutes(5, 45).as_seconds(), 20_700);
+    /// ```
+    pub fn as_seconds(&self) -> i32 {
+        self.seconds
+    }
+    /// ```offset_hours()``` returns the whole hours of the offset, the result has the sign
+    /// of the offset.
+    ///
+    /// ```
+    /// use date_and_time::offset::*;
+    /// assert_eq!(UtcOffset::from_seconds(-12_600).offset_hours(), -3);
+    /// ```
+    pub fn offset_hours(&self) -> i32 {
+        self.seconds / 3_600
+    }
+    /// ```offset_minutes()``` returns the minutes of the offset without the whole hours,
+    /// the result has the sign of the offset.
+    ///
+    /// ```
+    /// use date_and_time::offset::*;
+    /// assert_eq!(UtcOffset::from_seconds(-12_600).offset_minutes(), -30);
+    /// ```
+    pub fn offset_minutes(&self) -> i32 {
+        self.seconds % 3_600 / 60
+    }
+    /// ```as_string()``` gets the ```UtcOffset``` as a string in the format +HH:MM (ISO
+    /// 8601), the seconds are added as +HH:MM:SS if they are not zero.
+    ///
+    /// ```
+    /// use date_and_time::offset::*;
+    /// assert_eq!(UtcOffset::from_seconds(0).as_string(), "+00:00");
+    /// assert_eq!(UtcOffset::from_seconds(-1_800).as_string(), "-00:30");
+    /// ```
+    pub fn as_string(&self) -> String {
+        let sign = if self.seconds < 0 { '-' } else { '+' };
+        let secs = self.seconds.unsigned_abs();
+        let mut s = format!("{}{:02}:{:02}", sign, secs / 3_600, secs % 3_600 / 60);
+        if !secs.is_multiple_of(60) {
+            s.push_str(&format!(":{:02}", secs % 60));
+        }
+        s
+    }
+}
@@ -0,0 +1,412 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// The format of the TZ strings is described in the POSIX standard (section 8.3) and with
+// the extensions of RFC 8536 (section 3.3.1).
+//
+use crate::date_and_time::date::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::time::*;
+
+/// The day of a DST transition in a POSIX TZ rule.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TzRuleDate {
+    /// ```Jn```: the day of the year from 1 to 365, February 29 is never counted.
+    Julian(u16),
+    /// ```n```: the zero-based day of the year from 0 to 365, February 29 is counted.
+    DayOfYear(u16),
+    /// ```Mm.w.d```: the weekday ```d``` (0 = Sunday) of the week ```w``` (1 to 5, where
+    /// 5 means the last one) in the month ```m```.
+    MonthWeekDay { month: u8, week: u8, weekday: u8 },
+}
+
+/// A DST transition in a POSIX TZ rule, ```time``` is the local wall clock time of the
+/// transition in seconds (it can be negative or more than 24 hours).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TzRuleTransition {
+    pub date: TzRuleDate,
+    pub time: i32,
+}
+
+/// The daylight saving part of a POSIX TZ rule, ```offset``` is the offset to UTC in
+/// seconds (positive east of Greenwich).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TzRuleDst {
+    pub name: String,
+    pub offset: i32,
+    pub start: TzRuleTransition,
+    pub end: TzRuleTransition,
+}
+
+/// The ```TzRule``` structure is a parsed POSIX TZ string, e.g.
+/// ```CET-1CEST,M3.5.0,M10.5.0/3``` for central Europe. It can be build with
+/// ```parse()``` and describes the standard time and the optional daylight saving time.
+///
+/// Attention: in the TZ string the offsets are written west of Greenwich (CET-1), the
+/// offsets in the ```TzRule``` are positive east of Greenwich like everywhere else in the
+/// crate (CET = 3600 seconds).
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TzRule {
+    pub std_name: String,
+    pub std_offset: i32,
+    pub dst: Option<TzRuleDst>,
+}
+
+#[allow(dead_code)]
+impl TzRule {
+    /// ```parse(text)``` parses a POSIX TZ string. If a daylight saving name is given
+    /// without transition rules, the US rules ```M3.2.0,M11.1.0``` are used.
+    ///
+    /// ```
+    /// use date_and_time::posix_tz::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// assert_eq!(rule.std_offset, 3_600);
+    /// assert_eq!(rule.dst.unwrap().name, "CEST");
+    /// assert!(TzRule::parse("C").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<TzRule, DateTimeError> {
+        enter_trace_span!("TzRule::parse", text);
+        let mut parser = TzParser {
+            text,
+            bytes: text.as_bytes(),
+            pos: 0,
+        };
+        let std_name = parser.name()?;
+        let std_offset = -parser.offset()?;
+        if parser.at_end() {
+            return Ok(TzRule {
+                std_name,
+                std_offset,
+                dst: None,
+            });
+        }
+        let dst_name = parser.name()?;
+        let dst_offset = match parser.peek() {
+            Some(b',') | None => std_offset + 3_600,
+            _ => -parser.offset()?,
+        };
+        let (start, end) = if parser.at_end() {
+            (
+                TzRuleTransition {
+                    date: TzRuleDate::MonthWeekDay {
+                        month: 3,
+                        week: 2,
+                        weekday: 0,
+                    },
+                    time: 7_200,
+                },
+                TzRuleTransition {
+                    date: TzRuleDate::MonthWeekDay {
+                        month: 11,
+                        week: 1,
+                        weekday: 0,
+                    },
+                    time: 7_200,
+                },
+            )
+        } else {
+            parser.expect(b',')?;
+            let start = parser.transition()?;
+            parser.expect(b',')?;
+            let end = parser.transition()?;
+            (start, end)
+        };
+        if !parser.at_end() {
+            return Err(parser.error("unexpected characters at the end"));
+        }
+        Ok(TzRule {
+            std_name,
+            std_offset,
+            dst: Some(TzRuleDst {
+                name: dst_name,
+                offset: dst_offset,
+                start,
+                end,
+            }),
+        })
+    }
+    /// ```is_dst_at(&date, &time)``` returns true if the daylight saving time applies at
+    /// the given UTC ```date``` and ```time```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::time::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// assert!(rule.is_dst_at(&Date::from(1, 7, 2024), &Time::from(12, 0, 0)));
+    /// assert!(!rule.is_dst_at(&Date::from(1, 1, 2024), &Time::from(12, 0, 0)));
+    /// ```
+    pub fn is_dst_at(&self, date: &Date, time: &Time) -> bool {
+        self.is_dst_at_timestamp(utc_timestamp(date, time))
+    }
+    /// ```offset_at(&date, &time)``` returns the offset to UTC in seconds which applies at
+    /// the given UTC ```date``` and ```time```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::time::*;
+    /// let rule = TzRule::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+    /// assert_eq!(rule.offset_at(&Date::from(1, 1, 2024), &Time::new()), 39_600);
+    /// ```
+    pub fn offset_at(&self, date: &Date, time: &Time) -> i32 {
+        self.offset_at_timestamp(utc_timestamp(date, time))
+    }
+    /// ```abbreviation_at(&date, &time)``` returns the name of the local time (e.g. CET or
+    /// CEST) which applies at the given UTC ```date``` and ```time```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::time::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// assert_eq!(rule.abbreviation_at(&Date::from(1, 7, 2024), &Time::new()), "CEST");
+    /// ```
+    pub fn abbreviation_at(&self, date: &Date, time: &Time) -> &str {
+        match &self.dst {
+            Some(dst) if self.is_dst_at(date, time) => &dst.name,
+            _ => &self.std_name,
+        }
+    }
+    /// ```is_dst_at_timestamp(unix_secs)``` returns true if the daylight saving time
+    /// applies at the given seconds since the Linux epoch.
+    ///
+    /// ```
+    /// use date_and_time::posix_tz::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// // 2024-03-31 01:00:00 UTC is the begin of the summer time
+    /// assert!(!rule.is_dst_at_timestamp(1_711_846_799));
+    /// assert!(rule.is_dst_at_timestamp(1_711_846_800));
+    /// ```
+    pub fn is_dst_at_timestamp(&self, unix_secs: i64) -> bool {
+        let Some(dst) = &self.dst else {
+            return false;
+        };
+        // the transitions are calculated for the year of the local standard time
+        let local_days = (unix_secs + self.std_offset as i64).div_euclid(86_400);
+        let year = get_date_from_days(local_days).y;
+        let start = transition_days(&dst.start.date, year) * 86_400 + dst.start.time as i64
+            - self.std_offset as i64;
+        let end =
+            transition_days(&dst.end.date, year) * 86_400 + dst.end.time as i64 - dst.offset as i64;
+        if start < end {
+            unix_secs >= start && unix_secs < end
+        } else {
+            // southern hemisphere, the daylight saving time spans the new year
+            unix_secs < end || unix_secs >= start
+        }
+    }
+    /// ```offset_at_timestamp(unix_secs)``` returns the offset to UTC in seconds which
+    /// applies at the given seconds since the Linux epoch.
+    ///
+    /// ```
+    /// use date_and_time::posix_tz::*;
+    /// let rule = TzRule::parse("<+0530>-5:30").unwrap();
+    /// assert_eq!(rule.offset_at_timestamp(0), 19_800);
+    /// ```
+    pub fn offset_at_timestamp(&self, unix_secs: i64) -> i32 {
+        match &self.dst {
+            Some(dst) if self.is_dst_at_timestamp(unix_secs) => dst.offset,
+            _ => self.std_offset,
+        }
+    }
+}
+
+// Returns the seconds since the Linux epoch of the UTC date: &Date and time: &Time
+pub(crate) fn utc_timestamp(date: &Date, time: &Time) -> i64 {
+    get_days_from_date(date) * 86_400 + time.h as i64 * 3_600 + time.m as i64 * 60 + time.s as i64
+}
+
+// Returns the days since the Linux epoch of the transition date: &TzRuleDate in year: i32
+fn transition_days(date: &TzRuleDate, year: i32) -> i64 {
+    let jan1 = get_days_from_date(&Date {
+        d: 1,
+        m: 1,
+        y: year,
+    });
+    match *date {
+        TzRuleDate::Julian(n) => {
+            let mut days = jan1 + n as i64 - 1;
+            if is_leap_year(year) && n >= 60 {
+                days += 1;
+            }
+            days
+        }
+        TzRuleDate::DayOfYear(n) => jan1 + n as i64,
+        TzRuleDate::MonthWeekDay {
+            month,
+            week,
+            weekday,
+        } => {
+            let first = Date {
+                d: 1,
+                m: month,
+                y: year,
+            };
+            let first_wday = first.get_weekday();
+            let mut day = 1 + (weekday + 7 - first_wday) % 7 + (week - 1) * 7;
+            let max_days = get_max_days_of_month(month, year);
+            while day > max_days {
+                day -= 7;
+            }
+            get_days_from_date(&Date {
+                d: day,
+                m: month,
+                y: year,
+            })
+        }
+    }
+}
+
+// A small parser over the bytes of the TZ string
+struct TzParser<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl TzParser<'_> {
+    fn error(&self, reason: &str) -> DateTimeError {
+        DateTimeError::Parse(format!(
+            "{} at position {} in TZ string '{}'",
+            reason, self.pos, self.text
+        ))
+    }
+    fn at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+    fn expect(&mut self, c: u8) -> Result<(), DateTimeError> {
+        if self.peek() != Some(c) {
+            return Err(self.error(&format!("expected '{}'", c as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+    // Returns a zone name, either at least 3 letters or quoted in <>
+    fn name(&mut self) -> Result<String, DateTimeError> {
+        let start = self.pos;
+        if self.peek() == Some(b'<') {
+            self.pos += 1;
+            while let Some(c) = self.peek() {
+                if c == b'>' {
+                    break;
+                }
+                if !c.is_ascii_alphanumeric() && c != b'+' && c != b'-' {
+                    return Err(self.error("invalid character in quoted zone name"));
+                }
+                self.pos += 1;
+            }
+            self.expect(b'>')?;
+            if self.pos - start < 5 {
+                return Err(self.error("zone name is too short"));
+            }
+            return Ok(self.text[start + 1..self.pos - 1].to_string());
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        if self.pos - start < 3 {
+            return Err(self.error("zone name is too short"));
+        }
+        Ok(self.text[start..self.pos].to_string())
+    }
+    // Returns an unsigned number with up to max_digits digits
+    fn number(&mut self, max_digits: usize) -> Result<i32, DateTimeError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) && self.pos - start < max_digits {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error("expected a number"));
+        }
+        Ok(self.text[start..self.pos].parse().unwrap_or(0))
+    }
+    // Returns a time [+-]hh[:mm[:ss]] in seconds, the hours can be up to 167
+    fn hms(&mut self) -> Result<i32, DateTimeError> {
+        let sign = match self.peek() {
+            Some(b'-') => {
+                self.pos += 1;
+                -1
+            }
+            Some(b'+') => {
+                self.pos += 1;
+                1
+            }
+            _ => 1,
+        };
+        let hours = self.number(3)?;
+        if hours > 167 {
+            return Err(self.error("hours out of range"));
+        }
+        let mut secs = hours * 3_600;
+        for factor in [60, 1] {
+            if self.peek() != Some(b':') {
+                break;
+            }
+            self.pos += 1;
+            let value = self.number(2)?;
+            if value > 59 {
+                return Err(self.error("minutes or seconds out of range"));
+            }
+            secs += value * factor;
+        }
+        Ok(sign * secs)
+    }
+    // Returns an offset in seconds west of Greenwich like it is written in the TZ string
+    fn offset(&mut self) -> Result<i32, DateTimeError> {
+        let offset = self.hms()?;
+        if offset.abs() > 24 * 3_600 {
+            return Err(self.error("offset out of range"));
+        }
+        Ok(offset)
+    }
+    // Returns a transition date[/time]
+    fn transition(&mut self) -> Result<TzRuleTransition, DateTimeError> {
+        let date = match self.peek() {
+            Some(b'J') => {
+                self.pos += 1;
+                let n = self.number(3)?;
+                if !(1..=365).contains(&n) {
+                    return Err(self.error("julian day out of range"));
+                }
+                TzRuleDate::Julian(n as u16)
+            }
+            Some(b'M') => {
+                self.pos += 1;
+                let month = self.number(2)?;
+                self.expect(b'.')?;
+                let week = self.number(1)?;
+                self.expect(b'.')?;
+                let weekday = self.number(1)?;
+                if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+                    return Err(self.error("month, week or weekday out of range"));
+                }
+                TzRuleDate::MonthWeekDay {
+                    month: month as u8,
+                    week: week as u8,
+                    weekday: weekday as u8,
+                }
+            }
+            _ => {
+                let n = self.number(3)?;
+                if n > 365 {
+                    return Err(self.error("day of year out of range"));
+                }
+                TzRuleDate::DayOfYear(n as u16)
+            }
+        };
+        let time = if self.peek() == Some(b'/') {
+            self.pos += 1;
+            self.hms()?
+        } else {
+            7_200
+        };
+        Ok(TzRuleTransition { date, time })
+    }
+}
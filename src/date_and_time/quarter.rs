@@ -0,0 +1,240 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+#[cfg(feature = "serde")]
+use std::fmt;
+
+use crate::date_and_time::date::*;
+use crate::date_and_time::error::*;
+
+/// The YearQuarter structure is a calendar quarter (1 to 4) of a year, e.g. for reports. The
+/// quarters are ordered by the year and the quarter, so they can be compared and sorted.
+/// With the feature ```serde``` the quarter is (de)serialized as a text like ```2024-Q3```.
+/// The fields are private, so a YearQuarter always has a quarter from 1 to 4.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::quarter::*;
+/// let q3 = YearQuarter::parse("2024-Q3").unwrap();
+/// assert!(q3.contains(&Date::from(30, 9, 2024)));
+/// assert_eq!(q3.succ().unwrap().as_string(), "2024-Q4");
+/// assert!(q3 < YearQuarter::new(2025, 1).unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearQuarter {
+    year: i32,
+    quarter: u8,
+}
+
+#[allow(dead_code)]
+impl YearQuarter {
+    /// ```new(year, quarter)``` creates a ```YearQuarter```, it returns an error if the
+    /// ```quarter``` is not 1 to 4.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// assert_eq!(YearQuarter::new(2024, 2).unwrap().quarter(), 2);
+    /// assert!(YearQuarter::new(2024, 5).is_err());
+    /// ```
+    pub fn new(year: i32, quarter: u8) -> Result<YearQuarter, DateTimeError> {
+        if !(1..=4).contains(&quarter) {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{} is not a quarter (1 to 4)",
+                quarter
+            )));
+        }
+        Ok(YearQuarter { year, quarter })
+    }
+    /// ```from_date(&date)``` returns the ```YearQuarter``` of the ```date```, it returns an
+    /// error for an invalid date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::quarter::*;
+    /// let quarter = YearQuarter::from_date(&Date::from(1, 4, 2024)).unwrap();
+    /// assert_eq!(quarter.as_string(), "2024-Q2");
+    /// ```
+    pub fn from_date(date: &Date) -> Result<YearQuarter, DateTimeError> {
+        YearQuarter::new(date.y, date.quarter())
+    }
+    /// ```year()``` returns the year of the ```YearQuarter```.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// assert_eq!(YearQuarter::new(2024, 2).unwrap().year(), 2024);
+    /// ```
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+    /// ```quarter()``` returns the quarter (1 to 4) of the ```YearQuarter```.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// assert_eq!(YearQuarter::parse("2024-Q4").unwrap().quarter(), 4);
+    /// ```
+    pub fn quarter(&self) -> u8 {
+        self.quarter
+    }
+    /// ```parse(text)``` parses a quarter in the format ```YYYY-Qn```, the ```Q``` can be
+    /// lowercase.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// assert_eq!(YearQuarter::parse("2024-q1").unwrap(), YearQuarter::new(2024, 1).unwrap());
+    /// assert!(YearQuarter::parse("2024-Q0").is_err());
+    /// assert!(YearQuarter::parse("2024Q1").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<YearQuarter, DateTimeError> {
+        let text = text.trim();
+        let error = || DateTimeError::Parse(format!("'{}' is not a quarter like 2024-Q3", text));
+        let (year, quarter) = text
+            .rsplit_once("-Q")
+            .or_else(|| text.rsplit_once("-q"))
+            .ok_or_else(error)?;
+        if quarter.len() != 1 {
+            return Err(error());
+        }
+        let year: i32 = year.parse().map_err(|_| error())?;
+        let quarter: u8 = quarter.parse().map_err(|_| error())?;
+        YearQuarter::new(year, quarter)
+    }
+    /// ```as_string()``` gets the quarter as a string in the format ```YYYY-Qn```.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// assert_eq!(YearQuarter::new(812, 4).unwrap().as_string(), "0812-Q4");
+    /// ```
+    pub fn as_string(&self) -> String {
+        format!("{:04}-Q{}", self.year, self.quarter)
+    }
+    /// ```succ()``` returns the next quarter, or ```None``` after the last quarter of the
+    /// year ```i32::MAX```.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// let q4 = YearQuarter::new(2024, 4).unwrap();
+    /// assert_eq!(q4.succ(), Some(YearQuarter::new(2025, 1).unwrap()));
+    /// assert_eq!(YearQuarter::new(i32::MAX, 4).unwrap().succ(), None);
+    /// ```
+    pub fn succ(&self) -> Option<YearQuarter> {
+        match self.quarter {
+            4 => Some(YearQuarter {
+                year: self.year.checked_add(1)?,
+                quarter: 1,
+            }),
+            quarter => Some(YearQuarter {
+                year: self.year,
+                quarter: quarter + 1,
+            }),
+        }
+    }
+    /// ```pred()``` returns the previous quarter, or ```None``` before the first quarter of
+    /// the year ```i32::MIN```.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// let q1 = YearQuarter::new(2024, 1).unwrap();
+    /// assert_eq!(q1.pred(), Some(YearQuarter::new(2023, 4).unwrap()));
+    /// assert_eq!(YearQuarter::new(i32::MIN, 1).unwrap().pred(), None);
+    /// ```
+    pub fn pred(&self) -> Option<YearQuarter> {
+        match self.quarter {
+            1 => Some(YearQuarter {
+                year: self.year.checked_sub(1)?,
+                quarter: 4,
+            }),
+            quarter => Some(YearQuarter {
+                year: self.year,
+                quarter: quarter - 1,
+            }),
+        }
+    }
+    /// ```first_day()``` returns the first date of the quarter.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// let q2 = YearQuarter::new(2024, 2).unwrap();
+    /// assert_eq!(q2.first_day().as_string(), "2024-04-01");
+    /// ```
+    pub fn first_day(&self) -> Date {
+        Date::from(1, self.quarter * 3 - 2, self.year)
+    }
+    /// ```last_day()``` returns the last date of the quarter.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// let q2 = YearQuarter::new(2024, 2).unwrap();
+    /// assert_eq!(q2.last_day().as_string(), "2024-06-30");
+    /// ```
+    pub fn last_day(&self) -> Date {
+        self.first_day().last_day_of_quarter()
+    }
+    /// ```contains(&date)``` returns true if the ```date``` is in the quarter.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::quarter::*;
+    /// let q1 = YearQuarter::new(2024, 1).unwrap();
+    /// assert!(q1.contains(&Date::from(29, 2, 2024)));
+    /// assert!(!q1.contains(&Date::from(1, 4, 2024)));
+    /// ```
+    pub fn contains(&self, date: &Date) -> bool {
+        date.y == self.year && date.quarter() == self.quarter
+    }
+    /// ```dates()``` returns an iterator over all dates of the quarter.
+    ///
+    /// ```
+    /// use date_and_time::quarter::*;
+    /// let q1 = YearQuarter::new(2023, 1).unwrap();
+    /// assert_eq!(q1.dates().count(), 90);
+    /// assert_eq!(q1.dates().last().unwrap().as_string(), "2023-03-31");
+    /// ```
+    pub fn dates(&self) -> impl Iterator<Item = Date> {
+        let first = get_days_from_date(&self.first_day());
+        let last = get_days_from_date(&self.last_day());
+        (first..=last).map(get_date_from_days)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for YearQuarter {
+    /// Serializes the quarter as a text like ```2024-Q3```.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.as_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for YearQuarter {
+    /// Deserializes the quarter from a text like ```2024-Q3```.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(QuarterVisitor)
+    }
+}
+
+// The serde visitor which parses a YearQuarter from a text
+#[cfg(feature = "serde")]
+struct QuarterVisitor;
+
+#[cfg(feature = "serde")]
+impl serde::de::Visitor<'_> for QuarterVisitor {
+    type Value = YearQuarter;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a quarter like 2024-Q3")
+    }
+
+    fn visit_str<E>(self, text: &str) -> Result<YearQuarter, E>
+    where
+        E: serde::de::Error,
+    {
+        YearQuarter::parse(text).map_err(E::custom)
+    }
+}
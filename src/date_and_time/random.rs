@@ -0,0 +1,106 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// The functions draw the dates from the given random number generator, with a seeded
+// generator (e.g. rand::rngs::StdRng::seed_from_u64()) the dates are reproducible, which
+// is what you want for test data.
+//
+use std::ops::RangeInclusive;
+
+use rand::{Rng, RngCore};
+
+use crate::date_and_time::date::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::holiday::*;
+
+// The number of random tries before all business days of the range are counted
+const MAX_TRIES: u32 = 64;
+
+/// ```random_date_in(range, &mut rng)``` returns a random date of the ```range``` (e.g.
+/// ```Date::from(1, 1, 2024)..=Date::from(31, 12, 2024)```), every date has the same
+/// probability. An empty range or an invalid date returns an error.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::random::*;
+/// use rand::SeedableRng;
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+/// let range = Date::from(1, 1, 2024)..=Date::from(31, 12, 2024);
+/// let date = random_date_in(range.clone(), &mut rng).unwrap();
+/// assert!(range.contains(&date));
+/// ```
+pub fn random_date_in(
+    range: RangeInclusive<Date>,
+    rng: &mut impl RngCore,
+) -> Result<Date, DateTimeError> {
+    let (first, last) = range_in_days(&range)?;
+    Ok(get_date_from_days(rng.gen_range(first..=last)))
+}
+
+/// ```random_business_day_in(range, &calendar, &mut rng)``` returns a random business day
+/// (Monday to Friday and no holiday of the ```calendar```) of the ```range```, every
+/// business day has the same probability. If the range contains no business day an error
+/// is returned.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::holiday::*;
+/// use date_and_time::random::*;
+/// use rand::SeedableRng;
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+/// let holidays = vec![Date::from(25, 12, 2024), Date::from(26, 12, 2024)];
+/// let range = Date::from(23, 12, 2024)..=Date::from(29, 12, 2024);
+/// let date = random_business_day_in(range, &holidays, &mut rng).unwrap();
+/// assert!([23, 24, 27].contains(&date.d));
+/// ```
+pub fn random_business_day_in<C: HolidayCalendar + ?Sized>(
+    range: RangeInclusive<Date>,
+    calendar: &C,
+    rng: &mut impl RngCore,
+) -> Result<Date, DateTimeError> {
+    let (first, last) = range_in_days(&range)?;
+    // most days are business days, so a few tries are usually enough
+    for _ in 0..MAX_TRIES {
+        let date = get_date_from_days(rng.gen_range(first..=last));
+        if calendar.is_business_day(&date) {
+            return Ok(date);
+        }
+    }
+    let business_days: Vec<Date> = (first..=last)
+        .map(get_date_from_days)
+        .filter(|date| calendar.is_business_day(date))
+        .collect();
+    if business_days.is_empty() {
+        return Err(DateTimeError::OutOfRange(format!(
+            "no business day between {} and {}",
+            range.start().as_string(),
+            range.end().as_string()
+        )));
+    }
+    Ok(business_days[rng.gen_range(0..business_days.len())])
+}
+
+// Returns the first and the last day of the range: &RangeInclusive<Date> in days since the
+// Linux epoch
+fn range_in_days(range: &RangeInclusive<Date>) -> Result<(i64, i64), DateTimeError> {
+    let (start, end) = (range.start(), range.end());
+    if !is_date_valid(start) || !is_date_valid(end) {
+        return Err(DateTimeError::OutOfRange(format!(
+            "invalid date in the range {} to {}",
+            start.as_string(),
+            end.as_string()
+        )));
+    }
+    let first = get_days_from_date(start);
+    let last = get_days_from_date(end);
+    if first > last {
+        return Err(DateTimeError::OutOfRange(format!(
+            "the range {} to {} is empty",
+            start.as_string(),
+            end.as_string()
+        )));
+    }
+    Ok((first, last))
+}
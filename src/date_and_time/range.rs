@@ -0,0 +1,295 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use std::iter::FusedIterator;
+
+use crate::date_and_time::date::*;
+use crate::date_and_time::interval::*;
+
+/// The DateRange structure is an iterator over the days from a start to an end date, it can
+/// be build with the functions ```exclusive()``` and ```inclusive()``` or the functions
+/// ```iter_days_until()``` and ```iter_days_through()``` of a ```Date```. The range can be
+/// iterated backwards with ```rev()```, a range with an invalid date or an end before the
+/// start is empty.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::range::*;
+/// let days = DateRange::inclusive(Date::from(30, 12, 2024), Date::from(2, 1, 2025));
+/// assert_eq!(days.len(), 4);
+/// let last = days.rev().next().unwrap();
+/// assert_eq!(last.as_string(), "2025-01-02");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DateRange {
+    // the days since the epoch of the next date from the front and after the back
+    front: i64,
+    back: i64,
+}
+
+#[allow(dead_code)]
+impl DateRange {
+    /// ```exclusive(start, end)``` creates a ```DateRange``` from the ```start``` date to
+    /// the day before the ```end``` date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::range::*;
+    /// let june = DateRange::exclusive(Date::from(1, 6, 2024), Date::from(1, 7, 2024));
+    /// assert_eq!(june.count(), 30);
+    /// ```
+    pub fn exclusive(start: Date, end: Date) -> DateRange {
+        if !is_date_valid(&start) || !is_date_valid(&end) {
+            return DateRange { front: 0, back: 0 };
+        }
+        let front = get_days_from_date(&start);
+        DateRange {
+            front,
+            back: get_days_from_date(&end).max(front),
+        }
+    }
+    /// ```inclusive(start, end)``` creates a ```DateRange``` from the ```start``` date to
+    /// the ```end``` date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::range::*;
+    /// let day = Date::from(22, 6, 2024);
+    /// assert_eq!(DateRange::inclusive(day, day).collect::<Vec<Date>>(), [day]);
+    /// assert_eq!(DateRange::inclusive(day, Date::from(21, 6, 2024)).count(), 0);
+    /// ```
+    pub fn inclusive(start: Date, end: Date) -> DateRange {
+        let mut range = DateRange::exclusive(start, end);
+        if is_date_valid(&start) && start <= end {
+            range.back += 1;
+        }
+        range
+    }
+    /// ```contains(&date)``` returns true if the ```date``` is one of the remaining dates of
+    /// the range.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::range::*;
+    /// let week = Date::from(1, 7, 2024).iter_days_until(Date::from(8, 7, 2024));
+    /// assert!(week.contains(&Date::from(7, 7, 2024)));
+    /// assert!(!week.contains(&Date::from(8, 7, 2024)));
+    /// ```
+    pub fn contains(&self, date: &Date) -> bool {
+        is_date_valid(date) && (self.front..self.back).contains(&get_days_from_date(date))
+    }
+}
+
+impl Iterator for DateRange {
+    type Item = Date;
+
+    fn next(&mut self) -> Option<Date> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(get_date_from_days(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Date> {
+        self.front = self.front.saturating_add(n as i64).min(self.back);
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Date> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for DateRange {
+    fn next_back(&mut self) -> Option<Date> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(get_date_from_days(self.back))
+    }
+}
+
+impl ExactSizeIterator for DateRange {}
+
+impl FusedIterator for DateRange {}
+
+/// The Period enum is the step of a ```PeriodRange```, with the number of days, weeks,
+/// months, quarters or years. A step of 0 is handled like a step of 1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Period {
+    Days(u32),
+    Weeks(u32),
+    Months(u32),
+    Quarters(u32),
+    Years(u32),
+}
+
+/// The PeriodRange structure is an iterator over the dates from a start date in steps of a
+/// ```Period``` until the day before an end date, it can be build with the functions
+/// ```step_by_period()```, ```iter_weeks_until()``` and ```iter_months_until()``` of a
+/// ```Date```.
+///
+/// Every date is calculated from the start date and not from the date before, so a day
+/// which is missing in a short month (e.g. the 31st) is only moved in this month. The day
+/// is moved to the last day of the month.
+///
+/// ```
+/// use date_and_time::date::*;
+/// let start = Date::from(31, 1, 2024);
+/// let ends: Vec<String> = start
+///     .iter_months_until(Date::from(1, 6, 2024))
+///     .map(|date| date.as_string())
+///     .collect();
+/// assert_eq!(ends, ["2024-01-31", "2024-02-29", "2024-03-31", "2024-04-30", "2024-05-31"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeriodRange {
+    start: Date,
+    period: Period,
+    // the days since the epoch of the end date and the number of the next step
+    end: i64,
+    step: u32,
+}
+
+impl Iterator for PeriodRange {
+    type Item = Date;
+
+    fn next(&mut self) -> Option<Date> {
+        let date = get_period_step(&self.start, self.period, self.step)?;
+        if get_days_from_date(&date) >= self.end {
+            self.step = u32::MAX;
+            return None;
+        }
+        self.step += 1;
+        Some(date)
+    }
+}
+
+impl FusedIterator for PeriodRange {}
+
+#[allow(dead_code)]
+impl Date {
+    /// ```iter_days_until(end)``` returns a ```DateRange``` over the days from this date to
+    /// the day before the ```end``` date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let start = Date::from(27, 2, 2024);
+    /// let days: Vec<u8> = start.iter_days_until(Date::from(2, 3, 2024)).map(|d| d.d).collect();
+    /// assert_eq!(days, [27, 28, 29, 1]);
+    /// ```
+    pub fn iter_days_until(&self, end: Date) -> DateRange {
+        DateRange::exclusive(*self, end)
+    }
+    /// ```iter_days_through(end)``` returns a ```DateRange``` over the days from this date
+    /// to the ```end``` date, the ```end``` is included.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let start = Date::from(27, 2, 2023);
+    /// let days: Vec<u8> = start.iter_days_through(Date::from(2, 3, 2023)).rev().map(|d| d.d).collect();
+    /// assert_eq!(days, [2, 1, 28, 27]);
+    /// ```
+    pub fn iter_days_through(&self, end: Date) -> DateRange {
+        DateRange::inclusive(*self, end)
+    }
+    /// ```iter_weeks_until(end)``` returns a ```PeriodRange``` over this date and the same
+    /// weekday of the next weeks before the ```end``` date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let mondays = Date::from(1, 7, 2024).iter_weeks_until(Date::from(29, 7, 2024));
+    /// let days: Vec<u8> = mondays.map(|date| date.d).collect();
+    /// assert_eq!(days, [1, 8, 15, 22]);
+    /// ```
+    pub fn iter_weeks_until(&self, end: Date) -> PeriodRange {
+        self.step_by_period(end, Period::Weeks(1))
+    }
+    /// ```iter_months_until(end)``` returns a ```PeriodRange``` over this date and the same
+    /// day of the next months before the ```end``` date.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let firsts = Date::from(1, 11, 2024).iter_months_until(Date::from(1, 3, 2025));
+    /// let months: Vec<u8> = firsts.map(|date| date.m).collect();
+    /// assert_eq!(months, [11, 12, 1, 2]);
+    /// ```
+    pub fn iter_months_until(&self, end: Date) -> PeriodRange {
+        self.step_by_period(end, Period::Months(1))
+    }
+    /// ```step_by_period(end, period)``` returns a ```PeriodRange``` over this date and the
+    /// next dates in steps of the ```period``` before the ```end``` date. It is empty if a
+    /// date is invalid.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::range::*;
+    /// let start = Date::from(29, 2, 2024);
+    /// let dates: Vec<String> = start
+    ///     .step_by_period(Date::from(1, 1, 2026), Period::Quarters(2))
+    ///     .map(|date| date.as_string())
+    ///     .collect();
+    /// assert_eq!(dates, ["2024-02-29", "2024-08-29", "2025-02-28", "2025-08-29"]);
+    /// ```
+    pub fn step_by_period(&self, end: Date, period: Period) -> PeriodRange {
+        let valid = is_date_valid(self) && is_date_valid(&end);
+        PeriodRange {
+            start: *self,
+            period,
+            end: if valid {
+                get_days_from_date(&end)
+            } else {
+                i64::MIN
+            },
+            step: 0,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl DateInterval {
+    /// ```iter_days()``` returns a ```DateRange``` over all days of the interval.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// let vacation = DateInterval::new(Date::from(22, 7, 2024), Date::from(2, 8, 2024)).unwrap();
+    /// assert_eq!(vacation.iter_days().len() as i64, vacation.days());
+    /// ```
+    pub fn iter_days(&self) -> DateRange {
+        DateRange::inclusive(self.start, self.end)
+    }
+}
+
+// Returns the date of the step: u32 from the start: &Date in steps of the period: Period, or
+// None if it is out of range
+fn get_period_step(start: &Date, period: Period, step: u32) -> Option<Date> {
+    let (months, days) = match period {
+        Period::Days(n) => (0, n.max(1) as i64),
+        Period::Weeks(n) => (0, n.max(1) as i64 * 7),
+        Period::Months(n) => (n.max(1) as i64, 0),
+        Period::Quarters(n) => (n.max(1) as i64 * 3, 0),
+        Period::Years(n) => (n.max(1) as i64 * 12, 0),
+    };
+    if days > 0 {
+        return Some(get_date_from_days(
+            get_days_from_date(start) + days * step as i64,
+        ));
+    }
+    let month = start.y as i64 * 12 + start.m as i64 - 1 + months * step as i64;
+    let y = i32::try_from(month.div_euclid(12)).ok()?;
+    let m = month.rem_euclid(12) as u8 + 1;
+    Some(Date {
+        d: start.d.min(get_max_days_of_month(m, y)),
+        m,
+        y,
+    })
+}
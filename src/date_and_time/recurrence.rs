@@ -0,0 +1,599 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::time::*;
+
+// The weekdays of the BYDAY and WKST parts, from Sunday like Date::get_weekday()
+const WEEKDAY_CODES: [(&str, Weekday); 7] = [
+    ("SU", Weekday::Sunday),
+    ("MO", Weekday::Monday),
+    ("TU", Weekday::Tuesday),
+    ("WE", Weekday::Wednesday),
+    ("TH", Weekday::Thursday),
+    ("FR", Weekday::Friday),
+    ("SA", Weekday::Saturday),
+];
+
+/// The frequency of a ```Recurrence```, the FREQ part of an RRULE.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[allow(dead_code)]
+impl Frequency {
+    /// ```as_str()``` returns the frequency as in an RRULE, e.g. ```WEEKLY```.
+    ///
+    /// ```
+    /// use date_and_time::recurrence::*;
+    /// assert_eq!(Frequency::Monthly.as_str(), "MONTHLY");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+    // Returns the number of periods in the 400 years cycle of the Gregorian calendar, after
+    // so many periods without an occurrence there will be no more occurrences
+    fn periods_per_cycle(&self) -> u32 {
+        match self {
+            Frequency::Daily => 146_097,
+            Frequency::Weekly => 20_871,
+            Frequency::Monthly => 4_800,
+            Frequency::Yearly => 400,
+        }
+    }
+}
+
+/// A weekday of the BYDAY part of an RRULE. With ```n = 0``` it is every ```weekday``` of
+/// the period, else the ```n```th weekday of the month or the year (negative from the end,
+/// e.g. -1 is the last one).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ByDay {
+    pub n: i8,
+    pub weekday: Weekday,
+}
+
+#[allow(dead_code)]
+impl ByDay {
+    /// ```every(weekday)``` returns every ```weekday``` of the period, e.g. ```MO```.
+    pub fn every(weekday: Weekday) -> ByDay {
+        ByDay { n: 0, weekday }
+    }
+    /// ```nth(n, weekday)``` returns the ```n```th ```weekday``` of the month or the year,
+    /// e.g. ```-1FR``` for the last Friday.
+    pub fn nth(n: i8, weekday: Weekday) -> ByDay {
+        ByDay { n, weekday }
+    }
+    /// ```as_string()``` gets the weekday as in an RRULE, e.g. ```MO``` or ```-1FR```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::recurrence::*;
+    /// assert_eq!(ByDay::nth(-1, Weekday::Friday).as_string(), "-1FR");
+    /// assert_eq!(ByDay::every(Weekday::Monday).as_string(), "MO");
+    /// ```
+    pub fn as_string(&self) -> String {
+        let code = get_weekday_code(self.weekday);
+        match self.n {
+            0 => code.to_string(),
+            n => format!("{}{}", n, code),
+        }
+    }
+}
+
+/// The ```Recurrence``` structure is a subset of the recurrence rules (RRULE) of RFC 5545
+/// (iCalendar) with the parts ```FREQ```, ```INTERVAL```, ```BYDAY```, ```BYMONTHDAY```,
+/// ```COUNT```, ```UNTIL``` and ```WKST```. It can be build with ```new()``` and the
+/// ```with_...()``` functions or parsed with ```parse()```.
+///
+/// The occurrences are the dates from the ```start``` date which match the rule, the start
+/// date is only an occurrence if it matches the rule. Like in RFC 5545 the dates which
+/// don't exist are skipped (e.g. the 31st in a monthly rule), only a yearly rule on the
+/// 29th February can be moved with the ```leap_day``` policy.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::recurrence::*;
+/// let start = Date::from(1, 7, 2024);
+/// let rule = Recurrence::parse(start, "FREQ=MONTHLY;BYDAY=-1FR;COUNT=3").unwrap();
+/// let dates: Vec<String> = rule.occurrences().map(|date| date.as_string()).collect();
+/// assert_eq!(dates, ["2024-07-26", "2024-08-30", "2024-09-27"]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recurrence {
+    pub start: Date,
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub by_month_day: Vec<i8>,
+    pub count: Option<u32>,
+    pub until: Option<Date>,
+    pub week_start: Weekday,
+    pub leap_day: LeapDayPolicy,
+}
+
+#[allow(dead_code)]
+impl Recurrence {
+    /// ```new(start, frequency)``` creates a ```Recurrence``` in every period of the
+    /// ```frequency``` from the ```start``` date, without an end.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::recurrence::*;
+    /// let weekly = Recurrence::new(Date::from(3, 7, 2024), Frequency::Weekly);
+    /// assert_eq!(weekly.occurrences().nth(2), Some(Date::from(17, 7, 2024)));
+    /// ```
+    pub fn new(start: Date, frequency: Frequency) -> Recurrence {
+        Recurrence {
+            start,
+            frequency,
+            interval: 1,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            count: None,
+            until: None,
+            week_start: Weekday::Monday,
+            leap_day: LeapDayPolicy::Skip,
+        }
+    }
+    /// ```with_interval(interval)``` returns the ```Recurrence``` in every ```interval```th
+    /// period, e.g. 2 for every other week. An interval of 0 is 1.
+    pub fn with_interval(self, interval: u32) -> Recurrence {
+        Recurrence {
+            interval: interval.max(1),
+            ..self
+        }
+    }
+    /// ```with_by_day(&days)``` returns the ```Recurrence``` on the weekdays ```days```. In
+    /// a daily or weekly rule the ```n``` of the days is ignored.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::recurrence::*;
+    /// let days = [ByDay::every(Weekday::Tuesday), ByDay::every(Weekday::Thursday)];
+    /// let rule = Recurrence::new(Date::from(1, 7, 2024), Frequency::Weekly).with_by_day(&days);
+    /// assert_eq!(rule.occurrences().next(), Some(Date::from(2, 7, 2024)));
+    /// ```
+    pub fn with_by_day(self, days: &[ByDay]) -> Recurrence {
+        Recurrence {
+            by_day: days.to_vec(),
+            ..self
+        }
+    }
+    /// ```with_by_month_day(&days)``` returns the ```Recurrence``` on the ```days``` of the
+    /// month (1 to 31, negative from the end, e.g. -1 is the last day). RFC 5545 doesn't
+    /// allow the days of the month in a weekly rule, they are ignored there (```parse()```
+    /// rejects them).
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::recurrence::*;
+    /// let weekly = Recurrence::new(Date::from(1, 7, 2024), Frequency::Weekly);
+    /// let rule = weekly.with_by_month_day(&[1]);
+    /// assert_eq!(rule.as_string(), "FREQ=WEEKLY");
+    /// ```
+    pub fn with_by_month_day(self, days: &[i8]) -> Recurrence {
+        if self.frequency == Frequency::Weekly {
+            return self;
+        }
+        Recurrence {
+            by_month_day: days.to_vec(),
+            ..self
+        }
+    }
+    /// ```with_count(count)``` returns the ```Recurrence``` which ends after ```count```
+    /// occurrences.
+    pub fn with_count(self, count: u32) -> Recurrence {
+        Recurrence {
+            count: Some(count),
+            ..self
+        }
+    }
+    /// ```with_until(until)``` returns the ```Recurrence``` which ends with the ```until```
+    /// date (inclusive).
+    pub fn with_until(self, until: Date) -> Recurrence {
+        Recurrence {
+            until: Some(until),
+            ..self
+        }
+    }
+    /// ```with_week_start(weekday)``` returns the ```Recurrence``` with weeks starting on
+    /// the ```weekday``` (Monday by default), it matters for weekly rules with an interval.
+    pub fn with_week_start(self, weekday: Weekday) -> Recurrence {
+        Recurrence {
+            week_start: weekday,
+            ..self
+        }
+    }
+    /// ```with_leap_day_policy(policy)``` returns the ```Recurrence``` which moves the
+    /// yearly occurrences of a start date on the 29th February by the ```policy``` in the
+    /// years without leap day. By default they are skipped like in RFC 5545.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::recurrence::*;
+    /// let birthday = Recurrence::new(Date::from(29, 2, 2024), Frequency::Yearly)
+    ///     .with_leap_day_policy(LeapDayPolicy::Feb28);
+    /// assert_eq!(birthday.occurrences().nth(1), Some(Date::from(28, 2, 2025)));
+    /// ```
+    pub fn with_leap_day_policy(self, policy: LeapDayPolicy) -> Recurrence {
+        Recurrence {
+            leap_day: policy,
+            ..self
+        }
+    }
+    /// ```parse(start, rule)``` parses the ```rule``` of an RRULE (e.g.
+    /// ```FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE```, optionally with the prefix ```RRULE:```)
+    /// from the ```start``` date. It returns an error for the parts which are not supported
+    /// (e.g. ```BYMONTH```) and for rules which are not allowed by RFC 5545 (e.g.
+    /// ```COUNT``` together with ```UNTIL```). The time of ```UNTIL``` is ignored.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::recurrence::*;
+    /// let start = Date::from(1, 9, 1997);
+    /// let rule = Recurrence::parse(start, "RRULE:FREQ=WEEKLY;UNTIL=19971006T000000Z;WKST=SU;BYDAY=TU,TH").unwrap();
+    /// assert_eq!(rule.occurrences().count(), 10);
+    /// assert!(Recurrence::parse(start, "FREQ=WEEKLY;BYMONTH=1").is_err());
+    /// ```
+    pub fn parse(start: Date, rule: &str) -> Result<Recurrence, DateTimeError> {
+        let text = rule.trim();
+        let text = text.strip_prefix("RRULE:").unwrap_or(text);
+        let error = |part: &str, reason: &str| {
+            DateTimeError::Parse(format!("'{}' in '{}' {}", part, rule, reason))
+        };
+        let mut frequency: Option<Frequency> = None;
+        let mut recurrence = Recurrence::new(start, Frequency::Daily);
+        for part in text.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| error(part, "is not a NAME=VALUE part"))?;
+            let invalid = || error(part, "has an invalid value");
+            match name.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(error(part, "is not a supported frequency")),
+                    })
+                }
+                "INTERVAL" => match value.parse::<u32>() {
+                    Ok(interval) if interval > 0 => recurrence.interval = interval,
+                    _ => return Err(invalid()),
+                },
+                "COUNT" => recurrence.count = Some(value.parse().map_err(|_| invalid())?),
+                "UNTIL" => recurrence.until = Some(parse_until(value).ok_or_else(invalid)?),
+                "WKST" => recurrence.week_start = parse_weekday(value).ok_or_else(invalid)?,
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        recurrence
+                            .by_day
+                            .push(parse_by_day(day).ok_or_else(invalid)?);
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for day in value.split(',') {
+                        match day.parse::<i8>() {
+                            Ok(day) if day != 0 && (-31..=31).contains(&day) => {
+                                recurrence.by_month_day.push(day)
+                            }
+                            _ => return Err(invalid()),
+                        }
+                    }
+                }
+                _ => return Err(error(part, "is not supported")),
+            }
+        }
+        recurrence.frequency = frequency.ok_or_else(|| error(text, "has no FREQ part"))?;
+        if recurrence.count.is_some() && recurrence.until.is_some() {
+            return Err(error(text, "has both COUNT and UNTIL"));
+        }
+        let max_n = match recurrence.frequency {
+            Frequency::Daily | Frequency::Weekly => 0,
+            Frequency::Monthly => 5,
+            Frequency::Yearly => 53,
+        };
+        if let Some(day) = recurrence.by_day.iter().find(|day| day.n.abs() > max_n) {
+            return Err(error(&day.as_string(), "is not allowed with this FREQ"));
+        }
+        if recurrence.frequency == Frequency::Weekly && !recurrence.by_month_day.is_empty() {
+            return Err(error(text, "has BYMONTHDAY in a weekly rule"));
+        }
+        Ok(recurrence)
+    }
+    /// ```as_string()``` gets the rule as an RRULE without the start date, the parts with
+    /// the default values are left out.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::recurrence::*;
+    /// let rule = Recurrence::new(Date::from(1, 7, 2024), Frequency::Monthly)
+    ///     .with_interval(3)
+    ///     .with_by_month_day(&[1, -1])
+    ///     .with_until(Date::from(31, 12, 2024));
+    /// assert_eq!(rule.as_string(), "FREQ=MONTHLY;INTERVAL=3;BYMONTHDAY=1,-1;UNTIL=20241231");
+    /// ```
+    pub fn as_string(&self) -> String {
+        let mut parts = vec![format!("FREQ={}", self.frequency.as_str())];
+        if self.interval > 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self.by_day.iter().map(|day| day.as_string()).collect();
+            parts.push(format!("BYDAY={}", days.join(",")));
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(|d| d.to_string()).collect();
+            parts.push(format!("BYMONTHDAY={}", days.join(",")));
+        }
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+        if let Some(until) = self.until {
+            parts.push(format!("UNTIL={}", until.as_formated_string("%Y%m%d")));
+        }
+        if self.week_start != Weekday::Monday {
+            parts.push(format!("WKST={}", get_weekday_code(self.week_start)));
+        }
+        parts.join(";")
+    }
+    /// ```occurrences()``` returns an iterator over the dates of the recurrence. Without
+    /// ```count``` and ```until``` the iterator doesn't end (as long as there are dates),
+    /// take only the needed dates. For an invalid start date there are no occurrences.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::recurrence::*;
+    /// // every other day
+    /// let rule = Recurrence::parse(Date::from(30, 12, 2024), "FREQ=DAILY;INTERVAL=2").unwrap();
+    /// let dates: Vec<String> = rule.occurrences().take(3).map(|date| date.as_string()).collect();
+    /// assert_eq!(dates, ["2024-12-30", "2025-01-01", "2025-01-03"]);
+    /// ```
+    pub fn occurrences(&self) -> impl Iterator<Item = Date> {
+        let rule = self.clone();
+        let start = get_days_from_date(&self.start);
+        let until = self.until.map(|date| get_days_from_date(&date));
+        let mut pending: Vec<i64> = Vec::new();
+        let (mut period, mut emitted, mut empty_periods) = (0, 0, 0);
+        let mut finished = !is_date_valid(&self.start);
+        std::iter::from_fn(move || loop {
+            finished |= rule.count.is_some_and(|count| emitted >= count)
+                || empty_periods >= rule.frequency.periods_per_cycle();
+            if finished {
+                return None;
+            }
+            // the pending days are sorted backwards, the next one is the last
+            if let Some(day) = pending.pop() {
+                if until.is_some_and(|until| day > until) {
+                    finished = true;
+                    return None;
+                }
+                emitted += 1;
+                return Some(get_date_from_days(day));
+            }
+            pending = rule.get_period_days(period);
+            pending.retain(|&day| day >= start);
+            pending.sort_unstable_by(|a, b| b.cmp(a));
+            pending.dedup();
+            empty_periods = if pending.is_empty() {
+                empty_periods + 1
+            } else {
+                0
+            };
+            period += 1;
+        })
+    }
+    /// ```occurrences_at(time)``` returns an iterator over the occurrences at the ```time```
+    /// of the day, like ```occurrences()```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::recurrence::*;
+    /// use date_and_time::time::*;
+    /// let rule = Recurrence::parse(Date::from(1, 7, 2024), "FREQ=WEEKLY;COUNT=2").unwrap();
+    /// let meetings: Vec<String> = rule
+    ///     .occurrences_at(Time::from(9, 30, 0))
+    ///     .map(|dt| dt.as_string())
+    ///     .collect();
+    /// assert_eq!(meetings, ["2024-07-01T09:30:00", "2024-07-08T09:30:00"]);
+    /// ```
+    pub fn occurrences_at(&self, time: Time) -> impl Iterator<Item = DateTime> {
+        self.occurrences()
+            .map(move |date| DateTime::from(date, time))
+    }
+    // Returns the days since the epoch of the candidates in the period: i64 (counted from
+    // the period of the start date), they may be before the start date and unsorted
+    fn get_period_days(&self, period: i64) -> Vec<i64> {
+        let start = get_days_from_date(&self.start);
+        let step = period * self.interval as i64;
+        match self.frequency {
+            Frequency::Daily => {
+                let day = start + step;
+                let mut days = self.get_limited_days(day, day, &[day]);
+                days.retain(|&day| self.matches_month_day(day));
+                days
+            }
+            Frequency::Weekly => {
+                let week_start = self.week_start as i64;
+                let first =
+                    start - (get_weekday_of_day(start) - week_start).rem_euclid(7) + step * 7;
+                let weekdays: Vec<i64> = match self.by_day.is_empty() {
+                    true => vec![get_weekday_of_day(start)],
+                    false => self.by_day.iter().map(|day| day.weekday as i64).collect(),
+                };
+                weekdays
+                    .iter()
+                    .map(|weekday| first + (weekday - week_start).rem_euclid(7))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let month = self.start.y as i64 * 12 + self.start.m as i64 - 1 + step;
+                let (year, month) = (
+                    month.div_euclid(12) as i32,
+                    (month.rem_euclid(12) + 1) as u8,
+                );
+                let first = get_days_from_date(&Date {
+                    d: 1,
+                    m: month,
+                    y: year,
+                });
+                let last = first + days_in_month(year, month) as i64 - 1;
+                let month_days = if !self.by_month_day.is_empty() {
+                    self.get_month_days(first, last)
+                } else if self.by_day.is_empty() {
+                    // the day of the start date, the months without this day are skipped
+                    let day = first + self.start.d as i64 - 1;
+                    (day <= last).then_some(day).into_iter().collect()
+                } else {
+                    Vec::new()
+                };
+                self.get_limited_days(first, last, &month_days)
+            }
+            Frequency::Yearly => {
+                if self.by_day.is_empty() && self.by_month_day.is_empty() {
+                    return u32::try_from(step)
+                        .ok()
+                        .and_then(|years| self.start.add_years_with(years, self.leap_day))
+                        .map(|date| get_days_from_date(&date))
+                        .into_iter()
+                        .collect();
+                }
+                let year = (self.start.y as i64 + step) as i32;
+                let first = get_days_from_date(&Date {
+                    d: 1,
+                    m: 1,
+                    y: year,
+                });
+                let last = get_days_from_date(&Date {
+                    d: 31,
+                    m: 12,
+                    y: year,
+                });
+                let month_days: Vec<i64> = (1..=12)
+                    .flat_map(|month| {
+                        let first = get_days_from_date(&Date {
+                            d: 1,
+                            m: month,
+                            y: year,
+                        });
+                        self.get_month_days(first, first + days_in_month(year, month) as i64 - 1)
+                    })
+                    .collect();
+                self.get_limited_days(first, last, &month_days)
+            }
+        }
+    }
+    // Returns the days between first: i64 and last: i64 of the BYDAY part, limited to the
+    // month_days: &[i64] if there is a BYMONTHDAY part (or a default day)
+    fn get_limited_days(&self, first: i64, last: i64, month_days: &[i64]) -> Vec<i64> {
+        if self.by_day.is_empty() {
+            return month_days.to_vec();
+        }
+        let mut days: Vec<i64> = Vec::new();
+        for by_day in &self.by_day {
+            let weekday = by_day.weekday as i64;
+            let first_match = first + (weekday - get_weekday_of_day(first)).rem_euclid(7);
+            let last_match = last - (get_weekday_of_day(last) - weekday).rem_euclid(7);
+            // the number of the weekday is only used in monthly and yearly rules
+            let n = match self.frequency {
+                Frequency::Monthly | Frequency::Yearly => by_day.n as i64,
+                Frequency::Daily | Frequency::Weekly => 0,
+            };
+            match n {
+                0 => days.extend((first_match..=last).step_by(7)),
+                n if n > 0 => days.push(first_match + 7 * (n - 1)),
+                n => days.push(last_match + 7 * (n + 1)),
+            }
+        }
+        days.retain(|day| (first..=last).contains(day));
+        let limited = !self.by_month_day.is_empty() || self.frequency == Frequency::Daily;
+        if limited {
+            days.retain(|day| month_days.contains(day));
+        }
+        days
+    }
+    // Returns the days of the BYMONTHDAY part in the month from first: i64 to last: i64
+    fn get_month_days(&self, first: i64, last: i64) -> Vec<i64> {
+        self.by_month_day
+            .iter()
+            .map(|&day| match day > 0 {
+                true => first + day as i64 - 1,
+                false => last + day as i64 + 1,
+            })
+            .filter(|day| (first..=last).contains(day))
+            .collect()
+    }
+    // Returns true if there is no BYMONTHDAY part or the day: i64 is one of its days
+    fn matches_month_day(&self, day: i64) -> bool {
+        if self.by_month_day.is_empty() {
+            return true;
+        }
+        let date = get_date_from_days(day);
+        let first = day - date.d as i64 + 1;
+        let last = first + days_in_month(date.y, date.m) as i64 - 1;
+        self.get_month_days(first, last).contains(&day)
+    }
+}
+
+// Returns the weekday (0 = Sunday) of the day: i64 since the epoch, a Thursday
+fn get_weekday_of_day(day: i64) -> i64 {
+    (day + 4).rem_euclid(7)
+}
+
+// Returns the code of the weekday: Weekday in an RRULE, e.g. MO
+fn get_weekday_code(weekday: Weekday) -> &'static str {
+    WEEKDAY_CODES[weekday as usize].0
+}
+
+// Returns the weekday of the code: &str (e.g. MO), or None if it is not a weekday
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    WEEKDAY_CODES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(code))
+        .map(|(_, weekday)| *weekday)
+}
+
+// Returns the weekday of the BYDAY part: &str with an optional signed number (e.g. -1FR),
+// or None if it is invalid
+fn parse_by_day(text: &str) -> Option<ByDay> {
+    let split = text.len().checked_sub(2)?;
+    let (n, code) = (text.get(..split)?, text.get(split..)?);
+    let n: i8 = match n {
+        "" => 0,
+        n => n.parse().ok().filter(|n: &i8| *n != 0)?,
+    };
+    Some(ByDay {
+        n,
+        weekday: parse_weekday(code)?,
+    })
+}
+
+// Returns the date of the UNTIL part: &str (YYYYMMDD with an optional time), or None if it
+// is invalid
+fn parse_until(text: &str) -> Option<Date> {
+    let date = text
+        .get(..8)
+        .filter(|date| date.bytes().all(|b| b.is_ascii_digit()))?;
+    if text.len() > 8 && !text[8..].starts_with('T') {
+        return None;
+    }
+    let until = Date {
+        d: date[6..8].parse().ok()?,
+        m: date[4..6].parse().ok()?,
+        y: date[..4].parse().ok()?,
+    };
+    is_date_valid(&until).then_some(until)
+}
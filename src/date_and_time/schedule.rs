@@ -0,0 +1,256 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::error::*;
+use crate::date_and_time::time::*;
+
+/// The TimeInterval structure is a window of a day from the ```start``` time (included) to
+/// the ```end``` time (excluded). The end can be ```24:00:00``` for the end of the day, a
+/// window over the midnight must be split into two windows.
+///
+/// ```
+/// use date_and_time::schedule::*;
+/// use date_and_time::time::*;
+/// let lunch = TimeInterval::new(Time::from(12, 0, 0), Time::from(13, 30, 0)).unwrap();
+/// assert_eq!(lunch.as_string(), "12:00:00-13:30:00");
+/// assert!(lunch.contains(&Time::from(12, 59, 59)));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TimeInterval {
+    pub start: Time,
+    pub end: Time,
+}
+
+#[allow(dead_code)]
+impl TimeInterval {
+    /// ```new(start, end)``` creates a ```TimeInterval``` from the ```start``` to the
+    /// ```end``` time. It returns an error if a time is invalid, the end is after
+    /// ```24:00:00``` or not after the start.
+    ///
+    /// ```
+    /// use date_and_time::schedule::*;
+    /// use date_and_time::time::*;
+    /// assert!(TimeInterval::new(Time::from(18, 0, 0), Time::from(24, 0, 0)).is_ok());
+    /// assert!(TimeInterval::new(Time::from(22, 0, 0), Time::from(2, 0, 0)).is_err());
+    /// ```
+    pub fn new(start: Time, end: Time) -> Result<TimeInterval, DateTimeError> {
+        if !is_time_valid(&start) || !is_time_valid(&end) || start.as_seconds() < 0 {
+            return Err(DateTimeError::OutOfRange(String::from(
+                "the time interval has an invalid time",
+            )));
+        }
+        if start.as_seconds() >= end.as_seconds() || end.as_seconds() > 86_400 {
+            return Err(DateTimeError::OutOfRange(format!(
+                "the time interval {}-{} is empty or not within one day",
+                start.as_string(),
+                end.as_string()
+            )));
+        }
+        Ok(TimeInterval { start, end })
+    }
+    /// ```contains(&time)``` returns true if the ```time``` is in the window.
+    ///
+    /// ```
+    /// use date_and_time::schedule::*;
+    /// use date_and_time::time::*;
+    /// let morning = TimeInterval::new(Time::from(8, 0, 0), Time::from(12, 0, 0)).unwrap();
+    /// assert!(morning.contains(&Time::from(8, 0, 0)));
+    /// assert!(!morning.contains(&Time::from(12, 0, 0)));
+    /// ```
+    pub fn contains(&self, time: &Time) -> bool {
+        self.start.as_seconds() <= time.as_seconds() && time.as_seconds() < self.end.as_seconds()
+    }
+    /// ```as_string()``` gets the window as a string in the format HH:MM:SS-HH:MM:SS.
+    ///
+    /// ```
+    /// use date_and_time::schedule::*;
+    /// use date_and_time::time::*;
+    /// let night = TimeInterval::new(Time::from(22, 0, 0), Time::from(24, 0, 0)).unwrap();
+    /// assert_eq!(night.as_string(), "22:00:00-24:00:00");
+    /// ```
+    pub fn as_string(&self) -> String {
+        format!("{}-{}", self.start.as_string(), self.end.as_string())
+    }
+}
+
+/// The AvailabilityRule structure is a week schedule (e.g. opening hours) with the open
+/// windows of every weekday, the weekdays are numbered like ```Date::get_weekday()``` from
+/// 0 = Sunday to 6 = Saturday. Overlapping or adjacent windows are merged.
+///
+/// ```
+/// use date_and_time::schedule::*;
+/// use date_and_time::time::*;
+/// let window = |from, to| TimeInterval::new(Time::from(from, 0, 0), Time::from(to, 0, 0)).unwrap();
+/// let mut shop = AvailabilityRule::new();
+/// for weekday in 1..=5 {
+///     shop.add(weekday, window(9, 12)).add(weekday, window(13, 18));
+/// }
+/// shop.add(6, window(10, 14));
+/// assert!(shop.is_open(6, &Time::from(11, 0, 0)));
+/// assert!(!shop.is_open(0, &Time::from(11, 0, 0)));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AvailabilityRule {
+    windows: [Vec<TimeInterval>; 7],
+}
+
+#[allow(dead_code)]
+impl AvailabilityRule {
+    /// ```new()``` creates an ```AvailabilityRule``` without open windows.
+    ///
+    /// ```
+    /// use date_and_time::schedule::*;
+    /// assert!(AvailabilityRule::new().windows(1).is_empty());
+    /// ```
+    pub fn new() -> AvailabilityRule {
+        AvailabilityRule::default()
+    }
+    /// ```add(weekday, interval)``` adds the open window ```interval``` to the ```weekday```
+    /// (0 = Sunday to 6 = Saturday, other weekdays are ignored).
+    ///
+    /// ```
+    /// use date_and_time::schedule::*;
+    /// use date_and_time::time::*;
+    /// let mut rule = AvailabilityRule::new();
+    /// rule.add(1, TimeInterval::new(Time::from(9, 0, 0), Time::from(12, 0, 0)).unwrap());
+    /// rule.add(1, TimeInterval::new(Time::from(11, 0, 0), Time::from(14, 0, 0)).unwrap());
+    /// assert_eq!(rule.windows(1)[0].as_string(), "09:00:00-14:00:00");
+    /// ```
+    pub fn add(&mut self, weekday: u8, interval: TimeInterval) -> &mut AvailabilityRule {
+        let Some(windows) = self.windows.get_mut(weekday as usize) else {
+            return self;
+        };
+        windows.push(interval);
+        windows.sort_by_key(|w| w.start.as_seconds());
+        let mut merged: Vec<TimeInterval> = Vec::with_capacity(windows.len());
+        for window in windows.iter() {
+            match merged.last_mut() {
+                Some(last) if window.start.as_seconds() <= last.end.as_seconds() => {
+                    if window.end.as_seconds() > last.end.as_seconds() {
+                        last.end = window.end;
+                    }
+                }
+                _ => merged.push(*window),
+            }
+        }
+        *windows = merged;
+        self
+    }
+    /// ```windows(weekday)``` returns the sorted open windows of the ```weekday```.
+    ///
+    /// ```
+    /// use date_and_time::schedule::*;
+    /// assert!(AvailabilityRule::new().windows(7).is_empty());
+    /// ```
+    pub fn windows(&self, weekday: u8) -> &[TimeInterval] {
+        self.windows
+            .get(weekday as usize)
+            .map_or(&[], |windows| windows.as_slice())
+    }
+    /// ```is_open(weekday, &time)``` returns true if the ```time``` is in an open window of
+    /// the ```weekday```.
+    ///
+    /// ```
+    /// use date_and_time::schedule::*;
+    /// use date_and_time::time::*;
+    /// let mut rule = AvailabilityRule::new();
+    /// rule.add(3, TimeInterval::new(Time::from(9, 0, 0), Time::from(17, 0, 0)).unwrap());
+    /// assert!(rule.is_open(3, &Time::from(16, 59, 59)));
+    /// ```
+    pub fn is_open(&self, weekday: u8, time: &Time) -> bool {
+        self.windows(weekday).iter().any(|w| w.contains(time))
+    }
+}
+
+/// The kind of a ```ScheduleChange```.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The window is open in the new schedule, but was closed in the old one.
+    Added,
+    /// The window was open in the old schedule, but is closed in the new one.
+    Removed,
+}
+
+/// A difference between two week schedules, see ```compare_schedules()```.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScheduleChange {
+    pub weekday: u8,
+    pub kind: ChangeKind,
+    pub interval: TimeInterval,
+}
+
+/// ```compare_schedules(&old, &new)``` returns the windows which are added to or removed
+/// from the ```old``` schedule in the ```new``` one, sorted by the weekday (from Sunday) and
+/// the start time. Windows which only moved are reported as the removed and the added part.
+///
+/// ```
+/// use date_and_time::schedule::*;
+/// use date_and_time::time::*;
+/// let window = |from, to| TimeInterval::new(Time::from(from, 0, 0), Time::from(to, 0, 0)).unwrap();
+/// let mut old = AvailabilityRule::new();
+/// old.add(1, window(9, 17));
+/// let mut new = AvailabilityRule::new();
+/// new.add(1, window(10, 18));
+/// let changes = compare_schedules(&old, &new);
+/// assert_eq!(changes[0].kind, ChangeKind::Removed);
+/// assert_eq!(changes[0].interval.as_string(), "09:00:00-10:00:00");
+/// assert_eq!(changes[1].kind, ChangeKind::Added);
+/// assert_eq!(changes[1].interval.as_string(), "17:00:00-18:00:00");
+/// ```
+pub fn compare_schedules(old: &AvailabilityRule, new: &AvailabilityRule) -> Vec<ScheduleChange> {
+    let mut changes: Vec<ScheduleChange> = Vec::new();
+    for weekday in 0..7 {
+        let removed = subtract(old.windows(weekday), new.windows(weekday));
+        let added = subtract(new.windows(weekday), old.windows(weekday));
+        let mut day: Vec<ScheduleChange> = removed
+            .into_iter()
+            .map(|interval| (ChangeKind::Removed, interval))
+            .chain(
+                added
+                    .into_iter()
+                    .map(|interval| (ChangeKind::Added, interval)),
+            )
+            .map(|(kind, interval)| ScheduleChange {
+                weekday,
+                kind,
+                interval,
+            })
+            .collect();
+        day.sort_by_key(|c| c.interval.start.as_seconds());
+        changes.append(&mut day);
+    }
+    changes
+}
+
+// Returns the parts of the sorted windows: &[TimeInterval] which are not covered by the
+// sorted windows other: &[TimeInterval]
+fn subtract(windows: &[TimeInterval], other: &[TimeInterval]) -> Vec<TimeInterval> {
+    let mut result: Vec<TimeInterval> = Vec::new();
+    for window in windows {
+        let mut start = window.start.as_seconds();
+        let end = window.end.as_seconds();
+        for cut in other {
+            let (cut_start, cut_end) = (cut.start.as_seconds(), cut.end.as_seconds());
+            if cut_end <= start || cut_start >= end {
+                continue;
+            }
+            if cut_start > start {
+                result.push(get_interval(start, cut_start));
+            }
+            start = start.max(cut_end);
+        }
+        if start < end {
+            result.push(get_interval(start, end));
+        }
+    }
+    result
+}
+
+// Returns the TimeInterval from the start: i64 to the end: i64 in seconds of the day
+fn get_interval(start: i64, end: i64) -> TimeInterval {
+    TimeInterval {
+        start: Time::from_seconds(start),
+        end: Time::from_seconds(end),
+    }
+}
@@ -0,0 +1,291 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+
+// A week (the days since the epoch of its Monday) with the weekdays (bit 0 = Monday) and
+// the days since the epoch of its dates
+type Week = (i64, u8, Vec<i64>);
+
+/// A part of the summary of a date list, see ```summarize_dates()```.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DateSpanSummary {
+    /// A date without neighbours.
+    Single(Date),
+    /// All days from the ```start``` to the ```end``` date.
+    Range { start: Date, end: Date },
+    /// The same weekdays from the ```first_weekday``` to the ```last_weekday``` (0 =
+    /// Sunday to 6 = Saturday, like ```Date::get_weekday()```) in some consecutive
+    /// ```weeks``` from the ```start``` to the ```end``` date, without the ```missing```
+    /// dates (e.g. the holidays).
+    Weekly {
+        start: Date,
+        end: Date,
+        first_weekday: u8,
+        last_weekday: u8,
+        weeks: u32,
+        missing: Vec<Date>,
+    },
+}
+
+#[allow(dead_code)]
+impl DateSpanSummary {
+    /// ```count()``` returns the number of dates in the summary.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::summary::*;
+    /// let range = DateSpanSummary::Range {
+    ///     start: Date::from(30, 6, 2024),
+    ///     end: Date::from(2, 7, 2024),
+    /// };
+    /// assert_eq!(range.count(), 3);
+    /// ```
+    pub fn count(&self) -> usize {
+        match self {
+            DateSpanSummary::Single(_) => 1,
+            DateSpanSummary::Range { start, end } => {
+                start.diff_in_days(end).unsigned_abs() as usize + 1
+            }
+            DateSpanSummary::Weekly {
+                first_weekday,
+                last_weekday,
+                weeks,
+                missing,
+                ..
+            } => {
+                let days = (*last_weekday as usize + 6) % 7 - (*first_weekday as usize + 6) % 7 + 1;
+                days * *weeks as usize - missing.len()
+            }
+        }
+    }
+    /// ```as_string()``` gets the summary as a text for humans, the dates are in the format
+    /// YYYY-MM-DD.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::summary::*;
+    /// let range = DateSpanSummary::Range {
+    ///     start: Date::from(30, 6, 2024),
+    ///     end: Date::from(2, 7, 2024),
+    /// };
+    /// assert_eq!(range.as_string(), "2024-06-30 to 2024-07-02 (3 days)");
+    /// ```
+    pub fn as_string(&self) -> String {
+        match self {
+            DateSpanSummary::Single(date) => date.as_string(),
+            DateSpanSummary::Range { start, end } => format!(
+                "{} to {} ({} days)",
+                start.as_string(),
+                end.as_string(),
+                self.count()
+            ),
+            DateSpanSummary::Weekly {
+                start,
+                end,
+                first_weekday,
+                last_weekday,
+                weeks,
+                missing,
+            } => {
+                let mut s = String::from(WEEKDAY_ABBREVIATE[*first_weekday as usize % 7]);
+                if first_weekday != last_weekday {
+                    s.push('–');
+                    s.push_str(WEEKDAY_ABBREVIATE[*last_weekday as usize % 7]);
+                }
+                s.push_str(&format!(
+                    " ×{} weeks from {} to {}",
+                    weeks,
+                    start.as_string(),
+                    end.as_string()
+                ));
+                if !missing.is_empty() {
+                    let dates: Vec<String> = missing.iter().map(|d| d.as_string()).collect();
+                    s.push_str(&format!(" except {}", dates.join(", ")));
+                }
+                s
+            }
+        }
+    }
+}
+
+/// ```summarize_dates(&dates)``` compresses the ```dates``` into a chronological list of
+/// summaries for reports. The same weekdays in at least two consecutive weeks (e.g. Monday
+/// to Friday) become a ```DateSpanSummary::Weekly```, every week may miss one of the
+/// weekdays (e.g. a holiday) and the other dates of these weeks follow the weekly summary.
+/// The other consecutive days become a ```DateSpanSummary::Range``` and the remaining
+/// dates a ```DateSpanSummary::Single```. The ```dates``` don't need to be sorted,
+/// duplicates and invalid dates are ignored.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::summary::*;
+/// // the working days in July 2024 until the 19th, without the 4th of July
+/// let mut dates: Vec<Date> = (1..=19)
+///     .map(|day| Date::from(day, 7, 2024))
+///     .filter(|date| (1..=5).contains(&date.get_weekday()) && date.d != 4)
+///     .collect();
+/// dates.push(Date::from(27, 7, 2024));
+/// let summary = summarize_dates(&dates);
+/// assert_eq!(
+///     summary[0].as_string(),
+///     "Mon–Fri ×3 weeks from 2024-07-01 to 2024-07-19 except 2024-07-04"
+/// );
+/// assert_eq!(summary[0].count(), 14);
+/// assert_eq!(summary[1], DateSpanSummary::Single(Date::from(27, 7, 2024)));
+/// ```
+pub fn summarize_dates(dates: &[Date]) -> Vec<DateSpanSummary> {
+    let mut days: Vec<i64> = dates
+        .iter()
+        .filter(|date| is_date_valid(date))
+        .map(get_days_from_date)
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+    // the 1st January 1970 was a Thursday
+    let mut weeks: Vec<Week> = Vec::new();
+    for day in days {
+        let monday = day - (day + 3).rem_euclid(7);
+        let bit = 1 << (day - monday);
+        match weeks.last_mut() {
+            Some(week) if week.0 == monday => {
+                week.1 |= bit;
+                week.2.push(day);
+            }
+            _ => weeks.push((monday, bit, vec![day])),
+        }
+    }
+    let mut result: Vec<DateSpanSummary> = Vec::new();
+    let mut rest: Vec<i64> = Vec::new();
+    let mut n = 0;
+    while n < weeks.len() {
+        // a partial week before a group (e.g. starting on Wednesday) is not a part of it
+        let group = get_weekly_group(&weeks, n).filter(|&(_, _, covered)| {
+            get_weekly_group(&weeks, n + 1).is_none_or(|next| next.2 <= covered)
+        });
+        let Some((pattern, end, _)) = group else {
+            rest.extend(&weeks[n].2);
+            n += 1;
+            continue;
+        };
+        push_ranges(&mut result, &rest);
+        rest.clear();
+        for week in &weeks[n..end] {
+            rest.extend(
+                week.2
+                    .iter()
+                    .filter(|&day| pattern & 1 << (day - week.0) == 0),
+            );
+        }
+        let first = pattern.trailing_zeros() as i64;
+        let last = 7 - pattern.leading_zeros() as i64;
+        let missing: Vec<Date> = weeks[n..end]
+            .iter()
+            .flat_map(|week| {
+                (first..=last)
+                    .filter(|k| week.1 & (1 << k) == 0)
+                    .map(|k| get_date_from_days(week.0 + k))
+            })
+            .collect();
+        result.push(DateSpanSummary::Weekly {
+            start: get_date_from_days(weeks[n].0 + first),
+            end: get_date_from_days(weeks[end - 1].0 + last),
+            first_weekday: ((first + 1) % 7) as u8,
+            last_weekday: ((last + 1) % 7) as u8,
+            weeks: (end - n) as u32,
+            missing,
+        });
+        n = end;
+    }
+    push_ranges(&mut result, &rest);
+    result
+}
+
+/// ```describe_dates(&dates)``` returns the ```summarize_dates()``` of the ```dates``` as
+/// one text, e.g. for the description of a schedule export.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::summary::*;
+/// let dates = [1, 2, 3, 8, 9, 10, 14].map(|day| Date::from(day, 7, 2024));
+/// assert_eq!(
+///     describe_dates(&dates),
+///     "Mon–Wed ×2 weeks from 2024-07-01 to 2024-07-10, plus 2024-07-14"
+/// );
+/// ```
+pub fn describe_dates(dates: &[Date]) -> String {
+    let parts: Vec<String> = summarize_dates(dates)
+        .iter()
+        .map(|summary| summary.as_string())
+        .collect();
+    parts.join(", plus ")
+}
+
+// Returns the weekdays pattern: u8, the end: usize and the number of dates in the pattern:
+// u32 of the weekly group starting with the weeks[start], or None if the week doesn't start
+// a group
+fn get_weekly_group(weeks: &[Week], start: usize) -> Option<(u8, usize, u32)> {
+    let next = weeks.get(start + 1)?;
+    if next.0 != weeks[start].0 + 7 {
+        return None;
+    }
+    // the weekdays of both weeks, or all weekdays between the common weekdays
+    let (both, any) = (weeks[start].1 & next.1, weeks[start].1 | next.1);
+    let span = match both {
+        0 => 0,
+        _ => (0xff >> both.leading_zeros()) & (0xff << both.trailing_zeros()),
+    };
+    let mut best: Option<(u8, usize, u32, u32)> = None;
+    for pattern in [span, any] {
+        let len = pattern.count_ones();
+        if len == 0 || len == 7 || pattern >> pattern.trailing_zeros() != (1 << len) - 1 {
+            continue;
+        }
+        // the missing and the other dates of the weeks in the group
+        let mut deviations = 0;
+        let mut covered = 0;
+        let mut end = start;
+        while let Some(week) = weeks.get(end) {
+            let inside = (week.1 & pattern).count_ones();
+            let consecutive = end == start || week.0 == weeks[end - 1].0 + 7;
+            if !consecutive || inside == 0 || inside + 1 < len {
+                break;
+            }
+            deviations += len - inside + (week.1 & !pattern).count_ones();
+            covered += inside;
+            end += 1;
+        }
+        let better = match best {
+            Some((_, best_end, _, best_deviations)) => {
+                end > best_end || (end == best_end && deviations < best_deviations)
+            }
+            None => true,
+        };
+        if end - start >= 2 && better {
+            best = Some((pattern, end, covered, deviations));
+        }
+    }
+    best.map(|(pattern, end, covered, _)| (pattern, end, covered))
+}
+
+// Appends the Single and Range summaries of the sorted days: &[i64] since the epoch to the
+// summaries: &mut Vec<DateSpanSummary>
+fn push_ranges(summaries: &mut Vec<DateSpanSummary>, days: &[i64]) {
+    let mut n = 0;
+    while n < days.len() {
+        let mut end = n + 1;
+        while end < days.len() && days[end] == days[end - 1] + 1 {
+            end += 1;
+        }
+        if end - n == 1 {
+            summaries.push(DateSpanSummary::Single(get_date_from_days(days[n])));
+        } else {
+            summaries.push(DateSpanSummary::Range {
+                start: get_date_from_days(days[n]),
+                end: get_date_from_days(days[end - 1]),
+            });
+        }
+        n = end;
+    }
+}
@@ -2,7 +2,13 @@
 // (c) 2024 by markus dot mueller dot 73 at hotmail dot de
 // small crate to get some rudimentary date and time calculations
 // the license details are in the main library file.
-use std::time::SystemTime;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::formatted::*;
+use crate::date_and_time::invalid::*;
+use crate::date_and_time::locale::*;
+use std::fmt;
 
 /// The Time structure can build/filled with with the functions ```new()```, ```set()```,
 /// ```from()```,  ```from_seconds()``` and ```from_system_date()```. An ```as_strinng()``` function is
@@ -14,6 +20,10 @@ use std::time::SystemTime;
 /// In validity checks the hours didn't checked at all. Only minutes and seconds get checked
 /// and only in a few methods.
 ///
+/// A time can be negative (e.g. the result of ```sub_hours()```), the sign is in the first
+/// field which is not zero and the other fields are positive: ```Time{h: -1, m: 30, s: 0}```
+/// is -01:30:00 and ```Time{h: 0, m: -30, s: 0}``` is -00:30:00.
+///
 /// The structure owns the traits ```Copy```, ```Clone``` and ```PartialEq```. so you can
 /// compare two times if they are equal or not.
 ///
@@ -24,10 +34,30 @@ pub struct Time {
     pub s: i8,
 }
 
+/// The half of the day of a time on the 12-hour clock, see ```Time::from_12h()```.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Meridiem {
+    /// ante meridiem, the hours from midnight to noon (12 a.m. is 0:00).
+    AM,
+    /// post meridiem, the hours from noon to midnight (12 p.m. is 12:00).
+    PM,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Time::new()
+    }
+}
+
 #[allow(dead_code)]
 impl Time {
     /// ```new()``` creates a ```Time``` structure with this time 0:00:00.
-    pub fn new() -> Time {
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::new().as_string(), "00:00:00");
+    /// ```
+    pub const fn new() -> Time {
         Time { h: 0, m: 0, s: 0 }
     }
     /// ```from(hour, minute, second)``` creates a ```Time``` structure with
@@ -37,30 +67,98 @@ impl Time {
     /// will be ```Time{h: 0, m: -1, s: -1}```. You can check against the minutes or seconds if
     ///  you got a valid time.
     ///
-    pub fn from(hour: i32, minute: i8, second: i8) -> Time {
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(13, 45, 30).as_string(), "13:45:30");
+    /// // the minutes are invalid
+    /// assert_eq!(Time::from(12, 60, 0).m, -1);
+    /// // the function is const, e.g. for the opening hours
+    /// const OPENING: Time = Time::from(9, 0, 0);
+    /// assert_eq!(OPENING.h, 9);
+    /// ```
+    ///
+    pub const fn from(hour: i32, minute: i8, second: i8) -> Time {
         let t = Time {
             h: hour,
             m: minute,
             s: second,
         };
-        if is_time_valid(&t) == false {
+        if !is_time_valid(&t) {
             return Time { h: 0, m: -1, s: -1 };
         }
         t
     }
     /// ```from_seconds(seconds)``` creates a new ```Time``` structure from the ```seconds```
-    pub fn from_seconds(seconds: i64) -> Time {
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from_seconds(9_000), Time::from(2, 30, 0));
+    /// ```
+    pub const fn from_seconds(seconds: i64) -> Time {
         secs_to_time(seconds)
     }
+    /// ```from_milliseconds(milliseconds)``` creates a ```Duration``` from the
+    /// ```milliseconds```. A ```Time``` has whole seconds, so the ```Duration``` keeps the
+    /// fractions of a second for stopwatch-like uses, ```as_time()``` returns the
+    /// ```Time``` without them.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from_milliseconds(1_500).as_milliseconds(), 1_500);
+    /// assert_eq!(Time::from_milliseconds(9_000_999).as_time(), Time::from(2, 30, 0));
+    /// assert_eq!(Time::from_milliseconds(90_061_000).as_time(), Time::from(25, 1, 1));
+    /// ```
+    pub fn from_milliseconds(milliseconds: i64) -> Duration {
+        Duration::from_milliseconds(milliseconds)
+    }
+    /// ```from_12h(hour, minute, second, meridiem)``` creates a ```Time``` structure from a
+    /// time of the 12-hour clock, the ```hour``` is 1 to 12. It returns an error for an
+    /// invalid hour, minute or second.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from_12h(9, 5, 0, Meridiem::PM), Ok(Time::from(21, 5, 0)));
+    /// assert_eq!(Time::from_12h(12, 30, 0, Meridiem::AM), Ok(Time::from(0, 30, 0)));
+    /// assert_eq!(Time::from_12h(12, 0, 0, Meridiem::PM), Ok(Time::from(12, 0, 0)));
+    /// assert!(Time::from_12h(13, 0, 0, Meridiem::PM).is_err());
+    /// ```
+    pub fn from_12h(
+        hour: u8,
+        minute: i8,
+        second: i8,
+        meridiem: Meridiem,
+    ) -> Result<Time, DateTimeError> {
+        let time = Time {
+            h: (hour % 12) as i32,
+            m: minute,
+            s: second,
+        };
+        // the negative minutes and seconds are only valid for the durations with the hour 0
+        if !(1..=12).contains(&hour) || minute < 0 || second < 0 || !is_time_valid(&time) {
+            return Err(DateTimeError::OutOfRange(format!(
+                "{:02}:{:02}:{:02} {:?} is not a valid time of the 12-hour clock",
+                hour, minute, second, meridiem
+            )));
+        }
+        match meridiem {
+            Meridiem::AM => Ok(time),
+            Meridiem::PM => Ok(Time {
+                h: time.h + 12,
+                ..time
+            }),
+        }
+    }
     /// ```from_system_clock()``` creates a new ```Time``` structure from the systems clock.
     /// The result is in UTC time and will probably be different from your local time.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let now = Time::from_system_clock();
+    /// println!("It is {} UTC", now.as_string());
+    /// ```
     pub fn from_system_clock() -> Time {
-        let sys_secs = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        let secs: i64 = sys_secs as i64 % 86_400;
-        secs_to_time(secs)
+        let secs = get_system_clock_nanoseconds().div_euclid(1_000_000_000);
+        secs_to_time(secs.rem_euclid(86_400) as i64)
     }
     // TODO pub fn from_local_clock() -> Time
     /// ```set(hour, minute, second)``` modifies your ```Time``` structure to the hour, minute
@@ -70,22 +168,159 @@ impl Time {
     /// will be ```Time{h: 0, m: -1, s: -1}```. You can check against the minutes or seconds if
     ///  you got a valid time.
     ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let mut time = Time::new();
+    /// time.set(8, 15, 0);
+    /// assert_eq!(time.as_string(), "08:15:00");
+    /// ```
+    ///
     pub fn set(&mut self, hour: i32, minute: i8, second: i8) {
         self.h = hour;
         self.m = minute;
         self.s = second;
-        if is_time_valid(self) == false {
+        if !is_time_valid(self) {
             self.h = 0;
             self.m = -1;
             self.s = -1;
         }
     }
-    /// ```as_seconds()``` returns the seconds from your ```Time``` structure.
-    pub fn as_seconds(&self) -> u32 {
-        self.h as u32 * 3_600 + self.m as u32 * 60 + self.s as u32
+    /// ```hour()``` returns the hours of the ```Time```, it is the same as the field
+    /// ```h```.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(13, 45, 30).hour(), 13);
+    /// ```
+    pub fn hour(&self) -> i32 {
+        self.h
+    }
+    /// ```minute()``` returns the minutes of the ```Time```, it is the same as the field
+    /// ```m```.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(13, 45, 30).minute(), 45);
+    /// ```
+    pub fn minute(&self) -> i8 {
+        self.m
+    }
+    /// ```second()``` returns the seconds of the ```Time```, it is the same as the field
+    /// ```s```.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(13, 45, 30).second(), 30);
+    /// ```
+    pub fn second(&self) -> i8 {
+        self.s
+    }
+    /// ```to_12h()``` returns the hour (1 to 12) of the 12-hour clock and the half of the
+    /// day, the minutes and seconds are the same. Times of 24 hours and more are reduced to
+    /// the time of the day.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(0, 15, 0).to_12h(), (12, Meridiem::AM));
+    /// assert_eq!(Time::from(12, 15, 0).to_12h(), (12, Meridiem::PM));
+    /// assert_eq!(Time::from(23, 59, 59).to_12h(), (11, Meridiem::PM));
+    /// ```
+    pub fn to_12h(&self) -> (u8, Meridiem) {
+        let hour = self.normalize().1.h as u8;
+        let meridiem = if hour < 12 {
+            Meridiem::AM
+        } else {
+            Meridiem::PM
+        };
+        match hour % 12 {
+            0 => (12, meridiem),
+            h => (h, meridiem),
+        }
+    }
+    /// ```as_seconds()``` returns the seconds from your ```Time``` structure, they are
+    /// negative for a negative time.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(1, 1, 1).as_seconds(), 3_661);
+    /// assert_eq!(Time::from(-1, 30, 0).as_seconds(), -5_400);
+    /// ```
+    pub fn as_seconds(&self) -> i64 {
+        time_to_secs(self)
+    }
+    /// ```is_negative()``` returns true if the time is less than 0:00:00, e.g. the result of
+    /// ```Time::from(1, 0, 0).sub_hours(2)```.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert!(Time::from(0, -30, 0).is_negative());
+    /// assert!(Time::from(8, 0, 0).sub_hours(10).is_negative());
+    /// assert!(!Time::new().is_negative());
+    /// ```
+    pub fn is_negative(&self) -> bool {
+        time_to_secs(self) < 0
+    }
+    /// ```abs()``` returns the time without the sign.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(-1, 30, 0).abs(), Time::from(1, 30, 0));
+    /// ```
+    pub fn abs(&self) -> Time {
+        secs_to_time(time_to_secs(self).abs())
+    }
+    /// ```as_milliseconds()``` returns the milliseconds of the ```Time``` structure. The
+    /// ```Time``` has whole seconds, use the ```Duration``` of ```from_milliseconds()``` to
+    /// keep the fractions of a second.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(1, 1, 1).as_milliseconds(), 3_661_000);
+    /// assert_eq!(Time::from(0, 0, 0).sub_hours(1).as_milliseconds(), -3_600_000);
+    /// ```
+    pub fn as_milliseconds(&self) -> i64 {
+        time_to_secs(self) * 1_000
+    }
+    /// ```normalize()``` splits a counting time into the days and the clock time of the last
+    /// day (0:00:00 to 23:59:59), e.g. for the formatter or the clock functions. A negative
+    /// time is a negative number of days and a clock time before the midnight.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(75, 30, 0).normalize(), (3, Time::from(3, 30, 0)));
+    /// assert_eq!(Time::from(23, 0, 0).normalize(), (0, Time::from(23, 0, 0)));
+    /// assert_eq!(Time::from(1, 0, 0).sub_hours(2).normalize(), (-1, Time::from(23, 0, 0)));
+    /// ```
+    pub fn normalize(&self) -> (i64, Time) {
+        let secs = time_to_secs(self);
+        (
+            secs.div_euclid(86_400),
+            secs_to_time(secs.rem_euclid(86_400)),
+        )
+    }
+    /// ```bucket(minutes_per_bucket)``` returns the number of the bucket of the time of the
+    /// day, the day is divided into buckets of ```minutes_per_bucket``` minutes from the
+    /// midnight (e.g. 15 minutes: 00:00 to 00:14 is 0, 00:15 to 00:29 is 1). Times of 24
+    /// hours and more are in the buckets of the day, with ```0``` minutes per bucket every
+    /// minute is a bucket.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(13, 44, 59).bucket(15), 54);
+    /// assert_eq!(Time::from(13, 44, 59).bucket(60), 13);
+    /// assert_eq!(Time::from(25, 0, 0).bucket(60), 1);
+    /// ```
+    pub fn bucket(&self, minutes_per_bucket: u16) -> u16 {
+        let minute_of_day = time_to_secs(self).div_euclid(60).rem_euclid(1_440);
+        (minute_of_day / minutes_per_bucket.max(1) as i64) as u16
     }
     /// ```as_float()``` returns the your ```Time``` structure as a float.
     /// For the calculation, the seconds and minutes are each extrapolated to 100 values.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(12, 30, 0).as_float(), 12.5);
+    /// ```
     pub fn as_float(&self) -> f32 {
         let h: f32 = self.h as f32;
         let m: f32 = self.m as f32 / 60.0 * 100.0;
@@ -95,65 +330,208 @@ impl Time {
         h + m / 100.0 + s / 10_000.0
     }
     /// ```diff_in_seconds(&other_time)``` gets the difference between the two times in seconds.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let start = Time::from(8, 0, 0);
+    /// assert_eq!(start.diff_in_seconds(&Time::from(9, 30, 0)), 5_400);
+    /// ```
     pub fn diff_in_seconds(&self, t: &Time) -> i64 {
-        let diff_secs: i64 = time_to_secs(t) as i64 - time_to_secs(self) as i64;
+        let diff_secs: i64 = time_to_secs(t) - time_to_secs(self);
         diff_secs
     }
+    /// ```within(&other_time, seconds)``` returns true if the two times differ by not more
+    /// than the ```seconds``` (in both directions).
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let alarm = Time::from(7, 0, 0);
+    /// assert!(alarm.within(&Time::from(6, 59, 30), 30));
+    /// assert!(!alarm.within(&Time::from(7, 1, 0), 30));
+    /// ```
+    pub fn within(&self, t: &Time, seconds: i64) -> bool {
+        self.diff_in_seconds(t).abs() <= seconds
+    }
     /// ```add_time(&other_time)``` adds the ```&other_time``` to the time and returns a new
     /// ```Time``` structure.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// // the result is not reduced to 24 hours, see add_time_wrapping()
+    /// let late = Time::from(23, 30, 0).add_time(&Time::from(1, 0, 0));
+    /// assert_eq!(late, Time::from(24, 30, 0));
+    /// ```
     pub fn add_time(&self, time: &Time) -> Time {
-        let s: i64 = time_to_secs(self) as i64 + time_to_secs(time) as i64;
+        let s: i64 = time_to_secs(self) + time_to_secs(time);
         secs_to_time(s)
     }
     /// ```sub_time(&other_time)``` substract the ```&other_time``` from the time and returns
     /// a new ```Time``` structure.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let time = Time::from(1, 0, 0).sub_time(&Time::from(0, 30, 0));
+    /// assert_eq!(time, Time::from(0, 30, 0));
+    /// ```
     pub fn sub_time(&self, time: &Time) -> Time {
-        let s: i64 = time_to_secs(self) as i64 - time_to_secs(time) as i64;
+        let s: i64 = time_to_secs(self) - time_to_secs(time);
         secs_to_time(s)
     }
     /// ```add_hours(hours)``` adds the ```hours``` to the time and returns a new
     /// ```Time``` structure.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(10, 15, 0).add_hours(3), Time::from(13, 15, 0));
+    /// ```
     pub fn add_hours(&self, hours: i64) -> Time {
-        let s: i64 = time_to_secs(self) as i64 + hours * 3_600;
+        let s: i64 = time_to_secs(self) + hours * 3_600;
         secs_to_time(s)
     }
     /// ```sub_hours(hours)``` substract the ```hours``` from the time and returns a new
     /// ```Time``` structure.
     /// It is possible to get a negative result.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(10, 0, 0).sub_hours(12).h, -2);
+    /// ```
     pub fn sub_hours(&self, hours: i64) -> Time {
-        let s: i64 = time_to_secs(self) as i64 - hours * 3_600;
+        let s: i64 = time_to_secs(self) - hours * 3_600;
         secs_to_time(s)
     }
     /// ```add_minutes(minutes)``` adds the ```minutes``` to the time and returns a new
     /// ```Time``` structure.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(10, 30, 0).add_minutes(45), Time::from(11, 15, 0));
+    /// ```
     pub fn add_minutes(&self, minutes: i64) -> Time {
-        let s: i64 = time_to_secs(self) as i64 + minutes * 60;
+        let s: i64 = time_to_secs(self) + minutes * 60;
         secs_to_time(s)
     }
     /// ```sub_minutes(minutes)``` substract the ```minutes``` from the time and returns a new
     /// ```Time``` structure.
     /// It is possible to get a negative result.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let mut time = Time::from(10, 0, 0);
+    /// assert_eq!(time.sub_minutes(90), Time::from(8, 30, 0));
+    /// ```
     pub fn sub_minutes(&mut self, minutes: i64) -> Time {
-        let s: i64 = time_to_secs(self) as i64 - minutes * 60;
+        let s: i64 = time_to_secs(self) - minutes * 60;
         secs_to_time(s)
     }
     /// ```add_seconds(seconds)``` adds the ```seconds``` to the time and returns a new
     /// ```Time``` structure.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let mut time = Time::from(10, 59, 30);
+    /// assert_eq!(time.add_seconds(45), Time::from(11, 0, 15));
+    /// ```
     pub fn add_seconds(&mut self, seconds: i64) -> Time {
-        let s: i64 = time_to_secs(self) as i64 + seconds;
+        let s: i64 = time_to_secs(self) + seconds;
         secs_to_time(s)
     }
     /// ```sub_seconds(seconds)``` substract the ```seconds``` from the time and returns a new
     /// ```Time``` structure.
     /// It is possible to get a negative result.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let mut time = Time::from(11, 0, 15);
+    /// assert_eq!(time.sub_seconds(45), Time::from(10, 59, 30));
+    /// ```
     pub fn sub_seconds(&mut self, seconds: i64) -> Time {
-        let s: i64 = time_to_secs(self) as i64 - seconds;
+        let s: i64 = time_to_secs(self) - seconds;
         secs_to_time(s)
     }
-    /// ```as_string()``` gets the Time structure as a string in the format HH:MM:SS.
+    /// ```add_time_wrapping(&other_time)``` adds the ```&other_time``` to the time on a
+    /// 24-hour clock and returns a new ```Time``` structure, the result is reduced to
+    /// 0:00:00 to 23:59:59.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let late = Time::from(23, 30, 0).add_time_wrapping(&Time::from(1, 0, 0));
+    /// assert_eq!(late, Time::from(0, 30, 0));
+    /// ```
+    pub fn add_time_wrapping(&self, time: &Time) -> Time {
+        get_clock_time(time_to_secs(self) + time_to_secs(time))
+    }
+    /// ```sub_time_wrapping(&other_time)``` substract the ```&other_time``` from the time
+    /// on a 24-hour clock like ```add_time_wrapping()```.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let early = Time::from(0, 30, 0).sub_time_wrapping(&Time::from(1, 0, 0));
+    /// assert_eq!(early, Time::from(23, 30, 0));
+    /// ```
+    pub fn sub_time_wrapping(&self, time: &Time) -> Time {
+        get_clock_time(time_to_secs(self) - time_to_secs(time))
+    }
+    /// ```add_hours_wrapping(hours)``` adds the ```hours``` to the time on a 24-hour clock
+    /// and returns a new ```Time``` structure, negative ```hours``` are substracted.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// // 8 hours after 22:00
+    /// assert_eq!(Time::from(22, 0, 0).add_hours_wrapping(8), Time::from(6, 0, 0));
+    /// assert_eq!(Time::from(2, 0, 0).add_hours_wrapping(-3), Time::from(23, 0, 0));
+    /// ```
+    pub fn add_hours_wrapping(&self, hours: i64) -> Time {
+        get_clock_time(time_to_secs(self) + hours.rem_euclid(24) * 3_600)
+    }
+    /// ```add_minutes_wrapping(minutes)``` adds the ```minutes``` to the time on a 24-hour
+    /// clock and returns a new ```Time``` structure, negative ```minutes``` are substracted.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(23, 50, 0).add_minutes_wrapping(20), Time::from(0, 10, 0));
+    /// ```
+    pub fn add_minutes_wrapping(&self, minutes: i64) -> Time {
+        get_clock_time(time_to_secs(self) + minutes.rem_euclid(1_440) * 60)
+    }
+    /// ```add_seconds_wrapping(seconds)``` adds the ```seconds``` to the time on a 24-hour
+    /// clock and returns a new ```Time``` structure, negative ```seconds``` are substracted.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(0, 0, 10).add_seconds_wrapping(-20), Time::from(23, 59, 50));
+    /// ```
+    pub fn add_seconds_wrapping(&self, seconds: i64) -> Time {
+        get_clock_time(time_to_secs(self) + seconds.rem_euclid(86_400))
+    }
+    /// ```as_option()``` returns the time, or ```None``` if it is invalid (e.g. the result
+    /// ```Time{h: 0, m: -1, s: -1}``` of ```from()```).
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(7, 5, 3).as_option(), Some(Time::from(7, 5, 3)));
+    /// assert_eq!(Time::from(7, 60, 3).as_option(), None);
+    /// ```
+    pub fn as_option(&self) -> Option<Time> {
+        is_time_valid(self).then_some(*self)
+    }
+    /// ```as_string()``` gets the Time structure as a string in the format HH:MM:SS, a
+    /// negative time starts with a minus. An invalid time is printed as the text of
+    /// ```invalid::set_invalid_display()```, if it was set.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// assert_eq!(Time::from(7, 5, 3).as_string(), "07:05:03");
+    /// assert_eq!(Time::from(0, 0, 0).sub_minutes(90).as_string(), "-01:30:00");
+    /// ```
     pub fn as_string(&self) -> String {
-        let s = String::from(format!("{:02}:{:02}:{:02}", self.h, self.m, self.s));
-        s
+        if !is_time_valid(self) {
+            return get_invalid_display()
+                .unwrap_or_else(|| format!("{:02}:{:02}:{:02}", self.h, self.m, self.s));
+        }
+        let abs = self.abs();
+        let sign = if self.is_negative() { "-" } else { "" };
+        format!("{}{:02}:{:02}:{:02}", sign, abs.h, abs.m, abs.s)
     }
     /// ```as_formated_string(time_format)``` gets the ```Time``` structure as a string in
     /// the ```time_format``` parameter.
@@ -174,106 +552,135 @@ impl Time {
     /// | R | equivalent to "%H:%M" |
     /// | S | writes second as a decimal number (range 00-59) |
     /// | T | equivalent to "%H:%M:%S" (the ISO 8601 time format) |
+    /// | X | writes the preferred time format of the locale, e.g. 09:05:00 p.m. |
+    /// | z | writes the offset of the local timezone to UTC, e.g. +0200 (%:z writes +02:00) |
+    /// | Z | writes the abbreviation of the local timezone, e.g. CEST |
+    ///
+    /// A number can have the modifiers of ```Date::as_formated_string()```, %-I writes it
+    /// without the padding, %_H pads it with spaces and %0M with zeros.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let time = Time::from(21, 5, 0);
+    /// assert_eq!(time.as_formated_string("%H:%M"), "21:05");
+    /// assert_eq!(time.as_formated_string("%I:%M %p"), "09:05 p.m.");
+    /// assert_eq!(time.as_formated_string("%-I:%M %p"), "9:05 p.m.");
+    /// // a negative time has the minus in front of the hours
+    /// assert_eq!(Time::from(0, -45, 0).as_formated_string("%H:%M"), "-00:45");
+    /// ```
     ///
     pub fn as_formated_string(&self, time_format: &str) -> String {
-        let mut chars = time_format.chars();
-        let mut result = String::default();
-        while let Some(c) = chars.next() {
-            if c == '%' {
-                let Some(cn) = chars.next() else {
-                    continue;
-                };
-                match cn {
-                    '%' => result.push('%'),
-                    'H' => {
-                        let s = String::from(format!("{:02}", self.h));
-                        result.push_str(&s)
-                    }
-                    'I' => {
-                        let t = if self.h == 0 {
-                            12
-                        } else if self.h > 12 {
-                            self.h - 12
-                        } else {
-                            self.h
-                        };
-                        let s = String::from(format!("{:02}", t));
-                        result.push_str(&s)
-                    }
-                    'M' => {
-                        let s = String::from(format!("{:02}", self.m));
-                        result.push_str(&s)
-                    }
-                    'p' => {
-                        if self.h >= 12 {
-                            result.push_str("p.m.");
-                        } else {
-                            result.push_str("a.m.");
-                        }
-                    }
-                    'r' => {
-                        let mut hour = self.h;
-                        if hour == 0 {
-                            hour = 24
-                        };
-                        if hour > 12 {
-                            hour -= 12
-                        };
-                        let h_12 = if self.h >= 12 { "PM" } else { "AM" };
-                        let s = String::from(format!(
-                            "{:2}:{:02}:{:02} {}",
-                            hour, self.m, self.s, h_12
-                        ));
-                        result.push_str(&s)
-                    }
-                    'R' => {
-                        let s = String::from(format!("{:02}:{:02}", self.h, self.m));
-                        result.push_str(&s)
-                    }
-                    'S' => {
-                        let s = String::from(format!("{:02}", self.s));
-                        result.push_str(&s)
-                    }
-                    'T' => {
-                        let s = String::from(format!("{:02}:{:02}:{:02}", self.h, self.m, self.s));
-                        result.push_str(&s)
-                    }
-                    'n' => result.push('\n'),
-                    't' => result.push('\t'),
-                    _ => result.push(cn),
-                };
-            } else {
-                result.push(c)
+        self.as_formated_string_localized(time_format, &English)
+    }
+    /// ```try_as_formated_string(time_format)``` gets the ```Time``` structure as a string
+    /// like ```as_formated_string()```, but it returns an error with the placeholder and its
+    /// position if the ```time_format``` has a placeholder which is unknown for a time (see
+    /// ```Date::try_as_formated_string()```).
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let time = Time::from(21, 5, 0);
+    /// assert_eq!(time.try_as_formated_string("%-I:%M %p"), Ok(String::from("9:05 p.m.")));
+    /// assert!(time.try_as_formated_string("%H:%m").is_err());
+    /// ```
+    pub fn try_as_formated_string(&self, time_format: &str) -> Result<String, DateTimeError> {
+        check_format(time_format, false, true)?;
+        Ok(self.as_formated_string(time_format))
+    }
+    /// ```as_formated_string_localized(time_format, &locale)``` gets the ```Time```
+    /// structure as a string in the ```time_format``` parameter like
+    /// ```as_formated_string()```, the a.m. and p.m. of %p are taken from the ```locale```.
+    ///
+    /// ```
+    /// use date_and_time::locale::*;
+    /// use date_and_time::time::*;
+    /// let time = Time::from(21, 5, 0);
+    /// let text = time.as_formated_string_localized(English.time_format(), &English);
+    /// assert_eq!(text, "09:05:00 p.m.");
+    /// ```
+    pub fn as_formated_string_localized(
+        &self,
+        time_format: &str,
+        locale: &(impl Locale + ?Sized),
+    ) -> String {
+        if !is_time_valid(self) {
+            if let Some(text) = get_invalid_display() {
+                return text;
             }
         }
-        result
+        get_formated_string(None, Some(self), None, time_format, locale)
+    }
+    /// ```format_into(&mut out, time_format)``` writes the ```Time``` structure in the
+    /// ```time_format``` parameter of ```as_formated_string()``` into ```out```, without the
+    /// allocation of a new ```String```.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let mut line = String::new();
+    /// Time::from(21, 5, 0).format_into(&mut line, "%R").unwrap();
+    /// assert_eq!(line, "21:05");
+    /// ```
+    pub fn format_into(
+        &self,
+        out: &mut (impl fmt::Write + ?Sized),
+        time_format: &str,
+    ) -> fmt::Result {
+        if !is_time_valid(self) {
+            if let Some(text) = get_invalid_display() {
+                return out.write_str(&text);
+            }
+        }
+        write_formated_string(out, None, Some(self), None, time_format, &English)
     }
 }
 
-// Returns the time in the Time structure in seconds
-fn time_to_secs(t: &Time) -> u32 {
-    t.h as u32 * 3_600 + t.m as u32 * 60 + t.s as u32
+// Returns the time in the Time structure in seconds, the sign is in the first field which
+// is not zero
+pub(crate) const fn time_to_secs(t: &Time) -> i64 {
+    let secs = (t.h as i64).abs() * 3_600 + (t.m as i64).abs() * 60 + (t.s as i64).abs();
+    if t.h < 0 || (t.h == 0 && (t.m < 0 || (t.m == 0 && t.s < 0))) {
+        return -secs;
+    }
+    secs
 }
 
-// Returns a Time structure of the give secs: i64, the time in the Time structure is
-// always positive
-fn secs_to_time(secs: i64) -> Time {
-    let mut sec = secs;
-    let hrs = sec / 3_600;
-    sec -= hrs * 3_600;
-    let min = sec / 60;
-    sec -= min * 60;
-    Time {
-        h: hrs as i32,
-        m: min as i8,
-        s: sec as i8,
+// Returns a Time structure of the give secs: i64, a negative time has the sign in the
+// first field which is not zero
+const fn secs_to_time(secs: i64) -> Time {
+    let abs = secs.unsigned_abs();
+    let mut t = Time {
+        h: (abs / 3_600) as i32,
+        m: (abs / 60 % 60) as i8,
+        s: (abs % 60) as i8,
+    };
+    if secs < 0 {
+        if t.h > 0 {
+            t.h = -t.h;
+        } else if t.m > 0 {
+            t.m = -t.m;
+        } else {
+            t.s = -t.s;
+        }
     }
+    t
+}
+
+// Returns the Time of the secs: i64 on a 24-hour clock (0:00:00 to 23:59:59)
+const fn get_clock_time(secs: i64) -> Time {
+    secs_to_time(secs.rem_euclid(86_400))
 }
 
-// Returns true if the time is valid, else false
-fn is_time_valid(t: &Time) -> bool {
-    if t.m >= 0 && t.m < 60 && t.s >= 0 && t.s < 60 {
-        return true;
+// Returns true if the time is valid, else false. A negative minute is only valid without
+// hours and a negative second only without hours and minutes.
+pub(crate) const fn is_time_valid(t: &Time) -> bool {
+    if t.m <= -60 || t.m >= 60 || t.s <= -60 || t.s >= 60 {
+        return false;
+    }
+    if t.m < 0 && t.h != 0 {
+        return false;
+    }
+    if t.s < 0 && (t.h != 0 || t.m != 0) {
+        return false;
     }
-    false
+    true
 }
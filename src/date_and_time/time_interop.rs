@@ -0,0 +1,208 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// The conversions between the structures of this crate and the types of the time crate,
+// they are only compiled with the feature time. The paths of the time crate start with
+// ```::time``` to keep them apart from the module time of this crate.
+//
+use ::time::{Month, OffsetDateTime, PrimitiveDateTime};
+
+use crate::date_and_time::components::*;
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::offset::*;
+use crate::date_and_time::time::*;
+
+impl From<::time::Date> for Date {
+    /// Creates a ```Date``` from the ```time::Date```. Because ```Date``` owns its own
+    /// ```from()``` function, use ```let date: Date = td.into();```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let td = ::time::Date::from_calendar_date(2024, ::time::Month::June, 22).unwrap();
+    /// let date: Date = td.into();
+    /// assert_eq!(date.as_string(), "2024-06-22");
+    /// ```
+    fn from(td: ::time::Date) -> Date {
+        Date::from(td.day(), u8::from(td.month()), td.year())
+    }
+}
+
+impl TryFrom<Date> for ::time::Date {
+    type Error = DateTimeError;
+
+    /// Creates a ```time::Date``` from the ```Date```, it returns an error for an invalid
+    /// date or a year outside of the range of the time crate.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// let td = ::time::Date::try_from(Date::from(24, 12, 2024)).unwrap();
+    /// assert_eq!(td.day(), 24);
+    /// assert!(::time::Date::try_from(Date::from(0, 0, 0)).is_err());
+    /// ```
+    fn try_from(date: Date) -> Result<::time::Date, DateTimeError> {
+        Month::try_from(date.m)
+            .and_then(|month| ::time::Date::from_calendar_date(date.y, month, date.d))
+            .map_err(|_| {
+                DateTimeError::OutOfRange(format!(
+                    "the date {} can't be converted to a time::Date",
+                    date.as_string()
+                ))
+            })
+    }
+}
+
+impl From<::time::Time> for Time {
+    /// Creates a ```Time``` from the ```time::Time```, the fractions of the second are
+    /// truncated. Use ```let time: Time = tt.into();```.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let time: Time = ::time::Time::from_hms_nano(13, 45, 30, 500).unwrap().into();
+    /// assert_eq!(time, Time::from(13, 45, 30));
+    /// ```
+    fn from(tt: ::time::Time) -> Time {
+        Time::from(tt.hour() as i32, tt.minute() as i8, tt.second() as i8)
+    }
+}
+
+impl TryFrom<Time> for ::time::Time {
+    type Error = DateTimeError;
+
+    /// Creates a ```time::Time``` from the ```Time```, it returns an error for an invalid
+    /// time or a time of 24 hours and more.
+    ///
+    /// ```
+    /// use date_and_time::time::*;
+    /// let tt = ::time::Time::try_from(Time::from(8, 15, 0)).unwrap();
+    /// assert_eq!((tt.hour(), tt.minute()), (8, 15));
+    /// assert!(::time::Time::try_from(Time::from(24, 0, 0)).is_err());
+    /// ```
+    fn try_from(time: Time) -> Result<::time::Time, DateTimeError> {
+        time_of(&time, 0)
+    }
+}
+
+impl From<OffsetDateTime> for DateTime {
+    /// Creates a ```DateTime``` from the ```time::OffsetDateTime``` including the fractions
+    /// of the second. The ```DateTime``` is the UTC time, the offset is removed. Because
+    /// ```DateTime``` owns its own ```from()``` function, use ```let dt: DateTime =
+    /// odt.into();```.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let td = ::time::Date::from_calendar_date(2024, ::time::Month::June, 22).unwrap();
+    /// let odt = td
+    ///     .with_time(::time::Time::from_hms(13, 45, 30).unwrap())
+    ///     .assume_offset(::time::UtcOffset::from_hms(2, 0, 0).unwrap());
+    /// let dt: DateTime = odt.into();
+    /// assert_eq!(dt.as_string(), "2024-06-22T11:45:30");
+    /// ```
+    fn from(odt: OffsetDateTime) -> DateTime {
+        let utc = odt.to_offset(::time::UtcOffset::UTC);
+        DateTime {
+            date: utc.date().into(),
+            time: utc.time().into(),
+            nanos: utc.nanosecond(),
+        }
+    }
+}
+
+impl TryFrom<DateTime> for OffsetDateTime {
+    type Error = DateTimeError;
+
+    /// Creates a ```time::OffsetDateTime``` with the offset UTC from the ```DateTime```
+    /// including the fractions of the second, it returns an error if the date or the time
+    /// can't be converted.
+    ///
+    /// ```
+    /// use date_and_time::datetime::*;
+    /// let odt = ::time::OffsetDateTime::try_from(DateTime::from_timestamp(1_700_000_000)).unwrap();
+    /// assert_eq!(odt.unix_timestamp(), 1_700_000_000);
+    /// ```
+    fn try_from(dt: DateTime) -> Result<OffsetDateTime, DateTimeError> {
+        let date = ::time::Date::try_from(dt.date)?;
+        Ok(PrimitiveDateTime::new(date, time_of(&dt.time, dt.nanos)?).assume_utc())
+    }
+}
+
+impl HasDate for OffsetDateTime {
+    // The components are the local date in the offset of the OffsetDateTime.
+    fn year(&self) -> i32 {
+        OffsetDateTime::year(*self)
+    }
+    fn month(&self) -> u8 {
+        u8::from(OffsetDateTime::month(*self))
+    }
+    fn day(&self) -> u8 {
+        OffsetDateTime::day(*self)
+    }
+}
+
+impl HasTime for OffsetDateTime {
+    // The components are the local time in the offset of the OffsetDateTime.
+    fn hour(&self) -> i32 {
+        OffsetDateTime::hour(*self) as i32
+    }
+    fn minute(&self) -> i8 {
+        OffsetDateTime::minute(*self) as i8
+    }
+    fn second(&self) -> i8 {
+        OffsetDateTime::second(*self) as i8
+    }
+    fn nanosecond(&self) -> u32 {
+        OffsetDateTime::nanosecond(*self)
+    }
+}
+
+impl From<::time::UtcOffset> for UtcOffset {
+    /// Creates an ```UtcOffset``` from the ```time::UtcOffset```.
+    ///
+    /// ```
+    /// use date_and_time::offset::*;
+    /// let offset = UtcOffset::from(::time::UtcOffset::from_hms(5, 30, 0).unwrap());
+    /// assert_eq!(offset.as_string(), "+05:30");
+    /// ```
+    fn from(offset: ::time::UtcOffset) -> UtcOffset {
+        UtcOffset::from_seconds(offset.whole_seconds())
+    }
+}
+
+impl TryFrom<UtcOffset> for ::time::UtcOffset {
+    type Error = DateTimeError;
+
+    /// Creates a ```time::UtcOffset``` from the ```UtcOffset```, it returns an error if the
+    /// offset is 26 hours or more.
+    ///
+    /// ```
+    /// use date_and_time::offset::*;
+    /// let offset = ::time::UtcOffset::try_from(UtcOffset::from_hours_minutes(-3, -30)).unwrap();
+    /// assert_eq!(offset.whole_seconds(), -12_600);
+    /// ```
+    fn try_from(offset: UtcOffset) -> Result<::time::UtcOffset, DateTimeError> {
+        ::time::UtcOffset::from_whole_seconds(offset.as_seconds()).map_err(|_| {
+            DateTimeError::OutOfRange(format!(
+                "the offset {} can't be converted to a time::UtcOffset",
+                offset.as_string()
+            ))
+        })
+    }
+}
+
+// Returns the time::Time of the time: &Time and the nanos: u32, or an error
+fn time_of(time: &Time, nanos: u32) -> Result<::time::Time, DateTimeError> {
+    let error = || {
+        DateTimeError::OutOfRange(format!(
+            "the time {} can't be converted to a time::Time",
+            time.as_string()
+        ))
+    };
+    if !(0..24).contains(&time.h) || time.m < 0 || time.s < 0 {
+        return Err(error());
+    }
+    ::time::Time::from_hms_nano(time.h as u8, time.m as u8, time.s as u8, nanos)
+        .map_err(|_| error())
+}
@@ -0,0 +1,310 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+
+/// The ```FrameRate``` of a ```Timecode``` as the fraction ```numerator / denominator```
+/// frames per second, e.g. 30000/1001 for NTSC video. Drop frame counting is only
+/// possible for the rates with a nominal frame rate of 30 or 60 frames (29.97 and 59.94).
+/// A ```Timecode``` supports nominal frame rates up to 255 frames per second.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FrameRate {
+    pub numerator: u32,
+    pub denominator: u32,
+    pub drop_frame: bool,
+}
+
+#[allow(dead_code)]
+impl FrameRate {
+    pub const FPS_23_976: FrameRate = FrameRate::new(24_000, 1_001, false);
+    pub const FPS_24: FrameRate = FrameRate::new(24, 1, false);
+    pub const FPS_25: FrameRate = FrameRate::new(25, 1, false);
+    pub const FPS_29_97: FrameRate = FrameRate::new(30_000, 1_001, false);
+    pub const FPS_29_97_DF: FrameRate = FrameRate::new(30_000, 1_001, true);
+    pub const FPS_30: FrameRate = FrameRate::new(30, 1, false);
+    pub const FPS_50: FrameRate = FrameRate::new(50, 1, false);
+    pub const FPS_59_94: FrameRate = FrameRate::new(60_000, 1_001, false);
+    pub const FPS_59_94_DF: FrameRate = FrameRate::new(60_000, 1_001, true);
+    pub const FPS_60: FrameRate = FrameRate::new(60, 1, false);
+
+    /// ```new(numerator, denominator, drop_frame)``` creates a ```FrameRate``` of
+    /// ```numerator / denominator``` frames per second.
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// let rate = FrameRate::new(48, 1, false);
+    /// assert_eq!(rate.nominal_fps(), 48);
+    /// ```
+    pub const fn new(numerator: u32, denominator: u32, drop_frame: bool) -> FrameRate {
+        FrameRate {
+            numerator,
+            denominator,
+            drop_frame,
+        }
+    }
+    /// ```nominal_fps()``` returns the frames per second used for counting, this is the
+    /// rounded frame rate (e.g. 30 for 29.97).
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// assert_eq!(FrameRate::FPS_29_97.nominal_fps(), 30);
+    /// ```
+    pub fn nominal_fps(&self) -> u32 {
+        // the sum is computed in u64, it overflows u32 for large numerators
+        ((self.numerator as u64 + self.denominator as u64 / 2) / self.denominator as u64) as u32
+    }
+    /// ```as_float()``` returns the real frames per second as a float.
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// assert!((FrameRate::FPS_29_97.as_float() - 29.97).abs() < 0.001);
+    /// ```
+    pub fn as_float(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+    // Returns the number of frame numbers dropped every minute (except every 10th minute)
+    fn dropped_frames(&self) -> u64 {
+        if self.drop_frame {
+            self.nominal_fps() as u64 / 15
+        } else {
+            0
+        }
+    }
+    // Returns true if the frame rate can be used for a Timecode
+    fn is_valid(&self) -> bool {
+        // the frames of a Timecode are a u8, so the nominal frame rate is at most 255
+        if self.numerator == 0 || self.denominator == 0 {
+            return false;
+        }
+        if self.nominal_fps() == 0 || self.nominal_fps() > 255 {
+            return false;
+        }
+        !self.drop_frame || self.nominal_fps() == 30 || self.nominal_fps() == 60
+    }
+}
+
+/// The ```Timecode``` structure is a SMPTE timecode ```HH:MM:SS:FF``` at a given
+/// ```FrameRate```. It can be build with ```new()```, ```from_frames()```,
+/// ```from_duration()``` and ```parse()```.
+///
+/// A timecode is limited to 24 hours, the frame counts wrap around at midnight. Drop frame
+/// timecodes are printed with a ```;``` before the frames.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Timecode {
+    pub h: u8,
+    pub m: u8,
+    pub s: u8,
+    pub f: u8,
+    pub rate: FrameRate,
+}
+
+#[allow(dead_code)]
+impl Timecode {
+    /// ```new(hour, minute, second, frame, rate)``` creates a ```Timecode```, the values are
+    /// checked against the frame rate. In drop frame timecodes the first frame numbers of
+    /// each minute (except every 10th minute) don't exist.
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// let tc = Timecode::new(1, 0, 0, 24, FrameRate::FPS_25).unwrap();
+    /// assert_eq!(tc.as_string(), "01:00:00:24");
+    /// assert!(Timecode::new(1, 0, 0, 25, FrameRate::FPS_25).is_err());
+    /// // 00:01:00;00 is dropped in 29.97 drop frame
+    /// assert!(Timecode::new(0, 1, 0, 0, FrameRate::FPS_29_97_DF).is_err());
+    /// ```
+    pub fn new(
+        hour: u8,
+        minute: u8,
+        second: u8,
+        frame: u8,
+        rate: FrameRate,
+    ) -> Result<Timecode, DateTimeError> {
+        if !rate.is_valid() {
+            return Err(DateTimeError::OutOfRange(format!(
+                "unsupported frame rate {}/{}",
+                rate.numerator, rate.denominator
+            )));
+        }
+        if hour > 23 || minute > 59 || second > 59 || frame as u32 >= rate.nominal_fps() {
+            return Err(DateTimeError::OutOfRange(format!(
+                "invalid timecode {:02}:{:02}:{:02}:{:02}",
+                hour, minute, second, frame
+            )));
+        }
+        if second == 0 && !minute.is_multiple_of(10) && (frame as u64) < rate.dropped_frames() {
+            return Err(DateTimeError::OutOfRange(format!(
+                "frame {} is dropped at {:02}:{:02}:00",
+                frame, hour, minute
+            )));
+        }
+        Ok(Timecode {
+            h: hour,
+            m: minute,
+            s: second,
+            f: frame,
+            rate,
+        })
+    }
+    /// ```from_frames(frames, rate)``` creates a ```Timecode``` from the frame count since
+    /// 00:00:00:00. Frame counts of more than 24 hours wrap around.
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// let tc = Timecode::from_frames(90_000, FrameRate::FPS_25).unwrap();
+    /// assert_eq!(tc.as_string(), "01:00:00:00");
+    /// ```
+    pub fn from_frames(frames: u64, rate: FrameRate) -> Result<Timecode, DateTimeError> {
+        if !rate.is_valid() {
+            return Err(DateTimeError::OutOfRange(format!(
+                "unsupported frame rate {}/{}",
+                rate.numerator, rate.denominator
+            )));
+        }
+        let fps = rate.nominal_fps() as u64;
+        let drop = rate.dropped_frames();
+        let mut frames = frames % frames_per_day(&rate);
+        if drop > 0 {
+            let frames_per_10min = fps * 600 - drop * 9;
+            let frames_per_min = fps * 60 - drop;
+            let tens = frames / frames_per_10min;
+            let rest = frames % frames_per_10min;
+            frames += drop * 9 * tens;
+            if rest > drop {
+                frames += drop * ((rest - drop) / frames_per_min);
+            }
+        }
+        Ok(Timecode {
+            h: (frames / (fps * 3_600)) as u8,
+            m: (frames / (fps * 60) % 60) as u8,
+            s: (frames / fps % 60) as u8,
+            f: (frames % fps) as u8,
+            rate,
+        })
+    }
+    /// ```from_duration(&duration, rate)``` creates a ```Timecode``` from a ```Duration```,
+    /// the duration is rounded down to whole frames. Negative durations are not allowed.
+    ///
+    /// ```
+    /// use date_and_time::duration::*;
+    /// use date_and_time::timecode::*;
+    /// let tc = Timecode::from_duration(&Duration::from_milliseconds(2_500), FrameRate::FPS_24);
+    /// assert_eq!(tc.unwrap().as_string(), "00:00:02:12");
+    /// ```
+    pub fn from_duration(duration: &Duration, rate: FrameRate) -> Result<Timecode, DateTimeError> {
+        if duration.is_negative() {
+            return Err(DateTimeError::OutOfRange(String::from(
+                "a timecode can't be negative",
+            )));
+        }
+        if !rate.is_valid() {
+            return Err(DateTimeError::OutOfRange(format!(
+                "unsupported frame rate {}/{}",
+                rate.numerator, rate.denominator
+            )));
+        }
+        // the timecodes repeat after frames_per_day() * denominator seconds, the rest of the
+        // duration can be multiplied with the numerator without an overflow
+        let second = rate.denominator as i128 * 1_000_000_000;
+        let nanos = duration.as_nanoseconds() % (frames_per_day(&rate) as i128 * second);
+        let frames = nanos * rate.numerator as i128 / second;
+        let frames = (frames % frames_per_day(&rate) as i128) as u64;
+        Timecode::from_frames(frames, rate)
+    }
+    /// ```parse(text, rate)``` parses a timecode in the format ```HH:MM:SS:FF``` (or
+    /// ```HH:MM:SS;FF``` for drop frame) at the given frame rate.
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// let tc = Timecode::parse("00:01:00;02", FrameRate::FPS_29_97_DF).unwrap();
+    /// assert_eq!(tc.to_frames(), 1_800);
+    /// assert!(Timecode::parse("00:00:00", FrameRate::FPS_25).is_err());
+    /// ```
+    pub fn parse(text: &str, rate: FrameRate) -> Result<Timecode, DateTimeError> {
+        let text = text.trim();
+        let parts: Vec<&str> = text.split([':', ';']).collect();
+        if parts.len() != 4 || parts.iter().any(|p| p.len() != 2) {
+            return Err(DateTimeError::Parse(format!(
+                "timecode '{}' is not in the format HH:MM:SS:FF",
+                text
+            )));
+        }
+        if text.contains(';') && !rate.drop_frame {
+            return Err(DateTimeError::Parse(format!(
+                "timecode '{}' is drop frame, but the frame rate isn't",
+                text
+            )));
+        }
+        let mut values = [0_u8; 4];
+        for (n, part) in parts.iter().enumerate() {
+            if !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(DateTimeError::Parse(format!(
+                    "invalid component '{}' in timecode '{}'",
+                    part, text
+                )));
+            }
+            values[n] = part.parse().unwrap_or(0);
+        }
+        Timecode::new(values[0], values[1], values[2], values[3], rate)
+    }
+    /// ```to_frames()``` returns the number of frames since 00:00:00:00.
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// let tc = Timecode::parse("00:00:10:05", FrameRate::FPS_25).unwrap();
+    /// assert_eq!(tc.to_frames(), 255);
+    /// ```
+    pub fn to_frames(&self) -> u64 {
+        let fps = self.rate.nominal_fps() as u64;
+        let minutes = self.h as u64 * 60 + self.m as u64;
+        let frames = (minutes * 60 + self.s as u64) * fps + self.f as u64;
+        frames - self.rate.dropped_frames() * (minutes - minutes / 10)
+    }
+    /// ```to_duration()``` returns the real time since 00:00:00:00 as a ```Duration```.
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// let tc = Timecode::parse("00:00:01:12", FrameRate::FPS_24).unwrap();
+    /// assert_eq!(tc.to_duration().as_milliseconds(), 1_500);
+    /// ```
+    pub fn to_duration(&self) -> Duration {
+        let nanos = self.to_frames() as i128 * self.rate.denominator as i128 * 1_000_000_000
+            / self.rate.numerator as i128;
+        Duration::from_nanoseconds(nanos)
+    }
+    /// ```add_frames(frames)``` adds the ```frames``` to the timecode and returns a new
+    /// ```Timecode```. Negative values are substracted, the result wraps around at 24 hours.
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// let midnight = Timecode::new(0, 0, 0, 0, FrameRate::FPS_25).unwrap();
+    /// assert_eq!(midnight.add_frames(-1).as_string(), "23:59:59:24");
+    /// ```
+    pub fn add_frames(&self, frames: i64) -> Timecode {
+        let day = frames_per_day(&self.rate) as i64;
+        let total = (self.to_frames() as i64 + frames.rem_euclid(day)) % day;
+        Timecode::from_frames(total as u64, self.rate).unwrap_or(*self)
+    }
+    /// ```as_string()``` gets the ```Timecode``` as a string in the format HH:MM:SS:FF,
+    /// drop frame timecodes in the format HH:MM:SS;FF.
+    ///
+    /// ```
+    /// use date_and_time::timecode::*;
+    /// let tc = Timecode::from_frames(17_982, FrameRate::FPS_29_97_DF).unwrap();
+    /// assert_eq!(tc.as_string(), "00:10:00;00");
+    /// ```
+    pub fn as_string(&self) -> String {
+        let sep = if self.rate.drop_frame { ';' } else { ':' };
+        format!(
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.h, self.m, self.s, sep, self.f
+        )
+    }
+}
+
+// Returns the number of frames in 24 hours for the rate: &FrameRate
+fn frames_per_day(rate: &FrameRate) -> u64 {
+    rate.nominal_fps() as u64 * 86_400 - rate.dropped_frames() * (1_440 - 144)
+}
@@ -0,0 +1,164 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::interval::*;
+
+/// The TimelineRow structure is one labeled interval of a ```Timeline``` scaled to a
+/// window: ```start``` and ```length``` are fractions of the window (0.0 to 1.0). An
+/// interval outside of the window has the length 0.0.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimelineRow {
+    pub label: String,
+    pub interval: DateInterval,
+    pub start: f64,
+    pub length: f64,
+}
+
+#[allow(dead_code)]
+impl TimelineRow {
+    /// ```columns(width)``` returns the first column and the number of columns of the row
+    /// in a bar of ```width``` columns, e.g. for a Gantt chart in the terminal. A row
+    /// inside of the window has at least one column.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// use date_and_time::timeline::*;
+    /// let june = DateInterval::new(Date::from(1, 6, 2024), Date::from(30, 6, 2024)).unwrap();
+    /// let mut timeline = Timeline::new();
+    /// timeline.add("review", DateInterval::new(Date::from(16, 6, 2024), Date::from(16, 6, 2024)).unwrap());
+    /// assert_eq!(timeline.rows(&june)[0].columns(30), (15, 1));
+    /// assert_eq!(timeline.rows(&june)[0].columns(10), (5, 1));
+    /// ```
+    pub fn columns(&self, width: usize) -> (usize, usize) {
+        let first = (self.start * width as f64).floor() as usize;
+        if self.length <= 0.0 || width == 0 {
+            return (first.min(width), 0);
+        }
+        let last = ((self.start + self.length) * width as f64).ceil() as usize;
+        let first = first.min(width - 1);
+        (first, last.clamp(first + 1, width) - first)
+    }
+}
+
+/// The Timeline structure collects labeled ```DateInterval```s in the order they were added
+/// and scales them to a window with ```rows()```, e.g. for Gantt or utilization charts.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::interval::*;
+/// use date_and_time::timeline::*;
+/// let mut timeline = Timeline::new();
+/// timeline.add("design", DateInterval::new(Date::from(1, 7, 2024), Date::from(10, 7, 2024)).unwrap());
+/// timeline.add("build", DateInterval::new(Date::from(11, 7, 2024), Date::from(30, 7, 2024)).unwrap());
+/// for row in timeline.fit() {
+///     let (first, len) = row.columns(30);
+///     println!("{:<8}{}{}", row.label, " ".repeat(first), "#".repeat(len));
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Timeline {
+    entries: Vec<(String, DateInterval)>,
+}
+
+#[allow(dead_code)]
+impl Timeline {
+    /// ```new()``` creates an empty ```Timeline```.
+    ///
+    /// ```
+    /// use date_and_time::timeline::*;
+    /// assert!(Timeline::new().window().is_none());
+    /// ```
+    pub fn new() -> Timeline {
+        Timeline {
+            entries: Vec::new(),
+        }
+    }
+    /// ```add(label, interval)``` adds the ```interval``` with the ```label``` as a new
+    /// row.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// use date_and_time::timeline::*;
+    /// let mut timeline = Timeline::new();
+    /// let day = Date::from(22, 6, 2024);
+    /// timeline.add("release", DateInterval::new(day, day).unwrap());
+    /// assert_eq!(timeline.fit()[0].label, "release");
+    /// ```
+    pub fn add(&mut self, label: &str, interval: DateInterval) -> &mut Timeline {
+        self.entries.push((label.to_string(), interval));
+        self
+    }
+    /// ```window()``` returns the interval from the first start to the last end of all
+    /// rows, or ```None``` if the ```Timeline``` is empty.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// use date_and_time::timeline::*;
+    /// let mut timeline = Timeline::new();
+    /// timeline
+    ///     .add("a", DateInterval::new(Date::from(5, 6, 2024), Date::from(9, 6, 2024)).unwrap())
+    ///     .add("b", DateInterval::new(Date::from(1, 6, 2024), Date::from(3, 6, 2024)).unwrap());
+    /// assert_eq!(timeline.window().unwrap().days(), 9);
+    /// ```
+    pub fn window(&self) -> Option<DateInterval> {
+        let mut entries = self.entries.iter();
+        let (_, first) = entries.next()?;
+        Some(entries.fold(*first, |span, (_, interval)| span.union_span(interval)))
+    }
+    /// ```rows(&window)``` returns the rows scaled to the ```window```, the parts of the
+    /// intervals outside of the window are cut off.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// use date_and_time::timeline::*;
+    /// let mut timeline = Timeline::new();
+    /// timeline.add("sprint", DateInterval::new(Date::from(25, 6, 2024), Date::from(8, 7, 2024)).unwrap());
+    /// let june = DateInterval::new(Date::from(1, 6, 2024), Date::from(30, 6, 2024)).unwrap();
+    /// let row = &timeline.rows(&june)[0];
+    /// assert_eq!((row.start, row.length), (0.8, 0.2));
+    /// ```
+    pub fn rows(&self, window: &DateInterval) -> Vec<TimelineRow> {
+        let days = window.days() as f64;
+        self.entries
+            .iter()
+            .map(|(label, interval)| {
+                let (start, length) = match window.intersection(interval) {
+                    Some(part) => (
+                        window.start.diff_in_days(&part.start) as f64 / days,
+                        part.days() as f64 / days,
+                    ),
+                    None if interval.end < window.start => (0.0, 0.0),
+                    None => (1.0, 0.0),
+                };
+                TimelineRow {
+                    label: label.clone(),
+                    interval: *interval,
+                    start,
+                    length,
+                }
+            })
+            .collect()
+    }
+    /// ```fit()``` returns the rows scaled to the ```window()``` of all rows.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::interval::*;
+    /// use date_and_time::timeline::*;
+    /// let mut timeline = Timeline::new();
+    /// timeline.add("all", DateInterval::new(Date::from(1, 6, 2024), Date::from(30, 6, 2024)).unwrap());
+    /// assert_eq!(timeline.fit()[0].length, 1.0);
+    /// ```
+    pub fn fit(&self) -> Vec<TimelineRow> {
+        match self.window() {
+            Some(window) => self.rows(&window),
+            None => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,427 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+use crate::date_and_time::date::*;
+use crate::date_and_time::datetime::*;
+use crate::date_and_time::duration::*;
+use crate::date_and_time::error::*;
+use crate::date_and_time::posix_tz::*;
+use crate::date_and_time::time::*;
+#[cfg(feature = "tzdb")]
+use crate::date_and_time::tzdb;
+use crate::date_and_time::tzif::*;
+
+/// The ```LocalResult``` is returned by the conversion of a local wall clock time to UTC.
+/// In the spring-forward gap of the daylight saving time a local time doesn't exist
+/// (```None```), in the fall-back overlap it exists twice (```Ambiguous```, the earlier
+/// instant first).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LocalResult<T> {
+    Single(T),
+    Ambiguous(T, T),
+    None,
+}
+
+#[allow(dead_code)]
+impl<T> LocalResult<T> {
+    /// ```single()``` returns the result only if it is unique.
+    ///
+    /// ```
+    /// use date_and_time::timezone::*;
+    /// assert_eq!(LocalResult::Single(1).single(), Some(1));
+    /// assert_eq!(LocalResult::Ambiguous(1, 2).single(), None);
+    /// ```
+    pub fn single(self) -> Option<T> {
+        match self {
+            LocalResult::Single(t) => Some(t),
+            _ => None,
+        }
+    }
+    /// ```earliest()``` returns the unique or the earlier of the ambiguous results.
+    ///
+    /// ```
+    /// use date_and_time::timezone::*;
+    /// assert_eq!(LocalResult::Ambiguous(1, 2).earliest(), Some(1));
+    /// assert_eq!(LocalResult::<i32>::None.earliest(), None);
+    /// ```
+    pub fn earliest(self) -> Option<T> {
+        match self {
+            LocalResult::Single(t) | LocalResult::Ambiguous(t, _) => Some(t),
+            LocalResult::None => None,
+        }
+    }
+    /// ```latest()``` returns the unique or the later of the ambiguous results.
+    ///
+    /// ```
+    /// use date_and_time::timezone::*;
+    /// assert_eq!(LocalResult::Ambiguous(1, 2).latest(), Some(2));
+    /// ```
+    pub fn latest(self) -> Option<T> {
+        match self {
+            LocalResult::Single(t) | LocalResult::Ambiguous(_, t) => Some(t),
+            LocalResult::None => None,
+        }
+    }
+}
+
+/// The ```TimeZone``` structure combines the historical transitions of a zoneinfo file
+/// with the POSIX TZ rule for the times after the last transition. It can be build with
+/// ```utc()```, ```fixed()```, ```from_rule()```, ```from_zoneinfo()```, ```by_name()```
+/// and ```from_system()```.
+///
+/// With the cargo feature ```tzdb``` the rules of the IANA time zone database are
+/// embedded in the crate, so ```by_name()``` works without any OS support (e.g. on
+/// Windows). The embedded database only knows the current rules of a zone.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeZone {
+    pub name: String,
+    zone: Option<ZoneInfo>,
+    rule: Option<TzRule>,
+}
+
+#[allow(dead_code)]
+impl TimeZone {
+    /// ```utc()``` creates the UTC ```TimeZone```.
+    ///
+    /// ```
+    /// use date_and_time::timezone::*;
+    /// let utc = TimeZone::utc();
+    /// assert_eq!(utc.offset_at_timestamp(1_719_792_000), 0);
+    /// assert_eq!(utc.abbreviation_at_timestamp(0), "UTC");
+    /// ```
+    pub fn utc() -> TimeZone {
+        TimeZone::fixed("UTC", 0)
+    }
+    /// ```fixed(name, offset)``` creates a ```TimeZone``` with a fixed offset to UTC in
+    /// seconds and without daylight saving time.
+    ///
+    /// ```
+    /// use date_and_time::timezone::*;
+    /// let india = TimeZone::fixed("IST", 19_800);
+    /// assert_eq!(india.offset_at_timestamp(1_719_792_000), 19_800);
+    /// ```
+    pub fn fixed(name: &str, offset: i32) -> TimeZone {
+        TimeZone {
+            name: name.to_string(),
+            zone: None,
+            rule: Some(TzRule {
+                std_name: name.to_string(),
+                std_offset: offset,
+                dst: None,
+            }),
+        }
+    }
+    /// ```from_rule(name, rule)``` creates a ```TimeZone``` from a POSIX TZ rule.
+    ///
+    /// ```
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::timezone::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// let berlin = TimeZone::from_rule("Europe/Berlin", rule);
+    /// // the 1st July 2024 is in the summer time
+    /// assert!(berlin.is_dst_at_timestamp(1_719_792_000));
+    /// ```
+    pub fn from_rule(name: &str, rule: TzRule) -> TimeZone {
+        TimeZone {
+            name: name.to_string(),
+            zone: None,
+            rule: Some(rule),
+        }
+    }
+    /// ```from_zoneinfo(name, zone)``` creates a ```TimeZone``` from the data of a zoneinfo
+    /// file, the footer of the file is used for the times after the last transition.
+    ///
+    /// ```
+    /// use date_and_time::timezone::*;
+    /// use date_and_time::tzif::*;
+    /// if let Ok(zone) = ZoneInfo::by_name("Europe/Berlin") {
+    ///     let berlin = TimeZone::from_zoneinfo("Europe/Berlin", zone);
+    ///     assert_eq!(berlin.offset_at_timestamp(1_719_792_000), 7_200);
+    /// }
+    /// ```
+    pub fn from_zoneinfo(name: &str, zone: ZoneInfo) -> TimeZone {
+        let rule = zone.footer.as_deref().and_then(|f| TzRule::parse(f).ok());
+        TimeZone {
+            name: name.to_string(),
+            zone: Some(zone),
+            rule,
+        }
+    }
+    /// ```by_name(name)``` creates the ```TimeZone``` with the IANA name ```name``` (e.g.
+    /// ```"Europe/Berlin"```). The zoneinfo file of the system is used if it is available,
+    /// else the embedded database if the ```tzdb``` feature is enabled.
+    ///
+    /// ```
+    /// use date_and_time::timezone::*;
+    /// match TimeZone::by_name("Europe/Berlin") {
+    ///     Ok(berlin) => assert_eq!(berlin.offset_at_timestamp(1_719_792_000), 7_200),
+    ///     Err(e) => println!("no timezone data: {}", e),
+    /// }
+    /// assert!(TimeZone::by_name("Nowhere/Atlantis").is_err());
+    /// ```
+    pub fn by_name(name: &str) -> Result<TimeZone, DateTimeError> {
+        let result = ZoneInfo::by_name(name).map(|zone| TimeZone::from_zoneinfo(name, zone));
+        #[cfg(feature = "tzdb")]
+        if result.is_err() {
+            if let Some(tz) = tzdb::lookup(name) {
+                return Ok(TimeZone::from_rule(name, TzRule::parse(tz)?));
+            }
+        }
+        result
+    }
+    /// ```from_system()``` creates the local ```TimeZone``` of the system. A ```TZ```
+    /// environment variable is used first, it can contain a timezone name, the path of a
+    /// zoneinfo file or a POSIX TZ rule. Without it ```/etc/localtime``` is read.
+    ///
+    /// ```
+    /// use date_and_time::timezone::*;
+    /// if let Ok(local) = TimeZone::from_system() {
+    ///     println!("The local timezone is {}", local.name);
+    /// }
+    /// ```
+    pub fn from_system() -> Result<TimeZone, DateTimeError> {
+        let tz = std::env::var("TZ").unwrap_or_default();
+        let name = tz.trim_start_matches(':');
+        if name.is_empty() {
+            return ZoneInfo::from_system().map(|zone| TimeZone::from_zoneinfo("localtime", zone));
+        }
+        if name.starts_with('/') {
+            return ZoneInfo::from_file(name).map(|zone| TimeZone::from_zoneinfo(name, zone));
+        }
+        match TimeZone::by_name(name) {
+            Ok(zone) => Ok(zone),
+            Err(_) => Ok(TimeZone::from_rule(name, TzRule::parse(name)?)),
+        }
+    }
+    /// ```offset_at_timestamp(unix_secs)``` returns the offset to UTC in seconds which
+    /// applies at the given seconds since the Linux epoch.
+    ///
+    /// ```
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::timezone::*;
+    /// let rule = TzRule::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+    /// let new_york = TimeZone::from_rule("America/New_York", rule);
+    /// assert_eq!(new_york.offset_at_timestamp(1_704_067_200), -18_000);
+    /// assert_eq!(new_york.offset_at_timestamp(1_719_792_000), -14_400);
+    /// ```
+    pub fn offset_at_timestamp(&self, unix_secs: i64) -> i32 {
+        if let Some(zone) = self.zone_for(unix_secs) {
+            return zone.offset_at(unix_secs).unwrap_or(0);
+        }
+        match &self.rule {
+            Some(rule) => rule.offset_at_timestamp(unix_secs),
+            None => 0,
+        }
+    }
+    /// ```is_dst_at_timestamp(unix_secs)``` returns true if the daylight saving time
+    /// applies at the given seconds since the Linux epoch.
+    ///
+    /// ```
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::timezone::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// let berlin = TimeZone::from_rule("Europe/Berlin", rule);
+    /// assert!(!berlin.is_dst_at_timestamp(1_704_067_200));
+    /// ```
+    pub fn is_dst_at_timestamp(&self, unix_secs: i64) -> bool {
+        if let Some(zone) = self.zone_for(unix_secs) {
+            return zone.local_time_type_at(unix_secs).is_some_and(|t| t.is_dst);
+        }
+        match &self.rule {
+            Some(rule) => rule.is_dst_at_timestamp(unix_secs),
+            None => false,
+        }
+    }
+    /// ```abbreviation_at_timestamp(unix_secs)``` returns the name of the local time (e.g.
+    /// CET or CEST) which applies at the given seconds since the Linux epoch.
+    ///
+    /// ```
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::timezone::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// let berlin = TimeZone::from_rule("Europe/Berlin", rule);
+    /// assert_eq!(berlin.abbreviation_at_timestamp(1_704_067_200), "CET");
+    /// assert_eq!(berlin.abbreviation_at_timestamp(1_719_792_000), "CEST");
+    /// ```
+    pub fn abbreviation_at_timestamp(&self, unix_secs: i64) -> String {
+        if let Some(zone) = self.zone_for(unix_secs) {
+            if let Some(t) = zone.local_time_type_at(unix_secs) {
+                return t.abbreviation.clone();
+            }
+        }
+        match &self.rule {
+            Some(rule) => match &rule.dst {
+                Some(dst) if rule.is_dst_at_timestamp(unix_secs) => dst.name.clone(),
+                _ => rule.std_name.clone(),
+            },
+            None => self.name.clone(),
+        }
+    }
+    /// ```offset_at(&date, &time)``` returns the offset to UTC in seconds which applies at
+    /// the given UTC ```date``` and ```time```.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::time::*;
+    /// use date_and_time::timezone::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// let berlin = TimeZone::from_rule("Europe/Berlin", rule);
+    /// let offset = berlin.offset_at(&Date::from(1, 7, 2024), &Time::from(12, 0, 0));
+    /// assert_eq!(offset, 7_200);
+    /// ```
+    pub fn offset_at(&self, date: &Date, time: &Time) -> i32 {
+        self.offset_at_timestamp(utc_timestamp(date, time))
+    }
+    /// ```utc_to_local(&utc)``` converts the UTC date and time into the local wall clock
+    /// time of the timezone.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::time::*;
+    /// use date_and_time::timezone::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// let berlin = TimeZone::from_rule("Europe/Berlin", rule);
+    /// let utc = DateTime::from(Date::from(1, 7, 2024), Time::from(12, 0, 0));
+    /// assert_eq!(berlin.utc_to_local(&utc).as_string(), "2024-07-01T14:00:00");
+    /// ```
+    pub fn utc_to_local(&self, utc: &DateTime) -> DateTime {
+        let offset = self.offset_at_timestamp(utc.as_timestamp());
+        trace_event!(zone = %self.name, ?utc, offset, "converted UTC to the local time");
+        utc.add_duration(&Duration::from_seconds(offset as i64))
+    }
+    /// ```local_to_utc(&local)``` converts the local wall clock time of the timezone into
+    /// UTC. The result is ```LocalResult::None``` if the local time was skipped by a
+    /// daylight saving transition and ```LocalResult::Ambiguous``` if it occurs twice.
+    ///
+    /// ```
+    /// use date_and_time::date::*;
+    /// use date_and_time::datetime::*;
+    /// use date_and_time::posix_tz::*;
+    /// use date_and_time::time::*;
+    /// use date_and_time::timezone::*;
+    /// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    /// let berlin = TimeZone::from_rule("Europe/Berlin", rule);
+    /// // 2:30 was skipped in the spring and occurred twice in the fall of 2024
+    /// let spring = DateTime::from(Date::from(31, 3, 2024), Time::from(2, 30, 0));
+    /// assert_eq!(berlin.local_to_utc(&spring), LocalResult::None);
+    /// let fall = DateTime::from(Date::from(27, 10, 2024), Time::from(2, 30, 0));
+    /// let first = berlin.local_to_utc(&fall).earliest().unwrap();
+    /// assert_eq!(first.as_string(), "2024-10-27T00:30:00");
+    /// ```
+    pub fn local_to_utc(&self, local: &DateTime) -> LocalResult<DateTime> {
+        let local_secs = local.as_timestamp();
+        enter_trace_span!("TimeZone::local_to_utc", zone = %self.name, ?local);
+        // the possible offsets are the ones around the local time, a transition which
+        // affects the local time can't be more than a day away
+        let mut candidates: Vec<i64> = Vec::with_capacity(3);
+        for probe in [local_secs - 86_400, local_secs, local_secs + 86_400] {
+            let offset = self.offset_at_timestamp(probe) as i64;
+            let utc_secs = local_secs - offset;
+            if self.offset_at_timestamp(utc_secs) as i64 == offset
+                && !candidates.contains(&utc_secs)
+            {
+                candidates.push(utc_secs);
+            }
+        }
+        candidates.sort();
+        trace_event!(?candidates, "found the UTC timestamps of the local time");
+        let to_utc =
+            |utc_secs: i64| local.sub_duration(&Duration::from_seconds(local_secs - utc_secs));
+        match candidates.as_slice() {
+            [] => LocalResult::None,
+            [utc_secs] => LocalResult::Single(to_utc(*utc_secs)),
+            [first, .., last] => LocalResult::Ambiguous(to_utc(*first), to_utc(*last)),
+        }
+    }
+    // Returns the first UTC date and time of the local date: &Date, if the midnight was
+    // skipped by a transition the day starts with the transition
+    fn start_of_local_day(&self, date: &Date) -> DateTime {
+        let midnight = DateTime::from(*date, Time::new());
+        match self.local_to_utc(&midnight) {
+            LocalResult::Single(utc) | LocalResult::Ambiguous(utc, _) => utc,
+            LocalResult::None => {
+                // the transition is between the midnight in the offset after and before the
+                // gap, it is the first instant which has a local time after the midnight
+                let local_secs = midnight.as_timestamp();
+                let before = self.offset_at_timestamp(local_secs - 86_400) as i64;
+                let after = self.offset_at_timestamp(local_secs + 86_400) as i64;
+                let (mut low, mut high) = (local_secs - after, local_secs - before);
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if mid + self.offset_at_timestamp(mid) as i64 >= local_secs {
+                        high = mid;
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+                DateTime::from_timestamp(low)
+            }
+        }
+    }
+    // Returns the zoneinfo data if it is responsible for the unix_secs: i64, after the last
+    // transition the rule is responsible (if there is one)
+    fn zone_for(&self, unix_secs: i64) -> Option<&ZoneInfo> {
+        let zone = self.zone.as_ref()?;
+        if self.rule.is_none() {
+            return Some(zone);
+        }
+        match zone.transitions.last() {
+            Some(last) if unix_secs < last.at => Some(zone),
+            _ => None,
+        }
+    }
+}
+
+/// ```local_day_bounds(&date, &tz)``` returns the first UTC date and time of the local
+/// ```date``` in the timezone ```tz``` and the first UTC date and time of the following
+/// local day, e.g. to aggregate UTC events per local day. The days of the daylight saving
+/// transitions have 23 or 25 hours, if the midnight is skipped the day starts with the
+/// transition.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::posix_tz::*;
+/// use date_and_time::timezone::*;
+/// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+/// let berlin = TimeZone::from_rule("Europe/Berlin", rule);
+/// let (start, end) = local_day_bounds(&Date::from(31, 3, 2024), &berlin);
+/// assert_eq!(start.as_string(), "2024-03-30T23:00:00");
+/// assert_eq!(end.as_string(), "2024-03-31T22:00:00");
+/// assert_eq!(start.diff(&end).as_seconds(), 23 * 3_600);
+/// ```
+pub fn local_day_bounds(date: &Date, tz: &TimeZone) -> (DateTime, DateTime) {
+    (
+        tz.start_of_local_day(date),
+        tz.start_of_local_day(&date.add_days(1)),
+    )
+}
+
+/// ```week_bounds_local(&date, &tz, config)``` returns the first UTC date and time of the
+/// local week of the ```date``` in the timezone ```tz``` and the first UTC date and time of
+/// the following local week, e.g. for weekly reports. The weeks start with the first day
+/// of the ```config```, a week with a daylight saving transition has 167 or 169 hours.
+///
+/// ```
+/// use date_and_time::date::*;
+/// use date_and_time::posix_tz::*;
+/// use date_and_time::timezone::*;
+/// let rule = TzRule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+/// let berlin = TimeZone::from_rule("Europe/Berlin", rule);
+/// let date = Date::from(28, 3, 2024);
+/// let (start, end) = week_bounds_local(&date, &berlin, WeekConfig::iso());
+/// assert_eq!(start.as_string(), "2024-03-24T23:00:00");
+/// assert_eq!(end.as_string(), "2024-03-31T22:00:00");
+/// assert_eq!(start.diff(&end).as_seconds(), 167 * 3_600);
+/// ```
+pub fn week_bounds_local(date: &Date, tz: &TimeZone, config: WeekConfig) -> (DateTime, DateTime) {
+    let first_day = config.week_start(date);
+    (
+        tz.start_of_local_day(&first_day),
+        tz.start_of_local_day(&first_day.add_days(7)),
+    )
+}
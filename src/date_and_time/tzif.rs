@@ -0,0 +1,365 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+// the license details are in the main library file.
+//
+// The binary format is described in RFC 8536: https://www.rfc-editor.org/rfc/rfc8536
+//
+use crate::date_and_time::error::*;
+
+// The default location of the zoneinfo files on Linux and MacOS.
+const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+const LOCALTIME_FILE: &str = "/etc/localtime";
+const TZIF_HEADER_LEN: usize = 44;
+
+/// A ```TzifTransition``` is the moment (in seconds since the Linux epoch, UTC) at which
+/// the local time type with the index ```type_index``` starts to apply.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TzifTransition {
+    pub at: i64,
+    pub type_index: u8,
+}
+
+/// A ```TzifLocalTimeType``` describes one local time of a zone, e.g. CET or CEST for
+/// Europe/Berlin. ```utoff``` is the offset to UTC in seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TzifLocalTimeType {
+    pub utoff: i32,
+    pub is_dst: bool,
+    pub abbreviation: String,
+}
+
+/// A ```TzifLeapSecond``` record, ```occurrence``` is the time in seconds since the Linux
+/// epoch and ```correction``` the total number of leap seconds after that time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TzifLeapSecond {
+    pub occurrence: i64,
+    pub correction: i32,
+}
+
+/// The ```ZoneInfo``` structure contains the data of a TZif (zoneinfo) file. It can be
+/// read with ```parse()```, ```from_file()```, ```by_name()``` and ```from_system()```.
+///
+/// For files of version 2 and later the 64 bit data block is used, the POSIX TZ string
+/// from the end of the file is stored in ```footer``` and describes the local time after
+/// the last transition.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZoneInfo {
+    pub version: u8,
+    pub transitions: Vec<TzifTransition>,
+    pub local_time_types: Vec<TzifLocalTimeType>,
+    pub leap_seconds: Vec<TzifLeapSecond>,
+    pub footer: Option<String>,
+}
+
+#[allow(dead_code)]
+impl ZoneInfo {
+    /// ```parse(data)``` parses the content of a TZif file.
+    ///
+    /// ```
+    /// use date_and_time::tzif::*;
+    /// assert!(ZoneInfo::parse(b"no TZif data").is_err());
+    /// ```
+    pub fn parse(data: &[u8]) -> Result<ZoneInfo, DateTimeError> {
+        let mut reader = TzifReader { data, pos: 0 };
+        let header = read_header(&mut reader)?;
+        if header.version < 2 {
+            return read_data_block(&mut reader, &header, 4);
+        }
+        // skip the 32 bit data block, the 64 bit block after the second header contains
+        // the same data without the range limit
+        reader.take(reader.check_block(&header, 4)?)?;
+        let header = read_header(&mut reader)?;
+        let mut info = read_data_block(&mut reader, &header, 8)?;
+        info.footer = read_footer(&mut reader)?;
+        Ok(info)
+    }
+    /// ```from_file(path)``` reads and parses the TZif file from ```path```.
+    ///
+    /// ```
+    /// use date_and_time::tzif::*;
+    /// match ZoneInfo::from_file("/usr/share/zoneinfo/UTC") {
+    ///     Ok(zone) => assert_eq!(zone.offset_at(0), Some(0)),
+    ///     Err(e) => println!("{}", e),
+    /// }
+    /// ```
+    pub fn from_file(path: &str) -> Result<ZoneInfo, DateTimeError> {
+        match std::fs::read(path) {
+            Ok(data) => ZoneInfo::parse(&data),
+            Err(e) => Err(DateTimeError::Io(format!("{}: {}", path, e))),
+        }
+    }
+    /// ```by_name(name)``` reads the zoneinfo file of the timezone ```name``` (e.g.
+    /// ```"Europe/Berlin"```) from the system directory ```/usr/share/zoneinfo```.
+    ///
+    /// ```
+    /// use date_and_time::tzif::*;
+    /// if let Ok(zone) = ZoneInfo::by_name("Europe/Berlin") {
+    ///     println!("{} transitions, rule {:?}", zone.transitions.len(), zone.footer);
+    /// }
+    /// assert!(ZoneInfo::by_name("../etc/passwd").is_err());
+    /// ```
+    pub fn by_name(name: &str) -> Result<ZoneInfo, DateTimeError> {
+        if name.is_empty() || name.starts_with('/') || name.split('/').any(|p| p == "..") {
+            return Err(DateTimeError::Parse(format!(
+                "invalid timezone name '{}'",
+                name
+            )));
+        }
+        ZoneInfo::from_file(&format!("{}/{}", ZONEINFO_DIR, name))
+    }
+    /// ```from_system()``` reads the zoneinfo file of the local timezone. If the ```TZ```
+    /// environment variable names a zoneinfo file (e.g. ```TZ=":Europe/Berlin"```), this
+    /// file is used, else ```/etc/localtime```.
+    ///
+    /// ```
+    /// use date_and_time::tzif::*;
+    /// if let Ok(zone) = ZoneInfo::from_system() {
+    ///     println!("local rule: {:?}", zone.footer);
+    /// }
+    /// ```
+    pub fn from_system() -> Result<ZoneInfo, DateTimeError> {
+        if let Ok(tz) = std::env::var("TZ") {
+            let name = tz.strip_prefix(':').unwrap_or(&tz);
+            if name.starts_with('/') {
+                return ZoneInfo::from_file(name);
+            }
+            if let Ok(info) = ZoneInfo::by_name(name) {
+                return Ok(info);
+            }
+        }
+        ZoneInfo::from_file(LOCALTIME_FILE)
+    }
+    /// ```local_time_type_at(unix_secs)``` returns the local time type which applies at the
+    /// given seconds since the Linux epoch.
+    ///
+    /// Before the first transition the first local time type applies, after the last
+    /// transition the local time type of the last transition is returned. The ```footer```
+    /// rule is not evaluated here.
+    ///
+    /// ```
+    /// use date_and_time::tzif::*;
+    /// if let Ok(zone) = ZoneInfo::by_name("Europe/Berlin") {
+    ///     // the 1st January 2000
+    ///     let ltt = zone.local_time_type_at(946_684_800).unwrap();
+    ///     assert_eq!(ltt.abbreviation, "CET");
+    /// }
+    /// ```
+    ///
+    pub fn local_time_type_at(&self, unix_secs: i64) -> Option<&TzifLocalTimeType> {
+        let idx = self.transitions.partition_point(|t| t.at <= unix_secs);
+        if idx == 0 {
+            return self.local_time_types.first();
+        }
+        let type_index = self.transitions[idx - 1].type_index as usize;
+        self.local_time_types.get(type_index)
+    }
+    /// ```offset_at(unix_secs)``` returns the offset to UTC in seconds which applies at the
+    /// given seconds since the Linux epoch.
+    ///
+    /// ```
+    /// use date_and_time::tzif::*;
+    /// if let Ok(zone) = ZoneInfo::by_name("Europe/Berlin") {
+    ///     assert_eq!(zone.offset_at(946_684_800), Some(3_600));
+    /// }
+    /// ```
+    pub fn offset_at(&self, unix_secs: i64) -> Option<i32> {
+        self.local_time_type_at(unix_secs).map(|t| t.utoff)
+    }
+}
+
+// The counts from the TZif header
+struct TzifHeader {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl TzifHeader {
+    // Returns the length of the data block in bytes for the time size: usize (4 or 8), or
+    // None if it doesn't fit into an usize
+    fn block_len(&self, time_size: usize) -> Option<usize> {
+        self.timecnt
+            .checked_mul(time_size + 1)?
+            .checked_add(self.typecnt.checked_mul(6)?)?
+            .checked_add(self.charcnt)?
+            .checked_add(self.leapcnt.checked_mul(time_size + 4)?)?
+            .checked_add(self.isstdcnt)?
+            .checked_add(self.isutcnt)
+    }
+}
+
+// A simple big endian reader over the file data
+struct TzifReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TzifReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DateTimeError> {
+        if self.data.len() - self.pos < len {
+            return Err(DateTimeError::Parse(String::from("TZif data is truncated")));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+    // Returns the length of the data block of the header: &TzifHeader with the time size:
+    // usize, or an error if the counts don't fit into the remaining data
+    fn check_block(&self, header: &TzifHeader, time_size: usize) -> Result<usize, DateTimeError> {
+        match header.block_len(time_size) {
+            Some(len) if len <= self.data.len() - self.pos => Ok(len),
+            _ => Err(DateTimeError::Parse(String::from(
+                "TZif header counts exceed the data",
+            ))),
+        }
+    }
+    fn read_u32(&mut self) -> Result<u32, DateTimeError> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+    fn read_i32(&mut self) -> Result<i32, DateTimeError> {
+        Ok(self.read_u32()? as i32)
+    }
+    fn read_time(&mut self, time_size: usize) -> Result<i64, DateTimeError> {
+        if time_size == 4 {
+            return Ok(self.read_i32()? as i64);
+        }
+        let b = self.take(8)?;
+        Ok(i64::from_be_bytes([
+            b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
+        ]))
+    }
+}
+
+// Returns the header of a TZif data block
+fn read_header(reader: &mut TzifReader) -> Result<TzifHeader, DateTimeError> {
+    if reader.data.len() - reader.pos < TZIF_HEADER_LEN {
+        return Err(DateTimeError::Parse(String::from(
+            "TZif header is truncated",
+        )));
+    }
+    if reader.take(4)? != b"TZif" {
+        return Err(DateTimeError::Parse(String::from("missing TZif magic")));
+    }
+    let version = match reader.take(1)?[0] {
+        0 => 1,
+        b'2' => 2,
+        b'3' => 3,
+        b'4' => 4,
+        v => return Err(DateTimeError::Parse(format!("unknown TZif version {}", v))),
+    };
+    reader.take(15)?;
+    let header = TzifHeader {
+        version,
+        isutcnt: reader.read_u32()? as usize,
+        isstdcnt: reader.read_u32()? as usize,
+        leapcnt: reader.read_u32()? as usize,
+        timecnt: reader.read_u32()? as usize,
+        typecnt: reader.read_u32()? as usize,
+        charcnt: reader.read_u32()? as usize,
+    };
+    if header.typecnt == 0
+        || (header.isutcnt != 0 && header.isutcnt != header.typecnt)
+        || (header.isstdcnt != 0 && header.isstdcnt != header.typecnt)
+    {
+        return Err(DateTimeError::Parse(String::from(
+            "inconsistent TZif header counts",
+        )));
+    }
+    Ok(header)
+}
+
+// Returns the ZoneInfo of a data block with the time size: usize (4 or 8 bytes)
+fn read_data_block(
+    reader: &mut TzifReader,
+    header: &TzifHeader,
+    time_size: usize,
+) -> Result<ZoneInfo, DateTimeError> {
+    // the counts are validated before they size any allocation
+    reader.check_block(header, time_size)?;
+    let mut times: Vec<i64> = Vec::with_capacity(header.timecnt);
+    for _ in 0..header.timecnt {
+        times.push(reader.read_time(time_size)?);
+    }
+    let indices = reader.take(header.timecnt)?;
+    let mut transitions: Vec<TzifTransition> = Vec::with_capacity(header.timecnt);
+    for (n, at) in times.into_iter().enumerate() {
+        if indices[n] as usize >= header.typecnt {
+            return Err(DateTimeError::Parse(String::from(
+                "TZif transition refers to an unknown local time type",
+            )));
+        }
+        transitions.push(TzifTransition {
+            at,
+            type_index: indices[n],
+        });
+    }
+    let mut raw_types: Vec<(i32, bool, usize)> = Vec::with_capacity(header.typecnt);
+    for _ in 0..header.typecnt {
+        let utoff = reader.read_i32()?;
+        let b = reader.take(2)?;
+        raw_types.push((utoff, b[0] != 0, b[1] as usize));
+    }
+    let chars = reader.take(header.charcnt)?;
+    let mut local_time_types: Vec<TzifLocalTimeType> = Vec::with_capacity(header.typecnt);
+    for (utoff, is_dst, idx) in raw_types {
+        if idx >= chars.len() {
+            return Err(DateTimeError::Parse(String::from(
+                "TZif abbreviation index is out of range",
+            )));
+        }
+        let end = chars[idx..]
+            .iter()
+            .position(|&c| c == 0)
+            .map_or(chars.len(), |p| idx + p);
+        local_time_types.push(TzifLocalTimeType {
+            utoff,
+            is_dst,
+            abbreviation: String::from_utf8_lossy(&chars[idx..end]).into_owned(),
+        });
+    }
+    let mut leap_seconds: Vec<TzifLeapSecond> = Vec::with_capacity(header.leapcnt);
+    for _ in 0..header.leapcnt {
+        leap_seconds.push(TzifLeapSecond {
+            occurrence: reader.read_time(time_size)?,
+            correction: reader.read_i32()?,
+        });
+    }
+    // the standard/wall and UT/local indicators are only needed for POSIX TZ rules
+    // without transitions, they are skipped here
+    reader.take(header.isstdcnt + header.isutcnt)?;
+    Ok(ZoneInfo {
+        version: header.version,
+        transitions,
+        local_time_types,
+        leap_seconds,
+        footer: None,
+    })
+}
+
+// Returns the POSIX TZ string of the footer, it is enclosed in newlines
+fn read_footer(reader: &mut TzifReader) -> Result<Option<String>, DateTimeError> {
+    let rest = &reader.data[reader.pos..];
+    if rest.is_empty() {
+        return Ok(None);
+    }
+    if rest[0] != b'\n' {
+        return Err(DateTimeError::Parse(String::from("invalid TZif footer")));
+    }
+    let Some(end) = rest[1..].iter().position(|&c| c == b'\n') else {
+        return Err(DateTimeError::Parse(String::from(
+            "unterminated TZif footer",
+        )));
+    };
+    let tz = String::from_utf8_lossy(&rest[1..end + 1]).into_owned();
+    if tz.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(tz))
+}
@@ -0,0 +1,9 @@
+// date_and_time
+// (c) 2024 by markus dot mueller dot 73 at hotmail dot de
+// small crate to get some rudimentary date and time calculations
+//
+// The crate root only re-exports the module date_and_time, see src/date_and_time.rs for the
+// license and the documentation.
+
+pub mod date_and_time;
+pub use date_and_time::*;